@@ -3,15 +3,16 @@ use crate::kusama_test_net::*;
 
 use frame_support::{
 	assert_ok,
-	traits::{OnFinalize, OnInitialize},
+	traits::{Get, OnFinalize, OnInitialize},
 };
 
+use basilisk_runtime::MultiPaymentPricePeriod;
 use pallet_price_oracle::{BucketQueueT, PriceInfo};
 use pallet_transaction_multi_payment::Price;
 
 use hydradx_traits::{pools::SpotPriceProvider, AMM};
 use orml_traits::currency::MultiCurrency;
-use pallet_xyk::XYKSpotPrice;
+use pallet_xyk::{XYKSpotPrice, XYKTwapSpotPrice};
 use polkadot_primitives::v1::BlockNumber;
 use primitives::asset::AssetPair;
 use xcm_emulator::TestExt;
@@ -77,6 +78,7 @@ fn non_native_fee_payment_works() {
 			currency_1, // 500 KSM (500_000_033_400_002)
 			1_000 * BSX,
 			Price::from_float(0.5),
+			None,
 		));
 
 		let spot_price = XYKSpotPrice::<basilisk_runtime::Runtime>::spot_price(currency_0, currency_1);
@@ -100,6 +102,13 @@ fn non_native_fee_payment_works() {
 			Some(vec![currency_0, currency_1])
 		);
 
+		// average price is not available until the pool is older than the price period,
+		// fallback price is used instead
+		assert_eq!(
+			XYKTwapSpotPrice::<basilisk_runtime::Runtime, MultiPaymentPricePeriod>::spot_price(currency_0, currency_1),
+			None
+		);
+
 		// ------------ DAVE ------------
 		assert_ok!(basilisk_runtime::MultiTransactionPayment::set_currency(
 			basilisk_runtime::Origin::signed(DAVE.into()),
@@ -107,15 +116,14 @@ fn non_native_fee_payment_works() {
 		));
 
 		let dave_balance = basilisk_runtime::Tokens::free_balance(1, &AccountId::from(DAVE));
-		let expected_diff = 197_453_601_724;
-		assert_eq!(dave_balance, bob_balance + expected_diff);
+		assert_eq!(dave_balance, bob_balance);
 
 		expect_basilisk_events(vec![
 			pallet_transaction_multi_payment::Event::FeeWithdrawn(
 				DAVE.into(),
 				1,
 				462_676_500_000,
-				265_222_898_276,
+				462_676_500_000,
 				FALLBACK.into(),
 			)
 			.into(),
@@ -138,5 +146,13 @@ fn non_native_fee_payment_works() {
 				volume: 35_331_905_781_585
 			}
 		);
+
+		basilisk_run_to_block(MultiPaymentPricePeriod::get() + 3);
+
+		// pool price has not changed since the trade
+		assert_eq!(
+			XYKTwapSpotPrice::<basilisk_runtime::Runtime, MultiPaymentPricePeriod>::spot_price(currency_0, currency_1),
+			XYKSpotPrice::<basilisk_runtime::Runtime>::spot_price(currency_0, currency_1)
+		);
 	});
 }
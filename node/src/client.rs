@@ -3,7 +3,7 @@
 #![allow(clippy::upper_case_acronyms)]
 
 use crate::service::{BasiliskExecutorDispatch, FullBackend, FullClient, TestingBasiliskExecutorDispatch};
-use common_runtime::{AccountId, AssetId, Balance, Block, BlockNumber, Hash, Header, Index};
+use common_runtime::{AccountId, AssetId, Balance, Block, BlockNumber, Hash, Header, Index, LBPPool};
 use sc_client_api::{Backend as BackendT, BlockchainEvents, KeyIterator};
 use sp_api::{CallApiAt, NumberFor, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
@@ -27,7 +27,7 @@ pub trait RuntimeApiCollection:
 	+ sp_offchain::OffchainWorkerApi<Block>
 	+ sp_session::SessionKeys<Block>
 	+ pallet_xyk_rpc_runtime_api::XYKApi<Block, AccountId, AssetId, Balance>
-	+ pallet_lbp_rpc_runtime_api::LBPApi<Block, AccountId, AssetId>
+	+ pallet_lbp_rpc_runtime_api::LBPApi<Block, AccountId, AssetId, Balance, BlockNumber, LBPPool>
 where
 	<Self as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
 {
@@ -44,7 +44,7 @@ where
 		+ sp_offchain::OffchainWorkerApi<Block>
 		+ sp_session::SessionKeys<Block>
 		+ pallet_xyk_rpc_runtime_api::XYKApi<Block, AccountId, AssetId, Balance>
-		+ pallet_lbp_rpc_runtime_api::LBPApi<Block, AccountId, AssetId>,
+		+ pallet_lbp_rpc_runtime_api::LBPApi<Block, AccountId, AssetId, Balance, BlockNumber, LBPPool>,
 	<Self as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
 {
 }
@@ -0,0 +1,215 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2021  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks};
+use frame_support::sp_runtime::traits::One;
+use frame_system::RawOrigin;
+use sp_std::prelude::*;
+
+use crate::Pallet as ConcentratedLiquidity;
+
+const SEED: u32 = 1;
+
+const ASSET_A: AssetId = 1_000;
+const ASSET_B: AssetId = 2_000;
+const FEE: Permill = Permill::from_parts(3_000);
+const TICK_SPACING: u32 = 10;
+const LIQUIDITY: u128 = 1_000_000_000_000_000;
+const BASE_LIQUIDITY: u128 = 100 * LIQUIDITY;
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let caller: T::AccountId = account(name, index, SEED);
+	T::MultiCurrency::deposit(ASSET_A, &caller, 1_000_000_000_000_000_000).unwrap();
+	T::MultiCurrency::deposit(ASSET_B, &caller, 1_000_000_000_000_000_000).unwrap();
+	caller
+}
+
+fn pool_with_liquidity<T: Config>(maker: &T::AccountId) -> Result<(PoolId, PositionId), DispatchError> {
+	migration::init_nft_class::<T>();
+
+	ConcentratedLiquidity::<T>::create_pool(
+		RawOrigin::Root.into(),
+		ASSET_A,
+		ASSET_B,
+		FEE,
+		TICK_SPACING,
+		Price::one(),
+	)?;
+
+	let pool_id = ConcentratedLiquidity::<T>::pool_count();
+	let position_id = ConcentratedLiquidity::<T>::next_position_id();
+
+	ConcentratedLiquidity::<T>::mint_position(
+		RawOrigin::Signed(maker.clone()).into(),
+		pool_id,
+		-20_000,
+		20_000,
+		BASE_LIQUIDITY,
+		Balance::MAX,
+		Balance::MAX,
+	)?;
+
+	Ok((pool_id, position_id))
+}
+
+/// Mints positions starting at ticks `10, 20, .., 10 * n` so that moving the price of the pool
+/// to tick `10 * n + 5` crosses `n` ticks.
+fn positions_above_price<T: Config>(maker: &T::AccountId, pool_id: PoolId, n: u32) -> Result<(), DispatchError> {
+	for i in 1..=n {
+		ConcentratedLiquidity::<T>::mint_position(
+			RawOrigin::Signed(maker.clone()).into(),
+			pool_id,
+			(i * TICK_SPACING) as i32,
+			20_000,
+			LIQUIDITY,
+			Balance::MAX,
+			Balance::MAX,
+		)?;
+	}
+
+	Ok(())
+}
+
+fn target_sqrt_price(n: u32) -> u128 {
+	math::sqrt_price_at_tick((n * TICK_SPACING + 5) as i32).unwrap()
+}
+
+benchmarks! {
+	create_pool {
+		let pool_id = ConcentratedLiquidity::<T>::pool_count() + 1;
+	}: _(RawOrigin::Root, ASSET_A, ASSET_B, FEE, TICK_SPACING, Price::one())
+	verify {
+		assert!(<Pools<T>>::contains_key(pool_id));
+	}
+
+	mint_position {
+		let maker = funded_account::<T>("maker", 0);
+		let caller = funded_account::<T>("caller", 0);
+
+		let (pool_id, _) = pool_with_liquidity::<T>(&maker)?;
+		let position_id = ConcentratedLiquidity::<T>::next_position_id();
+
+	}: _(RawOrigin::Signed(caller.clone()), pool_id, -1_000, 1_000, LIQUIDITY, Balance::MAX, Balance::MAX)
+	verify {
+		assert_eq!(pallet_nft::Pallet::<T>::owner(T::NftClass::get(), position_id), Some(caller));
+	}
+
+	increase_liquidity {
+		let maker = funded_account::<T>("maker", 0);
+
+		let (pool_id, position_id) = pool_with_liquidity::<T>(&maker)?;
+
+	}: _(RawOrigin::Signed(maker.clone()), position_id, LIQUIDITY, Balance::MAX, Balance::MAX)
+	verify {
+		assert_eq!(ConcentratedLiquidity::<T>::pools(pool_id).unwrap().liquidity, BASE_LIQUIDITY + LIQUIDITY);
+	}
+
+	decrease_liquidity {
+		let maker = funded_account::<T>("maker", 0);
+
+		let (pool_id, position_id) = pool_with_liquidity::<T>(&maker)?;
+
+	}: _(RawOrigin::Signed(maker.clone()), position_id, LIQUIDITY, 0, 0)
+	verify {
+		assert_eq!(ConcentratedLiquidity::<T>::pools(pool_id).unwrap().liquidity, BASE_LIQUIDITY - LIQUIDITY);
+	}
+
+	collect_fees {
+		let maker = funded_account::<T>("maker", 0);
+		let caller = funded_account::<T>("caller", 0);
+
+		let (pool_id, position_id) = pool_with_liquidity::<T>(&maker)?;
+
+		ConcentratedLiquidity::<T>::sell(RawOrigin::Signed(caller).into(), pool_id, ASSET_B, ASSET_A, 1_000_000_000_000, 0)?;
+
+	}: _(RawOrigin::Signed(maker.clone()), position_id)
+	verify {
+		assert_eq!(ConcentratedLiquidity::<T>::positions(position_id).unwrap().fees_owed_b, 0);
+	}
+
+	burn_position {
+		let maker = funded_account::<T>("maker", 0);
+		let caller = funded_account::<T>("caller", 0);
+
+		let (pool_id, position_id) = pool_with_liquidity::<T>(&maker)?;
+
+		ConcentratedLiquidity::<T>::sell(RawOrigin::Signed(caller).into(), pool_id, ASSET_B, ASSET_A, 1_000_000_000_000, 0)?;
+		ConcentratedLiquidity::<T>::decrease_liquidity(RawOrigin::Signed(maker.clone()).into(), position_id, BASE_LIQUIDITY, 0, 0)?;
+
+	}: _(RawOrigin::Signed(maker.clone()), position_id)
+	verify {
+		assert!(ConcentratedLiquidity::<T>::positions(position_id).is_none());
+	}
+
+	sell {
+		let n in 0 .. T::MaxTicksCrossed::get();
+
+		let maker = funded_account::<T>("maker", 0);
+		let caller = funded_account::<T>("caller", 0);
+
+		let (pool_id, _) = pool_with_liquidity::<T>(&maker)?;
+		positions_above_price::<T>(&maker, pool_id, n)?;
+
+		let max_liquidity = BASE_LIQUIDITY + LIQUIDITY * n as u128;
+		let amount = 2 * math::amount_b_delta(math::SQRT_PRICE_ONE, target_sqrt_price(n), max_liquidity, true).unwrap();
+
+	}: _(RawOrigin::Signed(caller.clone()), pool_id, ASSET_B, ASSET_A, amount, 0)
+	verify {
+		assert!(ConcentratedLiquidity::<T>::pools(pool_id).unwrap().tick >= (n * TICK_SPACING) as i32);
+	}
+
+	buy {
+		let n in 0 .. T::MaxTicksCrossed::get();
+
+		let maker = funded_account::<T>("maker", 0);
+		let caller = funded_account::<T>("caller", 0);
+
+		let (pool_id, _) = pool_with_liquidity::<T>(&maker)?;
+		positions_above_price::<T>(&maker, pool_id, n)?;
+
+		let max_liquidity = BASE_LIQUIDITY + LIQUIDITY * n as u128;
+		let amount = math::amount_a_delta(math::SQRT_PRICE_ONE, target_sqrt_price(n), max_liquidity, false).unwrap();
+
+	}: _(RawOrigin::Signed(caller.clone()), pool_id, ASSET_A, ASSET_B, amount, Balance::MAX)
+	verify {
+		assert!(ConcentratedLiquidity::<T>::pools(pool_id).unwrap().tick >= (n * TICK_SPACING) as i32);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Pallet::<Test>::test_benchmark_create_pool());
+			assert_ok!(Pallet::<Test>::test_benchmark_mint_position());
+			assert_ok!(Pallet::<Test>::test_benchmark_increase_liquidity());
+			assert_ok!(Pallet::<Test>::test_benchmark_decrease_liquidity());
+			assert_ok!(Pallet::<Test>::test_benchmark_collect_fees());
+			assert_ok!(Pallet::<Test>::test_benchmark_burn_position());
+			assert_ok!(Pallet::<Test>::test_benchmark_sell());
+			assert_ok!(Pallet::<Test>::test_benchmark_buy());
+		});
+	}
+}
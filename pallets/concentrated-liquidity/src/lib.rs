@@ -0,0 +1,1446 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2021  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Concentrated Liquidity Pallet
+//!
+//! ## Overview
+//!
+//! Concentrated liquidity pallet provides pools of two assets in which liquidity providers supply
+//! liquidity within a price range of their choice. Liquidity of a position is used only while the
+//! price of the pool is within its range, which makes it much more capital efficient than liquidity
+//! spread over the whole price curve.
+//!
+//! Price range of a position is given by two ticks, price at tick `i` is `1.0001^i`. Trades move
+//! the price of the pool and cross the ticks where liquidity of positions starts or ends.
+//!
+//! Each position is an NFT instance of `NftClass` owned by the liquidity provider. Trading fees are
+//! accrued per position in proportion to its liquidity while the price is within its range.
+//!
+//! This pallet implements AMM Api trait therefore it is possible to plug this pool implementation
+//! into the exchange pallet. Trades of an asset pair through the AMM Api are routed to the first pool
+//! created with both assets.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+#![allow(clippy::upper_case_acronyms)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::sp_runtime::{
+	traits::{AccountIdConversion, Zero},
+	DispatchError, FixedPointNumber, Permill,
+};
+use frame_support::{dispatch::DispatchResult, ensure, traits::Get, transactional, BoundedVec, PalletId, RuntimeDebug};
+use frame_system::ensure_signed;
+use hydradx_traits::{AMMTransfer, AMM};
+use orml_traits::MultiCurrency;
+use primitives::{asset::AssetPair, nft::ClassType, AssetId, Balance, Price};
+use scale_info::TypeInfo;
+use sp_core::U256;
+use sp_std::{vec, vec::Vec};
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+mod benchmarking;
+pub mod math;
+pub mod migration;
+pub mod weights;
+
+use weights::WeightInfo;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+
+/// Identifier of a concentrated liquidity pool.
+pub type PoolId = u32;
+
+/// Identifier of a position, equal to the NFT instance id of the position.
+pub type PositionId = primitives::InstanceId;
+
+/// Concentrated liquidity pool of two assets.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct PoolInfo {
+	/// Assets of the pool, the lower asset id first.
+	pub assets: (AssetId, AssetId),
+	/// Trading fee rate.
+	pub fee: Permill,
+	/// Ticks of positions must be multiples of the tick spacing.
+	pub tick_spacing: u32,
+	/// Square root of the price of the first asset denominated in the second asset.
+	pub sqrt_price: u128,
+	/// Highest tick with square root price lower than or equal to `sqrt_price`.
+	pub tick: i32,
+	/// Liquidity of positions in range of the current price.
+	pub liquidity: u128,
+	/// Fees of the first asset earned per unit of liquidity since the pool was created.
+	pub fee_growth_a: u128,
+	/// Fees of the second asset earned per unit of liquidity since the pool was created.
+	pub fee_growth_b: u128,
+}
+
+impl PoolInfo {
+	/// Whether `asset` is the first asset of the pool.
+	pub fn is_asset_a(&self, asset: AssetId) -> Option<bool> {
+		if asset == self.assets.0 {
+			Some(true)
+		} else if asset == self.assets.1 {
+			Some(false)
+		} else {
+			None
+		}
+	}
+}
+
+/// Tick at which liquidity of at least one position starts or ends.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct TickInfo {
+	/// Total liquidity of positions starting or ending at the tick.
+	pub liquidity_gross: u128,
+	/// Liquidity added to the pool when the price crosses the tick upwards.
+	pub liquidity_net: i128,
+	/// Fee growth of the first asset on the other side of the tick from the current price.
+	pub fee_growth_outside_a: u128,
+	/// Fee growth of the second asset on the other side of the tick from the current price.
+	pub fee_growth_outside_b: u128,
+}
+
+/// Liquidity provided within a price range.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Position {
+	/// Pool of the position.
+	pub pool_id: PoolId,
+	/// Lower tick of the price range.
+	pub lower_tick: i32,
+	/// Upper tick of the price range.
+	pub upper_tick: i32,
+	/// Liquidity of the position.
+	pub liquidity: u128,
+	/// Fee growth of the first asset inside the range when fees of the position were last updated.
+	pub fee_growth_inside_a_last: u128,
+	/// Fee growth of the second asset inside the range when fees of the position were last updated.
+	pub fee_growth_inside_b_last: u128,
+	/// Fees of the first asset earned and not collected yet.
+	pub fees_owed_a: Balance,
+	/// Fees of the second asset earned and not collected yet.
+	pub fees_owed_b: Balance,
+}
+
+/// Result of a trade calculated on current state of a pool.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct SwapResult {
+	/// Amount paid to the pool, fee included.
+	pub amount_in: Balance,
+	/// Amount paid by the pool.
+	pub amount_out: Balance,
+	/// Fee paid in the sold asset.
+	pub fee: Balance,
+	/// Square root price of the pool after the trade.
+	pub sqrt_price: u128,
+	/// Tick of the pool after the trade.
+	pub tick: i32,
+	/// Liquidity in range after the trade.
+	pub liquidity: u128,
+	/// Fee growth of the sold asset after the trade.
+	pub fee_growth: u128,
+	/// Crossed ticks with the fee growth of the sold asset at the time of crossing.
+	pub crossed_ticks: Vec<(i32, u128)>,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::OriginFor;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migration::init_nft_class::<T>()
+		}
+
+		fn integrity_test() {
+			assert!(
+				T::NftClass::get() <= T::ReserveClassIdUpTo::get(),
+				"`NftClass` must be within the range of reserved NFT class IDs"
+			);
+		}
+	}
+
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config
+		+ pallet_nft::Config<
+			ClassType = ClassType,
+			NftClassId = primitives::ClassId,
+			NftInstanceId = primitives::InstanceId,
+		>
+	{
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Multi currency for transfer of currencies
+		type MultiCurrency: MultiCurrency<Self::AccountId, CurrencyId = AssetId, Balance = Balance>;
+
+		/// Pallet id used to derive pool accounts and the owner of the NFT class
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Origin allowed to create pools
+		type CreatePoolOrigin: EnsureOrigin<Self::Origin>;
+
+		/// NFT class id of positions. Has to be within the range of reserved NFT class IDs.
+		#[pallet::constant]
+		type NftClass: Get<primitives::ClassId>;
+
+		/// Maximum number of initialized ticks in a pool
+		#[pallet::constant]
+		type MaxInitializedTicks: Get<u32>;
+
+		/// Maximum number of ticks crossed by a single trade
+		#[pallet::constant]
+		type MaxTicksCrossed: Get<u32>;
+
+		/// Minimum trading limit
+		#[pallet::constant]
+		type MinTradingLimit: Get<Balance>;
+
+		/// Weight information for the extrinsics.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	#[cfg_attr(test, derive(PartialEq))]
+	pub enum Error<T> {
+		/// Pool must consist of two distinct assets.
+		IdenticalAssets,
+
+		/// Fee rate must be lower than 100%.
+		InvalidFee,
+
+		/// Tick spacing must be positive and lower than `MAX_TICK`.
+		InvalidTickSpacing,
+
+		/// Initial price is out of the supported range.
+		InvalidInitialPrice,
+
+		/// Pool does not exist.
+		PoolNotFound,
+
+		/// Asset is not in the pool.
+		AssetNotInPool,
+
+		/// Cannot trade asset for itself.
+		SameAssetTrade,
+
+		/// Ticks must be ordered, within the supported range and multiples of the tick spacing.
+		InvalidTickRange,
+
+		/// Liquidity amount must be positive.
+		ZeroLiquidity,
+
+		/// Number of initialized ticks of the pool reached `MaxInitializedTicks`.
+		TooManyInitializedTicks,
+
+		/// Position does not exist.
+		PositionNotFound,
+
+		/// Account is not the owner of the position NFT.
+		NotPositionOwner,
+
+		/// Position does not have enough liquidity.
+		InsufficientPositionLiquidity,
+
+		/// Position with liquidity cannot be burned.
+		PositionNotEmpty,
+
+		/// Account does not have enough balance.
+		InsufficientBalance,
+
+		/// Traded amount is lower than `MinTradingLimit`.
+		InsufficientTradingAmount,
+
+		/// Pool does not have enough liquidity for the trade.
+		InsufficientLiquidity,
+
+		/// Trade would cross more than `MaxTicksCrossed` ticks.
+		TooManyTicksCrossed,
+
+		/// Required amount exceeds the given maximum.
+		MaxAmountExceeded,
+
+		/// Withdrawn amount is lower than the given minimum.
+		MinAmountNotReached,
+
+		/// Bought amount is lower than the limit.
+		BuyLimitNotReached,
+
+		/// Sold amount is higher than the limit.
+		SellLimitExceeded,
+
+		/// Trading fee discount is not supported by concentrated liquidity pools.
+		DiscountNotSupported,
+
+		/// Calculation of prices or amounts failed.
+		MathError,
+
+		/// Overflow
+		Overflow,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// New pool was created. [pool id, asset a, asset b, fee, tick spacing, sqrt price, pool account id]
+		PoolCreated(PoolId, AssetId, AssetId, Permill, u32, u128, T::AccountId),
+
+		/// New position was minted. [who, position id, pool id, lower tick, upper tick, liquidity, amount a, amount b]
+		PositionMinted(T::AccountId, PositionId, PoolId, i32, i32, u128, Balance, Balance),
+
+		/// Liquidity of a position was increased. [who, position id, liquidity, amount a, amount b]
+		LiquidityIncreased(T::AccountId, PositionId, u128, Balance, Balance),
+
+		/// Liquidity of a position was decreased. [who, position id, liquidity, amount a, amount b]
+		LiquidityDecreased(T::AccountId, PositionId, u128, Balance, Balance),
+
+		/// Fees of a position were collected. [who, position id, amount a, amount b]
+		FeesCollected(T::AccountId, PositionId, Balance, Balance),
+
+		/// Position was burned. [who, position id]
+		PositionBurned(T::AccountId, PositionId),
+
+		/// Asset sale executed. [who, pool id, asset in, asset out, amount in, amount out, fee]
+		SellExecuted(T::AccountId, PoolId, AssetId, AssetId, Balance, Balance, Balance),
+
+		/// Asset purchase executed. [who, pool id, asset out, asset in, amount out, amount in, fee]
+		BuyExecuted(T::AccountId, PoolId, AssetId, AssetId, Balance, Balance, Balance),
+	}
+
+	/// Id of the last created pool.
+	#[pallet::storage]
+	#[pallet::getter(fn pool_count)]
+	pub(crate) type PoolCount<T: Config> = StorageValue<_, PoolId, ValueQuery>;
+
+	/// Existing pools.
+	#[pallet::storage]
+	#[pallet::getter(fn pools)]
+	pub(crate) type Pools<T: Config> = StorageMap<_, Blake2_128Concat, PoolId, PoolInfo, OptionQuery>;
+
+	/// Pool used for trades of an asset pair through the AMM Api. Assets of the pair are ordered.
+	#[pallet::storage]
+	#[pallet::getter(fn pair_pool)]
+	pub(crate) type PairPools<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, AssetId, Blake2_128Concat, AssetId, PoolId, OptionQuery>;
+
+	/// Pool identified by its account.
+	#[pallet::storage]
+	#[pallet::getter(fn pool_id_by_account)]
+	pub(crate) type PoolAccounts<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, PoolId, OptionQuery>;
+
+	/// Initialized ticks of a pool.
+	#[pallet::storage]
+	#[pallet::getter(fn ticks)]
+	pub(crate) type Ticks<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, PoolId, Twox64Concat, i32, TickInfo, OptionQuery>;
+
+	/// Sorted list of initialized ticks of a pool.
+	#[pallet::storage]
+	#[pallet::getter(fn initialized_ticks)]
+	pub(crate) type InitializedTicks<T: Config> =
+		StorageMap<_, Blake2_128Concat, PoolId, BoundedVec<i32, T::MaxInitializedTicks>, ValueQuery>;
+
+	/// Existing positions.
+	#[pallet::storage]
+	#[pallet::getter(fn positions)]
+	pub(crate) type Positions<T: Config> = StorageMap<_, Twox64Concat, PositionId, Position, OptionQuery>;
+
+	/// Id of the next minted position.
+	#[pallet::storage]
+	#[pallet::getter(fn next_position_id)]
+	pub(crate) type NextPositionId<T: Config> = StorageValue<_, PositionId, ValueQuery>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create new pool of two assets.
+		///
+		/// Can be called only by `CreatePoolOrigin`. Pool is created without liquidity.
+		///
+		/// `fee` - trading fee rate of the pool.
+		///
+		/// `tick_spacing` - ticks of positions in the pool must be multiples of the tick spacing.
+		///
+		/// `initial_price` - price of `asset_a` denominated in `asset_b`.
+		///
+		/// Emits `PoolCreated` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::create_pool())]
+		#[transactional]
+		pub fn create_pool(
+			origin: OriginFor<T>,
+			asset_a: AssetId,
+			asset_b: AssetId,
+			fee: Permill,
+			tick_spacing: u32,
+			initial_price: Price,
+		) -> DispatchResult {
+			T::CreatePoolOrigin::ensure_origin(origin)?;
+
+			ensure!(asset_a != asset_b, Error::<T>::IdenticalAssets);
+			ensure!(fee < Permill::one(), Error::<T>::InvalidFee);
+			ensure!(
+				tick_spacing > 0 && tick_spacing < math::MAX_TICK as u32,
+				Error::<T>::InvalidTickSpacing
+			);
+
+			let (assets, price) = if asset_a < asset_b {
+				((asset_a, asset_b), initial_price)
+			} else {
+				(
+					(asset_b, asset_a),
+					initial_price.reciprocal().ok_or(Error::<T>::InvalidInitialPrice)?,
+				)
+			};
+
+			let sqrt_price = math::sqrt_price_from_price(price.into_inner()).ok_or(Error::<T>::InvalidInitialPrice)?;
+			ensure!(
+				(math::MIN_SQRT_PRICE..math::MAX_SQRT_PRICE).contains(&sqrt_price),
+				Error::<T>::InvalidInitialPrice
+			);
+			let tick = math::tick_at_sqrt_price(sqrt_price).ok_or(Error::<T>::MathError)?;
+
+			let pool_id = Self::pool_count().checked_add(1).ok_or(Error::<T>::Overflow)?;
+			let pool_account = Self::pool_account(pool_id);
+
+			if !<PairPools<T>>::contains_key(assets.0, assets.1) {
+				<PairPools<T>>::insert(assets.0, assets.1, pool_id);
+			}
+
+			<Pools<T>>::insert(
+				pool_id,
+				PoolInfo {
+					assets,
+					fee,
+					tick_spacing,
+					sqrt_price,
+					tick,
+					liquidity: 0,
+					fee_growth_a: 0,
+					fee_growth_b: 0,
+				},
+			);
+			<PoolAccounts<T>>::insert(&pool_account, pool_id);
+			<PoolCount<T>>::put(pool_id);
+
+			Self::deposit_event(Event::PoolCreated(
+				pool_id,
+				assets.0,
+				assets.1,
+				fee,
+				tick_spacing,
+				sqrt_price,
+				pool_account,
+			));
+
+			Ok(())
+		}
+
+		/// Provide `liquidity` to a pool within the price range between `lower_tick` and `upper_tick`.
+		///
+		/// Position NFT is minted to the origin. Amounts of assets needed for the liquidity depend on
+		/// the current price of the pool and are limited by `max_amount_a` and `max_amount_b`.
+		///
+		/// Emits `PositionMinted` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::mint_position())]
+		#[transactional]
+		pub fn mint_position(
+			origin: OriginFor<T>,
+			pool_id: PoolId,
+			lower_tick: i32,
+			upper_tick: i32,
+			liquidity: u128,
+			max_amount_a: Balance,
+			max_amount_b: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!liquidity.is_zero(), Error::<T>::ZeroLiquidity);
+
+			let pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+
+			let spacing = pool.tick_spacing as i32;
+			ensure!(
+				lower_tick < upper_tick
+					&& lower_tick >= math::MIN_TICK
+					&& upper_tick <= math::MAX_TICK
+					&& lower_tick % spacing == 0
+					&& upper_tick % spacing == 0,
+				Error::<T>::InvalidTickRange
+			);
+
+			let position_id = Self::next_position_id();
+			<NextPositionId<T>>::put(position_id.checked_add(1).ok_or(Error::<T>::Overflow)?);
+
+			let mut position = Position {
+				pool_id,
+				lower_tick,
+				upper_tick,
+				liquidity: 0,
+				fee_growth_inside_a_last: 0,
+				fee_growth_inside_b_last: 0,
+				fees_owed_a: 0,
+				fees_owed_b: 0,
+			};
+
+			let (amount_a, amount_b) =
+				Self::deposit_liquidity(&who, pool_id, &mut position, liquidity, max_amount_a, max_amount_b)?;
+
+			pallet_nft::Pallet::<T>::do_mint(who.clone(), T::NftClass::get(), position_id, BoundedVec::default())?;
+
+			<Positions<T>>::insert(position_id, position);
+
+			Self::deposit_event(Event::PositionMinted(
+				who,
+				position_id,
+				pool_id,
+				lower_tick,
+				upper_tick,
+				liquidity,
+				amount_a,
+				amount_b,
+			));
+
+			Ok(())
+		}
+
+		/// Add `liquidity` to an existing position.
+		///
+		/// Can be called only by the owner of the position NFT.
+		///
+		/// Emits `LiquidityIncreased` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::increase_liquidity())]
+		#[transactional]
+		pub fn increase_liquidity(
+			origin: OriginFor<T>,
+			position_id: PositionId,
+			liquidity: u128,
+			max_amount_a: Balance,
+			max_amount_b: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!liquidity.is_zero(), Error::<T>::ZeroLiquidity);
+
+			let mut position = Self::owned_position(&who, position_id)?;
+
+			let (amount_a, amount_b) = Self::deposit_liquidity(
+				&who,
+				position.pool_id,
+				&mut position,
+				liquidity,
+				max_amount_a,
+				max_amount_b,
+			)?;
+
+			<Positions<T>>::insert(position_id, position);
+
+			Self::deposit_event(Event::LiquidityIncreased(
+				who,
+				position_id,
+				liquidity,
+				amount_a,
+				amount_b,
+			));
+
+			Ok(())
+		}
+
+		/// Remove `liquidity` from a position.
+		///
+		/// Can be called only by the owner of the position NFT. Withdrawn assets are transferred to the
+		/// origin, fees earned by the position stay in the position until collected.
+		///
+		/// Emits `LiquidityDecreased` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::decrease_liquidity())]
+		#[transactional]
+		pub fn decrease_liquidity(
+			origin: OriginFor<T>,
+			position_id: PositionId,
+			liquidity: u128,
+			min_amount_a: Balance,
+			min_amount_b: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!liquidity.is_zero(), Error::<T>::ZeroLiquidity);
+
+			let mut position = Self::owned_position(&who, position_id)?;
+
+			ensure!(
+				position.liquidity >= liquidity,
+				Error::<T>::InsufficientPositionLiquidity
+			);
+
+			let pool_id = position.pool_id;
+			let (amount_a, amount_b) = Self::modify_position(pool_id, &mut position, liquidity, false)?;
+
+			ensure!(
+				amount_a >= min_amount_a && amount_b >= min_amount_b,
+				Error::<T>::MinAmountNotReached
+			);
+
+			let pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			let pool_account = Self::pool_account(pool_id);
+
+			T::MultiCurrency::transfer(pool.assets.0, &pool_account, &who, amount_a)?;
+			T::MultiCurrency::transfer(pool.assets.1, &pool_account, &who, amount_b)?;
+
+			<Positions<T>>::insert(position_id, position);
+
+			Self::deposit_event(Event::LiquidityDecreased(
+				who,
+				position_id,
+				liquidity,
+				amount_a,
+				amount_b,
+			));
+
+			Ok(())
+		}
+
+		/// Transfer fees earned by a position to the owner of the position NFT.
+		///
+		/// Emits `FeesCollected` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::collect_fees())]
+		#[transactional]
+		pub fn collect_fees(origin: OriginFor<T>, position_id: PositionId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut position = Self::owned_position(&who, position_id)?;
+
+			Self::do_collect_fees(&who, position_id, &mut position)?;
+
+			<Positions<T>>::insert(position_id, position);
+
+			Ok(())
+		}
+
+		/// Burn position NFT of a position without liquidity.
+		///
+		/// Fees earned by the position which were not collected yet are transferred to the owner.
+		///
+		/// Emits `PositionBurned` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::burn_position())]
+		#[transactional]
+		pub fn burn_position(origin: OriginFor<T>, position_id: PositionId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut position = Self::owned_position(&who, position_id)?;
+
+			ensure!(position.liquidity.is_zero(), Error::<T>::PositionNotEmpty);
+
+			if !position.fees_owed_a.is_zero() || !position.fees_owed_b.is_zero() {
+				Self::do_collect_fees(&who, position_id, &mut position)?;
+			}
+
+			pallet_nft::Pallet::<T>::do_burn(who.clone(), T::NftClass::get(), position_id)?;
+
+			<Positions<T>>::remove(position_id);
+
+			Self::deposit_event(Event::PositionBurned(who, position_id));
+
+			Ok(())
+		}
+
+		/// Trade `amount_in` of `asset_in` for `asset_out` in a pool.
+		///
+		/// Trading fee is paid in `asset_in` and is included in `amount_in`.
+		///
+		/// `min_buy_amount` - minimum amount of `asset_out` to receive.
+		///
+		/// Emits `SellExecuted` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::sell(T::MaxTicksCrossed::get()))]
+		#[transactional]
+		pub fn sell(
+			origin: OriginFor<T>,
+			pool_id: PoolId,
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount_in: Balance,
+			min_buy_amount: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let transfer = Self::validate_pool_sell(&who, pool_id, asset_in, asset_out, amount_in, min_buy_amount)?;
+
+			Self::execute_pool_sell(pool_id, &transfer)
+		}
+
+		/// Trade `asset_in` for `amount_out` of `asset_out` in a pool.
+		///
+		/// Trading fee is paid in `asset_in` and is added to the sold amount.
+		///
+		/// `max_sell_amount` - maximum amount of `asset_in` to pay, fee included.
+		///
+		/// Emits `BuyExecuted` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::buy(T::MaxTicksCrossed::get()))]
+		#[transactional]
+		pub fn buy(
+			origin: OriginFor<T>,
+			pool_id: PoolId,
+			asset_out: AssetId,
+			asset_in: AssetId,
+			amount_out: Balance,
+			max_sell_amount: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let transfer = Self::validate_pool_buy(&who, pool_id, asset_out, asset_in, amount_out, max_sell_amount)?;
+
+			Self::execute_pool_buy(pool_id, &transfer)
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Account of a pool.
+	pub fn pool_account(pool_id: PoolId) -> T::AccountId {
+		T::PalletId::get().into_sub_account(pool_id)
+	}
+
+	/// Account owning the NFT class of positions.
+	fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account()
+	}
+
+	fn owned_position(who: &T::AccountId, position_id: PositionId) -> Result<Position, DispatchError> {
+		let position = Self::positions(position_id).ok_or(Error::<T>::PositionNotFound)?;
+
+		let owner =
+			pallet_nft::Pallet::<T>::owner(T::NftClass::get(), position_id).ok_or(Error::<T>::PositionNotFound)?;
+		ensure!(owner == *who, Error::<T>::NotPositionOwner);
+
+		Ok(position)
+	}
+
+	/// Fee growth of both assets of a pool inside the price range between `lower_tick` and `upper_tick`.
+	pub fn fee_growth_inside(
+		pool: &PoolInfo,
+		lower: &TickInfo,
+		lower_tick: i32,
+		upper: &TickInfo,
+		upper_tick: i32,
+	) -> (u128, u128) {
+		let inside = |global: u128, lower_outside: u128, upper_outside: u128| {
+			let below = if pool.tick >= lower_tick {
+				lower_outside
+			} else {
+				global.wrapping_sub(lower_outside)
+			};
+			let above = if pool.tick < upper_tick {
+				upper_outside
+			} else {
+				global.wrapping_sub(upper_outside)
+			};
+			global.wrapping_sub(below).wrapping_sub(above)
+		};
+
+		(
+			inside(
+				pool.fee_growth_a,
+				lower.fee_growth_outside_a,
+				upper.fee_growth_outside_a,
+			),
+			inside(
+				pool.fee_growth_b,
+				lower.fee_growth_outside_b,
+				upper.fee_growth_outside_b,
+			),
+		)
+	}
+
+	/// Accrue fees earned by `position` since its last update.
+	fn accrue_fees(
+		position: &mut Position,
+		fee_growth_inside_a: u128,
+		fee_growth_inside_b: u128,
+	) -> Result<(), DispatchError> {
+		let earned_a = math::fees_earned(
+			fee_growth_inside_a.wrapping_sub(position.fee_growth_inside_a_last),
+			position.liquidity,
+		)
+		.ok_or(Error::<T>::Overflow)?;
+		let earned_b = math::fees_earned(
+			fee_growth_inside_b.wrapping_sub(position.fee_growth_inside_b_last),
+			position.liquidity,
+		)
+		.ok_or(Error::<T>::Overflow)?;
+
+		position.fees_owed_a = position.fees_owed_a.checked_add(earned_a).ok_or(Error::<T>::Overflow)?;
+		position.fees_owed_b = position.fees_owed_b.checked_add(earned_b).ok_or(Error::<T>::Overflow)?;
+		position.fee_growth_inside_a_last = fee_growth_inside_a;
+		position.fee_growth_inside_b_last = fee_growth_inside_b;
+
+		Ok(())
+	}
+
+	fn update_tick(
+		pool_id: PoolId,
+		pool: &PoolInfo,
+		tick: i32,
+		liquidity: u128,
+		add: bool,
+		upper: bool,
+	) -> Result<TickInfo, DispatchError> {
+		let mut info = match Self::ticks(pool_id, tick) {
+			Some(info) => info,
+			None => {
+				<InitializedTicks<T>>::try_mutate(pool_id, |ticks| -> DispatchResult {
+					let idx = ticks.partition_point(|t| *t < tick);
+					ticks
+						.try_insert(idx, tick)
+						.map_err(|_| Error::<T>::TooManyInitializedTicks.into())
+				})?;
+
+				let (fee_growth_outside_a, fee_growth_outside_b) = if tick <= pool.tick {
+					(pool.fee_growth_a, pool.fee_growth_b)
+				} else {
+					(0, 0)
+				};
+
+				TickInfo {
+					liquidity_gross: 0,
+					liquidity_net: 0,
+					fee_growth_outside_a,
+					fee_growth_outside_b,
+				}
+			}
+		};
+
+		let net_delta: i128 = liquidity.try_into().map_err(|_| Error::<T>::Overflow)?;
+		let net_delta = if add == upper { -net_delta } else { net_delta };
+
+		info.liquidity_gross = if add {
+			info.liquidity_gross.checked_add(liquidity)
+		} else {
+			info.liquidity_gross.checked_sub(liquidity)
+		}
+		.ok_or(Error::<T>::Overflow)?;
+		info.liquidity_net = info.liquidity_net.checked_add(net_delta).ok_or(Error::<T>::Overflow)?;
+
+		Ok(info)
+	}
+
+	fn store_tick(pool_id: PoolId, tick: i32, info: TickInfo) {
+		if info.liquidity_gross.is_zero() {
+			<Ticks<T>>::remove(pool_id, tick);
+			<InitializedTicks<T>>::mutate(pool_id, |ticks| {
+				if let Ok(idx) = ticks.binary_search(&tick) {
+					ticks.remove(idx);
+				}
+			});
+		} else {
+			<Ticks<T>>::insert(pool_id, tick, info);
+		}
+	}
+
+	/// Add or remove `liquidity` of a position and accrue its fees.
+	///
+	/// Returns amounts of assets to be paid to the pool when adding liquidity, rounded up, or paid by
+	/// the pool when removing liquidity, rounded down. Transfers are left to the caller.
+	fn modify_position(
+		pool_id: PoolId,
+		position: &mut Position,
+		liquidity: u128,
+		add: bool,
+	) -> Result<(Balance, Balance), DispatchError> {
+		let mut pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+
+		let lower = Self::update_tick(pool_id, &pool, position.lower_tick, liquidity, add, false)?;
+		let upper = Self::update_tick(pool_id, &pool, position.upper_tick, liquidity, add, true)?;
+
+		let (fee_growth_inside_a, fee_growth_inside_b) =
+			Self::fee_growth_inside(&pool, &lower, position.lower_tick, &upper, position.upper_tick);
+		Self::accrue_fees(position, fee_growth_inside_a, fee_growth_inside_b)?;
+
+		let in_range = position.lower_tick <= pool.tick && pool.tick < position.upper_tick;
+
+		if add {
+			position.liquidity = position.liquidity.checked_add(liquidity).ok_or(Error::<T>::Overflow)?;
+			if in_range {
+				pool.liquidity = pool.liquidity.checked_add(liquidity).ok_or(Error::<T>::Overflow)?;
+			}
+		} else {
+			position.liquidity = position.liquidity.checked_sub(liquidity).ok_or(Error::<T>::Overflow)?;
+			if in_range {
+				pool.liquidity = pool.liquidity.checked_sub(liquidity).ok_or(Error::<T>::Overflow)?;
+			}
+		}
+
+		Self::store_tick(pool_id, position.lower_tick, lower);
+		Self::store_tick(pool_id, position.upper_tick, upper);
+
+		let sqrt_price_lower = math::sqrt_price_at_tick(position.lower_tick).ok_or(Error::<T>::MathError)?;
+		let sqrt_price_upper = math::sqrt_price_at_tick(position.upper_tick).ok_or(Error::<T>::MathError)?;
+
+		let amounts = if pool.tick < position.lower_tick {
+			(
+				math::amount_a_delta(sqrt_price_lower, sqrt_price_upper, liquidity, add),
+				Some(0),
+			)
+		} else if pool.tick >= position.upper_tick {
+			(
+				Some(0),
+				math::amount_b_delta(sqrt_price_lower, sqrt_price_upper, liquidity, add),
+			)
+		} else {
+			(
+				math::amount_a_delta(pool.sqrt_price, sqrt_price_upper, liquidity, add),
+				math::amount_b_delta(sqrt_price_lower, pool.sqrt_price, liquidity, add),
+			)
+		};
+
+		<Pools<T>>::insert(pool_id, pool);
+
+		match amounts {
+			(Some(amount_a), Some(amount_b)) => Ok((amount_a, amount_b)),
+			_ => Err(Error::<T>::MathError.into()),
+		}
+	}
+
+	fn deposit_liquidity(
+		who: &T::AccountId,
+		pool_id: PoolId,
+		position: &mut Position,
+		liquidity: u128,
+		max_amount_a: Balance,
+		max_amount_b: Balance,
+	) -> Result<(Balance, Balance), DispatchError> {
+		let (amount_a, amount_b) = Self::modify_position(pool_id, position, liquidity, true)?;
+
+		ensure!(
+			amount_a <= max_amount_a && amount_b <= max_amount_b,
+			Error::<T>::MaxAmountExceeded
+		);
+
+		let pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+		let pool_account = Self::pool_account(pool_id);
+
+		ensure!(
+			T::MultiCurrency::free_balance(pool.assets.0, who) >= amount_a
+				&& T::MultiCurrency::free_balance(pool.assets.1, who) >= amount_b,
+			Error::<T>::InsufficientBalance
+		);
+
+		T::MultiCurrency::transfer(pool.assets.0, who, &pool_account, amount_a)?;
+		T::MultiCurrency::transfer(pool.assets.1, who, &pool_account, amount_b)?;
+
+		Ok((amount_a, amount_b))
+	}
+
+	fn do_collect_fees(who: &T::AccountId, position_id: PositionId, position: &mut Position) -> DispatchResult {
+		let pool = Self::pools(position.pool_id).ok_or(Error::<T>::PoolNotFound)?;
+
+		if !position.liquidity.is_zero() {
+			let lower = Self::ticks(position.pool_id, position.lower_tick).unwrap_or_default();
+			let upper = Self::ticks(position.pool_id, position.upper_tick).unwrap_or_default();
+
+			let (fee_growth_inside_a, fee_growth_inside_b) =
+				Self::fee_growth_inside(&pool, &lower, position.lower_tick, &upper, position.upper_tick);
+			Self::accrue_fees(position, fee_growth_inside_a, fee_growth_inside_b)?;
+		}
+
+		let (amount_a, amount_b) = (position.fees_owed_a, position.fees_owed_b);
+		let pool_account = Self::pool_account(position.pool_id);
+
+		T::MultiCurrency::transfer(pool.assets.0, &pool_account, who, amount_a)?;
+		T::MultiCurrency::transfer(pool.assets.1, &pool_account, who, amount_b)?;
+
+		position.fees_owed_a = 0;
+		position.fees_owed_b = 0;
+
+		Self::deposit_event(Event::FeesCollected(who.clone(), position_id, amount_a, amount_b));
+
+		Ok(())
+	}
+
+	/// Calculate a trade in a pool without changing its state.
+	///
+	/// `amount` is the sold amount including fee if `exact_in`, the bought amount otherwise.
+	pub fn calculate_swap(
+		pool_id: PoolId,
+		pool: &PoolInfo,
+		sell_a: bool,
+		exact_in: bool,
+		amount: Balance,
+	) -> Result<SwapResult, DispatchError> {
+		let ticks = Self::initialized_ticks(pool_id);
+
+		let mut swap = SwapResult {
+			amount_in: 0,
+			amount_out: 0,
+			fee: 0,
+			sqrt_price: pool.sqrt_price,
+			tick: pool.tick,
+			liquidity: pool.liquidity,
+			fee_growth: if sell_a { pool.fee_growth_a } else { pool.fee_growth_b },
+			crossed_ticks: vec![],
+		};
+
+		let mut remaining = amount;
+
+		while !remaining.is_zero() {
+			let idx = ticks.partition_point(|t| *t <= swap.tick);
+			let next_tick = if sell_a {
+				idx.checked_sub(1).map(|i| ticks[i])
+			} else {
+				ticks.get(idx).copied()
+			};
+
+			let sqrt_price_target = match (next_tick, sell_a) {
+				(Some(tick), _) => math::sqrt_price_at_tick(tick).ok_or(Error::<T>::MathError)?,
+				(None, true) => math::MIN_SQRT_PRICE,
+				(None, false) => math::MAX_SQRT_PRICE,
+			};
+
+			let reached_target = if swap.liquidity.is_zero() {
+				swap.sqrt_price = sqrt_price_target;
+				true
+			} else {
+				let step = math::compute_swap_step(
+					swap.sqrt_price,
+					sqrt_price_target,
+					swap.liquidity,
+					remaining,
+					exact_in,
+					pool.fee,
+				)
+				.ok_or(Error::<T>::MathError)?;
+
+				let step_in = step.amount_in.checked_add(step.fee).ok_or(Error::<T>::Overflow)?;
+
+				remaining = remaining
+					.checked_sub(if exact_in { step_in } else { step.amount_out })
+					.ok_or(Error::<T>::MathError)?;
+
+				swap.amount_in = swap.amount_in.checked_add(step_in).ok_or(Error::<T>::Overflow)?;
+				swap.amount_out = swap
+					.amount_out
+					.checked_add(step.amount_out)
+					.ok_or(Error::<T>::Overflow)?;
+				swap.fee = swap.fee.checked_add(step.fee).ok_or(Error::<T>::Overflow)?;
+				swap.fee_growth = swap
+					.fee_growth
+					.wrapping_add(math::fee_growth(step.fee, swap.liquidity).ok_or(Error::<T>::Overflow)?);
+				swap.sqrt_price = step.sqrt_price;
+
+				step.reached_target
+			};
+
+			if reached_target {
+				let tick = next_tick.ok_or(Error::<T>::InsufficientLiquidity)?;
+
+				ensure!(
+					swap.crossed_ticks.len() < T::MaxTicksCrossed::get() as usize,
+					Error::<T>::TooManyTicksCrossed
+				);
+
+				let liquidity_net = Self::ticks(pool_id, tick).unwrap_or_default().liquidity_net;
+				let liquidity_net = if sell_a {
+					liquidity_net.checked_neg().ok_or(Error::<T>::Overflow)?
+				} else {
+					liquidity_net
+				};
+
+				swap.liquidity = if liquidity_net >= 0 {
+					swap.liquidity.checked_add(liquidity_net.unsigned_abs())
+				} else {
+					swap.liquidity.checked_sub(liquidity_net.unsigned_abs())
+				}
+				.ok_or(Error::<T>::MathError)?;
+
+				swap.tick = if sell_a { tick - 1 } else { tick };
+				swap.crossed_ticks.push((tick, swap.fee_growth));
+			} else {
+				swap.tick = math::tick_at_sqrt_price(swap.sqrt_price).ok_or(Error::<T>::MathError)?;
+			}
+		}
+
+		Ok(swap)
+	}
+
+	fn apply_swap(pool_id: PoolId, sell_a: bool, swap: &SwapResult) -> DispatchResult {
+		<Pools<T>>::try_mutate(pool_id, |maybe_pool| -> DispatchResult {
+			let pool = maybe_pool.as_mut().ok_or(Error::<T>::PoolNotFound)?;
+
+			for (tick, fee_growth) in swap.crossed_ticks.iter() {
+				let (fee_growth_a, fee_growth_b) = if sell_a {
+					(*fee_growth, pool.fee_growth_b)
+				} else {
+					(pool.fee_growth_a, *fee_growth)
+				};
+
+				<Ticks<T>>::mutate(pool_id, tick, |maybe_info| {
+					if let Some(info) = maybe_info {
+						info.fee_growth_outside_a = fee_growth_a.wrapping_sub(info.fee_growth_outside_a);
+						info.fee_growth_outside_b = fee_growth_b.wrapping_sub(info.fee_growth_outside_b);
+					}
+				});
+			}
+
+			pool.sqrt_price = swap.sqrt_price;
+			pool.tick = swap.tick;
+			pool.liquidity = swap.liquidity;
+			if sell_a {
+				pool.fee_growth_a = swap.fee_growth;
+			} else {
+				pool.fee_growth_b = swap.fee_growth;
+			}
+
+			Ok(())
+		})
+	}
+
+	fn pool_for_trade(
+		pool_id: PoolId,
+		asset_in: AssetId,
+		asset_out: AssetId,
+	) -> Result<(PoolInfo, bool), DispatchError> {
+		ensure!(asset_in != asset_out, Error::<T>::SameAssetTrade);
+
+		let pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+
+		let sell_a = pool.is_asset_a(asset_in).ok_or(Error::<T>::AssetNotInPool)?;
+		pool.is_asset_a(asset_out).ok_or(Error::<T>::AssetNotInPool)?;
+
+		Ok((pool, sell_a))
+	}
+
+	/// Amount of `asset_out` received for `amount_in` of `asset_in` and the fee paid in `asset_in`.
+	pub fn calculate_sell(
+		pool_id: PoolId,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_in: Balance,
+	) -> Result<(Balance, Balance), DispatchError> {
+		let (pool, sell_a) = Self::pool_for_trade(pool_id, asset_in, asset_out)?;
+
+		let swap = Self::calculate_swap(pool_id, &pool, sell_a, true, amount_in)?;
+
+		Ok((swap.amount_out, swap.fee))
+	}
+
+	/// Amount of `asset_in` needed to receive `amount_out` of `asset_out` and the fee, fee not included.
+	pub fn calculate_buy(
+		pool_id: PoolId,
+		asset_out: AssetId,
+		asset_in: AssetId,
+		amount_out: Balance,
+	) -> Result<(Balance, Balance), DispatchError> {
+		let (pool, sell_a) = Self::pool_for_trade(pool_id, asset_in, asset_out)?;
+
+		let swap = Self::calculate_swap(pool_id, &pool, sell_a, false, amount_out)?;
+
+		Ok((
+			swap.amount_in.checked_sub(swap.fee).ok_or(Error::<T>::Overflow)?,
+			swap.fee,
+		))
+	}
+
+	fn validate_pool_sell(
+		who: &T::AccountId,
+		pool_id: PoolId,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_in: Balance,
+		min_buy_amount: Balance,
+	) -> Result<AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>, DispatchError> {
+		ensure!(
+			amount_in >= T::MinTradingLimit::get(),
+			Error::<T>::InsufficientTradingAmount
+		);
+
+		ensure!(
+			T::MultiCurrency::free_balance(asset_in, who) >= amount_in,
+			Error::<T>::InsufficientBalance
+		);
+
+		let (amount_out, fee) = Self::calculate_sell(pool_id, asset_in, asset_out, amount_in)?;
+
+		ensure!(
+			!amount_out.is_zero() && amount_out >= min_buy_amount,
+			Error::<T>::BuyLimitNotReached
+		);
+
+		Ok(AMMTransfer {
+			origin: who.clone(),
+			assets: AssetPair { asset_in, asset_out },
+			amount: amount_in,
+			amount_out,
+			discount: false,
+			discount_amount: Balance::zero(),
+			fee: (asset_in, fee),
+		})
+	}
+
+	fn validate_pool_buy(
+		who: &T::AccountId,
+		pool_id: PoolId,
+		asset_out: AssetId,
+		asset_in: AssetId,
+		amount_out: Balance,
+		max_sell_amount: Balance,
+	) -> Result<AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>, DispatchError> {
+		ensure!(
+			amount_out >= T::MinTradingLimit::get(),
+			Error::<T>::InsufficientTradingAmount
+		);
+
+		let (amount_in, fee) = Self::calculate_buy(pool_id, asset_out, asset_in, amount_out)?;
+
+		let amount_in_with_fee = amount_in.checked_add(fee).ok_or(Error::<T>::Overflow)?;
+
+		ensure!(amount_in_with_fee <= max_sell_amount, Error::<T>::SellLimitExceeded);
+
+		ensure!(
+			T::MultiCurrency::free_balance(asset_in, who) >= amount_in_with_fee,
+			Error::<T>::InsufficientBalance
+		);
+
+		Ok(AMMTransfer {
+			origin: who.clone(),
+			assets: AssetPair { asset_in, asset_out },
+			amount: amount_out,
+			amount_out: amount_in,
+			discount: false,
+			discount_amount: Balance::zero(),
+			fee: (asset_in, fee),
+		})
+	}
+
+	fn execute_pool_sell(
+		pool_id: PoolId,
+		transfer: &AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>,
+	) -> DispatchResult {
+		let (pool, sell_a) = Self::pool_for_trade(pool_id, transfer.assets.asset_in, transfer.assets.asset_out)?;
+		let pool_account = Self::pool_account(pool_id);
+
+		let swap = Self::calculate_swap(pool_id, &pool, sell_a, true, transfer.amount)?;
+		Self::apply_swap(pool_id, sell_a, &swap)?;
+
+		T::MultiCurrency::transfer(
+			transfer.assets.asset_in,
+			&transfer.origin,
+			&pool_account,
+			transfer.amount,
+		)?;
+		T::MultiCurrency::transfer(
+			transfer.assets.asset_out,
+			&pool_account,
+			&transfer.origin,
+			transfer.amount_out,
+		)?;
+
+		Self::deposit_event(Event::SellExecuted(
+			transfer.origin.clone(),
+			pool_id,
+			transfer.assets.asset_in,
+			transfer.assets.asset_out,
+			transfer.amount,
+			transfer.amount_out,
+			transfer.fee.1,
+		));
+
+		Ok(())
+	}
+
+	fn execute_pool_buy(
+		pool_id: PoolId,
+		transfer: &AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>,
+	) -> DispatchResult {
+		let (pool, sell_a) = Self::pool_for_trade(pool_id, transfer.assets.asset_in, transfer.assets.asset_out)?;
+		let pool_account = Self::pool_account(pool_id);
+
+		let swap = Self::calculate_swap(pool_id, &pool, sell_a, false, transfer.amount)?;
+		Self::apply_swap(pool_id, sell_a, &swap)?;
+
+		let amount_in = transfer
+			.amount_out
+			.checked_add(transfer.fee.1)
+			.ok_or(Error::<T>::Overflow)?;
+
+		T::MultiCurrency::transfer(transfer.assets.asset_in, &transfer.origin, &pool_account, amount_in)?;
+		T::MultiCurrency::transfer(
+			transfer.assets.asset_out,
+			&pool_account,
+			&transfer.origin,
+			transfer.amount,
+		)?;
+
+		Self::deposit_event(Event::BuyExecuted(
+			transfer.origin.clone(),
+			pool_id,
+			transfer.assets.asset_out,
+			transfer.assets.asset_in,
+			transfer.amount,
+			amount_in,
+			transfer.fee.1,
+		));
+
+		Ok(())
+	}
+
+	/// Pool used for trades of an asset pair through the AMM Api.
+	pub(crate) fn pool_for_pair(assets: AssetPair) -> Option<PoolId> {
+		let (asset_a, asset_b) = assets.ordered_pair();
+		Self::pair_pool(asset_a, asset_b)
+	}
+
+	/// Value of `amount` of `asset_a` denominated in `asset_b` at the current price of a pool.
+	pub fn spot_price(pool_id: PoolId, asset_a: AssetId, asset_b: AssetId, amount: Balance) -> Option<Balance> {
+		let pool = Self::pools(pool_id)?;
+
+		let one = U256::from(math::SQRT_PRICE_ONE);
+		let sqrt_price = U256::from(pool.sqrt_price);
+		let price_numerator = sqrt_price.checked_mul(sqrt_price)?;
+		let price_denominator = one.checked_mul(one)?;
+
+		let (numerator, denominator) = match (pool.is_asset_a(asset_a)?, pool.is_asset_a(asset_b)?) {
+			(true, false) => (price_numerator, price_denominator),
+			(false, true) => (price_denominator, price_numerator),
+			_ => return None,
+		};
+
+		U256::from(amount)
+			.checked_mul(numerator)?
+			.checked_div(denominator)?
+			.try_into()
+			.ok()
+	}
+}
+
+impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
+	fn exists(assets: AssetPair) -> bool {
+		Self::pool_for_pair(assets).is_some()
+	}
+
+	fn get_pair_id(assets: AssetPair) -> T::AccountId {
+		Self::pool_account(Self::pool_for_pair(assets).unwrap_or_default())
+	}
+
+	/// Liquidity of concentrated liquidity pools is represented by position NFTs, there is no share token.
+	fn get_share_token(_assets: AssetPair) -> AssetId {
+		AssetId::default()
+	}
+
+	fn get_pool_assets(pool_account_id: &T::AccountId) -> Option<Vec<AssetId>> {
+		Self::pool_id_by_account(pool_account_id)
+			.and_then(Self::pools)
+			.map(|pool| vec![pool.assets.0, pool.assets.1])
+	}
+
+	fn get_spot_price_unchecked(asset_a: AssetId, asset_b: AssetId, amount: Balance) -> Balance {
+		Self::pool_for_pair(AssetPair {
+			asset_in: asset_a,
+			asset_out: asset_b,
+		})
+		.and_then(|pool_id| Self::spot_price(pool_id, asset_a, asset_b, amount))
+		.unwrap_or_else(Balance::zero)
+	}
+
+	/// Validate a sell. Perform all necessary checks and calculations.
+	/// No storage changes are performed yet.
+	///
+	/// Return `AMMTransfer` with all info needed to execute the transaction.
+	fn validate_sell(
+		who: &T::AccountId,
+		assets: AssetPair,
+		amount: Balance,
+		min_bought: Balance,
+		discount: bool,
+	) -> Result<AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>, DispatchError> {
+		ensure!(!discount, Error::<T>::DiscountNotSupported);
+
+		let pool_id = Self::pool_for_pair(assets).ok_or(Error::<T>::PoolNotFound)?;
+
+		Self::validate_pool_sell(who, pool_id, assets.asset_in, assets.asset_out, amount, min_bought)
+	}
+
+	/// Execute sell. validate_sell must be called first.
+	/// Perform necessary storage/state changes.
+	#[transactional]
+	fn execute_sell(transfer: &AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>) -> DispatchResult {
+		let pool_id = Self::pool_for_pair(transfer.assets).ok_or(Error::<T>::PoolNotFound)?;
+
+		Self::execute_pool_sell(pool_id, transfer)
+	}
+
+	/// Validate a buy. Perform all necessary checks and calculations.
+	/// No storage changes are performed yet.
+	///
+	/// Return `AMMTransfer` with all info needed to execute the transaction.
+	fn validate_buy(
+		who: &T::AccountId,
+		assets: AssetPair,
+		amount: Balance,
+		max_limit: Balance,
+		discount: bool,
+	) -> Result<AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>, DispatchError> {
+		ensure!(!discount, Error::<T>::DiscountNotSupported);
+
+		let pool_id = Self::pool_for_pair(assets).ok_or(Error::<T>::PoolNotFound)?;
+
+		Self::validate_pool_buy(who, pool_id, assets.asset_out, assets.asset_in, amount, max_limit)
+	}
+
+	/// Execute buy. validate_buy must be called first.
+	/// Perform necessary storage/state changes.
+	#[transactional]
+	fn execute_buy(transfer: &AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>) -> DispatchResult {
+		let pool_id = Self::pool_for_pair(transfer.assets).ok_or(Error::<T>::PoolNotFound)?;
+
+		Self::execute_pool_buy(pool_id, transfer)
+	}
+
+	fn get_min_trading_limit() -> Balance {
+		T::MinTradingLimit::get()
+	}
+
+	/// There is no minimum liquidity of a position.
+	fn get_min_pool_liquidity() -> Balance {
+		Balance::zero()
+	}
+
+	/// Trades are limited by `MaxTicksCrossed` instead of a fraction of the pool reserves.
+	fn get_max_in_ratio() -> u128 {
+		1
+	}
+
+	/// Trades are limited by `MaxTicksCrossed` instead of a fraction of the pool reserves.
+	fn get_max_out_ratio() -> u128 {
+		1
+	}
+
+	fn get_fee(pool_account_id: &T::AccountId) -> (u32, u32) {
+		Self::pool_id_by_account(pool_account_id)
+			.and_then(Self::pools)
+			.map(|pool| (pool.fee.deconstruct(), Permill::one().deconstruct()))
+			.unwrap_or((0, 1))
+	}
+}
@@ -0,0 +1,349 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2021  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Concentrated liquidity math.
+//!
+//! Price of a pool is the price of asset A denominated in asset B. Prices are represented by their
+//! square roots, fixed point numbers with 18 decimals. Price at tick `i` is `1.0001^i`.
+//!
+//! Liquidity `L` provided between square root prices `sa < sb` is backed by
+//! `L * (sb - sa) / (sa * sb)` of asset A and `L * (sb - sa)` of asset B.
+//!
+//! Amounts paid to the pool are rounded up, amounts paid by the pool are rounded down.
+//! All functions return `None` if the calculation overflows.
+
+use primitives::Balance;
+use sp_core::U256;
+use sp_runtime::Permill;
+
+/// Fixed point accuracy of square root prices and fee growth.
+pub const SQRT_PRICE_ONE: u128 = 1_000_000_000_000_000_000;
+
+/// Lowest tick, price of about `10^-12`.
+pub const MIN_TICK: i32 = -276_324;
+
+/// Highest tick, price of about `10^12`.
+pub const MAX_TICK: i32 = 276_324;
+
+/// Square root price at `MIN_TICK`.
+pub const MIN_SQRT_PRICE: u128 = 1_000_001_321_914;
+
+/// Square root price at `MAX_TICK`.
+pub const MAX_SQRT_PRICE: u128 = 999_998_678_087_145_848_523_531;
+
+/// `sqrt(1.0001)^(2^i)` for bits of the tick.
+const TICK_FACTORS: [u128; 19] = [
+	1_000_049_998_750_062_496,
+	1_000_100_000_000_000_000,
+	1_000_200_010_000_000_000,
+	1_000_400_060_004_000_100,
+	1_000_800_280_056_007_001,
+	1_001_601_200_560_182_044,
+	1_003_204_964_963_598_015,
+	1_006_420_201_727_613_920,
+	1_012_881_622_445_451_097,
+	1_025_929_181_087_729_344,
+	1_052_530_684_607_338_948,
+	1_107_820_842_039_993_614,
+	1_227_267_018_058_200_482,
+	1_506_184_333_613_467_388,
+	2_268_591_246_822_644_827,
+	5_146_506_245_160_322_223,
+	26_486_526_531_474_198_664,
+	701_536_087_702_486_644_953,
+	492_152_882_348_911_033_633_684,
+];
+
+const PPM: u128 = 1_000_000;
+
+/// Square root price at `tick`.
+pub fn sqrt_price_at_tick(tick: i32) -> Option<u128> {
+	if !(MIN_TICK..=MAX_TICK).contains(&tick) {
+		return None;
+	}
+
+	let one = U256::from(SQRT_PRICE_ONE);
+	let abs_tick = tick.unsigned_abs();
+
+	let mut sqrt_price = one;
+	for (bit, factor) in TICK_FACTORS.iter().enumerate() {
+		if abs_tick & (1 << bit) != 0 {
+			sqrt_price = sqrt_price.checked_mul(U256::from(*factor))? / one;
+		}
+	}
+
+	if tick < 0 {
+		sqrt_price = one.checked_mul(one)?.checked_div(sqrt_price)?;
+	}
+
+	sqrt_price.try_into().ok()
+}
+
+/// Highest tick with square root price lower than or equal to `sqrt_price`.
+pub fn tick_at_sqrt_price(sqrt_price: u128) -> Option<i32> {
+	let (mut low, mut high) = (MIN_TICK, MAX_TICK);
+
+	while low < high {
+		let mid = low + (high - low + 1) / 2;
+		if sqrt_price_at_tick(mid)? <= sqrt_price {
+			low = mid;
+		} else {
+			high = mid - 1;
+		}
+	}
+
+	Some(low)
+}
+
+/// Square root of `price` given as inner value of 18 decimals fixed point number.
+pub fn sqrt_price_from_price(price: u128) -> Option<u128> {
+	U256::from(price)
+		.checked_mul(U256::from(SQRT_PRICE_ONE))?
+		.integer_sqrt()
+		.try_into()
+		.ok()
+}
+
+fn div_round(numerator: U256, denominator: U256, round_up: bool) -> Option<U256> {
+	let result = numerator.checked_div(denominator)?;
+	if round_up && !(numerator % denominator).is_zero() {
+		result.checked_add(U256::one())
+	} else {
+		Some(result)
+	}
+}
+
+fn sorted(sqrt_price_a: u128, sqrt_price_b: u128) -> (u128, u128) {
+	if sqrt_price_a > sqrt_price_b {
+		(sqrt_price_b, sqrt_price_a)
+	} else {
+		(sqrt_price_a, sqrt_price_b)
+	}
+}
+
+/// Amount of asset A backing `liquidity` between two square root prices.
+pub fn amount_a_delta(sqrt_price_a: u128, sqrt_price_b: u128, liquidity: u128, round_up: bool) -> Option<Balance> {
+	let (lower, upper) = sorted(sqrt_price_a, sqrt_price_b);
+
+	let numerator = U256::from(liquidity)
+		.checked_mul(U256::from(upper - lower))?
+		.checked_mul(U256::from(SQRT_PRICE_ONE))?;
+	let denominator = U256::from(lower).checked_mul(U256::from(upper))?;
+
+	div_round(numerator, denominator, round_up)?.try_into().ok()
+}
+
+/// Amount of asset B backing `liquidity` between two square root prices.
+pub fn amount_b_delta(sqrt_price_a: u128, sqrt_price_b: u128, liquidity: u128, round_up: bool) -> Option<Balance> {
+	let (lower, upper) = sorted(sqrt_price_a, sqrt_price_b);
+
+	let numerator = U256::from(liquidity).checked_mul(U256::from(upper - lower))?;
+
+	div_round(numerator, U256::from(SQRT_PRICE_ONE), round_up)?
+		.try_into()
+		.ok()
+}
+
+/// Square root price after `amount_in` is paid to the pool.
+///
+/// Selling asset A moves the price down, selling asset B moves the price up.
+pub fn next_sqrt_price_from_input(sqrt_price: u128, liquidity: u128, amount_in: Balance, sell_a: bool) -> Option<u128> {
+	let one = U256::from(SQRT_PRICE_ONE);
+	let sqrt_price = U256::from(sqrt_price);
+	let liquidity = U256::from(liquidity);
+
+	let next = if sell_a {
+		let numerator = liquidity.checked_mul(sqrt_price)?.checked_mul(one)?;
+		let denominator = liquidity
+			.checked_mul(one)?
+			.checked_add(U256::from(amount_in).checked_mul(sqrt_price)?)?;
+		div_round(numerator, denominator, true)?
+	} else {
+		sqrt_price.checked_add(U256::from(amount_in).checked_mul(one)?.checked_div(liquidity)?)?
+	};
+
+	next.try_into().ok()
+}
+
+/// Square root price after `amount_out` is paid by the pool.
+///
+/// Buying asset B moves the price down, buying asset A moves the price up.
+pub fn next_sqrt_price_from_output(
+	sqrt_price: u128,
+	liquidity: u128,
+	amount_out: Balance,
+	sell_a: bool,
+) -> Option<u128> {
+	let one = U256::from(SQRT_PRICE_ONE);
+	let sqrt_price = U256::from(sqrt_price);
+	let liquidity = U256::from(liquidity);
+
+	let next = if sell_a {
+		sqrt_price.checked_sub(div_round(U256::from(amount_out).checked_mul(one)?, liquidity, true)?)?
+	} else {
+		let numerator = liquidity.checked_mul(sqrt_price)?.checked_mul(one)?;
+		let denominator = liquidity
+			.checked_mul(one)?
+			.checked_sub(U256::from(amount_out).checked_mul(sqrt_price)?)?;
+		if denominator.is_zero() {
+			return None;
+		}
+		div_round(numerator, denominator, true)?
+	};
+
+	next.try_into().ok()
+}
+
+/// Result of a swap within a range of constant liquidity.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SwapStep {
+	/// Square root price after the step.
+	pub sqrt_price: u128,
+	/// Amount paid to the pool, fee not included.
+	pub amount_in: Balance,
+	/// Amount paid by the pool.
+	pub amount_out: Balance,
+	/// Fee paid to the pool in the sold asset.
+	pub fee: Balance,
+	/// Whether the target price was reached.
+	pub reached_target: bool,
+}
+
+/// Swap within a range of constant `liquidity` from `sqrt_price` towards `sqrt_price_target`.
+///
+/// `amount_remaining` is the amount paid to the pool including fee if `exact_in`, amount paid
+/// by the pool otherwise. Fee is charged in the sold asset.
+pub fn compute_swap_step(
+	sqrt_price: u128,
+	sqrt_price_target: u128,
+	liquidity: u128,
+	amount_remaining: Balance,
+	exact_in: bool,
+	fee: Permill,
+) -> Option<SwapStep> {
+	let sell_a = sqrt_price_target <= sqrt_price;
+	let fee_rate = fee.deconstruct() as u128;
+
+	let amount_in_to = |next: u128| {
+		if sell_a {
+			amount_a_delta(next, sqrt_price, liquidity, true)
+		} else {
+			amount_b_delta(sqrt_price, next, liquidity, true)
+		}
+	};
+	let amount_out_to = |next: u128| {
+		if sell_a {
+			amount_b_delta(next, sqrt_price, liquidity, false)
+		} else {
+			amount_a_delta(sqrt_price, next, liquidity, false)
+		}
+	};
+	let clamp = |next: u128| {
+		if sell_a {
+			next.max(sqrt_price_target)
+		} else {
+			next.min(sqrt_price_target)
+		}
+	};
+
+	if exact_in {
+		let max_in = amount_in_to(sqrt_price_target)?;
+		let max_in_with_fee = div_round(
+			U256::from(max_in).checked_mul(U256::from(PPM))?,
+			U256::from(PPM.checked_sub(fee_rate)?),
+			true,
+		)?
+		.try_into()
+		.ok()?;
+
+		if amount_remaining >= max_in_with_fee {
+			return Some(SwapStep {
+				sqrt_price: sqrt_price_target,
+				amount_in: max_in,
+				amount_out: amount_out_to(sqrt_price_target)?,
+				fee: max_in_with_fee - max_in,
+				reached_target: true,
+			});
+		}
+
+		let amount_in = multiply_by_rational(amount_remaining, PPM - fee_rate, PPM)?;
+		let next = clamp(next_sqrt_price_from_input(sqrt_price, liquidity, amount_in, sell_a)?);
+
+		Some(SwapStep {
+			sqrt_price: next,
+			amount_in,
+			amount_out: amount_out_to(next)?,
+			fee: amount_remaining - amount_in,
+			reached_target: next == sqrt_price_target,
+		})
+	} else {
+		let max_out = amount_out_to(sqrt_price_target)?;
+
+		let (next, amount_out) = if amount_remaining >= max_out {
+			(sqrt_price_target, max_out)
+		} else {
+			(
+				clamp(next_sqrt_price_from_output(
+					sqrt_price,
+					liquidity,
+					amount_remaining,
+					sell_a,
+				)?),
+				amount_remaining,
+			)
+		};
+
+		let amount_in = amount_in_to(next)?;
+		let fee = div_round(
+			U256::from(amount_in).checked_mul(U256::from(fee_rate))?,
+			U256::from(PPM - fee_rate),
+			true,
+		)?
+		.try_into()
+		.ok()?;
+
+		Some(SwapStep {
+			sqrt_price: next,
+			amount_in,
+			amount_out,
+			fee,
+			reached_target: next == sqrt_price_target,
+		})
+	}
+}
+
+/// Growth of fees per unit of liquidity caused by `fee` paid to `liquidity`.
+pub fn fee_growth(fee: Balance, liquidity: u128) -> Option<u128> {
+	U256::from(fee)
+		.checked_mul(U256::from(SQRT_PRICE_ONE))?
+		.checked_div(U256::from(liquidity))?
+		.try_into()
+		.ok()
+}
+
+/// Fees earned by `liquidity` while fees per unit of liquidity grew by `fee_growth`.
+pub fn fees_earned(fee_growth: u128, liquidity: u128) -> Option<Balance> {
+	(U256::from(fee_growth).checked_mul(U256::from(liquidity))? / U256::from(SQRT_PRICE_ONE))
+		.try_into()
+		.ok()
+}
+
+fn multiply_by_rational(a: u128, b: u128, c: u128) -> Option<u128> {
+	(U256::from(a).checked_mul(U256::from(b))?.checked_div(U256::from(c))?)
+		.try_into()
+		.ok()
+}
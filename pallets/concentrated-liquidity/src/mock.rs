@@ -0,0 +1,234 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2021  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate as concentrated_liquidity;
+use crate::Config;
+use frame_support::{
+	parameter_types,
+	traits::{Everything, GenesisBuild, Nothing},
+	PalletId,
+};
+use frame_system as system;
+use orml_traits::parameter_type_with_key;
+use primitives::{
+	nft::{ClassType, NftPermissions},
+	Amount, AssetId, Balance, ReserveIdentifier,
+};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+use frame_system::EnsureRoot;
+
+pub type AccountId = u128;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+
+pub const HDX: AssetId = 1000;
+pub const DOT: AssetId = 2000;
+pub const KSM: AssetId = 3000;
+
+pub const ONE: Balance = 1_000_000_000_000;
+
+pub const POSITION_NFT_CLASS: primitives::ClassId = 1;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+	Block = Block,
+	NodeBlock = Block,
+	UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		ConcentratedLiquidity: concentrated_liquidity::{Pallet, Call, Storage, Event<T>},
+		Tokens: orml_tokens::{Pallet, Call, Storage, Event<T>},
+		NFT: pallet_nft::{Pallet, Call, Event<T>, Storage},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Uniques: pallet_uniques::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 63;
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u128>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+parameter_types! {
+	pub const ConcentratedLiquidityPalletId: PalletId = PalletId(*b"concliq_");
+	pub const NftClass: primitives::ClassId = POSITION_NFT_CLASS;
+	pub const MaxInitializedTicks: u32 = 100;
+	pub const MaxTicksCrossed: u32 = 3;
+	pub const MinTradingLimit: Balance = 1_000;
+}
+
+impl Config for Test {
+	type Event = Event;
+	type MultiCurrency = Tokens;
+	type PalletId = ConcentratedLiquidityPalletId;
+	type CreatePoolOrigin = EnsureRoot<AccountId>;
+	type NftClass = NftClass;
+	type MaxInitializedTicks = MaxInitializedTicks;
+	type MaxTicksCrossed = MaxTicksCrossed;
+	type MinTradingLimit = MinTradingLimit;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub ReserveClassIdUpTo: u128 = 999;
+}
+
+impl pallet_nft::Config for Test {
+	type Currency = Balances;
+	type Event = Event;
+	type WeightInfo = pallet_nft::weights::BasiliskWeight<Test>;
+	type NftClassId = primitives::ClassId;
+	type NftInstanceId = primitives::InstanceId;
+	type ProtocolOrigin = EnsureRoot<AccountId>;
+	type ClassType = ClassType;
+	type Permissions = NftPermissions;
+	type ReserveClassIdUpTo = ReserveClassIdUpTo;
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u128 = 500;
+	pub const MaxReserves: u32 = 50;
+}
+
+impl pallet_balances::Config for Test {
+	type Balance = Balance;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = frame_system::Pallet<Test>;
+	type MaxLocks = ();
+	type WeightInfo = ();
+	type MaxReserves = MaxReserves;
+	type ReserveIdentifier = ReserveIdentifier;
+}
+
+parameter_types! {
+	pub const ClassDeposit: Balance = 1_000_000_000_000_000;
+	pub const InstanceDeposit: Balance = 10_000_000_000_000;
+	pub const KeyLimit: u32 = 32;
+	pub const ValueLimit: u32 = 64;
+	pub const UniquesMetadataDepositBase: Balance = 100_000_000_000_000;
+	pub const AttributeDepositBase: Balance = 10_000_000_000_000;
+	pub const DepositPerByte: Balance = 1_000_000_000_000;
+	pub const UniquesStringLimit: u32 = 32;
+}
+
+impl pallet_uniques::Config for Test {
+	type Event = Event;
+	type ClassId = primitives::ClassId;
+	type InstanceId = primitives::InstanceId;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type ClassDeposit = ClassDeposit;
+	type InstanceDeposit = InstanceDeposit;
+	type MetadataDepositBase = UniquesMetadataDepositBase;
+	type AttributeDepositBase = AttributeDepositBase;
+	type DepositPerByte = DepositPerByte;
+	type StringLimit = UniquesStringLimit;
+	type KeyLimit = KeyLimit;
+	type ValueLimit = ValueLimit;
+	type WeightInfo = ();
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: AssetId| -> Balance {
+		1u128
+	};
+}
+
+impl orml_tokens::Config for Test {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = Amount;
+	type CurrencyId = AssetId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+	type MaxLocks = ();
+	type DustRemovalWhitelist = Nothing;
+}
+
+pub struct ExtBuilder {
+	endowed_accounts: Vec<(AccountId, AssetId, Balance)>,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self {
+			endowed_accounts: vec![
+				(ALICE, HDX, 1_000_000 * ONE),
+				(ALICE, DOT, 1_000_000 * ONE),
+				(ALICE, KSM, 1_000_000 * ONE),
+				(BOB, HDX, 1_000_000 * ONE),
+				(BOB, DOT, 1_000_000 * ONE),
+				(CHARLIE, HDX, 1_000_000 * ONE),
+				(CHARLIE, DOT, 1_000_000 * ONE),
+			],
+		}
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+		orml_tokens::GenesisConfig::<Test> {
+			balances: self.endowed_accounts,
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		t.into()
+	}
+}
@@ -0,0 +1,731 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2021  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+pub use crate::mock::{
+	ConcentratedLiquidity, Event as TestEvent, ExtBuilder, Origin, System, Test, Tokens, ALICE, BOB, CHARLIE, DOT, HDX,
+	KSM, NFT, ONE, POSITION_NFT_CLASS,
+};
+use frame_support::sp_runtime::traits::One;
+use frame_support::{assert_noop, assert_ok};
+use hydradx_traits::AMM as AmmPool;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext = ExtBuilder::default().build();
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		migration::init_nft_class::<Test>();
+	});
+	ext
+}
+
+fn expect_events(e: Vec<TestEvent>) {
+	e.into_iter().for_each(frame_system::Pallet::<Test>::assert_has_event);
+}
+
+const FEE: Permill = Permill::from_parts(3_000);
+const LIQUIDITY: u128 = 100_000_000_000_000_000;
+
+/// Creates pool of HDX and DOT with price 1 and tick spacing 10.
+///
+/// ALICE provides liquidity in range [-1000, 1000] as position 0, BOB provides the same liquidity
+/// in range [500, 1500] as position 1.
+fn create_pool_with_positions() -> PoolId {
+	assert_ok!(ConcentratedLiquidity::create_pool(
+		Origin::root(),
+		HDX,
+		DOT,
+		FEE,
+		10,
+		Price::one()
+	));
+
+	let pool_id = ConcentratedLiquidity::pool_count();
+
+	assert_ok!(ConcentratedLiquidity::mint_position(
+		Origin::signed(ALICE),
+		pool_id,
+		-1_000,
+		1_000,
+		LIQUIDITY,
+		Balance::MAX,
+		Balance::MAX
+	));
+	assert_ok!(ConcentratedLiquidity::mint_position(
+		Origin::signed(BOB),
+		pool_id,
+		500,
+		1_500,
+		LIQUIDITY,
+		Balance::MAX,
+		Balance::MAX
+	));
+
+	pool_id
+}
+
+#[test]
+fn create_pool_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ConcentratedLiquidity::create_pool(
+			Origin::root(),
+			HDX,
+			DOT,
+			FEE,
+			10,
+			Price::one()
+		));
+
+		let pool = ConcentratedLiquidity::pools(1).unwrap();
+		let pool_account = ConcentratedLiquidity::pool_account(1);
+
+		assert_eq!(pool.assets, (HDX, DOT));
+		assert_eq!(pool.sqrt_price, math::SQRT_PRICE_ONE);
+		assert_eq!(pool.tick, 0);
+		assert_eq!(pool.liquidity, 0);
+
+		assert_eq!(ConcentratedLiquidity::pool_id_by_account(&pool_account), Some(1));
+		assert_eq!(ConcentratedLiquidity::pair_pool(HDX, DOT), Some(1));
+
+		expect_events(vec![Event::PoolCreated(
+			1,
+			HDX,
+			DOT,
+			FEE,
+			10,
+			math::SQRT_PRICE_ONE,
+			pool_account,
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn create_pool_should_invert_price_of_unordered_assets() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ConcentratedLiquidity::create_pool(
+			Origin::root(),
+			DOT,
+			HDX,
+			FEE,
+			10,
+			Price::from(2)
+		));
+
+		let pool = ConcentratedLiquidity::pools(1).unwrap();
+
+		assert_eq!(pool.assets, (HDX, DOT));
+		assert_eq!(pool.sqrt_price, 707_106_781_186_547_524);
+		assert_eq!(pool.tick, -6_932);
+	});
+}
+
+#[test]
+fn create_pool_with_invalid_params_should_not_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ConcentratedLiquidity::create_pool(Origin::signed(ALICE), HDX, DOT, FEE, 10, Price::one()),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			ConcentratedLiquidity::create_pool(Origin::root(), HDX, HDX, FEE, 10, Price::one()),
+			Error::<Test>::IdenticalAssets
+		);
+		assert_noop!(
+			ConcentratedLiquidity::create_pool(Origin::root(), HDX, DOT, Permill::one(), 10, Price::one()),
+			Error::<Test>::InvalidFee
+		);
+		assert_noop!(
+			ConcentratedLiquidity::create_pool(Origin::root(), HDX, DOT, FEE, 0, Price::one()),
+			Error::<Test>::InvalidTickSpacing
+		);
+		assert_noop!(
+			ConcentratedLiquidity::create_pool(Origin::root(), HDX, DOT, FEE, 10, Price::from(10_000_000_000_000)),
+			Error::<Test>::InvalidInitialPrice
+		);
+		assert_noop!(
+			ConcentratedLiquidity::create_pool(Origin::root(), HDX, DOT, FEE, 10, Price::zero()),
+			Error::<Test>::InvalidInitialPrice
+		);
+	});
+}
+
+#[test]
+fn mint_position_should_work() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_positions();
+
+		let pool = ConcentratedLiquidity::pools(pool_id).unwrap();
+		let pool_account = ConcentratedLiquidity::pool_account(pool_id);
+
+		// BOB's range is above the current price, only ALICE's liquidity is in range
+		assert_eq!(pool.liquidity, LIQUIDITY);
+		assert_eq!(
+			ConcentratedLiquidity::initialized_ticks(pool_id).into_inner(),
+			vec![-1_000, 500, 1_000, 1_500]
+		);
+		assert_eq!(
+			ConcentratedLiquidity::ticks(pool_id, 1_000).unwrap().liquidity_net,
+			-(LIQUIDITY as i128)
+		);
+		assert_eq!(
+			ConcentratedLiquidity::ticks(pool_id, 500).unwrap().liquidity_net,
+			LIQUIDITY as i128
+		);
+
+		assert_eq!(
+			ConcentratedLiquidity::positions(0).unwrap(),
+			Position {
+				pool_id,
+				lower_tick: -1_000,
+				upper_tick: 1_000,
+				liquidity: LIQUIDITY,
+				fee_growth_inside_a_last: 0,
+				fee_growth_inside_b_last: 0,
+				fees_owed_a: 0,
+				fees_owed_b: 0,
+			}
+		);
+		assert_eq!(NFT::owner(POSITION_NFT_CLASS, 0), Some(ALICE));
+		assert_eq!(NFT::owner(POSITION_NFT_CLASS, 1), Some(BOB));
+
+		assert_eq!(
+			Tokens::free_balance(HDX, &ALICE),
+			1_000_000 * ONE - 4_876_819_758_127_889
+		);
+		assert_eq!(
+			Tokens::free_balance(DOT, &ALICE),
+			1_000_000 * ONE - 4_876_819_758_127_889
+		);
+		assert_eq!(Tokens::free_balance(HDX, &BOB), 1_000_000 * ONE - 4_756_416_594_398_436);
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 1_000_000 * ONE);
+		assert_eq!(
+			Tokens::free_balance(HDX, &pool_account),
+			4_876_819_758_127_889 + 4_756_416_594_398_436
+		);
+		assert_eq!(Tokens::free_balance(DOT, &pool_account), 4_876_819_758_127_889);
+
+		expect_events(vec![
+			Event::PositionMinted(
+				ALICE,
+				0,
+				pool_id,
+				-1_000,
+				1_000,
+				LIQUIDITY,
+				4_876_819_758_127_889,
+				4_876_819_758_127_889,
+			)
+			.into(),
+			Event::PositionMinted(BOB, 1, pool_id, 500, 1_500, LIQUIDITY, 4_756_416_594_398_436, 0).into(),
+		]);
+	});
+}
+
+#[test]
+fn mint_position_with_invalid_params_should_not_work() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_positions();
+
+		assert_noop!(
+			ConcentratedLiquidity::mint_position(Origin::signed(ALICE), 2, -10, 10, LIQUIDITY, ONE, ONE),
+			Error::<Test>::PoolNotFound
+		);
+		assert_noop!(
+			ConcentratedLiquidity::mint_position(Origin::signed(ALICE), pool_id, -10, 10, 0, ONE, ONE),
+			Error::<Test>::ZeroLiquidity
+		);
+		assert_noop!(
+			ConcentratedLiquidity::mint_position(Origin::signed(ALICE), pool_id, 10, -10, LIQUIDITY, ONE, ONE),
+			Error::<Test>::InvalidTickRange
+		);
+		assert_noop!(
+			ConcentratedLiquidity::mint_position(Origin::signed(ALICE), pool_id, -15, 10, LIQUIDITY, ONE, ONE),
+			Error::<Test>::InvalidTickRange
+		);
+		assert_noop!(
+			ConcentratedLiquidity::mint_position(Origin::signed(ALICE), pool_id, -276_330, 10, LIQUIDITY, ONE, ONE),
+			Error::<Test>::InvalidTickRange
+		);
+		assert_noop!(
+			ConcentratedLiquidity::mint_position(
+				Origin::signed(ALICE),
+				pool_id,
+				-1_000,
+				1_000,
+				LIQUIDITY,
+				4_876_819_758_127_888,
+				Balance::MAX
+			),
+			Error::<Test>::MaxAmountExceeded
+		);
+		assert_noop!(
+			ConcentratedLiquidity::mint_position(
+				Origin::signed(BOB),
+				pool_id,
+				-1_000,
+				1_000,
+				1_000 * LIQUIDITY,
+				Balance::MAX,
+				Balance::MAX
+			),
+			Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn sell_should_work() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_positions();
+
+		assert_ok!(ConcentratedLiquidity::sell(
+			Origin::signed(CHARLIE),
+			pool_id,
+			DOT,
+			HDX,
+			10 * ONE,
+			9 * ONE
+		));
+
+		let pool = ConcentratedLiquidity::pools(pool_id).unwrap();
+
+		assert_eq!(pool.sqrt_price, 1_000_099_700_000_000_000);
+		assert_eq!(pool.tick, 1);
+		assert_eq!(pool.liquidity, LIQUIDITY);
+		assert_eq!(pool.fee_growth_a, 0);
+		assert_eq!(pool.fee_growth_b, 300_000_000_000);
+
+		assert_eq!(Tokens::free_balance(DOT, &CHARLIE), 999_990 * ONE);
+		assert_eq!(Tokens::free_balance(HDX, &CHARLIE), 1_000_000 * ONE + 9_969_006_090_092);
+
+		expect_events(vec![Event::SellExecuted(
+			CHARLIE,
+			pool_id,
+			DOT,
+			HDX,
+			10 * ONE,
+			9_969_006_090_092,
+			30_000_000_000,
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn sell_of_asset_a_should_move_price_down() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_positions();
+
+		assert_ok!(ConcentratedLiquidity::sell(
+			Origin::signed(CHARLIE),
+			pool_id,
+			HDX,
+			DOT,
+			10 * ONE,
+			9 * ONE
+		));
+
+		let pool = ConcentratedLiquidity::pools(pool_id).unwrap();
+
+		assert_eq!(pool.sqrt_price, 999_900_309_939_099_072);
+		assert_eq!(pool.tick, -2);
+		assert_eq!(pool.fee_growth_a, 300_000_000_000);
+		assert_eq!(Tokens::free_balance(DOT, &CHARLIE), 1_000_000 * ONE + 9_969_006_090_092);
+	});
+}
+
+#[test]
+fn buy_should_work() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_positions();
+
+		assert_ok!(ConcentratedLiquidity::buy(
+			Origin::signed(CHARLIE),
+			pool_id,
+			HDX,
+			DOT,
+			10 * ONE,
+			11 * ONE
+		));
+
+		let pool = ConcentratedLiquidity::pools(pool_id).unwrap();
+
+		assert_eq!(pool.sqrt_price, 1_000_100_010_001_000_101);
+		assert_eq!(pool.tick, 2);
+
+		assert_eq!(Tokens::free_balance(HDX, &CHARLIE), 1_000_010 * ONE);
+		assert_eq!(
+			Tokens::free_balance(DOT, &CHARLIE),
+			1_000_000 * ONE - 10_031_093_380_152
+		);
+
+		expect_events(vec![Event::BuyExecuted(
+			CHARLIE,
+			pool_id,
+			HDX,
+			DOT,
+			10 * ONE,
+			10_031_093_380_152,
+			30_093_280_141,
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn trade_limits_should_be_respected() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_positions();
+
+		assert_noop!(
+			ConcentratedLiquidity::sell(Origin::signed(CHARLIE), pool_id, DOT, HDX, 10 * ONE, 10 * ONE),
+			Error::<Test>::BuyLimitNotReached
+		);
+		assert_noop!(
+			ConcentratedLiquidity::buy(Origin::signed(CHARLIE), pool_id, HDX, DOT, 10 * ONE, 10 * ONE),
+			Error::<Test>::SellLimitExceeded
+		);
+		assert_noop!(
+			ConcentratedLiquidity::sell(Origin::signed(CHARLIE), pool_id, DOT, HDX, 999, 0),
+			Error::<Test>::InsufficientTradingAmount
+		);
+		assert_noop!(
+			ConcentratedLiquidity::sell(Origin::signed(CHARLIE), pool_id, DOT, DOT, 10 * ONE, 0),
+			Error::<Test>::SameAssetTrade
+		);
+		assert_noop!(
+			ConcentratedLiquidity::sell(Origin::signed(ALICE), pool_id, KSM, HDX, 10 * ONE, 0),
+			Error::<Test>::AssetNotInPool
+		);
+	});
+}
+
+#[test]
+fn sell_should_cross_ticks_and_accrue_fees_to_positions_in_range() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_positions();
+
+		assert_ok!(ConcentratedLiquidity::sell(
+			Origin::signed(CHARLIE),
+			pool_id,
+			DOT,
+			HDX,
+			3_000 * ONE,
+			0
+		));
+
+		let pool = ConcentratedLiquidity::pools(pool_id).unwrap();
+
+		// tick 500 was crossed, liquidity of BOB is in range
+		assert_eq!(pool.sqrt_price, 1_027_611_919_483_382_802);
+		assert_eq!(pool.tick, 544);
+		assert_eq!(pool.liquidity, 2 * LIQUIDITY);
+		assert_eq!(pool.fee_growth_b, 83_085_013_490_630);
+		assert_eq!(
+			Tokens::free_balance(HDX, &CHARLIE),
+			1_000_000 * ONE + 2_905_110_618_644_623
+		);
+
+		assert_ok!(ConcentratedLiquidity::collect_fees(Origin::signed(ALICE), 0));
+		assert_ok!(ConcentratedLiquidity::collect_fees(Origin::signed(BOB), 1));
+
+		expect_events(vec![
+			Event::SellExecuted(
+				CHARLIE,
+				pool_id,
+				DOT,
+				HDX,
+				3_000 * ONE,
+				2_905_110_618_644_623,
+				9_000_000_000_001,
+			)
+			.into(),
+			Event::FeesCollected(ALICE, 0, 0, 8_308_501_349_063).into(),
+			Event::FeesCollected(BOB, 1, 0, 691_498_650_938).into(),
+		]);
+
+		assert_eq!(ConcentratedLiquidity::positions(0).unwrap().fees_owed_b, 0);
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 1_000_000 * ONE + 691_498_650_938);
+	});
+}
+
+#[test]
+fn trade_should_fail_when_liquidity_runs_out() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_positions();
+
+		assert_noop!(
+			ConcentratedLiquidity::sell(Origin::signed(CHARLIE), pool_id, DOT, HDX, 100_000 * ONE, 0),
+			Error::<Test>::InsufficientLiquidity
+		);
+	});
+}
+
+#[test]
+fn trade_should_fail_when_too_many_ticks_are_crossed() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_positions();
+
+		assert_ok!(ConcentratedLiquidity::mint_position(
+			Origin::signed(CHARLIE),
+			pool_id,
+			1_500,
+			2_000,
+			LIQUIDITY,
+			Balance::MAX,
+			Balance::MAX
+		));
+
+		assert_noop!(
+			ConcentratedLiquidity::sell(Origin::signed(CHARLIE), pool_id, DOT, HDX, 100_000 * ONE, 0),
+			Error::<Test>::TooManyTicksCrossed
+		);
+	});
+}
+
+#[test]
+fn decrease_liquidity_should_work() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_positions();
+
+		assert_ok!(ConcentratedLiquidity::sell(
+			Origin::signed(CHARLIE),
+			pool_id,
+			DOT,
+			HDX,
+			3_000 * ONE,
+			0
+		));
+
+		let hdx_balance = Tokens::free_balance(HDX, &ALICE);
+		let dot_balance = Tokens::free_balance(DOT, &ALICE);
+
+		assert_noop!(
+			ConcentratedLiquidity::decrease_liquidity(Origin::signed(ALICE), 0, LIQUIDITY + 1, 0, 0),
+			Error::<Test>::InsufficientPositionLiquidity
+		);
+		assert_noop!(
+			ConcentratedLiquidity::decrease_liquidity(Origin::signed(ALICE), 0, LIQUIDITY, 0, 8_000 * ONE),
+			Error::<Test>::MinAmountNotReached
+		);
+
+		assert_ok!(ConcentratedLiquidity::decrease_liquidity(
+			Origin::signed(ALICE),
+			0,
+			LIQUIDITY,
+			0,
+			0
+		));
+
+		assert_eq!(Tokens::free_balance(HDX, &ALICE), hdx_balance + 2_189_821_003_066_318);
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), dot_balance + 7_638_011_706_466_169);
+
+		// fees stay in the position until collected
+		let position = ConcentratedLiquidity::positions(0).unwrap();
+		assert_eq!(position.liquidity, 0);
+		assert_eq!(position.fees_owed_b, 8_308_501_349_063);
+
+		// only BOB's liquidity is left in range
+		assert_eq!(ConcentratedLiquidity::pools(pool_id).unwrap().liquidity, LIQUIDITY);
+		assert_eq!(ConcentratedLiquidity::ticks(pool_id, -1_000), None);
+		assert_eq!(
+			ConcentratedLiquidity::initialized_ticks(pool_id).into_inner(),
+			vec![500, 1_500]
+		);
+
+		expect_events(vec![Event::LiquidityDecreased(
+			ALICE,
+			0,
+			LIQUIDITY,
+			2_189_821_003_066_318,
+			7_638_011_706_466_169,
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn burn_position_should_collect_fees_and_burn_nft() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_positions();
+
+		assert_ok!(ConcentratedLiquidity::buy(
+			Origin::signed(CHARLIE),
+			pool_id,
+			DOT,
+			HDX,
+			10 * ONE,
+			11 * ONE
+		));
+
+		assert_eq!(
+			ConcentratedLiquidity::pools(pool_id).unwrap().sqrt_price,
+			999_900_000_000_000_000
+		);
+
+		assert_noop!(
+			ConcentratedLiquidity::burn_position(Origin::signed(ALICE), 0),
+			Error::<Test>::PositionNotEmpty
+		);
+
+		assert_ok!(ConcentratedLiquidity::decrease_liquidity(
+			Origin::signed(ALICE),
+			0,
+			LIQUIDITY,
+			0,
+			0
+		));
+		assert_ok!(ConcentratedLiquidity::burn_position(Origin::signed(ALICE), 0));
+
+		assert_eq!(ConcentratedLiquidity::positions(0), None);
+		assert_eq!(NFT::owner(POSITION_NFT_CLASS, 0), None);
+
+		expect_events(vec![
+			Event::FeesCollected(ALICE, 0, 30_093_280_141, 0).into(),
+			Event::PositionBurned(ALICE, 0).into(),
+		]);
+	});
+}
+
+#[test]
+fn position_should_be_managed_by_nft_owner() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_positions();
+
+		assert_ok!(ConcentratedLiquidity::sell(
+			Origin::signed(CHARLIE),
+			pool_id,
+			DOT,
+			HDX,
+			10 * ONE,
+			0
+		));
+
+		assert_noop!(
+			ConcentratedLiquidity::collect_fees(Origin::signed(BOB), 0),
+			Error::<Test>::NotPositionOwner
+		);
+		assert_noop!(
+			ConcentratedLiquidity::increase_liquidity(Origin::signed(BOB), 0, LIQUIDITY, Balance::MAX, Balance::MAX),
+			Error::<Test>::NotPositionOwner
+		);
+		assert_noop!(
+			ConcentratedLiquidity::collect_fees(Origin::signed(ALICE), 2),
+			Error::<Test>::PositionNotFound
+		);
+
+		assert_ok!(NFT::transfer(Origin::signed(ALICE), POSITION_NFT_CLASS, 0, BOB));
+
+		assert_noop!(
+			ConcentratedLiquidity::collect_fees(Origin::signed(ALICE), 0),
+			Error::<Test>::NotPositionOwner
+		);
+		assert_ok!(ConcentratedLiquidity::collect_fees(Origin::signed(BOB), 0));
+
+		expect_events(vec![Event::FeesCollected(BOB, 0, 0, 30_000_000_000).into()]);
+	});
+}
+
+#[test]
+fn increase_liquidity_should_work() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_positions();
+
+		assert_ok!(ConcentratedLiquidity::increase_liquidity(
+			Origin::signed(ALICE),
+			0,
+			LIQUIDITY,
+			Balance::MAX,
+			Balance::MAX
+		));
+
+		assert_eq!(ConcentratedLiquidity::positions(0).unwrap().liquidity, 2 * LIQUIDITY);
+		assert_eq!(ConcentratedLiquidity::pools(pool_id).unwrap().liquidity, 2 * LIQUIDITY);
+		assert_eq!(
+			ConcentratedLiquidity::ticks(pool_id, -1_000).unwrap().liquidity_gross,
+			2 * LIQUIDITY
+		);
+
+		expect_events(vec![Event::LiquidityIncreased(
+			ALICE,
+			0,
+			LIQUIDITY,
+			4_876_819_758_127_889,
+			4_876_819_758_127_889,
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn amm_trait_should_route_trades_to_pool() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_positions();
+		let pool_account = ConcentratedLiquidity::pool_account(pool_id);
+
+		let assets = AssetPair {
+			asset_in: DOT,
+			asset_out: HDX,
+		};
+
+		assert!(ConcentratedLiquidity::exists(assets));
+		assert_eq!(ConcentratedLiquidity::get_pair_id(assets), pool_account);
+		assert_eq!(
+			ConcentratedLiquidity::get_pool_assets(&pool_account),
+			Some(vec![HDX, DOT])
+		);
+		assert_eq!(ConcentratedLiquidity::get_fee(&pool_account), (3_000, 1_000_000));
+		assert_eq!(ConcentratedLiquidity::get_spot_price_unchecked(HDX, DOT, ONE), ONE);
+
+		let transfer = ConcentratedLiquidity::validate_sell(&CHARLIE, assets, 10 * ONE, 0, false).unwrap();
+
+		assert_eq!(transfer.amount_out, 9_969_006_090_092);
+		assert_eq!(transfer.fee, (DOT, 30_000_000_000));
+
+		assert_ok!(ConcentratedLiquidity::execute_sell(&transfer));
+
+		assert_eq!(
+			ConcentratedLiquidity::get_spot_price_unchecked(HDX, DOT, ONE),
+			1_000_199_409_940
+		);
+		assert_eq!(
+			ConcentratedLiquidity::get_spot_price_unchecked(DOT, HDX, ONE),
+			999_800_629_816
+		);
+
+		let transfer = ConcentratedLiquidity::validate_buy(&CHARLIE, assets, 10 * ONE, Balance::MAX, false).unwrap();
+
+		assert_eq!(
+			transfer.amount_out + transfer.fee.1,
+			ConcentratedLiquidity::calculate_swap(
+				pool_id,
+				&ConcentratedLiquidity::pools(pool_id).unwrap(),
+				false,
+				false,
+				10 * ONE
+			)
+			.unwrap()
+			.amount_in
+		);
+
+		assert_noop!(
+			ConcentratedLiquidity::validate_sell(&CHARLIE, assets, 10 * ONE, 0, true),
+			Error::<Test>::DiscountNotSupported
+		);
+	});
+}
@@ -28,9 +28,9 @@ use pallet_exchange::Pallet as Exchange;
 use frame_benchmarking::{account, benchmarks};
 use frame_support::traits::OnFinalize;
 use frame_system::RawOrigin;
-use orml_traits::{MultiCurrency, MultiCurrencyExtended};
+use orml_traits::{MultiCurrency, MultiCurrencyExtended, MultiReservableCurrency};
 use primitives::{AssetId, Balance, Price};
-use sp_runtime::DispatchError;
+use sp_runtime::{DispatchError, FixedPointNumber};
 
 use sp_runtime::traits::{BlakeTwo256, Hash};
 
@@ -45,6 +45,8 @@ const INITIAL_ASSET_BALANCE: Balance = 1_000_000_000_000_000;
 
 const MAX_INTENTIONS_IN_BLOCK: u32 = 1000;
 
+const MAX_ORDERS: u32 = 100;
+
 const SEED: u32 = 0;
 pub const MILLICENTS: Balance = 1_000_000_000;
 pub const CENTS: Balance = 1_000 * MILLICENTS;
@@ -69,7 +71,7 @@ fn initialize_pool<T: Config>(
 	amount: Balance,
 	price: Price,
 ) -> dispatch::DispatchResult {
-	xykpool::Pallet::<T>::create_pool(RawOrigin::Signed(caller).into(), asset_a, asset_b, amount, price)?;
+	xykpool::Pallet::<T>::create_pool(RawOrigin::Signed(caller).into(), asset_a, asset_b, amount, price, None)?;
 
 	Ok(())
 }
@@ -110,6 +112,26 @@ fn feed_intentions<T: Config>(
 	Ok(())
 }
 
+const ORDER_AMOUNT: Balance = 1_000_000_000;
+
+fn feed_orders<T: Config>(asset_sell: AssetId, asset_buy: AssetId, number: u32, price: Price) -> dispatch::DispatchResult {
+	let expires_at = frame_system::Pallet::<T>::block_number() + 10u32.into();
+
+	for idx in 0..number {
+		let user = funded_account::<T>("user", idx + 2);
+		pallet_exchange::Pallet::<T>::place_order(
+			RawOrigin::Signed(user).into(),
+			asset_sell,
+			asset_buy,
+			ORDER_AMOUNT,
+			price,
+			expires_at,
+		)?;
+	}
+
+	Ok(())
+}
+
 fn validate_finalize<T: Config>(
 	asset_a: AssetId,
 	_asset_b: AssetId,
@@ -369,6 +391,86 @@ benchmarks! {
 		assert_eq!(<T as xykpool::Config>::Currency::free_balance(asset_a, &buyer), 1000001000000000);
 		assert_eq!(<T as xykpool::Config>::Currency::free_balance(asset_b, &buyer), 999998886666666);
 	}
+
+	place_order {
+		let caller = funded_account::<T>("caller", 1);
+
+		let asset_a: AssetId = 1;
+		let asset_b: AssetId = 2;
+		let amount : Balance = 100_000_000_000_000;
+		let price = Price::saturating_from_integer(2);
+		let expires_at = frame_system::Pallet::<T>::block_number() + 10u32.into();
+
+		initialize_pool::<T>(caller.clone(), asset_a, asset_b, amount, Price::from(1))?;
+
+		feed_orders::<T>(asset_a, asset_b, MAX_ORDERS - 1, price)?;
+
+	}: {  Exchange::<T>::place_order(RawOrigin::Signed(caller.clone()).into(), asset_a, asset_b, ORDER_AMOUNT, price, expires_at)? }
+	verify {
+		assert_eq!(pallet_exchange::Pallet::<T>::order_count(), MAX_ORDERS);
+		assert_eq!(<T as pallet_exchange::Config>::Currency::reserved_balance(asset_a, &caller), ORDER_AMOUNT);
+	}
+
+	cancel_order {
+		let caller = funded_account::<T>("caller", 1);
+
+		let asset_a: AssetId = 1;
+		let asset_b: AssetId = 2;
+		let amount : Balance = 100_000_000_000_000;
+		let price = Price::saturating_from_integer(2);
+
+		initialize_pool::<T>(caller, asset_a, asset_b, amount, Price::from(1))?;
+
+		feed_orders::<T>(asset_a, asset_b, MAX_ORDERS, price)?;
+
+		let owner: T::AccountId = account("user", 2, SEED);
+
+	}: {  Exchange::<T>::cancel_order(RawOrigin::Signed(owner.clone()).into(), 0)? }
+	verify {
+		assert_eq!(pallet_exchange::Pallet::<T>::order_count(), MAX_ORDERS - 1);
+		assert_eq!(<T as pallet_exchange::Config>::Currency::reserved_balance(asset_a, &owner), 0);
+	}
+
+	modify_order {
+		let caller = funded_account::<T>("caller", 1);
+
+		let asset_a: AssetId = 1;
+		let asset_b: AssetId = 2;
+		let amount : Balance = 100_000_000_000_000;
+		let price = Price::saturating_from_integer(2);
+		let new_price = Price::saturating_from_integer(3);
+
+		initialize_pool::<T>(caller, asset_a, asset_b, amount, Price::from(1))?;
+
+		feed_orders::<T>(asset_a, asset_b, MAX_ORDERS, price)?;
+
+		let owner: T::AccountId = account("user", 2, SEED);
+
+	}: {  Exchange::<T>::modify_order(RawOrigin::Signed(owner.clone()).into(), 0, Some(2 * ORDER_AMOUNT), Some(new_price), None)? }
+	verify {
+		assert_eq!(pallet_exchange::Pallet::<T>::order(0).unwrap().price, new_price);
+		assert_eq!(<T as pallet_exchange::Config>::Currency::reserved_balance(asset_a, &owner), 2 * ORDER_AMOUNT);
+	}
+
+	process_orders {
+		let n in 0 .. MAX_ORDERS; // Order component
+		let caller = funded_account::<T>("caller", 1);
+
+		let asset_a: AssetId = 1;
+		let asset_b: AssetId = 2;
+		let amount : Balance = 100_000_000_000_000;
+
+		initialize_pool::<T>(caller, asset_a, asset_b, amount, Price::from(1))?;
+
+		// worst case, all orders are sold in the pool so processing of the order book never stops early
+		feed_orders::<T>(asset_a, asset_b, n, Price::saturating_from_rational(1, 2))?;
+
+		assert_eq!(pallet_exchange::Pallet::<T>::order_count(), n);
+
+	}: {  Exchange::<T>::on_finalize(1u32.into()); }
+	verify {
+		assert_eq!(pallet_exchange::Pallet::<T>::order_count(), 0);
+	}
 }
 
 #[cfg(test)]
@@ -390,6 +492,10 @@ mod tests {
 			assert_ok!(Pallet::<Test>::test_benchmark_on_finalize_for_one_sell_extrinsic());
 			assert_ok!(Pallet::<Test>::test_benchmark_buy_extrinsic());
 			assert_ok!(Pallet::<Test>::test_benchmark_on_finalize_for_one_buy_extrinsic());
+			assert_ok!(Pallet::<Test>::test_benchmark_place_order());
+			assert_ok!(Pallet::<Test>::test_benchmark_cancel_order());
+			assert_ok!(Pallet::<Test>::test_benchmark_modify_order());
+			assert_ok!(Pallet::<Test>::test_benchmark_process_orders());
 		});
 	}
 }
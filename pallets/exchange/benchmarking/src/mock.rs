@@ -28,7 +28,7 @@ use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup, Zero},
 };
 
-use frame_system::EnsureSigned;
+use frame_system::{EnsureRoot, EnsureSigned};
 use hydradx_traits::AssetPairAccountIdFor;
 use primitives::{
 	constants::chain::{MAX_IN_RATIO, MAX_OUT_RATIO, MIN_POOL_LIQUIDITY, MIN_TRADING_LIMIT},
@@ -71,6 +71,7 @@ parameter_types! {
 	pub const BlockHashCount: u64 = 250;
 	pub const HDXAssetId: AssetId = HDX;
 	pub ExchangeFeeRate: (u32, u32) = (2, 1_000);
+	pub FeeTiers: Vec<(u32, u32)> = vec![(1, 10_000), (2, 1_000), (1, 100)];
 	pub RegistryStringLimit: u32 = 100;
 }
 
@@ -146,22 +147,35 @@ impl pallet_asset_registry::Config for Test {
 parameter_types! {
 	pub const MinTradingLimit: Balance = MIN_TRADING_LIMIT;
 	pub const MinPoolLiquidity: Balance = MIN_POOL_LIQUIDITY;
+	pub const LockedLiquidity: Balance = 1_000;
 	pub const MaxInRatio: u128 = MAX_IN_RATIO;
 	pub const MaxOutRatio: u128 = MAX_OUT_RATIO;
+	pub const MaxRouteHops: u32 = 5;
+	pub const MaxPriceObservations: u32 = 10;
+	pub const ProtocolFeeAccount: AccountId = 99;
+	pub const MaxOrders: u32 = 100;
 }
 
 impl pallet_xyk::Config for Test {
 	type Event = Event;
 	type AssetRegistry = AssetRegistry;
+	type ShareTokenCleanup = ();
 	type AssetPairAccountId = AssetPairAccountIdTest;
 	type Currency = Currency;
 	type NativeAssetId = HDXAssetId;
 	type WeightInfo = ();
 	type GetExchangeFee = ExchangeFeeRate;
+	type FeeTiers = FeeTiers;
+	type UpdatePoolFeeOrigin = EnsureRoot<AccountId>;
+	type UpdatePoolStateOrigin = EnsureRoot<AccountId>;
+	type ProtocolFeeAccount = ProtocolFeeAccount;
 	type MinTradingLimit = MinTradingLimit;
 	type MinPoolLiquidity = MinPoolLiquidity;
+	type LockedLiquidity = LockedLiquidity;
 	type MaxInRatio = MaxInRatio;
 	type MaxOutRatio = MaxOutRatio;
+	type MaxRouteHops = MaxRouteHops;
+	type MaxPriceObservations = MaxPriceObservations;
 	type CanCreatePool = pallet_xyk::AllowAllPools;
 	type AMMHandler = ();
 }
@@ -171,6 +185,7 @@ impl pallet_exchange::Config for Test {
 	type AMMPool = XYKPallet;
 	type Currency = Currency;
 	type Resolver = pallet_exchange::Pallet<Test>;
+	type MaxOrders = MaxOrders;
 	type WeightInfo = ();
 }
 
@@ -0,0 +1,74 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2021  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for exchange pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::{Codec, Decode, Encode};
+use sp_runtime::FixedU128;
+use sp_std::prelude::*;
+
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct LimitOrder<AccountId, AssetId, Balance, BlockNumber> {
+	/// Id of the order.
+	pub order_id: u32,
+
+	/// Owner of the order.
+	pub who: AccountId,
+
+	/// Asset sold by the order.
+	pub asset_sell: AssetId,
+
+	/// Asset bought by the order.
+	pub asset_buy: AssetId,
+
+	/// Remaining amount of the sold asset.
+	pub amount: Balance,
+
+	/// Min amount of the bought asset per one unit of the sold asset.
+	pub price: FixedU128,
+
+	/// Last block in which the order can be filled.
+	pub expires_at: BlockNumber,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait ExchangeApi<AccountId, AssetId, Balance, BlockNumber> where
+		AccountId: Codec,
+		AssetId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		fn get_order_book(
+			asset_sell: AssetId,
+			asset_buy: AssetId,
+		) -> Vec<LimitOrder<AccountId, AssetId, Balance, BlockNumber>>;
+
+		fn get_orders_of(
+			who: AccountId,
+		) -> Vec<LimitOrder<AccountId, AssetId, Balance, BlockNumber>>;
+
+		fn get_order(
+			order_id: u32,
+		) -> Option<LimitOrder<AccountId, AssetId, Balance, BlockNumber>>;
+	}
+}
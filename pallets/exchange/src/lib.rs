@@ -22,7 +22,7 @@
 #![allow(clippy::unnecessary_wraps)]
 #![feature(drain_filter)]
 
-use frame_support::{dispatch, ensure};
+use frame_support::{dispatch, ensure, transactional, BoundedVec};
 use frame_system::{self as system, ensure_signed};
 
 use codec::Encode;
@@ -34,6 +34,7 @@ use hydradx_traits::{AMMTransfer, Resolver, AMM};
 use orml_traits::{MultiCurrency, MultiCurrencyExtended, MultiReservableCurrency};
 use primitives::{
 	asset::AssetPair, constants::chain::MIN_TRADING_LIMIT, Amount, AssetId, Balance, ExchangeIntention, IntentionType,
+	Price,
 };
 
 use frame_support::sp_runtime::traits::BlockNumberProvider;
@@ -47,6 +48,7 @@ pub mod weights;
 use weights::WeightInfo;
 
 mod direct;
+mod orders;
 #[cfg(test)]
 mod tests;
 
@@ -54,6 +56,30 @@ mod tests;
 type IntentionId<T> = <T as system::Config>::Hash;
 pub type Intention<T> = ExchangeIntention<<T as system::Config>::AccountId, Balance, IntentionId<T>>;
 
+/// Limit order id
+pub type OrderId = u32;
+
+/// Limit order which rests in the order book until it is filled, cancelled or it expires.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct LimitOrder<AccountId, BlockNumber> {
+	/// Owner of the order
+	pub who: AccountId,
+
+	/// Asset sold by the order (asset_in) and asset bought by the order (asset_out)
+	pub assets: AssetPair,
+
+	/// Remaining amount of the sold asset, reserved from the owner
+	pub amount: Balance,
+
+	/// Min amount of the bought asset per one unit of the sold asset
+	pub price: Price,
+
+	/// Last block in which the order can be filled
+	pub expires_at: BlockNumber,
+}
+
+pub type LimitOrderOf<T> = LimitOrder<<T as system::Config>::AccountId, <T as system::Config>::BlockNumber>;
+
 // Re-export pallet items so that they can be accessed from the crate namespace.
 use frame_support::pallet_prelude::*;
 pub use pallet::*;
@@ -72,6 +98,8 @@ pub mod pallet {
 		/// Finalize and resolve all registered intentions.
 		/// Group/match intentions which can be directly traded.
 		fn on_finalize(_n: T::BlockNumber) {
+			let now = <system::Pallet<T>>::current_block_number();
+
 			for ((asset_1, asset_2), count) in ExchangeAssetsIntentionCount::<T>::iter() {
 				// If no intention registered for asset1/2, move onto next one
 				if count == 0u32 {
@@ -87,6 +115,10 @@ pub mod pallet {
 				let mut asset_a_ins = <ExchangeAssetsIntentions<T>>::get((asset_2, asset_1));
 				let mut asset_b_ins = <ExchangeAssetsIntentions<T>>::get((asset_1, asset_2));
 
+				// Resting limit orders which give better price than the pool are filled first.
+				Self::fill_intentions_from_orders(&mut asset_a_ins, now);
+				Self::fill_intentions_from_orders(&mut asset_b_ins, now);
+
 				//TODO: we can short circuit here if nothing in asset_b_sells and just resolve asset_a sells.
 
 				Self::process_exchange_intentions(&pair_account, &mut asset_a_ins, &mut asset_b_ins);
@@ -94,10 +126,13 @@ pub mod pallet {
 
 			ExchangeAssetsIntentionCount::<T>::remove_all(None);
 			ExchangeAssetsIntentions::<T>::remove_all(None);
+
+			Self::process_orders(now);
 		}
 
 		fn on_initialize(_n: T::BlockNumber) -> Weight {
 			T::WeightInfo::known_overhead_for_on_finalize()
+				.saturating_add(T::WeightInfo::process_orders(Self::order_count()))
 		}
 	}
 
@@ -115,6 +150,10 @@ pub mod pallet {
 		type Currency: MultiCurrencyExtended<Self::AccountId, CurrencyId = AssetId, Balance = Balance, Amount = Amount>
 			+ MultiReservableCurrency<Self::AccountId>;
 
+		/// Max number of limit orders resting in all order books
+		#[pallet::constant]
+		type MaxOrders: Get<u32>;
+
 		/// Weight information for the extrinsics.
 		type WeightInfo: WeightInfo;
 	}
@@ -171,6 +210,30 @@ pub mod pallet {
 			IntentionId<T>,
 			dispatch::DispatchError,
 		),
+
+		/// Intention resolved as trade with a limit order
+		/// [who, intention id, order id, amount paid, amount received]
+		IntentionResolvedOrderTrade(T::AccountId, IntentionId<T>, OrderId, Balance, Balance),
+
+		/// Limit order placed
+		/// [who, order id, asset sell, asset buy, amount, price, expires at]
+		OrderPlaced(T::AccountId, OrderId, AssetId, AssetId, Balance, Price, T::BlockNumber),
+
+		/// Limit order modified
+		/// [who, order id, amount, price, expires at]
+		OrderModified(T::AccountId, OrderId, Balance, Price, T::BlockNumber),
+
+		/// Limit order cancelled
+		/// [who, order id, unreserved amount]
+		OrderCancelled(T::AccountId, OrderId, Balance),
+
+		/// Limit order expired
+		/// [who, order id, unreserved amount]
+		OrderExpired(T::AccountId, OrderId, Balance),
+
+		/// Limit order filled, partially or completely
+		/// [who, order id, amount sold, amount bought]
+		OrderFilled(T::AccountId, OrderId, Balance, Balance),
 	}
 
 	#[pallet::error]
@@ -195,6 +258,24 @@ pub mod pallet {
 
 		/// Overflow
 		IntentionCountOverflow,
+
+		/// Limit order does not exist.
+		OrderNotFound,
+
+		/// Limit order belongs to another account.
+		NotOrderOwner,
+
+		/// Limit price of the order is zero.
+		InvalidOrderPrice,
+
+		/// Limit order expires in the past.
+		InvalidOrderExpiry,
+
+		/// Max number of limit orders was reached.
+		TooManyOrders,
+
+		/// Overflow
+		OrderIdOverflow,
 	}
 
 	/// Intention count for current block
@@ -210,6 +291,34 @@ pub mod pallet {
 	pub type ExchangeAssetsIntentions<T: Config> =
 		StorageMap<_, Blake2_128Concat, (AssetId, AssetId), Vec<Intention<T>>, ValueQuery>;
 
+	/// Id of the next limit order
+	#[pallet::storage]
+	#[pallet::getter(fn next_order_id)]
+	pub type NextOrderId<T: Config> = StorageValue<_, OrderId, ValueQuery>;
+
+	/// Number of limit orders resting in all order books
+	#[pallet::storage]
+	#[pallet::getter(fn order_count)]
+	pub type OrderCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Resting limit orders
+	#[pallet::storage]
+	#[pallet::getter(fn order)]
+	pub type LimitOrders<T: Config> = StorageMap<_, Blake2_128Concat, OrderId, LimitOrderOf<T>, OptionQuery>;
+
+	/// Order books, ids of limit orders selling asset_a for asset_b ordered by price, best price first
+	/// Stored as ( asset_a, asset_b ) combination, orders with the same price are ordered by time of placement
+	#[pallet::storage]
+	#[pallet::getter(fn order_book)]
+	pub type OrderBook<T: Config> =
+		StorageMap<_, Blake2_128Concat, (AssetId, AssetId), BoundedVec<(Price, OrderId), T::MaxOrders>, ValueQuery>;
+
+	/// Ids of limit orders by the last block in which they can be filled
+	#[pallet::storage]
+	#[pallet::getter(fn order_expiries)]
+	pub type OrderExpiries<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::BlockNumber, BoundedVec<OrderId, T::MaxOrders>, ValueQuery>;
+
 	#[allow(dead_code)]
 	#[pallet::extra_constants]
 	impl<T: Config> Pallet<T> {
@@ -337,6 +446,145 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Place limit order
+		/// Reserve `amount_sell` and store the order in the order book of the pair until it is filled or cancelled,
+		/// or until `expires_at` has passed. `price` is min amount of `asset_buy` received per one unit of `asset_sell`.
+		///
+		/// Order is filled by intentions of following blocks if their spot price is not better than the limit price and
+		/// it is sold in the pool once the whole remaining amount can be sold for at least the limit price.
+		#[pallet::weight(<T as Config>::WeightInfo::place_order())]
+		#[transactional]
+		pub fn place_order(
+			origin: OriginFor<T>,
+			asset_sell: AssetId,
+			asset_buy: AssetId,
+			amount_sell: Balance,
+			price: Price,
+			expires_at: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let assets = AssetPair {
+				asset_in: asset_sell,
+				asset_out: asset_buy,
+			};
+
+			ensure!(T::AMMPool::exists(assets), Error::<T>::TokenPoolNotFound);
+
+			Self::validate_order(amount_sell, price, expires_at)?;
+
+			ensure!(Self::order_count() < T::MaxOrders::get(), Error::<T>::TooManyOrders);
+
+			let order_id = Self::next_order_id();
+			let next_order_id = order_id.checked_add(1).ok_or(Error::<T>::OrderIdOverflow)?;
+
+			Self::insert_into_order_book(assets, price, order_id)?;
+			Self::insert_into_order_expiries(expires_at, order_id)?;
+
+			T::Currency::reserve(asset_sell, &who, amount_sell).map_err(|_| Error::<T>::InsufficientAssetBalance)?;
+
+			<NextOrderId<T>>::put(next_order_id);
+			<OrderCount<T>>::mutate(|count| *count = count.saturating_add(1));
+
+			<LimitOrders<T>>::insert(
+				order_id,
+				LimitOrder {
+					who: who.clone(),
+					assets,
+					amount: amount_sell,
+					price,
+					expires_at,
+				},
+			);
+
+			Self::deposit_event(Event::OrderPlaced(
+				who,
+				order_id,
+				asset_sell,
+				asset_buy,
+				amount_sell,
+				price,
+				expires_at,
+			));
+
+			Ok(())
+		}
+
+		/// Cancel limit order
+		/// Remove the order from the order book and unreserve its remaining amount.
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_order())]
+		pub fn cancel_order(origin: OriginFor<T>, order_id: OrderId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let order = Self::order(order_id).ok_or(Error::<T>::OrderNotFound)?;
+			ensure!(order.who == who, Error::<T>::NotOrderOwner);
+
+			let unreserved = Self::close_order(order_id, &order);
+			Self::remove_from_order_book(order.assets, &[order_id]);
+
+			Self::deposit_event(Event::OrderCancelled(who, order_id, unreserved));
+
+			Ok(())
+		}
+
+		/// Modify limit order
+		/// Change remaining amount, limit price or expiry of the order. Difference of the amount is reserved or
+		/// unreserved. Order keeps its place in the order book unless the price changes.
+		#[pallet::weight(<T as Config>::WeightInfo::modify_order())]
+		#[transactional]
+		pub fn modify_order(
+			origin: OriginFor<T>,
+			order_id: OrderId,
+			amount_sell: Option<Balance>,
+			price: Option<Price>,
+			expires_at: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			<LimitOrders<T>>::try_mutate(order_id, |maybe_order| -> DispatchResult {
+				let order = maybe_order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
+				ensure!(order.who == who, Error::<T>::NotOrderOwner);
+
+				let amount_sell = amount_sell.unwrap_or(order.amount);
+				let price = price.unwrap_or(order.price);
+				let expires_at = expires_at.unwrap_or(order.expires_at);
+
+				Self::validate_order(amount_sell, price, expires_at)?;
+
+				let asset_sell = order.assets.asset_in;
+				if amount_sell > order.amount {
+					T::Currency::reserve(asset_sell, &who, amount_sell - order.amount)
+						.map_err(|_| Error::<T>::InsufficientAssetBalance)?;
+				} else {
+					T::Currency::unreserve(asset_sell, &who, order.amount - amount_sell);
+				}
+
+				if price != order.price {
+					Self::remove_from_order_book(order.assets, &[order_id]);
+					Self::insert_into_order_book(order.assets, price, order_id)?;
+				}
+
+				if expires_at != order.expires_at {
+					Self::remove_from_order_expiries(order.expires_at, order_id);
+					Self::insert_into_order_expiries(expires_at, order_id)?;
+				}
+
+				order.amount = amount_sell;
+				order.price = price;
+				order.expires_at = expires_at;
+
+				Self::deposit_event(Event::OrderModified(
+					who.clone(),
+					order_id,
+					amount_sell,
+					price,
+					expires_at,
+				));
+
+				Ok(())
+			})
+		}
 	}
 }
 
@@ -30,7 +30,7 @@ use sp_runtime::{
 use pallet_xyk as xyk;
 
 use frame_support::traits::{Everything, GenesisBuild, Get, Nothing};
-use frame_system::EnsureSigned;
+use frame_system::{EnsureRoot, EnsureSigned};
 use hydradx_traits::AssetPairAccountIdFor;
 use primitives::{
 	constants::chain::{MAX_IN_RATIO, MAX_OUT_RATIO, MIN_POOL_LIQUIDITY, MIN_TRADING_LIMIT},
@@ -88,6 +88,7 @@ parameter_types! {
 	pub const HDXAssetId: AssetId = HDX;
 
 	pub ExchangeFeeRate: (u32, u32) = (2, 1_000);
+	pub FeeTiers: Vec<(u32, u32)> = vec![(1, 10_000), (2, 1_000), (1, 100)];
 
 	pub RegistryStringLimit: u32 = 100;
 }
@@ -152,22 +153,35 @@ impl AssetPairAccountIdFor<AssetId, u64> for AssetPairAccountIdTest {
 parameter_types! {
 	pub const MinTradingLimit: Balance = MIN_TRADING_LIMIT;
 	pub const MinPoolLiquidity: Balance = MIN_POOL_LIQUIDITY;
+	pub const LockedLiquidity: Balance = 1_000;
 	pub const MaxInRatio: u128 = MAX_IN_RATIO;
 	pub const MaxOutRatio: u128 = MAX_OUT_RATIO;
+	pub const MaxRouteHops: u32 = 5;
+	pub const MaxPriceObservations: u32 = 10;
+	pub const ProtocolFeeAccount: AccountId = 99;
+	pub const MaxOrders: u32 = 10;
 }
 
 impl xyk::Config for Test {
 	type Event = Event;
 	type AssetRegistry = AssetRegistry;
+	type ShareTokenCleanup = ();
 	type AssetPairAccountId = AssetPairAccountIdTest;
 	type Currency = Currency;
 	type NativeAssetId = HDXAssetId;
 	type WeightInfo = ();
 	type GetExchangeFee = ExchangeFeeRate;
+	type FeeTiers = FeeTiers;
+	type UpdatePoolFeeOrigin = EnsureRoot<AccountId>;
+	type UpdatePoolStateOrigin = EnsureRoot<AccountId>;
+	type ProtocolFeeAccount = ProtocolFeeAccount;
 	type MinTradingLimit = MinTradingLimit;
 	type MinPoolLiquidity = MinPoolLiquidity;
+	type LockedLiquidity = LockedLiquidity;
 	type MaxInRatio = MaxInRatio;
 	type MaxOutRatio = MaxOutRatio;
+	type MaxRouteHops = MaxRouteHops;
+	type MaxPriceObservations = MaxPriceObservations;
 	type CanCreatePool = pallet_xyk::AllowAllPools;
 	type AMMHandler = ();
 }
@@ -177,6 +191,7 @@ impl Config for Test {
 	type AMMPool = XYK;
 	type Currency = Currency;
 	type Resolver = exchange::Pallet<Test>;
+	type MaxOrders = MaxOrders;
 	type WeightInfo = ();
 }
 
@@ -0,0 +1,408 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2021  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use frame_support::{traits::BalanceStatus, transactional};
+use primitive_types::U256;
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational,
+	traits::{One, Zero},
+	FixedPointNumber,
+};
+
+/// Amount of the bought asset the order requires for `amount_in` of the sold asset at `price`, rounded up.
+pub(crate) fn amount_out_at(price: Price, amount_in: Balance) -> Option<Balance> {
+	let accuracy = U256::from(Price::accuracy());
+	let amount_out = U256::from(amount_in)
+		.checked_mul(U256::from(price.into_inner()))?
+		.checked_add(accuracy - 1)?
+		/ accuracy;
+
+	(amount_out <= U256::from(Balance::MAX)).then(|| amount_out.low_u128())
+}
+
+/// Amount of the sold asset the order gives for `amount_out` of the bought asset at `price`, rounded down.
+pub(crate) fn amount_in_at(price: Price, amount_out: Balance) -> Option<Balance> {
+	let amount_in = U256::from(amount_out)
+		.checked_mul(U256::from(Price::accuracy()))?
+		.checked_div(U256::from(price.into_inner()))?;
+
+	(amount_in <= U256::from(Balance::MAX)).then(|| amount_in.low_u128())
+}
+
+/// Limit order handling
+impl<T: Config> Pallet<T> {
+	/// Validate parameters of a new or modified limit order.
+	pub(crate) fn validate_order(amount: Balance, price: Price, expires_at: T::BlockNumber) -> DispatchResult {
+		ensure!(amount >= MIN_TRADING_LIMIT, Error::<T>::MinimumTradeLimitNotReached);
+
+		ensure!(!price.is_zero(), Error::<T>::InvalidOrderPrice);
+
+		ensure!(
+			expires_at >= <system::Pallet<T>>::current_block_number(),
+			Error::<T>::InvalidOrderExpiry
+		);
+
+		Ok(())
+	}
+
+	/// Insert the order into the order book of its assets after the orders with the same or better price.
+	pub(crate) fn insert_into_order_book(assets: AssetPair, price: Price, order_id: OrderId) -> DispatchResult {
+		<OrderBook<T>>::try_mutate((assets.asset_in, assets.asset_out), |book| -> DispatchResult {
+			let index = book.partition_point(|(order_price, _)| *order_price <= price);
+			book.try_insert(index, (price, order_id))
+				.map_err(|_| Error::<T>::TooManyOrders)?;
+
+			Ok(())
+		})
+	}
+
+	/// Remove the orders from the order book of `assets`, empty order book is removed from the storage.
+	pub(crate) fn remove_from_order_book(assets: AssetPair, order_ids: &[OrderId]) {
+		if order_ids.is_empty() {
+			return;
+		}
+
+		<OrderBook<T>>::mutate_exists((assets.asset_in, assets.asset_out), |maybe_book| {
+			if let Some(book) = maybe_book {
+				book.retain(|(_, order_id)| !order_ids.contains(order_id));
+
+				if book.is_empty() {
+					*maybe_book = None;
+				}
+			}
+		});
+	}
+
+	/// Insert the order into the orders expiring after `expires_at`.
+	pub(crate) fn insert_into_order_expiries(expires_at: T::BlockNumber, order_id: OrderId) -> DispatchResult {
+		<OrderExpiries<T>>::try_mutate(expires_at, |order_ids| -> DispatchResult {
+			order_ids.try_push(order_id).map_err(|_| Error::<T>::TooManyOrders)?;
+
+			Ok(())
+		})
+	}
+
+	/// Remove the order from the orders expiring after `expires_at`, empty list is removed from the storage.
+	pub(crate) fn remove_from_order_expiries(expires_at: T::BlockNumber, order_id: OrderId) {
+		<OrderExpiries<T>>::mutate_exists(expires_at, |maybe_order_ids| {
+			if let Some(order_ids) = maybe_order_ids {
+				order_ids.retain(|id| *id != order_id);
+
+				if order_ids.is_empty() {
+					*maybe_order_ids = None;
+				}
+			}
+		});
+	}
+
+	/// Remove the order from the storage and from the order expiries. Order book has to be updated separately.
+	fn remove_order(order_id: OrderId, order: &LimitOrderOf<T>) {
+		<LimitOrders<T>>::remove(order_id);
+		<OrderCount<T>>::mutate(|count| *count = count.saturating_sub(1));
+		Self::remove_from_order_expiries(order.expires_at, order_id);
+	}
+
+	/// Remove the order and unreserve its remaining amount. Returns the unreserved amount.
+	pub(crate) fn close_order(order_id: OrderId, order: &LimitOrderOf<T>) -> Balance {
+		Self::remove_order(order_id, order);
+
+		let not_unreserved = T::Currency::unreserve(order.assets.asset_in, &order.who, order.amount);
+		order.amount.saturating_sub(not_unreserved)
+	}
+
+	/// Fill intentions from the resting limit orders on the other side of the pair.
+	///
+	/// Orders are taken from the best price as long as the limit price is not worse for the intention than the spot
+	/// price the intention was registered with. Trades are executed at the limit price of the order. Intentions which
+	/// were filled are removed, the rest continues to be matched and resolved as usual.
+	pub(crate) fn fill_intentions_from_orders(intentions: &mut Vec<Intention<T>>, now: T::BlockNumber) {
+		let assets = match intentions.first() {
+			Some(intention) => intention.assets,
+			None => return,
+		};
+
+		// orders selling the asset bought by the intentions
+		let order_assets = AssetPair {
+			asset_in: assets.asset_out,
+			asset_out: assets.asset_in,
+		};
+		let book = Self::order_book((order_assets.asset_in, order_assets.asset_out));
+
+		if book.is_empty() {
+			return;
+		}
+
+		let mut closed = Vec::<OrderId>::new();
+
+		for intention in intentions.iter_mut() {
+			for (price, order_id) in book.iter() {
+				if closed.contains(order_id) {
+					continue;
+				}
+
+				if !Self::fill_intention_from_order(intention, *price, *order_id, now, &mut closed)
+					|| Self::intention_amount_left(intention) < MIN_TRADING_LIMIT
+				{
+					break;
+				}
+			}
+		}
+
+		Self::remove_from_order_book(order_assets, &closed);
+
+		intentions.retain(|intention| Self::intention_amount_left(intention) >= MIN_TRADING_LIMIT);
+	}
+
+	/// Amount which is still to be sold by sell intention or bought by buy intention.
+	fn intention_amount_left(intention: &Intention<T>) -> Balance {
+		match intention.sell_or_buy {
+			IntentionType::SELL => intention.amount_in,
+			IntentionType::BUY => intention.amount_out,
+		}
+	}
+
+	/// Fill the intention from the order as much as possible and update the remaining amounts of both.
+	///
+	/// Returns false if the intention can't be filled from the order and any following order in the order book.
+	fn fill_intention_from_order(
+		intention: &mut Intention<T>,
+		price: Price,
+		order_id: OrderId,
+		now: T::BlockNumber,
+		closed: &mut Vec<OrderId>,
+	) -> bool {
+		let mut order = match Self::order(order_id) {
+			Some(order) => order,
+			None => {
+				closed.push(order_id);
+				return true;
+			}
+		};
+
+		// expired orders are removed in `process_orders`
+		if order.expires_at < now {
+			return true;
+		}
+
+		// orders are sorted by price, so none of the following orders is better than the spot price either
+		match amount_out_at(price, intention.amount_out) {
+			Some(amount) if amount <= intention.amount_in => {}
+			_ => return false,
+		}
+
+		// amount sold by the order is received by the intention and vice versa
+		let sold = match intention.sell_or_buy {
+			IntentionType::SELL => amount_in_at(price, intention.amount_in).unwrap_or_else(Zero::zero),
+			IntentionType::BUY => intention.amount_out,
+		}
+		.min(order.amount);
+
+		let bought = match amount_out_at(price, sold) {
+			Some(bought) if !sold.is_zero() && !bought.is_zero() => bought,
+			_ => return false,
+		};
+
+		let within_limit = match intention.sell_or_buy {
+			IntentionType::SELL => {
+				U256::from(sold) * U256::from(intention.amount_in)
+					>= U256::from(intention.trade_limit) * U256::from(bought)
+			}
+			IntentionType::BUY => {
+				U256::from(bought) * U256::from(intention.amount_out)
+					<= U256::from(intention.trade_limit) * U256::from(sold)
+			}
+		};
+
+		if !within_limit {
+			return false;
+		}
+
+		if Self::trade_with_order(intention, &order, sold, bought).is_err() {
+			Self::deposit_event(Event::InsufficientAssetBalanceEvent(
+				intention.who.clone(),
+				intention.assets.asset_in,
+				intention.sell_or_buy,
+				intention.intention_id,
+				Error::<T>::InsufficientAssetBalance.into(),
+			));
+			return false;
+		}
+
+		Self::deposit_event(Event::IntentionResolvedOrderTrade(
+			intention.who.clone(),
+			intention.intention_id,
+			order_id,
+			bought,
+			sold,
+		));
+		Self::deposit_event(Event::OrderFilled(order.who.clone(), order_id, sold, bought));
+
+		// Remaining amounts of the intention keep the spot price the intention was registered with.
+		match intention.sell_or_buy {
+			IntentionType::SELL => {
+				let amount_in = intention.amount_in.saturating_sub(bought);
+				intention.amount_out = multiply_by_rational(intention.amount_out, amount_in, intention.amount_in)
+					.unwrap_or_else(|_| Zero::zero());
+				intention.amount_in = amount_in;
+				intention.trade_limit = intention.trade_limit.saturating_sub(sold);
+			}
+			IntentionType::BUY => {
+				let amount_out = intention.amount_out.saturating_sub(sold);
+				intention.amount_in = multiply_by_rational(intention.amount_in, amount_out, intention.amount_out)
+					.unwrap_or_else(|_| Zero::zero());
+				intention.amount_out = amount_out;
+				intention.trade_limit = intention.trade_limit.saturating_sub(bought);
+			}
+		}
+
+		order.amount = order.amount.saturating_sub(sold);
+
+		// the rest of the order could not be sold in the pool
+		if order.amount < MIN_TRADING_LIMIT {
+			Self::close_order(order_id, &order);
+			closed.push(order_id);
+		} else {
+			<LimitOrders<T>>::insert(order_id, order);
+		}
+
+		true
+	}
+
+	/// Transfer `bought` from the intention account to the order owner and `sold` from the reserve of the order owner
+	/// to the intention account.
+	#[transactional]
+	fn trade_with_order(
+		intention: &Intention<T>,
+		order: &LimitOrderOf<T>,
+		sold: Balance,
+		bought: Balance,
+	) -> DispatchResult {
+		T::Currency::transfer(order.assets.asset_out, &intention.who, &order.who, bought)?;
+
+		let not_repatriated = T::Currency::repatriate_reserved(
+			order.assets.asset_in,
+			&order.who,
+			&intention.who,
+			sold,
+			BalanceStatus::Free,
+		)?;
+		ensure!(not_repatriated.is_zero(), Error::<T>::InsufficientAssetBalance);
+
+		Ok(())
+	}
+
+	/// Remove expired orders and sell orders in the pool if the pool price reached their limit.
+	///
+	/// Order books are sorted by price, so processing of a book stops at the first order which can't be sold.
+	pub(crate) fn process_orders(now: T::BlockNumber) {
+		if Self::order_count().is_zero() {
+			return;
+		}
+
+		// orders which could be filled until the previous block
+		for order_id in <OrderExpiries<T>>::take(now.saturating_sub(One::one())) {
+			if let Some(order) = Self::order(order_id) {
+				let unreserved = Self::close_order(order_id, &order);
+				Self::remove_from_order_book(order.assets, &[order_id]);
+				Self::deposit_event(Event::OrderExpired(order.who, order_id, unreserved));
+			}
+		}
+
+		let books: Vec<((AssetId, AssetId), BoundedVec<(Price, OrderId), T::MaxOrders>)> =
+			<OrderBook<T>>::iter().collect();
+
+		for ((asset_sell, asset_buy), book) in books {
+			let mut closed = Vec::<OrderId>::new();
+
+			for (price, order_id) in book.iter() {
+				let order = match Self::order(order_id) {
+					Some(order) => order,
+					None => {
+						closed.push(*order_id);
+						continue;
+					}
+				};
+
+				if Self::execute_order_in_pool(*order_id, &order, *price) {
+					closed.push(*order_id);
+				} else {
+					break;
+				}
+			}
+
+			Self::remove_from_order_book(AssetPair::new(asset_sell, asset_buy), &closed);
+		}
+	}
+
+	/// Sell the whole remaining amount of the order in the pool if it receives at least the limit price.
+	///
+	/// Returns true if the order was executed.
+	fn execute_order_in_pool(order_id: OrderId, order: &LimitOrderOf<T>, price: Price) -> bool {
+		let min_bought = match amount_out_at(price, order.amount) {
+			Some(min_bought) => min_bought,
+			None => return false,
+		};
+
+		// sale in the pool never gets more than the spot price
+		if T::AMMPool::get_spot_price_unchecked(order.assets.asset_in, order.assets.asset_out, order.amount)
+			< min_bought
+		{
+			return false;
+		}
+
+		// price including the slippage and the fee may still not reach the limit
+		match Self::sell_order_in_pool(order, min_bought) {
+			Ok(bought) => {
+				Self::remove_order(order_id, order);
+				Self::deposit_event(Event::OrderFilled(order.who.clone(), order_id, order.amount, bought));
+				true
+			}
+			Err(_) => false,
+		}
+	}
+
+	/// Unreserve the remaining amount of the order and sell it in the pool. Returns the amount bought.
+	#[transactional]
+	fn sell_order_in_pool(order: &LimitOrderOf<T>, min_bought: Balance) -> Result<Balance, DispatchError> {
+		let not_unreserved = T::Currency::unreserve(order.assets.asset_in, &order.who, order.amount);
+		ensure!(not_unreserved.is_zero(), Error::<T>::InsufficientAssetBalance);
+
+		let transfer = T::AMMPool::validate_sell(&order.who, order.assets, order.amount, min_bought, false)?;
+		T::AMMPool::execute_sell(&transfer)?;
+
+		Ok(transfer.amount_out)
+	}
+
+	/// Limit orders selling `asset_sell` for `asset_buy`, best price first.
+	pub fn orders_in_book(asset_sell: AssetId, asset_buy: AssetId) -> Vec<(OrderId, LimitOrderOf<T>)> {
+		Self::order_book((asset_sell, asset_buy))
+			.into_inner()
+			.into_iter()
+			.filter_map(|(_, order_id)| Self::order(order_id).map(|order| (order_id, order)))
+			.collect()
+	}
+
+	/// Limit orders of the account, ordered by the order id.
+	pub fn orders_of(who: &T::AccountId) -> Vec<(OrderId, LimitOrderOf<T>)> {
+		let mut orders: Vec<(OrderId, LimitOrderOf<T>)> = <LimitOrders<T>>::iter()
+			.filter(|(_, order)| order.who == *who)
+			.collect();
+		orders.sort_by_key(|(order_id, _)| *order_id);
+		orders
+	}
+}
@@ -17,8 +17,8 @@
 
 use super::*;
 pub use crate::mock::{
-	Currency, EndowedAmount, Event as TestEvent, Exchange, ExtBuilder, Origin, System, Test, ALICE, BOB, CHARLIE, DAVE,
-	DOT, ETH, FERDIE, GEORGE, HDX, XYK as XYKPallet,
+	Currency, EndowedAmount, Event as TestEvent, Exchange, ExtBuilder, LockedLiquidity, Origin, System, Test, ALICE,
+	BOB, CHARLIE, DAVE, DOT, ETH, FERDIE, GEORGE, HDX, XYK as XYKPallet,
 };
 use frame_support::sp_runtime::traits::Hash;
 use frame_support::sp_runtime::FixedPointNumber;
@@ -59,7 +59,8 @@ fn initialize_pool(asset_a: u32, asset_b: u32, user: u64, amount: u128, price: P
 		asset_a,
 		asset_b,
 		amount,
-		price
+		price,
+		None
 	));
 
 	let shares = if asset_a <= asset_b {
@@ -94,7 +95,10 @@ fn initialize_pool(asset_a: u32, asset_b: u32, user: u64, amount: u128, price: P
 	assert_eq!(Currency::free_balance(asset_b, &pair_account), amount_b);
 
 	// Check pool shares
-	assert_eq!(Currency::free_balance(share_token, &user), shares);
+	assert_eq!(
+		Currency::free_balance(share_token, &user),
+		shares - LockedLiquidity::get()
+	);
 
 	// Advance blockchain so that we kill old events
 	System::initialize(&1, &[0u8; 32].into(), &Default::default());
@@ -1613,7 +1617,8 @@ fn sell_more_than_owner_should_not_work() {
 			HDX,
 			ETH,
 			200_000,
-			Price::from(2)
+			Price::from(2),
+			None
 		));
 
 		// With SELL
@@ -5367,3 +5372,565 @@ fn direct_buy_sell_transfers_without_other_asset_should_work() {
 		]);
 	});
 }
+
+fn order_pair_account() -> <Test as system::Config>::AccountId {
+	XYKPallet::get_pair_id(AssetPair {
+		asset_in: ETH,
+		asset_out: DOT,
+	})
+}
+
+#[test]
+fn place_order_should_work() {
+	new_test_ext().execute_with(|| {
+		initialize_pool(ETH, DOT, ALICE, 100_000_000_000_000, Price::from(2));
+
+		assert_ok!(Exchange::place_order(
+			Origin::signed(BOB),
+			ETH,
+			DOT,
+			1_000_000_000_000,
+			Price::from(3),
+			10,
+		));
+
+		assert_eq!(
+			Exchange::order(0),
+			Some(LimitOrder {
+				who: BOB,
+				assets: AssetPair {
+					asset_in: ETH,
+					asset_out: DOT,
+				},
+				amount: 1_000_000_000_000,
+				price: Price::from(3),
+				expires_at: 10,
+			})
+		);
+		assert_eq!(Exchange::order_book((ETH, DOT)).into_inner(), vec![(Price::from(3), 0)]);
+		assert_eq!(Exchange::order_expiries(10).into_inner(), vec![0]);
+		assert_eq!(Exchange::order_count(), 1);
+		assert_eq!(Exchange::next_order_id(), 1);
+
+		assert_eq!(Currency::reserved_balance(ETH, &BOB), 1_000_000_000_000);
+		assert_eq!(
+			Currency::free_balance(ETH, &BOB),
+			EndowedAmount::get() - 1_000_000_000_000
+		);
+
+		expect_event(Event::OrderPlaced(
+			BOB,
+			0,
+			ETH,
+			DOT,
+			1_000_000_000_000,
+			Price::from(3),
+			10,
+		));
+
+		// pool price is below the limit price
+		<Exchange as OnFinalize<u64>>::on_finalize(1);
+
+		assert_eq!(Exchange::order(0).unwrap().amount, 1_000_000_000_000);
+		assert_eq!(Currency::reserved_balance(ETH, &BOB), 1_000_000_000_000);
+	});
+}
+
+#[test]
+fn place_order_should_not_work() {
+	new_test_ext().execute_with(|| {
+		initialize_pool(ETH, DOT, ALICE, 100_000_000_000_000, Price::from(2));
+
+		assert_noop!(
+			Exchange::place_order(Origin::signed(BOB), ETH, HDX, 1_000_000_000_000, Price::from(3), 10),
+			Error::<Test>::TokenPoolNotFound
+		);
+		assert_noop!(
+			Exchange::place_order(Origin::signed(BOB), ETH, DOT, 100, Price::from(3), 10),
+			Error::<Test>::MinimumTradeLimitNotReached
+		);
+		assert_noop!(
+			Exchange::place_order(Origin::signed(BOB), ETH, DOT, 1_000_000_000_000, Price::from(0), 10),
+			Error::<Test>::InvalidOrderPrice
+		);
+		assert_noop!(
+			Exchange::place_order(Origin::signed(BOB), ETH, DOT, 1_000_000_000_000, Price::from(3), 0),
+			Error::<Test>::InvalidOrderExpiry
+		);
+		assert_noop!(
+			Exchange::place_order(
+				Origin::signed(BOB),
+				ETH,
+				DOT,
+				EndowedAmount::get() + 1,
+				Price::from(3),
+				10
+			),
+			Error::<Test>::InsufficientAssetBalance
+		);
+
+		for _ in 0..<Test as Config>::MaxOrders::get() {
+			assert_ok!(Exchange::place_order(
+				Origin::signed(BOB),
+				ETH,
+				DOT,
+				1_000_000_000_000,
+				Price::from(3),
+				10
+			));
+		}
+
+		assert_noop!(
+			Exchange::place_order(Origin::signed(BOB), ETH, DOT, 1_000_000_000_000, Price::from(3), 10),
+			Error::<Test>::TooManyOrders
+		);
+	});
+}
+
+#[test]
+fn place_order_should_not_reserve_when_order_id_overflows() {
+	new_test_ext().execute_with(|| {
+		initialize_pool(ETH, DOT, ALICE, 100_000_000_000_000, Price::from(2));
+
+		NextOrderId::<Test>::put(OrderId::MAX);
+
+		assert_noop!(
+			Exchange::place_order(Origin::signed(BOB), ETH, DOT, 1_000_000_000_000, Price::from(3), 10),
+			Error::<Test>::OrderIdOverflow
+		);
+
+		assert_eq!(Currency::reserved_balance(ETH, &BOB), 0);
+	});
+}
+
+#[test]
+fn order_book_should_be_ordered_by_price() {
+	new_test_ext().execute_with(|| {
+		initialize_pool(ETH, DOT, ALICE, 100_000_000_000_000, Price::from(2));
+
+		let price_a = Price::from(3);
+		let price_b = Price::saturating_from_rational(5, 2);
+
+		assert_ok!(Exchange::place_order(
+			Origin::signed(BOB),
+			ETH,
+			DOT,
+			1_000_000_000_000,
+			price_a,
+			10
+		));
+		assert_ok!(Exchange::place_order(
+			Origin::signed(CHARLIE),
+			ETH,
+			DOT,
+			2_000_000_000_000,
+			price_b,
+			10
+		));
+		assert_ok!(Exchange::place_order(
+			Origin::signed(BOB),
+			ETH,
+			DOT,
+			3_000_000_000_000,
+			price_a,
+			10
+		));
+
+		assert_eq!(
+			Exchange::order_book((ETH, DOT)).into_inner(),
+			vec![(price_b, 1), (price_a, 0), (price_a, 2)]
+		);
+
+		let order_ids: Vec<OrderId> = Exchange::orders_in_book(ETH, DOT).iter().map(|(id, _)| *id).collect();
+		assert_eq!(order_ids, vec![1, 0, 2]);
+
+		let order_ids: Vec<OrderId> = Exchange::orders_of(&BOB).iter().map(|(id, _)| *id).collect();
+		assert_eq!(order_ids, vec![0, 2]);
+
+		assert!(Exchange::orders_in_book(DOT, ETH).is_empty());
+	});
+}
+
+#[test]
+fn cancel_order_should_work() {
+	new_test_ext().execute_with(|| {
+		initialize_pool(ETH, DOT, ALICE, 100_000_000_000_000, Price::from(2));
+
+		assert_ok!(Exchange::place_order(
+			Origin::signed(BOB),
+			ETH,
+			DOT,
+			1_000_000_000_000,
+			Price::from(3),
+			10
+		));
+
+		assert_noop!(
+			Exchange::cancel_order(Origin::signed(CHARLIE), 0),
+			Error::<Test>::NotOrderOwner
+		);
+		assert_noop!(
+			Exchange::cancel_order(Origin::signed(BOB), 1),
+			Error::<Test>::OrderNotFound
+		);
+
+		assert_ok!(Exchange::cancel_order(Origin::signed(BOB), 0));
+
+		assert_eq!(Exchange::order(0), None);
+		assert_eq!(Exchange::order_count(), 0);
+		assert!(!OrderBook::<Test>::contains_key((ETH, DOT)));
+		assert_eq!(Currency::reserved_balance(ETH, &BOB), 0);
+		assert_eq!(Currency::free_balance(ETH, &BOB), EndowedAmount::get());
+
+		expect_event(Event::OrderCancelled(BOB, 0, 1_000_000_000_000));
+	});
+}
+
+#[test]
+fn modify_order_should_work() {
+	new_test_ext().execute_with(|| {
+		initialize_pool(ETH, DOT, ALICE, 100_000_000_000_000, Price::from(2));
+
+		assert_ok!(Exchange::place_order(
+			Origin::signed(BOB),
+			ETH,
+			DOT,
+			1_000_000_000_000,
+			Price::from(3),
+			10
+		));
+		assert_ok!(Exchange::place_order(
+			Origin::signed(CHARLIE),
+			ETH,
+			DOT,
+			1_000_000_000_000,
+			Price::from(4),
+			10
+		));
+
+		assert_noop!(
+			Exchange::modify_order(Origin::signed(CHARLIE), 0, None, Some(Price::from(5)), None),
+			Error::<Test>::NotOrderOwner
+		);
+		assert_noop!(
+			Exchange::modify_order(Origin::signed(BOB), 2, None, Some(Price::from(5)), None),
+			Error::<Test>::OrderNotFound
+		);
+		assert_noop!(
+			Exchange::modify_order(Origin::signed(BOB), 0, None, Some(Price::from(0)), None),
+			Error::<Test>::InvalidOrderPrice
+		);
+		assert_noop!(
+			Exchange::modify_order(Origin::signed(BOB), 0, None, None, Some(0)),
+			Error::<Test>::InvalidOrderExpiry
+		);
+		assert_noop!(
+			Exchange::modify_order(Origin::signed(BOB), 0, Some(EndowedAmount::get() + 1), None, None),
+			Error::<Test>::InsufficientAssetBalance
+		);
+
+		assert_ok!(Exchange::modify_order(
+			Origin::signed(BOB),
+			0,
+			Some(3_000_000_000_000),
+			Some(Price::from(5)),
+			Some(20)
+		));
+
+		assert_eq!(
+			Exchange::order(0),
+			Some(LimitOrder {
+				who: BOB,
+				assets: AssetPair {
+					asset_in: ETH,
+					asset_out: DOT,
+				},
+				amount: 3_000_000_000_000,
+				price: Price::from(5),
+				expires_at: 20,
+			})
+		);
+		assert_eq!(
+			Exchange::order_book((ETH, DOT)).into_inner(),
+			vec![(Price::from(4), 1), (Price::from(5), 0)]
+		);
+		assert_eq!(Exchange::order_expiries(10).into_inner(), vec![1]);
+		assert_eq!(Exchange::order_expiries(20).into_inner(), vec![0]);
+		assert_eq!(Currency::reserved_balance(ETH, &BOB), 3_000_000_000_000);
+
+		expect_event(Event::OrderModified(BOB, 0, 3_000_000_000_000, Price::from(5), 20));
+
+		assert_ok!(Exchange::modify_order(
+			Origin::signed(BOB),
+			0,
+			Some(2_000_000_000_000),
+			None,
+			None
+		));
+
+		assert_eq!(Exchange::order(0).unwrap().amount, 2_000_000_000_000);
+		assert_eq!(Currency::reserved_balance(ETH, &BOB), 2_000_000_000_000);
+		assert_eq!(
+			Currency::free_balance(ETH, &BOB),
+			EndowedAmount::get() - 2_000_000_000_000
+		);
+	});
+}
+
+#[test]
+fn order_should_expire() {
+	new_test_ext().execute_with(|| {
+		initialize_pool(ETH, DOT, ALICE, 100_000_000_000_000, Price::from(2));
+
+		assert_ok!(Exchange::place_order(
+			Origin::signed(BOB),
+			ETH,
+			DOT,
+			1_000_000_000_000,
+			Price::from(3),
+			2
+		));
+
+		System::set_block_number(2);
+		<Exchange as OnFinalize<u64>>::on_finalize(2);
+		assert!(Exchange::order(0).is_some());
+
+		System::set_block_number(3);
+		<Exchange as OnFinalize<u64>>::on_finalize(3);
+
+		assert_eq!(Exchange::order(0), None);
+		assert_eq!(Exchange::order_count(), 0);
+		assert!(!OrderBook::<Test>::contains_key((ETH, DOT)));
+		assert!(!OrderExpiries::<Test>::contains_key(2));
+		assert_eq!(Currency::reserved_balance(ETH, &BOB), 0);
+		assert_eq!(Currency::free_balance(ETH, &BOB), EndowedAmount::get());
+
+		expect_event(Event::OrderExpired(BOB, 0, 1_000_000_000_000));
+	});
+}
+
+#[test]
+fn order_should_expire_behind_order_which_can_not_be_sold() {
+	new_test_ext().execute_with(|| {
+		initialize_pool(ETH, DOT, ALICE, 100_000_000_000_000, Price::from(2));
+
+		assert_ok!(Exchange::place_order(
+			Origin::signed(BOB),
+			ETH,
+			DOT,
+			1_000_000_000_000,
+			Price::from(3),
+			10
+		));
+		assert_ok!(Exchange::place_order(
+			Origin::signed(CHARLIE),
+			ETH,
+			DOT,
+			1_000_000_000_000,
+			Price::from(4),
+			2
+		));
+
+		System::set_block_number(3);
+		<Exchange as OnFinalize<u64>>::on_finalize(3);
+
+		assert!(Exchange::order(0).is_some());
+		assert_eq!(Exchange::order(1), None);
+		assert_eq!(Exchange::order_book((ETH, DOT)).into_inner(), vec![(Price::from(3), 0)]);
+		assert_eq!(Currency::reserved_balance(ETH, &CHARLIE), 0);
+
+		expect_event(Event::OrderExpired(CHARLIE, 1, 1_000_000_000_000));
+	});
+}
+
+#[test]
+fn order_should_be_sold_in_pool_when_price_reaches_limit() {
+	new_test_ext().execute_with(|| {
+		initialize_pool(ETH, DOT, ALICE, 100_000_000_000_000, Price::from(2));
+
+		let price = Price::saturating_from_rational(21, 10);
+
+		assert_ok!(Exchange::place_order(
+			Origin::signed(BOB),
+			ETH,
+			DOT,
+			1_000_000_000_000,
+			price,
+			10
+		));
+
+		<Exchange as OnFinalize<u64>>::on_finalize(1);
+		assert!(Exchange::order(0).is_some());
+
+		// ETH becomes more expensive
+		assert_ok!(XYKPallet::sell(
+			Origin::signed(CHARLIE),
+			DOT,
+			ETH,
+			20_000_000_000_000,
+			1,
+			false
+		));
+
+		<Exchange as OnFinalize<u64>>::on_finalize(1);
+
+		let bought = Currency::free_balance(DOT, &BOB) - EndowedAmount::get();
+		assert!(bought >= price.checked_mul_int(1_000_000_000_000_u128).unwrap());
+
+		assert_eq!(Exchange::order(0), None);
+		assert_eq!(Exchange::order_count(), 0);
+		assert!(!OrderBook::<Test>::contains_key((ETH, DOT)));
+		assert_eq!(Currency::reserved_balance(ETH, &BOB), 0);
+		assert_eq!(
+			Currency::free_balance(ETH, &BOB),
+			EndowedAmount::get() - 1_000_000_000_000
+		);
+
+		expect_event(Event::OrderFilled(BOB, 0, 1_000_000_000_000, bought));
+	});
+}
+
+#[test]
+fn sell_intention_should_be_filled_from_order() {
+	new_test_ext().execute_with(|| {
+		initialize_pool(ETH, DOT, ALICE, 100_000_000_000_000, Price::from(2));
+
+		let pair_account = order_pair_account();
+
+		// DAVE sells DOT for at least the spot price
+		assert_ok!(Exchange::place_order(
+			Origin::signed(DAVE),
+			DOT,
+			ETH,
+			1_500_000_000_000,
+			Price::saturating_from_rational(1, 2),
+			10
+		));
+
+		assert_ok!(Exchange::sell(
+			Origin::signed(CHARLIE),
+			ETH,
+			DOT,
+			1_000_000_000_000,
+			1_000_000_000_000,
+			false,
+		));
+		let intention_id = generate_intention_id(&CHARLIE, 0);
+
+		<Exchange as OnFinalize<u64>>::on_finalize(1);
+
+		// whole order is filled and the rest of the intention is sold in the pool
+		assert_eq!(Exchange::order(0), None);
+		assert_eq!(Exchange::order_count(), 0);
+		assert_eq!(Currency::reserved_balance(DOT, &DAVE), 0);
+		assert_eq!(
+			Currency::free_balance(DOT, &DAVE),
+			EndowedAmount::get() - 1_500_000_000_000
+		);
+		assert_eq!(
+			Currency::free_balance(ETH, &DAVE),
+			EndowedAmount::get() + 750_000_000_000
+		);
+
+		assert_eq!(
+			Currency::free_balance(ETH, &CHARLIE),
+			EndowedAmount::get() - 1_000_000_000_000
+		);
+		assert_eq!(Currency::free_balance(ETH, &pair_account), 100_250_000_000_000);
+
+		expect_events(vec![
+			Event::IntentionResolvedOrderTrade(CHARLIE, intention_id, 0, 750_000_000_000, 1_500_000_000_000).into(),
+			Event::OrderFilled(DAVE, 0, 1_500_000_000_000, 750_000_000_000).into(),
+		]);
+	});
+}
+
+#[test]
+fn buy_intention_should_be_filled_from_order() {
+	new_test_ext().execute_with(|| {
+		initialize_pool(ETH, DOT, ALICE, 100_000_000_000_000, Price::from(2));
+
+		let pair_account = order_pair_account();
+
+		assert_ok!(Exchange::place_order(
+			Origin::signed(DAVE),
+			DOT,
+			ETH,
+			3_000_000_000_000,
+			Price::saturating_from_rational(1, 2),
+			10
+		));
+
+		assert_ok!(Exchange::buy(
+			Origin::signed(CHARLIE),
+			DOT,
+			ETH,
+			1_000_000_000_000,
+			600_000_000_000,
+			false,
+		));
+		let intention_id = generate_intention_id(&CHARLIE, 0);
+
+		<Exchange as OnFinalize<u64>>::on_finalize(1);
+
+		// intention is filled completely, the rest of the order keeps resting
+		assert_eq!(Exchange::order(0).unwrap().amount, 2_000_000_000_000);
+		assert_eq!(Currency::reserved_balance(DOT, &DAVE), 2_000_000_000_000);
+		assert_eq!(
+			Currency::free_balance(ETH, &DAVE),
+			EndowedAmount::get() + 500_000_000_000
+		);
+
+		assert_eq!(
+			Currency::free_balance(DOT, &CHARLIE),
+			EndowedAmount::get() + 1_000_000_000_000
+		);
+		assert_eq!(
+			Currency::free_balance(ETH, &CHARLIE),
+			EndowedAmount::get() - 500_000_000_000
+		);
+
+		// pool is not touched
+		assert_eq!(Currency::free_balance(ETH, &pair_account), 100_000_000_000_000);
+		assert_eq!(Currency::free_balance(DOT, &pair_account), 200_000_000_000_000);
+
+		expect_events(vec![
+			Event::IntentionResolvedOrderTrade(CHARLIE, intention_id, 0, 500_000_000_000, 1_000_000_000_000).into(),
+			Event::OrderFilled(DAVE, 0, 1_000_000_000_000, 500_000_000_000).into(),
+		]);
+	});
+}
+
+#[test]
+fn intention_should_not_be_filled_from_order_worse_than_spot_price() {
+	new_test_ext().execute_with(|| {
+		initialize_pool(ETH, DOT, ALICE, 100_000_000_000_000, Price::from(2));
+
+		assert_ok!(Exchange::place_order(
+			Origin::signed(DAVE),
+			DOT,
+			ETH,
+			1_500_000_000_000,
+			Price::saturating_from_rational(6, 10),
+			10
+		));
+
+		assert_ok!(Exchange::sell(
+			Origin::signed(CHARLIE),
+			ETH,
+			DOT,
+			1_000_000_000_000,
+			1_000_000_000_000,
+			false,
+		));
+
+		<Exchange as OnFinalize<u64>>::on_finalize(1);
+
+		assert_eq!(Exchange::order(0).unwrap().amount, 1_500_000_000_000);
+		assert_eq!(Currency::reserved_balance(DOT, &DAVE), 1_500_000_000_000);
+		assert_eq!(Currency::free_balance(ETH, &DAVE), EndowedAmount::get());
+
+		// intention is sold in the pool
+		assert_eq!(Currency::free_balance(ETH, &order_pair_account()), 101_000_000_000_000);
+	});
+}
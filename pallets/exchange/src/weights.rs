@@ -57,6 +57,10 @@ pub trait WeightInfo {
 	fn on_finalize_for_one_sell_extrinsic() -> Weight;
 	fn buy_extrinsic() -> Weight;
 	fn on_finalize_for_one_buy_extrinsic() -> Weight;
+	fn place_order() -> Weight;
+	fn cancel_order() -> Weight;
+	fn modify_order() -> Weight;
+	fn process_orders(n: u32) -> Weight;
 }
 
 /// Weights for exchange using the hydraDX node and recommended hardware.
@@ -123,6 +127,29 @@ impl<T: frame_system::Config> WeightInfo for HydraWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(9 as Weight))
 			.saturating_add(T::DbWeight::get().writes(6 as Weight))
 	}
+	fn place_order() -> Weight {
+		(58_312_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn cancel_order() -> Weight {
+		(49_837_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn modify_order() -> Weight {
+		(55_104_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn process_orders(n: u32) -> Weight {
+		(4_126_000 as Weight)
+			// Standard Error: 41_000
+			.saturating_add((142_318_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((11 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((10 as Weight).saturating_mul(n as Weight)))
+	}
 }
 
 // For backwards compatibility and tests
@@ -187,4 +214,27 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
 	}
+	fn place_order() -> Weight {
+		(58_312_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn cancel_order() -> Weight {
+		(49_837_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn modify_order() -> Weight {
+		(55_104_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn process_orders(n: u32) -> Weight {
+		(4_126_000 as Weight)
+			// Standard Error: 41_000
+			.saturating_add((142_318_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((11 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((10 as Weight).saturating_mul(n as Weight)))
+	}
 }
@@ -18,17 +18,306 @@
 //! Runtime API definition for lbp pallet.
 
 #![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+#![allow(clippy::upper_case_acronyms)]
 
-use codec::Codec;
+use codec::{Codec, Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sp_runtime::traits::{MaybeDisplay, MaybeFromStr};
+use sp_runtime::FixedU128;
+use sp_std::vec::Vec;
+
+#[derive(Eq, PartialEq, Encode, Decode, Clone, Copy)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum SaleStatus {
+	/// Sale has not started yet or its start has not been set.
+	NotStarted,
+
+	/// Sale is running and trading is allowed.
+	Running,
+
+	/// Sale is paused.
+	Paused,
+
+	/// Sale has ended.
+	Ended,
+}
+
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PoolInfo<AccountId, PoolData, Balance> {
+	/// Account of the pool.
+	pub pool_account: AccountId,
+
+	/// Configuration of the pool.
+	pub pool: PoolData,
+
+	/// Status of the sale.
+	pub status: SaleStatus,
+
+	/// Weights of the first and the second asset at the requested block.
+	pub weights: (u32, u32),
+
+	/// Reserve of the first asset.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub asset_a_reserve: Balance,
+
+	/// Reserve of the second asset.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub asset_b_reserve: Balance,
+
+	/// Price of the second asset denominated in the first asset at the requested block.
+	pub spot_price: Option<FixedU128>,
+
+	/// Net amount of the first asset paid to the pool by trades.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub amount_raised: Balance,
+
+	/// Amount of fees to be collected before the repay fee stops being applied.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub repay_target_remaining: Balance,
+
+	/// Fee rate applied to trades at the current block.
+	pub fee_rate: (u32, u32),
+}
+
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct TradeQuote<AssetId, Balance> {
+	/// Amount received in sell or amount paid in buy, fee included.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub amount: Balance,
+
+	/// Fee charged for the trade.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub fee: Balance,
+
+	/// Asset in which the fee is charged.
+	pub fee_asset: AssetId,
+
+	/// Fee rate applied to the trade.
+	pub fee_rate: (u32, u32),
+
+	/// Repay fee is applied instead of the pool fee.
+	pub repay_fee_applied: bool,
+
+	/// Trade would be rejected because sold amount exceeds max fraction of the pool reserve.
+	pub max_in_ratio_exceeded: bool,
+
+	/// Trade would be rejected because bought amount exceeds max fraction of the pool reserve.
+	pub max_out_ratio_exceeded: bool,
+}
+
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct Allowance<Balance> {
+	/// Account is allowed to buy the distributed asset from the pool.
+	pub allowed: bool,
+
+	/// Amount of the distributed asset the account can still buy, `None` if there is no limit.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_option_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_option_from_string"))]
+	pub remaining: Option<Balance>,
+}
+
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct VestingInfo<AssetId, Balance> {
+	/// Vested asset.
+	pub asset: AssetId,
+
+	/// Total amount bought from the pool under vesting.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub total: Balance,
+
+	/// Amount already claimed.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub claimed: Balance,
+
+	/// Amount that can be claimed now.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub claimable: Balance,
+}
+
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct SaleRound<AccountId, PoolData, Balance, BlockNumber> {
+	/// Account of the pool of the round.
+	pub pool_account: AccountId,
+
+	/// Configuration of the pool, final configuration if the round is closed.
+	pub pool: PoolData,
+
+	/// Net amount of the first asset paid to the pool by trades.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub amount_raised: Balance,
+
+	/// Block at which the pool of the round was destroyed, `None` if the pool still exists.
+	pub closed_at: Option<BlockNumber>,
+}
+
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PriceSnapshot<BlockNumber, Balance> {
+	/// Block of the snapshot.
+	pub block: BlockNumber,
+
+	/// Price of the second asset denominated in the first asset.
+	pub spot_price: Option<FixedU128>,
+
+	/// Weights of the first and the second asset.
+	pub weights: (u32, u32),
+
+	/// Reserve of the first asset.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub asset_a_reserve: Balance,
+
+	/// Reserve of the second asset.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub asset_b_reserve: Balance,
+}
+
+#[cfg(feature = "std")]
+fn serialize_as_string<S: Serializer, T: std::fmt::Display>(t: &T, serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.serialize_str(&t.to_string())
+}
+
+#[cfg(feature = "std")]
+fn deserialize_from_string<'de, D: Deserializer<'de>, T: std::str::FromStr>(deserializer: D) -> Result<T, D::Error> {
+	let s = String::deserialize(deserializer)?;
+	s.parse::<T>()
+		.map_err(|_| serde::de::Error::custom("Parse from string failed"))
+}
+
+#[cfg(feature = "std")]
+fn serialize_option_as_string<S: Serializer, T: std::fmt::Display>(
+	t: &Option<T>,
+	serializer: S,
+) -> Result<S::Ok, S::Error> {
+	match t {
+		Some(t) => serializer.serialize_some(&t.to_string()),
+		None => serializer.serialize_none(),
+	}
+}
+
+#[cfg(feature = "std")]
+fn deserialize_option_from_string<'de, D: Deserializer<'de>, T: std::str::FromStr>(
+	deserializer: D,
+) -> Result<Option<T>, D::Error> {
+	Option::<String>::deserialize(deserializer)?
+		.map(|s| s.parse::<T>())
+		.transpose()
+		.map_err(|_| serde::de::Error::custom("Parse from string failed"))
+}
 
 sp_api::decl_runtime_apis! {
-	pub trait LBPApi<AccountId, AssetId> where
+	pub trait LBPApi<AccountId, AssetId, Balance, BlockNumber, PoolData> where
 		AccountId: Codec,
 		AssetId: Codec,
+		Balance: Codec + MaybeDisplay + MaybeFromStr,
+		BlockNumber: Codec,
+		PoolData: Codec,
 	{
 		fn get_pool_id(
 			asset_a: AssetId,
 			asset_b: AssetId
 		) -> AccountId;
+
+		fn get_pool_data(
+			asset_a: AssetId,
+			asset_b: AssetId,
+			block: Option<BlockNumber>,
+		) -> Option<PoolInfo<AccountId, PoolData, Balance>>;
+
+		fn quote_sell(
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount: Balance,
+		) -> Option<TradeQuote<AssetId, Balance>>;
+
+		fn quote_buy(
+			asset_out: AssetId,
+			asset_in: AssetId,
+			amount: Balance,
+		) -> Option<TradeQuote<AssetId, Balance>>;
+
+		fn get_remaining_allowance(
+			asset_a: AssetId,
+			asset_b: AssetId,
+			who: AccountId,
+			round: Option<u32>,
+		) -> Option<Allowance<Balance>>;
+
+		fn get_vesting(
+			asset_a: AssetId,
+			asset_b: AssetId,
+			who: AccountId,
+			round: Option<u32>,
+		) -> Option<VestingInfo<AssetId, Balance>>;
+
+		fn get_sale_rounds_count(
+			asset_a: AssetId,
+			asset_b: AssetId,
+		) -> u32;
+
+		fn get_sale_round(
+			asset_a: AssetId,
+			asset_b: AssetId,
+			round: u32,
+		) -> Option<SaleRound<AccountId, PoolData, Balance, BlockNumber>>;
+
+		fn get_price_history(
+			asset_a: AssetId,
+			asset_b: AssetId,
+			round: Option<u32>,
+		) -> Vec<PriceSnapshot<BlockNumber, Balance>>;
 	}
 }
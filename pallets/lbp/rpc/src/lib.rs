@@ -20,27 +20,99 @@
 use codec::Codec;
 use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
 use jsonrpc_derive::rpc;
+use pallet_lbp_rpc_runtime_api::{Allowance, PoolInfo, PriceSnapshot, SaleRound, TradeQuote, VestingInfo};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
-use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, MaybeDisplay, MaybeFromStr},
+};
 use std::sync::Arc;
 
 pub use pallet_lbp_rpc_runtime_api::LBPApi as LBPRuntimeApi;
 
 #[rpc]
-pub trait LBPApi<BlockHash, AccountId, AssetId> {
+pub trait LBPApi<BlockHash, AccountId, AssetId, Balance, BlockNumber, PoolInfoType, QuoteType, SaleRoundType> {
 	#[rpc(name = "lbp_getPoolAccount")]
 	fn get_pool_id(&self, asset_a: AssetId, asset_b: AssetId) -> Result<AccountId>;
+
+	#[rpc(name = "lbp_getPoolData")]
+	fn get_pool_data(
+		&self,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		block: Option<BlockNumber>,
+		at: Option<BlockHash>,
+	) -> Result<Option<PoolInfoType>>;
+
+	#[rpc(name = "lbp_quoteSell")]
+	fn quote_sell(
+		&self,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount: Balance,
+		at: Option<BlockHash>,
+	) -> Result<Option<QuoteType>>;
+
+	#[rpc(name = "lbp_quoteBuy")]
+	fn quote_buy(
+		&self,
+		asset_out: AssetId,
+		asset_in: AssetId,
+		amount: Balance,
+		at: Option<BlockHash>,
+	) -> Result<Option<QuoteType>>;
+
+	#[rpc(name = "lbp_getRemainingAllowance")]
+	fn get_remaining_allowance(
+		&self,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		who: AccountId,
+		round: Option<u32>,
+		at: Option<BlockHash>,
+	) -> Result<Option<Allowance<Balance>>>;
+
+	#[rpc(name = "lbp_getVesting")]
+	fn get_vesting(
+		&self,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		who: AccountId,
+		round: Option<u32>,
+		at: Option<BlockHash>,
+	) -> Result<Option<VestingInfo<AssetId, Balance>>>;
+
+	#[rpc(name = "lbp_getSaleRoundsCount")]
+	fn get_sale_rounds_count(&self, asset_a: AssetId, asset_b: AssetId, at: Option<BlockHash>) -> Result<u32>;
+
+	#[rpc(name = "lbp_getSaleRound")]
+	fn get_sale_round(
+		&self,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		round: u32,
+		at: Option<BlockHash>,
+	) -> Result<Option<SaleRoundType>>;
+
+	#[rpc(name = "lbp_getPriceHistory")]
+	fn get_price_history(
+		&self,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		round: Option<u32>,
+		at: Option<BlockHash>,
+	) -> Result<Vec<PriceSnapshot<BlockNumber, Balance>>>;
 }
 
-/// A struct that implements the [`XYKApi`].
+/// A struct that implements the [`LBPApi`].
 pub struct LBP<C, B> {
 	client: Arc<C>,
 	_marker: std::marker::PhantomData<B>,
 }
 
 impl<C, B> LBP<C, B> {
-	/// Create new `XYK` with the given reference to the client.
+	/// Create new `LBP` with the given reference to the client.
 	pub fn new(client: Arc<C>) -> Self {
 		LBP {
 			client,
@@ -62,13 +134,26 @@ impl From<Error> for i64 {
 	}
 }
 
-impl<C, Block, AccountId, AssetId> LBPApi<<Block as BlockT>::Hash, AccountId, AssetId> for LBP<C, Block>
+impl<C, Block, AccountId, AssetId, Balance, BlockNumber, PoolData>
+	LBPApi<
+		<Block as BlockT>::Hash,
+		AccountId,
+		AssetId,
+		Balance,
+		BlockNumber,
+		PoolInfo<AccountId, PoolData, Balance>,
+		TradeQuote<AssetId, Balance>,
+		SaleRound<AccountId, PoolData, Balance, BlockNumber>,
+	> for LBP<C, Block>
 where
 	Block: BlockT,
 	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
-	C::Api: LBPRuntimeApi<Block, AccountId, AssetId>,
+	C::Api: LBPRuntimeApi<Block, AccountId, AssetId, Balance, BlockNumber, PoolData>,
 	AccountId: Codec,
 	AssetId: Codec,
+	Balance: Codec + MaybeDisplay + MaybeFromStr,
+	BlockNumber: Codec,
+	PoolData: Codec,
 {
 	fn get_pool_id(&self, asset_a: AssetId, asset_b: AssetId) -> Result<AccountId> {
 		let api = self.client.runtime_api();
@@ -80,4 +165,160 @@ where
 			data: Some(format!("{:?}", e).into()),
 		})
 	}
+
+	fn get_pool_data(
+		&self,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		block: Option<BlockNumber>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<PoolInfo<AccountId, PoolData, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_pool_data(&at, asset_a, asset_b, block).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to retrieve pool data.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn quote_sell(
+		&self,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<TradeQuote<AssetId, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.quote_sell(&at, asset_in, asset_out, amount).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to quote sell.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn quote_buy(
+		&self,
+		asset_out: AssetId,
+		asset_in: AssetId,
+		amount: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<TradeQuote<AssetId, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.quote_buy(&at, asset_out, asset_in, amount).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to quote buy.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_remaining_allowance(
+		&self,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		who: AccountId,
+		round: Option<u32>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Allowance<Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_remaining_allowance(&at, asset_a, asset_b, who, round)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to retrieve remaining allowance.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+
+	fn get_vesting(
+		&self,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		who: AccountId,
+		round: Option<u32>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<VestingInfo<AssetId, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_vesting(&at, asset_a, asset_b, who, round)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to retrieve vesting.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+
+	fn get_sale_rounds_count(
+		&self,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<u32> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_sale_rounds_count(&at, asset_a, asset_b).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to retrieve sale rounds count.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_sale_round(
+		&self,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		round: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<SaleRound<AccountId, PoolData, Balance, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_sale_round(&at, asset_a, asset_b, round).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to retrieve sale round.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_price_history(
+		&self,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		round: Option<u32>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<PriceSnapshot<BlockNumber, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_price_history(&at, asset_a, asset_b, round)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to retrieve price history.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
 }
@@ -20,6 +20,7 @@
 use super::*;
 
 use frame_benchmarking::{account, benchmarks};
+use frame_support::traits::Hooks;
 use frame_system::RawOrigin;
 
 use primitives::AssetId;
@@ -46,12 +47,25 @@ fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
 	caller
 }
 
+// all the tiers of the schedule are validated
+fn full_repay_fee_schedule<T: Config>() -> RepayFeeSchedule {
+	let max_fee = T::MaxRepayFee::get();
+	let tiers = (1..=MAX_REPAY_FEE_TIERS)
+		.map(|i| (Balance::from(i) * 1_000_000, max_fee))
+		.collect::<Vec<_>>();
+
+	RepayFeeSchedule {
+		fee: max_fee,
+		tiers: tiers.try_into().unwrap(),
+	}
+}
+
 benchmarks! {
 	create_pool {
 		let caller = funded_account::<T>("caller", 0);
 		let pool_id = LBP::<T>::pair_account_from_assets(ASSET_A_ID, ASSET_B_ID);
 
-	}: _(RawOrigin::Root, caller.clone(), ASSET_A_ID, ASSET_A_AMOUNT, ASSET_B_ID, ASSET_B_AMOUNT, INITIAL_WEIGHT, FINAL_WEIGHT, WeightCurveType::Linear, DEFAULT_FEE, caller, 0)
+	}: _(RawOrigin::Root, caller.clone(), ASSET_A_ID, ASSET_A_AMOUNT, ASSET_B_ID, ASSET_B_AMOUNT, INITIAL_WEIGHT, FINAL_WEIGHT, WeightCurveType::Linear, DEFAULT_FEE, caller, 0, TradeProtection::default(), full_repay_fee_schedule::<T>())
 	verify {
 		assert!(PoolData::<T>::contains_key(&pool_id));
 	}
@@ -66,10 +80,10 @@ benchmarks! {
 		let new_final_weight = 55_250_600;
 		let fee = (5, 1000);
 
-		LBP::<T>::create_pool(RawOrigin::Root.into(), caller.clone(), ASSET_A_ID, ASSET_A_AMOUNT, ASSET_B_ID, ASSET_B_AMOUNT, INITIAL_WEIGHT, FINAL_WEIGHT, WeightCurveType::Linear, fee, caller.clone(), 0)?;
+		LBP::<T>::create_pool(RawOrigin::Root.into(), caller.clone(), ASSET_A_ID, ASSET_A_AMOUNT, ASSET_B_ID, ASSET_B_AMOUNT, INITIAL_WEIGHT, FINAL_WEIGHT, WeightCurveType::Linear, fee, caller.clone(), 0, TradeProtection::default(), RepayFeeSchedule::default())?;
 		ensure!(PoolData::<T>::contains_key(&pool_id), "Pool does not exist.");
 
-	}: _(RawOrigin::Signed(caller.clone()), pool_id.clone(), Some(caller.clone()), new_start, new_end, Some(new_initial_weight), Some(new_final_weight), Some(DEFAULT_FEE), Some(fee_collector), Some(1))
+	}: _(RawOrigin::Signed(caller.clone()), pool_id.clone(), Some(caller.clone()), new_start, new_end, Some(new_initial_weight), Some(new_final_weight), Some(WeightCurveType::Linear), Some(DEFAULT_FEE), Some(fee_collector), Some(1), None, Some(full_repay_fee_schedule::<T>()))
 	verify {
 		let pool_data = LBP::<T>::pool_data(pool_id).unwrap();
 		assert_eq!(pool_data.start, new_start);
@@ -82,7 +96,7 @@ benchmarks! {
 		let caller = funded_account::<T>("caller", 0);
 		let pool_id = LBP::<T>::pair_account_from_assets(ASSET_A_ID, ASSET_B_ID);
 
-		LBP::<T>::create_pool(RawOrigin::Root.into(), caller.clone(), ASSET_A_ID, ASSET_A_AMOUNT, ASSET_B_ID, ASSET_B_AMOUNT, INITIAL_WEIGHT, FINAL_WEIGHT, WeightCurveType::Linear, DEFAULT_FEE, caller.clone(), 0)?;
+		LBP::<T>::create_pool(RawOrigin::Root.into(), caller.clone(), ASSET_A_ID, ASSET_A_AMOUNT, ASSET_B_ID, ASSET_B_AMOUNT, INITIAL_WEIGHT, FINAL_WEIGHT, WeightCurveType::Linear, DEFAULT_FEE, caller.clone(), 0, TradeProtection::default(), RepayFeeSchedule::default())?;
 		ensure!(PoolData::<T>::contains_key(&pool_id), "Pool does not exist.");
 
 	}: _(RawOrigin::Signed(caller), (ASSET_A_ID, 1_000_000_000_u128), (ASSET_B_ID, 2_000_000_000_u128))
@@ -95,7 +109,7 @@ benchmarks! {
 		let caller = funded_account::<T>("caller", 0);
 		let pool_id = LBP::<T>::pair_account_from_assets(ASSET_A_ID, ASSET_B_ID);
 
-		LBP::<T>::create_pool(RawOrigin::Root.into(), caller.clone(), ASSET_A_ID, ASSET_A_AMOUNT, ASSET_B_ID, ASSET_B_AMOUNT, INITIAL_WEIGHT, FINAL_WEIGHT, WeightCurveType::Linear, DEFAULT_FEE, caller.clone(), 0)?;
+		LBP::<T>::create_pool(RawOrigin::Root.into(), caller.clone(), ASSET_A_ID, ASSET_A_AMOUNT, ASSET_B_ID, ASSET_B_AMOUNT, INITIAL_WEIGHT, FINAL_WEIGHT, WeightCurveType::Linear, DEFAULT_FEE, caller.clone(), 0, TradeProtection::default(), RepayFeeSchedule::default())?;
 		ensure!(PoolData::<T>::contains_key(&pool_id), "Pool does not exist.");
 
 	}: _(RawOrigin::Signed(caller.clone()), pool_id.clone())
@@ -115,13 +129,27 @@ benchmarks! {
 
 		let pool_id = LBP::<T>::pair_account_from_assets(ASSET_A_ID, ASSET_B_ID);
 
-		LBP::<T>::create_pool(RawOrigin::Root.into(), caller.clone(), ASSET_A_ID, ASSET_A_AMOUNT, ASSET_B_ID, ASSET_B_AMOUNT, INITIAL_WEIGHT, FINAL_WEIGHT, WeightCurveType::Linear, DEFAULT_FEE, fee_collector.clone(), 0)?;
+		LBP::<T>::create_pool(RawOrigin::Root.into(), caller.clone(), ASSET_A_ID, ASSET_A_AMOUNT, ASSET_B_ID, ASSET_B_AMOUNT, INITIAL_WEIGHT, FINAL_WEIGHT, WeightCurveType::Linear, DEFAULT_FEE, fee_collector.clone(), 0, TradeProtection {
+			max_buy_per_block: Some(ASSET_B_AMOUNT),
+			cooldown: Some(T::BlockNumber::from(1_u32)),
+			opening_fee: None,
+		}, RepayFeeSchedule::default())?;
 		ensure!(PoolData::<T>::contains_key(&pool_id), "Pool does not exist.");
 
+		LBP::<T>::set_purchase_restrictions(RawOrigin::Signed(caller.clone()).into(), pool_id.clone(), PurchaseRestrictions {
+			allowlist_only: true,
+			max_purchase: Some(ASSET_B_AMOUNT),
+		})?;
+		LBP::<T>::add_to_allowlist(RawOrigin::Signed(caller.clone()).into(), pool_id.clone(), vec![caller.clone()])?;
+		LBP::<T>::set_vesting(RawOrigin::Signed(caller.clone()).into(), pool_id.clone(), Some(VestingSchedule {
+			cliff: T::BlockNumber::from(10_u32),
+			duration: T::BlockNumber::from(100_u32),
+		}))?;
+
 		let start = T::BlockNumber::from(1u32);
 		let end = T::BlockNumber::from(11u32);
 
-		LBP::<T>::update_pool_data(RawOrigin::Signed(caller.clone()).into(), pool_id, None, Some(start), Some(end), None, None, None, None, None)?;
+		LBP::<T>::update_pool_data(RawOrigin::Signed(caller.clone()).into(), pool_id, None, Some(start), Some(end), None, None, None, None, None, None, None, None)?;
 
 	}: _(RawOrigin::Signed(caller.clone()), asset_in, asset_out, amount, max_limit)
 	verify{
@@ -139,13 +167,27 @@ benchmarks! {
 		let max_limit: Balance = 1_000_000_000;
 		let pool_id = LBP::<T>::pair_account_from_assets(ASSET_A_ID, ASSET_B_ID);
 
-		LBP::<T>::create_pool(RawOrigin::Root.into(), caller.clone(), ASSET_A_ID, ASSET_A_AMOUNT, ASSET_B_ID, ASSET_B_AMOUNT, INITIAL_WEIGHT, FINAL_WEIGHT, WeightCurveType::Linear, DEFAULT_FEE, fee_collector.clone(), 0)?;
+		LBP::<T>::create_pool(RawOrigin::Root.into(), caller.clone(), ASSET_A_ID, ASSET_A_AMOUNT, ASSET_B_ID, ASSET_B_AMOUNT, INITIAL_WEIGHT, FINAL_WEIGHT, WeightCurveType::Linear, DEFAULT_FEE, fee_collector.clone(), 0, TradeProtection {
+			max_buy_per_block: Some(ASSET_B_AMOUNT),
+			cooldown: Some(T::BlockNumber::from(1_u32)),
+			opening_fee: None,
+		}, RepayFeeSchedule::default())?;
 		ensure!(PoolData::<T>::contains_key(&pool_id), "Pool does not exist.");
 
+		LBP::<T>::set_purchase_restrictions(RawOrigin::Signed(caller.clone()).into(), pool_id.clone(), PurchaseRestrictions {
+			allowlist_only: true,
+			max_purchase: Some(ASSET_B_AMOUNT),
+		})?;
+		LBP::<T>::add_to_allowlist(RawOrigin::Signed(caller.clone()).into(), pool_id.clone(), vec![caller.clone()])?;
+		LBP::<T>::set_vesting(RawOrigin::Signed(caller.clone()).into(), pool_id.clone(), Some(VestingSchedule {
+			cliff: T::BlockNumber::from(10_u32),
+			duration: T::BlockNumber::from(100_u32),
+		}))?;
+
 		let start = T::BlockNumber::from(1u32);
 		let end = T::BlockNumber::from(11u32);
 
-		LBP::<T>::update_pool_data(RawOrigin::Signed(caller.clone()).into(), pool_id, None, Some(start), Some(end), None, None, None, None, None)?;
+		LBP::<T>::update_pool_data(RawOrigin::Signed(caller.clone()).into(), pool_id, None, Some(start), Some(end), None, None, None, None, None, None, None, None)?;
 
 	}: _(RawOrigin::Signed(caller.clone()), asset_out, asset_in, amount, max_limit)
 	verify{
@@ -153,6 +195,301 @@ benchmarks! {
 		assert_eq!(T::MultiCurrency::free_balance(asset_in, &caller), 999998772262327);
 		assert_eq!(T::MultiCurrency::free_balance(asset_in, &fee_collector), 1000000000455474);
 	}
+
+	set_graduation {
+		let caller = funded_account::<T>("caller", 0);
+		let pool_id = LBP::<T>::pair_account_from_assets(ASSET_A_ID, ASSET_B_ID);
+		let graduation = Graduation {
+			fraction: Permill::from_percent(50),
+			lock_period: T::BlockNumber::from(10_u32),
+		};
+
+		LBP::<T>::create_pool(RawOrigin::Root.into(), caller.clone(), ASSET_A_ID, ASSET_A_AMOUNT, ASSET_B_ID, ASSET_B_AMOUNT, INITIAL_WEIGHT, FINAL_WEIGHT, WeightCurveType::Linear, DEFAULT_FEE, caller.clone(), 0, TradeProtection::default(), RepayFeeSchedule::default())?;
+		ensure!(PoolData::<T>::contains_key(&pool_id), "Pool does not exist.");
+
+		let start = T::BlockNumber::from(50_u32);
+		let end = T::BlockNumber::from(100_u32);
+
+		LBP::<T>::update_pool_data(RawOrigin::Signed(caller.clone()).into(), pool_id.clone(), None, Some(start), Some(end), None, None, None, None, None, None, None, None)?;
+
+	}: _(RawOrigin::Signed(caller), pool_id.clone(), Some(graduation))
+	verify {
+		assert_eq!(LBP::<T>::pool_data(pool_id).unwrap().graduation, Some(graduation));
+		assert_eq!(LBP::<T>::scheduled_graduations().len(), 1);
+	}
+
+	unlock_graduation_shares {
+		let caller = funded_account::<T>("caller", 0);
+
+		T::MultiCurrency::set_lock(GRADUATION_LOCK_ID, ASSET_A_ID, &caller, ASSET_A_AMOUNT)?;
+		GraduationSharesUnlock::<T>::insert(&caller, ASSET_A_ID, T::BlockNumber::zero());
+
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone(), ASSET_A_ID)
+	verify {
+		assert_eq!(LBP::<T>::graduation_shares_unlock(caller, ASSET_A_ID), None);
+	}
+
+	graduate {
+		let n in 1 .. MAX_SCHEDULED_GRADUATIONS;
+
+		let caller = funded_account::<T>("caller", 0);
+		let now = frame_system::Pallet::<T>::block_number();
+		let start = now + T::BlockNumber::from(1_u32);
+		let end = now + T::BlockNumber::from(11_u32);
+		let graduation = Graduation {
+			fraction: Permill::from_percent(50),
+			lock_period: T::BlockNumber::from(10_u32),
+		};
+
+		for i in 0..n {
+			let asset_b = ASSET_B_ID + 1 + i;
+			let fee_collector = funded_account::<T>("fee_collector", i);
+			let pool_id = LBP::<T>::pair_account_from_assets(ASSET_A_ID, asset_b);
+
+			T::MultiCurrency::update_balance(asset_b, &caller, 1_000_000_000_000_000)?;
+
+			LBP::<T>::create_pool(RawOrigin::Root.into(), caller.clone(), ASSET_A_ID, ASSET_A_AMOUNT, asset_b, ASSET_B_AMOUNT, INITIAL_WEIGHT, FINAL_WEIGHT, WeightCurveType::Linear, DEFAULT_FEE, fee_collector, 0, TradeProtection::default(), RepayFeeSchedule::default())?;
+			LBP::<T>::update_pool_data(RawOrigin::Signed(caller.clone()).into(), pool_id.clone(), None, Some(start), Some(end), None, None, None, None, None, None, None, None)?;
+			LBP::<T>::set_graduation(RawOrigin::Signed(caller.clone()).into(), pool_id, Some(graduation))?;
+		}
+
+		let now = end + T::BlockNumber::from(1_u32);
+		frame_system::Pallet::<T>::set_block_number(now);
+
+	}: { LBP::<T>::on_initialize(now); }
+	verify {
+		assert!(LBP::<T>::scheduled_graduations().is_empty());
+	}
+
+	record_prices {
+		let n in 1 .. MAX_PRICE_HISTORY_POOLS;
+
+		let caller = funded_account::<T>("caller", 0);
+		let now = frame_system::Pallet::<T>::block_number();
+		let start = now + T::BlockNumber::from(1_u32);
+		let end = now + T::BlockNumber::from(11_u32);
+
+		for i in 0..n {
+			let asset_b = ASSET_B_ID + 1 + i;
+			let fee_collector = funded_account::<T>("fee_collector", i);
+			let pool_id = LBP::<T>::pair_account_from_assets(ASSET_A_ID, asset_b);
+
+			T::MultiCurrency::update_balance(asset_b, &caller, 1_000_000_000_000_000)?;
+
+			LBP::<T>::create_pool(RawOrigin::Root.into(), caller.clone(), ASSET_A_ID, ASSET_A_AMOUNT, asset_b, ASSET_B_AMOUNT, INITIAL_WEIGHT, FINAL_WEIGHT, WeightCurveType::Linear, DEFAULT_FEE, fee_collector, 0, TradeProtection::default(), RepayFeeSchedule::default())?;
+			LBP::<T>::update_pool_data(RawOrigin::Signed(caller.clone()).into(), pool_id, None, Some(start), Some(end), None, None, None, None, None, None, None, None)?;
+		}
+
+		let now = start + T::BlockNumber::from(1_u32);
+		frame_system::Pallet::<T>::set_block_number(now);
+
+	}: { LBP::<T>::on_initialize(now); }
+	verify {
+		let pool_id = LBP::<T>::pair_account_from_assets(ASSET_A_ID, ASSET_B_ID + n);
+		assert_eq!(LBP::<T>::price_history_cursor(&pool_id).1, 1);
+	}
+
+	prune_price_history {
+		let n in 1 .. MAX_PRICE_HISTORY_POOLS;
+
+		let caller = funded_account::<T>("caller", 0);
+		let now = frame_system::Pallet::<T>::block_number();
+		let start = now + T::BlockNumber::from(1_u32);
+		let end = now + T::BlockNumber::from(11_u32);
+		let length = T::PriceHistoryLength::get();
+
+		for i in 0..n {
+			let asset_b = ASSET_B_ID + 1 + i;
+			let fee_collector = funded_account::<T>("fee_collector", i);
+			let pool_id = LBP::<T>::pair_account_from_assets(ASSET_A_ID, asset_b);
+
+			T::MultiCurrency::update_balance(asset_b, &caller, 1_000_000_000_000_000)?;
+
+			LBP::<T>::create_pool(RawOrigin::Root.into(), caller.clone(), ASSET_A_ID, ASSET_A_AMOUNT, asset_b, ASSET_B_AMOUNT, INITIAL_WEIGHT, FINAL_WEIGHT, WeightCurveType::Linear, DEFAULT_FEE, fee_collector, 0, TradeProtection::default(), RepayFeeSchedule::default())?;
+			LBP::<T>::update_pool_data(RawOrigin::Signed(caller.clone()).into(), pool_id.clone(), None, Some(start), Some(end), None, None, None, None, None, None, None, None)?;
+
+			for slot in 0..length {
+				PriceHistory::<T>::insert(&pool_id, slot, PriceSnapshot {
+					block: start,
+					spot_price: None,
+					weights: (INITIAL_WEIGHT, MAX_WEIGHT - INITIAL_WEIGHT),
+					reserves: (ASSET_A_AMOUNT, ASSET_B_AMOUNT),
+				});
+			}
+			PriceHistoryCursor::<T>::insert(&pool_id, (0, length));
+		}
+
+		let now = end + T::PriceHistoryGracePeriod::get() + T::BlockNumber::from(1_u32);
+		frame_system::Pallet::<T>::set_block_number(now);
+
+	}: { LBP::<T>::on_initialize(now); }
+	verify {
+		assert!(LBP::<T>::price_history_pools().is_empty());
+	}
+
+	pause_sale {
+		let caller = funded_account::<T>("caller", 0);
+		let pool_id = LBP::<T>::pair_account_from_assets(ASSET_A_ID, ASSET_B_ID);
+
+		LBP::<T>::create_pool(RawOrigin::Root.into(), caller.clone(), ASSET_A_ID, ASSET_A_AMOUNT, ASSET_B_ID, ASSET_B_AMOUNT, INITIAL_WEIGHT, FINAL_WEIGHT, WeightCurveType::Linear, DEFAULT_FEE, caller.clone(), 0, TradeProtection::default(), RepayFeeSchedule::default())?;
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let start = now + T::BlockNumber::from(1_u32);
+		let end = now + T::BlockNumber::from(11_u32);
+
+		LBP::<T>::update_pool_data(RawOrigin::Signed(caller.clone()).into(), pool_id.clone(), None, Some(start), Some(end), None, None, None, None, None, None, None, None)?;
+		LBP::<T>::set_graduation(RawOrigin::Signed(caller.clone()).into(), pool_id.clone(), Some(Graduation {
+			fraction: Permill::from_percent(50),
+			lock_period: T::BlockNumber::from(10_u32),
+		}))?;
+
+		frame_system::Pallet::<T>::set_block_number(start);
+
+	}: _(RawOrigin::Signed(caller), pool_id.clone())
+	verify {
+		assert_eq!(LBP::<T>::paused_sale(pool_id), Some((start, false)));
+		assert!(LBP::<T>::scheduled_graduations().is_empty());
+	}
+
+	resume_sale {
+		let caller = funded_account::<T>("caller", 0);
+		let pool_id = LBP::<T>::pair_account_from_assets(ASSET_A_ID, ASSET_B_ID);
+
+		LBP::<T>::create_pool(RawOrigin::Root.into(), caller.clone(), ASSET_A_ID, ASSET_A_AMOUNT, ASSET_B_ID, ASSET_B_AMOUNT, INITIAL_WEIGHT, FINAL_WEIGHT, WeightCurveType::Linear, DEFAULT_FEE, caller.clone(), 0, TradeProtection::default(), RepayFeeSchedule::default())?;
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let start = now + T::BlockNumber::from(1_u32);
+		let end = now + T::BlockNumber::from(11_u32);
+		let paused_for = T::BlockNumber::from(5_u32);
+
+		LBP::<T>::update_pool_data(RawOrigin::Signed(caller.clone()).into(), pool_id.clone(), None, Some(start), Some(end), None, None, None, None, None, None, None, None)?;
+		LBP::<T>::set_graduation(RawOrigin::Signed(caller.clone()).into(), pool_id.clone(), Some(Graduation {
+			fraction: Permill::from_percent(50),
+			lock_period: T::BlockNumber::from(10_u32),
+		}))?;
+
+		frame_system::Pallet::<T>::set_block_number(start);
+		LBP::<T>::pause_sale(RawOrigin::Signed(caller.clone()).into(), pool_id.clone())?;
+		frame_system::Pallet::<T>::set_block_number(start + paused_for);
+
+	}: _(RawOrigin::Signed(caller), pool_id.clone())
+	verify {
+		let pool_data = LBP::<T>::pool_data(&pool_id).unwrap();
+		assert_eq!(pool_data.start, Some(start + paused_for));
+		assert_eq!(pool_data.end, Some(end + paused_for));
+		assert_eq!(LBP::<T>::paused_sale(pool_id), None);
+	}
+
+	cancel_sale {
+		let caller = funded_account::<T>("caller", 0);
+		let fee_collector = funded_account::<T>("fee_collector", 0);
+		let pool_id = LBP::<T>::pair_account_from_assets(ASSET_A_ID, ASSET_B_ID);
+
+		LBP::<T>::create_pool(RawOrigin::Root.into(), caller.clone(), ASSET_A_ID, ASSET_A_AMOUNT, ASSET_B_ID, ASSET_B_AMOUNT, INITIAL_WEIGHT, FINAL_WEIGHT, WeightCurveType::Linear, DEFAULT_FEE, fee_collector, 0, TradeProtection::default(), RepayFeeSchedule::default())?;
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let start = now + T::BlockNumber::from(1_u32);
+		let end = now + T::BlockNumber::from(11_u32);
+
+		LBP::<T>::update_pool_data(RawOrigin::Signed(caller.clone()).into(), pool_id.clone(), None, Some(start), Some(end), None, None, None, None, None, None, None, None)?;
+
+		frame_system::Pallet::<T>::set_block_number(start);
+		LBP::<T>::sell(RawOrigin::Signed(caller.clone()).into(), ASSET_A_ID, ASSET_B_ID, 100_000_000, 10_000_000)?;
+
+	}: _(RawOrigin::Root, pool_id.clone())
+	verify {
+		assert!(!PoolData::<T>::contains_key(&pool_id));
+		assert_eq!(T::MultiCurrency::free_balance(ASSET_A_ID, &pool_id), 0);
+		assert_eq!(T::MultiCurrency::free_balance(ASSET_B_ID, &pool_id), 0);
+	}
+
+	set_purchase_restrictions {
+		let caller = funded_account::<T>("caller", 0);
+		let pool_id = LBP::<T>::pair_account_from_assets(ASSET_A_ID, ASSET_B_ID);
+		let restrictions = PurchaseRestrictions {
+			allowlist_only: true,
+			max_purchase: Some(ASSET_B_AMOUNT),
+		};
+
+		LBP::<T>::create_pool(RawOrigin::Root.into(), caller.clone(), ASSET_A_ID, ASSET_A_AMOUNT, ASSET_B_ID, ASSET_B_AMOUNT, INITIAL_WEIGHT, FINAL_WEIGHT, WeightCurveType::Linear, DEFAULT_FEE, caller.clone(), 0, TradeProtection::default(), RepayFeeSchedule::default())?;
+
+	}: _(RawOrigin::Signed(caller), pool_id.clone(), restrictions)
+	verify {
+		assert_eq!(LBP::<T>::purchase_restrictions(pool_id), Some(restrictions));
+	}
+
+	add_to_allowlist {
+		let n in 1 .. MAX_ALLOWLIST_SIZE;
+
+		let caller = funded_account::<T>("caller", 0);
+		let pool_id = LBP::<T>::pair_account_from_assets(ASSET_A_ID, ASSET_B_ID);
+		let accounts: Vec<T::AccountId> = (0..n).map(|i| account("participant", i, SEED)).collect();
+
+		LBP::<T>::create_pool(RawOrigin::Root.into(), caller.clone(), ASSET_A_ID, ASSET_A_AMOUNT, ASSET_B_ID, ASSET_B_AMOUNT, INITIAL_WEIGHT, FINAL_WEIGHT, WeightCurveType::Linear, DEFAULT_FEE, caller.clone(), 0, TradeProtection::default(), RepayFeeSchedule::default())?;
+
+	}: _(RawOrigin::Signed(caller), pool_id.clone(), accounts)
+	verify {
+		assert_eq!(LBP::<T>::allowlist_size(pool_id), n);
+	}
+
+	remove_from_allowlist {
+		let n in 1 .. MAX_ALLOWLIST_SIZE;
+
+		let caller = funded_account::<T>("caller", 0);
+		let pool_id = LBP::<T>::pair_account_from_assets(ASSET_A_ID, ASSET_B_ID);
+		let accounts: Vec<T::AccountId> = (0..n).map(|i| account("participant", i, SEED)).collect();
+
+		LBP::<T>::create_pool(RawOrigin::Root.into(), caller.clone(), ASSET_A_ID, ASSET_A_AMOUNT, ASSET_B_ID, ASSET_B_AMOUNT, INITIAL_WEIGHT, FINAL_WEIGHT, WeightCurveType::Linear, DEFAULT_FEE, caller.clone(), 0, TradeProtection::default(), RepayFeeSchedule::default())?;
+		LBP::<T>::add_to_allowlist(RawOrigin::Signed(caller.clone()).into(), pool_id.clone(), accounts.clone())?;
+
+	}: _(RawOrigin::Signed(caller), pool_id.clone(), accounts)
+	verify {
+		assert_eq!(LBP::<T>::allowlist_size(pool_id), 0);
+	}
+
+	set_vesting {
+		let caller = funded_account::<T>("caller", 0);
+		let pool_id = LBP::<T>::pair_account_from_assets(ASSET_A_ID, ASSET_B_ID);
+		let vesting = VestingSchedule {
+			cliff: T::BlockNumber::from(10_u32),
+			duration: T::BlockNumber::from(100_u32),
+		};
+
+		LBP::<T>::create_pool(RawOrigin::Root.into(), caller.clone(), ASSET_A_ID, ASSET_A_AMOUNT, ASSET_B_ID, ASSET_B_AMOUNT, INITIAL_WEIGHT, FINAL_WEIGHT, WeightCurveType::Linear, DEFAULT_FEE, caller.clone(), 0, TradeProtection::default(), RepayFeeSchedule::default())?;
+
+	}: _(RawOrigin::Signed(caller), pool_id.clone(), Some(vesting))
+	verify {
+		assert_eq!(LBP::<T>::sale_vesting(pool_id), Some(vesting));
+	}
+
+	claim_vested {
+		let caller = funded_account::<T>("caller", 0);
+		let buyer = funded_account::<T>("buyer", 0);
+		let fee_collector = funded_account::<T>("fee_collector", 0);
+		let pool_id = LBP::<T>::pair_account_from_assets(ASSET_A_ID, ASSET_B_ID);
+
+		LBP::<T>::create_pool(RawOrigin::Root.into(), caller.clone(), ASSET_A_ID, ASSET_A_AMOUNT, ASSET_B_ID, ASSET_B_AMOUNT, INITIAL_WEIGHT, FINAL_WEIGHT, WeightCurveType::Linear, DEFAULT_FEE, fee_collector, 0, TradeProtection::default(), RepayFeeSchedule::default())?;
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let start = now + T::BlockNumber::from(1_u32);
+		let end = now + T::BlockNumber::from(11_u32);
+
+		LBP::<T>::update_pool_data(RawOrigin::Signed(caller.clone()).into(), pool_id.clone(), None, Some(start), Some(end), None, None, None, None, None, None, None, None)?;
+		LBP::<T>::set_vesting(RawOrigin::Signed(caller).into(), pool_id.clone(), Some(VestingSchedule {
+			cliff: T::BlockNumber::from(10_u32),
+			duration: T::BlockNumber::from(100_u32),
+		}))?;
+
+		frame_system::Pallet::<T>::set_block_number(start);
+		LBP::<T>::buy(RawOrigin::Signed(buyer.clone()).into(), ASSET_B_ID, ASSET_A_ID, 10_000_000, 1_000_000_000)?;
+
+		// half of the tokens is vested
+		frame_system::Pallet::<T>::set_block_number(end + T::BlockNumber::from(60_u32));
+
+	}: _(RawOrigin::Signed(buyer.clone()), pool_id.clone())
+	verify {
+		assert_eq!(LBP::<T>::vesting(pool_id, buyer).unwrap().claimed, 5_000_000);
+	}
 }
 
 #[cfg(test)]
@@ -170,6 +507,19 @@ mod tests {
 			assert_ok!(Pallet::<Test>::test_benchmark_remove_liquidity());
 			assert_ok!(Pallet::<Test>::test_benchmark_sell());
 			assert_ok!(Pallet::<Test>::test_benchmark_buy());
+			assert_ok!(Pallet::<Test>::test_benchmark_set_graduation());
+			assert_ok!(Pallet::<Test>::test_benchmark_unlock_graduation_shares());
+			assert_ok!(Pallet::<Test>::test_benchmark_graduate());
+			assert_ok!(Pallet::<Test>::test_benchmark_record_prices());
+			assert_ok!(Pallet::<Test>::test_benchmark_prune_price_history());
+			assert_ok!(Pallet::<Test>::test_benchmark_pause_sale());
+			assert_ok!(Pallet::<Test>::test_benchmark_resume_sale());
+			assert_ok!(Pallet::<Test>::test_benchmark_cancel_sale());
+			assert_ok!(Pallet::<Test>::test_benchmark_set_purchase_restrictions());
+			assert_ok!(Pallet::<Test>::test_benchmark_add_to_allowlist());
+			assert_ok!(Pallet::<Test>::test_benchmark_remove_from_allowlist());
+			assert_ok!(Pallet::<Test>::test_benchmark_set_vesting());
+			assert_ok!(Pallet::<Test>::test_benchmark_claim_vested());
 		});
 	}
 }
@@ -0,0 +1,162 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2021  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weight curves other than the linear one.
+//!
+//! Progress of a sale is given as `elapsed / duration` blocks. All functions return `None` if the
+//! calculation overflows.
+
+use hydra_dx_math::types::LBPWeight;
+use sp_runtime::Permill;
+
+/// Fixed point accuracy of the exponential decay calculation.
+const DECAY_ONE: u128 = 1_000_000_000_000_000_000;
+
+/// `2^(-2^(-i))` for bits of the fractional exponent.
+const HALVING_FACTORS: [u128; 30] = [
+	707_106_781_186_547_524,
+	840_896_415_253_714_543,
+	917_004_043_204_671_231,
+	957_603_280_698_573_646,
+	978_572_062_087_700_134,
+	989_228_013_193_975_484,
+	994_599_423_483_633_175,
+	997_296_056_085_470_126,
+	998_647_112_890_970_173,
+	999_323_327_502_650_752,
+	999_661_606_496_243_683,
+	999_830_788_931_929_063,
+	999_915_390_886_613_497,
+	999_957_694_548_431_132,
+	999_978_847_050_491_929,
+	999_989_423_469_314_464,
+	999_994_711_720_674_283,
+	999_997_355_856_841_394,
+	999_998_677_927_546_759,
+	999_999_338_963_554_895,
+	999_999_669_481_722_826,
+	999_999_834_740_847_757,
+	999_999_917_370_420_465,
+	999_999_958_685_209_379,
+	999_999_979_342_604_476,
+	999_999_989_671_302_184,
+	999_999_994_835_651_079,
+	999_999_997_417_825_536,
+	999_999_998_708_912_767,
+	999_999_999_354_456_383,
+];
+
+/// Weight at `x` on the line between `(x0, y0)` and `(x1, y1)`, rounded down.
+fn interpolate(x0: u128, x1: u128, y0: LBPWeight, y1: LBPWeight, x: u128) -> Option<LBPWeight> {
+	let left = (y0 as u128).checked_mul(x1.checked_sub(x)?)?;
+	let right = (y1 as u128).checked_mul(x.checked_sub(x0)?)?;
+
+	left.checked_add(right)?
+		.checked_div(x1.checked_sub(x0)?)?
+		.try_into()
+		.ok()
+}
+
+/// `2^(-numerator / denominator)` with `DECAY_ONE` accuracy.
+fn pow2_neg(numerator: u128, denominator: u128) -> Option<u128> {
+	let integer = numerator.checked_div(denominator)?;
+	let mut remainder = numerator % denominator;
+
+	let mut result = if integer < 64 { DECAY_ONE >> integer } else { 0 };
+	for factor in HALVING_FACTORS.iter() {
+		remainder = remainder.checked_mul(2)?;
+		if remainder >= denominator {
+			remainder -= denominator;
+			result = result.checked_mul(*factor)? / DECAY_ONE;
+		}
+	}
+
+	Some(result)
+}
+
+/// Weight which halves its distance to `final_weight` about `rate` times during the sale.
+///
+/// The curve is scaled so that it starts at `initial_weight` and ends exactly at `final_weight`.
+pub(crate) fn exponential_decay(
+	elapsed: u128,
+	duration: u128,
+	rate: u32,
+	initial_weight: LBPWeight,
+	final_weight: LBPWeight,
+) -> Option<LBPWeight> {
+	let current = pow2_neg((rate as u128).checked_mul(elapsed)?, duration)?;
+	let end = pow2_neg(rate as u128, 1)?;
+
+	// share of the initial weight in the current weight
+	let share = current
+		.checked_sub(end)?
+		.checked_mul(DECAY_ONE)?
+		.checked_div(DECAY_ONE.checked_sub(end)?)?;
+
+	(initial_weight as u128)
+		.checked_mul(share)?
+		.checked_add((final_weight as u128).checked_mul(DECAY_ONE.checked_sub(share)?)?)?
+		.checked_div(DECAY_ONE)?
+		.try_into()
+		.ok()
+}
+
+/// Weight constant within each of `steps` periods of equal length. The first period has
+/// `initial_weight`, the last one `final_weight` and the periods in between are spread evenly.
+pub(crate) fn step(
+	elapsed: u128,
+	duration: u128,
+	steps: u32,
+	initial_weight: LBPWeight,
+	final_weight: LBPWeight,
+) -> Option<LBPWeight> {
+	let last_step = (steps as u128).checked_sub(1)?;
+	let current_step = elapsed
+		.checked_mul(steps as u128)?
+		.checked_div(duration)?
+		.min(last_step);
+
+	interpolate(0, last_step, initial_weight, final_weight, current_step)
+}
+
+/// Weight on the polyline going from `initial_weight` through `points` to `final_weight`.
+///
+/// `points` are pairs of sale progress and weight, ordered by progress.
+pub(crate) fn piecewise_linear(
+	elapsed: u128,
+	duration: u128,
+	points: &[(Permill, LBPWeight)],
+	initial_weight: LBPWeight,
+	final_weight: LBPWeight,
+) -> Option<LBPWeight> {
+	let accuracy = Permill::one().deconstruct() as u128;
+	let progress = elapsed.checked_mul(accuracy)?.checked_div(duration)?;
+
+	let mut previous = (0, initial_weight);
+	for (x, y) in points
+		.iter()
+		.map(|(x, y)| (x.deconstruct() as u128, *y))
+		.chain(sp_std::iter::once((accuracy, final_weight)))
+	{
+		if progress <= x {
+			return interpolate(previous.0, x, previous.1, y, progress);
+		}
+		previous = (x, y);
+	}
+
+	None
+}
@@ -23,14 +23,15 @@
 
 use codec::{Decode, Encode};
 use frame_support::sp_runtime::{
-	traits::{AtLeast32BitUnsigned, BlockNumberProvider, Saturating, Zero},
-	DispatchError, RuntimeDebug,
+	helpers_128bit::multiply_by_rational,
+	traits::{AtLeast32BitUnsigned, BlockNumberProvider, CheckedMul, Saturating, TrailingZeroInput, Zero},
+	DispatchError, FixedPointNumber, Permill, RuntimeDebug, SaturatedConversion,
 };
 use frame_support::{
 	dispatch::DispatchResult,
 	ensure,
-	traits::{EnsureOrigin, Get, LockIdentifier},
-	transactional,
+	traits::{ConstU32, EnsureOrigin, Get, LockIdentifier},
+	transactional, BoundedVec,
 };
 use frame_system::ensure_signed;
 use hydra_dx_math::types::LBPWeight;
@@ -39,10 +40,11 @@ use orml_traits::{MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency};
 use primitives::{
 	asset::AssetPair,
 	constants::chain::{MAX_IN_RATIO, MAX_OUT_RATIO},
-	Amount, AssetId, Balance,
+	Amount, AssetId, Balance, Price,
 };
 
 use scale_info::TypeInfo;
+use sp_core::hashing::blake2_256;
 
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
@@ -56,6 +58,10 @@ mod tests;
 
 mod benchmarking;
 
+mod curves;
+
+pub mod migration;
+
 #[allow(clippy::all)]
 pub mod weights;
 use weights::WeightInfo;
@@ -65,10 +71,29 @@ pub use pallet::*;
 type BalanceOf<T> = <<T as Config>::MultiCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::Balance;
 type PoolId<T> = <T as frame_system::Config>::AccountId;
 
+/// Max number of intermediate points of a piecewise linear weight curve
+pub const MAX_WEIGHT_CURVE_POINTS: u32 = 10;
+
+/// Max rate of an exponential decay weight curve
+pub const MAX_DECAY_RATE: u32 = 32;
+
+/// Intermediate points of a piecewise linear weight curve, pairs of sale progress and weight of the asset_a
+pub type WeightCurvePoints = BoundedVec<(Permill, LBPWeight), ConstU32<MAX_WEIGHT_CURVE_POINTS>>;
+
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-#[derive(RuntimeDebug, Encode, Decode, Copy, Clone, PartialEq, Eq, TypeInfo)]
+#[derive(RuntimeDebug, Encode, Decode, Clone, PartialEq, Eq, TypeInfo)]
 pub enum WeightCurveType {
+	/// Weight changes linearly from the initial to the final weight.
 	Linear,
+	/// Weight approaches the final weight quickly at the beginning of the sale and slowly at the end.
+	/// The distance to the final weight halves about `rate` times during the sale.
+	ExponentialDecay { rate: u32 },
+	/// Sale is split into `steps` periods of equal length with constant weight. The first period has
+	/// the initial weight, the last one has the final weight.
+	Step { steps: u32 },
+	/// Weight changes linearly from the initial weight through the points to the final weight.
+	/// Points have to be ordered by sale progress.
+	PiecewiseLinear(WeightCurvePoints),
 }
 
 impl Default for WeightCurveType {
@@ -86,6 +111,125 @@ pub const MAX_SALE_DURATION: u32 = (60 * 60 * 24 / 6) * 14;
 /// Lock Identifier for the collected fees
 pub const COLLECTOR_LOCK_ID: LockIdentifier = *b"lbpcllct";
 
+/// Lock Identifier for the shares received when a pool graduates
+pub const GRADUATION_LOCK_ID: LockIdentifier = *b"lbpgradu";
+
+/// Max number of pools waiting for graduation
+pub const MAX_SCHEDULED_GRADUATIONS: u32 = 20;
+
+/// Pools waiting for graduation, pairs of the sale end and the pool id ordered by the sale end
+pub type ScheduledGraduationsOf<AccountId, BlockNumber> =
+	BoundedVec<(BlockNumber, AccountId), ConstU32<MAX_SCHEDULED_GRADUATIONS>>;
+
+/// Max number of accounts in the allowlist of a pool
+pub const MAX_ALLOWLIST_SIZE: u32 = 500;
+
+/// Lock of the distributed asset bought from a pool with vesting
+pub const VESTING_LOCK_ID: LockIdentifier = *b"lbpvestn";
+
+/// Max number of tiers of a repay fee schedule
+pub const MAX_REPAY_FEE_TIERS: u32 = 10;
+
+/// Repay fee of the pools created before the repay fee became configurable
+pub const DEFAULT_REPAY_FEE: (u32, u32) = (2, 10);
+
+pub type RepayFeeTiers = BoundedVec<(Balance, (u32, u32)), ConstU32<MAX_REPAY_FEE_TIERS>>;
+
+/// Max number of pools with a scheduled sale whose price history is recorded or kept
+pub const MAX_PRICE_HISTORY_POOLS: u32 = 50;
+
+/// Pools with a price history, pairs of the sale end and the pool id ordered by the sale end
+pub type PriceHistoryPoolsOf<AccountId, BlockNumber> =
+	BoundedVec<(BlockNumber, AccountId), ConstU32<MAX_PRICE_HISTORY_POOLS>>;
+
+/// Settings of moving the pool reserves into a new pool when the sale ends.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(RuntimeDebug, Encode, Decode, Clone, Copy, PartialEq, Eq, TypeInfo)]
+pub struct Graduation<BlockNumber> {
+	/// fraction of the remaining reserves moved into the new pool
+	pub fraction: Permill,
+
+	/// number of blocks the shares of the new pool are locked in the owner's account, shares are not locked if zero
+	pub lock_period: BlockNumber,
+}
+
+/// Vesting of the distributed asset bought from a pool, counted from the end of the sale.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(RuntimeDebug, Encode, Decode, Clone, Copy, PartialEq, Eq, TypeInfo)]
+pub struct VestingSchedule<BlockNumber> {
+	/// number of blocks after the end of the sale before the first tokens are unlocked
+	pub cliff: BlockNumber,
+
+	/// number of blocks after the cliff during which the tokens are unlocked linearly, all the tokens are unlocked at
+	/// the end of the cliff if zero
+	pub duration: BlockNumber,
+}
+
+/// Distributed asset bought by an account from a pool with vesting.
+#[derive(RuntimeDebug, Encode, Decode, Clone, Copy, PartialEq, Eq, TypeInfo)]
+pub struct VestingInfo<BlockNumber> {
+	/// vested asset
+	pub asset: AssetId,
+
+	/// total amount bought from the pool
+	pub total: Balance,
+
+	/// amount already claimed
+	pub claimed: Balance,
+
+	/// end of the sale, the schedule is counted from this block
+	pub start: BlockNumber,
+
+	/// vesting schedule of the pool
+	pub schedule: VestingSchedule<BlockNumber>,
+}
+
+/// Fee charged instead of the pool fee until the repay target is reached.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(RuntimeDebug, Encode, Decode, Clone, PartialEq, Eq, TypeInfo)]
+pub struct RepayFeeSchedule {
+	/// fee charged until the collected fees reach the first tier
+	pub fee: (u32, u32),
+
+	/// pairs of the amount of collected fees and the fee charged from that amount, ordered by the amount
+	pub tiers: RepayFeeTiers,
+}
+
+impl Default for RepayFeeSchedule {
+	fn default() -> Self {
+		RepayFeeSchedule {
+			fee: DEFAULT_REPAY_FEE,
+			tiers: Default::default(),
+		}
+	}
+}
+
+impl RepayFeeSchedule {
+	/// Fee of the tier reached by the `collected` fees.
+	pub fn fee_at(&self, collected: Balance) -> (u32, u32) {
+		self.tiers
+			.iter()
+			.rev()
+			.find(|(amount, _)| *amount <= collected)
+			.map_or(self.fee, |(_, fee)| *fee)
+	}
+}
+
+/// Protections of a sale against trading bots, all of them are off by default.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(RuntimeDebug, Encode, Decode, Clone, Copy, Default, PartialEq, Eq, TypeInfo)]
+pub struct TradeProtection<BlockNumber> {
+	/// max amount of the distributed asset a single account can buy in one block, unlimited if `None`
+	pub max_buy_per_block: Option<Balance>,
+
+	/// min number of blocks between two trades of the same account, no cool-down if `None`
+	pub cooldown: Option<BlockNumber>,
+
+	/// fee charged instead of the pool fee during the first blocks of the sale, unless the repay fee is higher
+	/// [fee, number of blocks]
+	pub opening_fee: Option<((u32, u32), BlockNumber)>,
+}
+
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(RuntimeDebug, Encode, Decode, Clone, PartialEq, Eq, TypeInfo)]
 pub struct Pool<AccountId, BlockNumber: AtLeast32BitUnsigned + Copy> {
@@ -116,8 +260,18 @@ pub struct Pool<AccountId, BlockNumber: AtLeast32BitUnsigned + Copy> {
 	/// person that receives the fee
 	pub fee_collector: AccountId,
 
-	/// repayment target of the accumulated asset in fee collectors account, when this target is reached fee drops from 20% to fee
+	/// repayment target of the accumulated asset in fee collectors account, when this target is reached fee drops from
+	/// the repay fee to fee
 	pub repay_target: Balance,
+
+	/// fee charged until the repay target is reached
+	pub repay_fee: RepayFeeSchedule,
+
+	/// graduation into a new pool at the end of the sale
+	pub graduation: Option<Graduation<BlockNumber>>,
+
+	/// protections against trading bots
+	pub trade_protection: TradeProtection<BlockNumber>,
 }
 
 impl<AccountId, BlockNumber: AtLeast32BitUnsigned + Copy> Pool<AccountId, BlockNumber> {
@@ -131,6 +285,8 @@ impl<AccountId, BlockNumber: AtLeast32BitUnsigned + Copy> Pool<AccountId, BlockN
 		fee: (u32, u32),
 		fee_collector: AccountId,
 		repay_target: Balance,
+		trade_protection: TradeProtection<BlockNumber>,
+		repay_fee: RepayFeeSchedule,
 	) -> Self {
 		Pool {
 			owner: pool_owner,
@@ -143,13 +299,148 @@ impl<AccountId, BlockNumber: AtLeast32BitUnsigned + Copy> Pool<AccountId, BlockN
 			fee,
 			fee_collector,
 			repay_target,
+			repay_fee,
+			graduation: None,
+			trade_protection,
 		}
 	}
 }
 
+/// State of a pool recorded at the beginning of a block of the sale.
+#[derive(RuntimeDebug, Encode, Decode, Clone, Copy, PartialEq, Eq, TypeInfo)]
+pub struct PriceSnapshot<BlockNumber> {
+	/// block of the snapshot
+	pub block: BlockNumber,
+
+	/// price of the asset_b denominated in the asset_a
+	pub spot_price: Option<Price>,
+
+	/// weights of the asset_a and the asset_b
+	pub weights: (LBPWeight, LBPWeight),
+
+	/// reserves of the asset_a and the asset_b
+	pub reserves: (Balance, Balance),
+}
+
+/// Sale round of an asset pair.
+#[derive(RuntimeDebug, Encode, Decode, Clone, PartialEq, Eq, TypeInfo)]
+pub struct SaleRound<AccountId, BlockNumber: AtLeast32BitUnsigned + Copy> {
+	/// configuration of the pool, final configuration if the round is closed
+	pub pool: Pool<AccountId, BlockNumber>,
+
+	/// net amount of the accumulated asset paid to the pool by trades
+	pub amount_raised: Balance,
+
+	/// block at which the pool of the round was destroyed, `None` if the pool still exists
+	pub closed_at: Option<BlockNumber>,
+}
+
+/// Status of a sale.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum SaleStatus {
+	/// Sale has not started yet or its start has not been set.
+	NotStarted,
+	/// Sale is running and trading is allowed.
+	Running,
+	/// Sale is paused and trading is not allowed until it is resumed.
+	Paused,
+	/// Sale has ended.
+	Ended,
+}
+
+/// Configuration and current state of a pool.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct SaleInfo<AccountId, BlockNumber: AtLeast32BitUnsigned + Copy> {
+	/// Account of the pool.
+	pub pool_account: AccountId,
+	/// Configuration of the pool.
+	pub pool: Pool<AccountId, BlockNumber>,
+	/// Status of the sale.
+	pub status: SaleStatus,
+	/// Weights of the asset_a and the asset_b at the requested block.
+	pub weights: (LBPWeight, LBPWeight),
+	/// Reserve of the asset_a.
+	pub asset_a_reserve: Balance,
+	/// Reserve of the asset_b.
+	pub asset_b_reserve: Balance,
+	/// Price of the asset_b denominated in the asset_a at the requested block.
+	pub spot_price: Option<Price>,
+	/// Net amount of the asset_a paid to the pool by trades.
+	pub amount_raised: Balance,
+	/// Amount of fees to be collected before the repay fee stops being applied.
+	pub repay_target_remaining: Balance,
+	/// Fee rate applied to trades at the current block.
+	pub fee_rate: (u32, u32),
+}
+
+/// Outcome of a trade calculated without executing it.
+#[derive(Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct TradeQuote {
+	/// Amount received in sell or amount paid in buy, fee included.
+	pub amount: Balance,
+	/// Fee charged for the trade.
+	pub fee: Balance,
+	/// Asset in which the fee is charged, always the accumulated asset.
+	pub fee_asset: AssetId,
+	/// Fee rate applied to the trade.
+	pub fee_rate: (u32, u32),
+	/// Repay fee is applied instead of the pool fee.
+	pub repay_fee_applied: bool,
+	/// Sold amount exceeds max fraction of the pool reserve.
+	pub max_in_ratio_exceeded: bool,
+	/// Bought amount exceeds max fraction of the pool reserve.
+	pub max_out_ratio_exceeded: bool,
+}
+
+/// Restrictions on buying the distributed asset (asset_b) from a pool.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(RuntimeDebug, Encode, Decode, Clone, Copy, Default, PartialEq, Eq, TypeInfo)]
+pub struct PurchaseRestrictions {
+	/// only accounts in the allowlist of the pool can buy
+	pub allowlist_only: bool,
+
+	/// max amount of the distributed asset a single account can buy during the sale, unlimited if `None`
+	pub max_purchase: Option<Balance>,
+}
+
+/// Amount of the distributed asset an account can still buy from a pool.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct Allowance {
+	/// Account is allowed to buy from the pool.
+	pub allowed: bool,
+	/// Amount the account can still buy, `None` if there is no limit.
+	pub remaining: Option<Balance>,
+}
+
+/// Creates the pool a finished sale graduates into.
+pub trait GraduationHandler<AccountId> {
+	/// Create a pool of `asset_a` and `asset_b` with the liquidity provided by `who`.
+	///
+	/// Returns the share token of the new pool and the amount of shares received by `who`.
+	fn create_pool(
+		who: &AccountId,
+		asset_a: AssetId,
+		amount_a: Balance,
+		asset_b: AssetId,
+		amount_b: Balance,
+	) -> Result<(AssetId, Balance), DispatchError>;
+}
+
+impl<AccountId> GraduationHandler<AccountId> for () {
+	fn create_pool(
+		_who: &AccountId,
+		_asset_a: AssetId,
+		_amount_a: Balance,
+		_asset_b: AssetId,
+		_amount_b: Balance,
+	) -> Result<(AssetId, Balance), DispatchError> {
+		Err(DispatchError::Other("Graduation is not supported"))
+	}
+}
+
 pub trait LBPWeightCalculation<BlockNumber: AtLeast32BitUnsigned> {
 	fn calculate_weight(
-		weight_curve: WeightCurveType,
+		weight_curve: &WeightCurveType,
 		start: BlockNumber,
 		end: BlockNumber,
 		initial_weight: LBPWeight,
@@ -158,17 +449,61 @@ pub trait LBPWeightCalculation<BlockNumber: AtLeast32BitUnsigned> {
 	) -> Option<LBPWeight>;
 }
 
+/// Blocks elapsed since `start` and duration of the sale. Returns `None` if `at` is outside of the sale.
+fn sale_progress<BlockNumber: AtLeast32BitUnsigned>(
+	start: BlockNumber,
+	end: BlockNumber,
+	at: BlockNumber,
+) -> Option<(u128, u128)> {
+	if !(start <= at && at <= end && start < end) {
+		return None;
+	}
+
+	Some((
+		at.saturating_sub(start.clone()).saturated_into(),
+		end.saturating_sub(start).saturated_into(),
+	))
+}
+
+/// Price of the asset_b denominated in the asset_a, `reserve_a * weight_b / (reserve_b * weight_a)`.
+fn spot_price(reserves: (Balance, Balance), weights: (LBPWeight, LBPWeight)) -> Option<Price> {
+	Price::checked_from_rational(reserves.0, reserves.1)
+		.zip(Price::checked_from_rational(weights.1, weights.0))
+		.and_then(|(reserves_ratio, weights_ratio)| reserves_ratio.checked_mul(&weights_ratio))
+}
+
+/// Returns true if the fee rate `fee` is higher than the fee rate `max`. Denominators have to be non-zero.
+fn fee_exceeds(fee: (u32, u32), max: (u32, u32)) -> bool {
+	(fee.0 as u64).saturating_mul(max.1 as u64) > (max.0 as u64).saturating_mul(fee.1 as u64)
+}
+
 pub struct LBPWeightFunction;
 impl<BlockNumber: AtLeast32BitUnsigned> LBPWeightCalculation<BlockNumber> for LBPWeightFunction {
 	fn calculate_weight(
-		_weight_curve: WeightCurveType,
+		weight_curve: &WeightCurveType,
 		start: BlockNumber,
 		end: BlockNumber,
 		initial_weight: LBPWeight,
 		final_weight: LBPWeight,
 		at: BlockNumber,
 	) -> Option<LBPWeight> {
-		hydra_dx_math::lbp::calculate_linear_weights(start, end, initial_weight, final_weight, at).ok()
+		match weight_curve {
+			WeightCurveType::Linear => {
+				hydra_dx_math::lbp::calculate_linear_weights(start, end, initial_weight, final_weight, at).ok()
+			}
+			WeightCurveType::ExponentialDecay { rate } => {
+				let (elapsed, duration) = sale_progress(start, end, at)?;
+				curves::exponential_decay(elapsed, duration, *rate, initial_weight, final_weight)
+			}
+			WeightCurveType::Step { steps } => {
+				let (elapsed, duration) = sale_progress(start, end, at)?;
+				curves::step(elapsed, duration, *steps, initial_weight, final_weight)
+			}
+			WeightCurveType::PiecewiseLinear(points) => {
+				let (elapsed, duration) = sale_progress(start, end, at)?;
+				curves::piecewise_linear(elapsed, duration, points, initial_weight, final_weight)
+			}
+		}
 	}
 }
 
@@ -178,7 +513,11 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::OriginFor;
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
@@ -196,6 +535,9 @@ pub mod pallet {
 		/// The origin which can create a new pool
 		type CreatePoolOrigin: EnsureOrigin<Self::Origin>;
 
+		/// The origin which can pause, resume and cancel any sale
+		type EmergencyOrigin: EnsureOrigin<Self::Origin>;
+
 		/// Function for calculation of LBP weights
 		type LBPWeightFunction: LBPWeightCalculation<Self::BlockNumber>;
 
@@ -221,12 +563,55 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxOutRatio: Get<u128>;
 
+		/// Max fee of the repay fee schedules
+		#[pallet::constant]
+		type MaxRepayFee: Get<(u32, u32)>;
+
+		/// Max number of price snapshots kept for a pool
+		#[pallet::constant]
+		type PriceHistoryLength: Get<u32>;
+
+		/// Number of blocks the price history of a pool is kept after the end of the sale
+		#[pallet::constant]
+		type PriceHistoryGracePeriod: Get<Self::BlockNumber>;
+
 		/// The block number provider
 		type BlockNumberProvider: BlockNumberProvider<BlockNumber = Self::BlockNumber>;
+
+		/// Creates the pool a finished sale graduates into
+		type GraduationHandler: GraduationHandler<Self::AccountId>;
 	}
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migration::migrate::<T>()
+		}
+
+		fn on_initialize(_n: T::BlockNumber) -> Weight {
+			let now = T::BlockNumberProvider::current_block_number();
+
+			let scheduled = <ScheduledGraduations<T>>::get();
+			let ended = scheduled.iter().take_while(|(end, _)| *end < now).count();
+
+			if !ended.is_zero() {
+				// pools are removed from the schedule first, so that the new pool can be created
+				<ScheduledGraduations<T>>::mutate(|scheduled| scheduled.retain(|(end, _)| *end >= now));
+
+				for (_, pool_id) in scheduled.iter().take(ended) {
+					if let Err(error) = Self::graduate(pool_id) {
+						Self::deposit_event(Event::GraduationFailed(pool_id.clone(), error));
+					}
+				}
+			}
+
+			let (checked, pruned) = Self::update_price_history(now);
+
+			T::WeightInfo::graduate(ended as u32)
+				.saturating_add(T::WeightInfo::record_prices(checked))
+				.saturating_add(T::WeightInfo::prune_price_history(pruned))
+		}
+	}
 
 	#[pallet::error]
 	pub enum Error<T> {
@@ -257,7 +642,7 @@ pub mod pallet {
 		/// Pool does not exist
 		PoolNotFound,
 
-		/// Pool has been already created
+		/// Pool has been already created and the sale of the pool has not ended
 		PoolAlreadyExists,
 
 		/// Invalid block range
@@ -298,6 +683,66 @@ pub mod pallet {
 
 		/// Not more than one fee collector per asset id
 		FeeCollectorWithAssetAlreadyUsed,
+
+		/// Weight curve parameters are invalid
+		InvalidWeightCurve,
+
+		/// Graduation parameters are invalid
+		InvalidGraduation,
+
+		/// Trade protection parameters are invalid
+		InvalidTradeProtection,
+
+		/// Vesting parameters are invalid
+		InvalidVesting,
+
+		/// No vested tokens to claim
+		NothingToClaim,
+
+		/// Pool has no graduation set
+		GraduationNotSet,
+
+		/// Too many pools are waiting for graduation
+		TooManyScheduledGraduations,
+
+		/// Account has no locked shares of the asset
+		SharesNotLocked,
+
+		/// Shares are still locked
+		SharesStillLocked,
+
+		/// Sale is paused
+		SaleIsPaused,
+
+		/// Sale is not paused
+		SaleIsNotPaused,
+
+		/// Sale paused by the emergency origin can be resumed only by the emergency origin
+		PausedByEmergencyOrigin,
+
+		/// Account is not in the allowlist of the pool
+		NotAllowlisted,
+
+		/// Account would exceed the max purchase amount of the pool
+		MaxPurchaseExceeded,
+
+		/// Allowlist of the pool is full
+		AllowlistFull,
+
+		/// Account would exceed the max amount bought in one block
+		MaxBuyPerBlockExceeded,
+
+		/// Account has to wait for the trade cool-down to pass
+		TradeCooldownNotPassed,
+
+		/// Repay fee schedule is invalid
+		InvalidRepayFee,
+
+		/// Repay fee exceeds the max repay fee
+		RepayFeeTooHigh,
+
+		/// Max number of pools with a price history was reached
+		TooManyPriceHistoryPools,
 	}
 
 	#[pallet::event]
@@ -315,7 +760,7 @@ pub mod pallet {
 		/// Liquidity was removed from the pool and the pool was destroyed. [who, asset_a, asset_b, amount_a, amount_b]
 		LiquidityRemoved(T::AccountId, AssetId, AssetId, BalanceOf<T>, BalanceOf<T>),
 
-		/// Sale executed. [who, asset_in, asset_out, amount, sale_price, fee_asset, fee_amount]
+		/// Sale executed. [who, asset_in, asset_out, amount, sale_price, fee_asset, fee_amount, fee_rate]
 		SellExecuted(
 			T::AccountId,
 			AssetId,
@@ -324,9 +769,10 @@ pub mod pallet {
 			BalanceOf<T>,
 			AssetId,
 			BalanceOf<T>,
+			(u32, u32),
 		),
 
-		/// Purchase executed. [who, asset_out, asset_in, amount, buy_price, fee_asset, fee_amount]
+		/// Purchase executed. [who, asset_out, asset_in, amount, buy_price, fee_asset, fee_amount, fee_rate]
 		BuyExecuted(
 			T::AccountId,
 			AssetId,
@@ -335,9 +781,72 @@ pub mod pallet {
 			BalanceOf<T>,
 			AssetId,
 			BalanceOf<T>,
+			(u32, u32),
 		),
+
+		/// Pool reserves were moved into a new pool. [pool_id, share_token, amount_a, amount_b, shares]
+		PoolGraduated(PoolId<T>, AssetId, BalanceOf<T>, BalanceOf<T>, BalanceOf<T>),
+
+		/// Pool could not graduate, the reserves stay in the pool. [pool_id, error]
+		GraduationFailed(PoolId<T>, DispatchError),
+
+		/// Shares received on graduation were unlocked. [who, share_token]
+		GraduationSharesUnlocked(T::AccountId, AssetId),
+
+		/// Trading in the pool was paused. [pool_id, paused_at]
+		SalePaused(PoolId<T>, T::BlockNumber),
+
+		/// Trading in the pool was resumed and the sale was shifted by the paused period. [pool_id, start, end]
+		SaleResumed(PoolId<T>, T::BlockNumber, T::BlockNumber),
+
+		/// Sale was cancelled, reserves were returned to the owner and the pool was destroyed. [pool_id, amount_a, amount_b]
+		SaleCancelled(PoolId<T>, BalanceOf<T>, BalanceOf<T>),
+
+		/// Purchase restrictions of the pool were set. [pool_id, restrictions]
+		PurchaseRestrictionsSet(PoolId<T>, PurchaseRestrictions),
+
+		/// Accounts were added to the allowlist of the pool. [pool_id, accounts]
+		AllowlistAdded(PoolId<T>, Vec<T::AccountId>),
+
+		/// Accounts were removed from the allowlist of the pool. [pool_id, accounts]
+		AllowlistRemoved(PoolId<T>, Vec<T::AccountId>),
+
+		/// Vesting of the bought tokens was set. [pool_id, vesting]
+		VestingSet(PoolId<T>, Option<VestingSchedule<T::BlockNumber>>),
+
+		/// Vested tokens were unlocked. [who, pool_id, asset, amount]
+		VestedClaimed(T::AccountId, PoolId<T>, AssetId, BalanceOf<T>),
 	}
 
+	/// Pools with a scheduled sale whose price is recorded or whose price history waits for pruning, pairs of the
+	/// sale end and the pool id ordered by the sale end.
+	#[pallet::storage]
+	#[pallet::getter(fn price_history_pools)]
+	pub type PriceHistoryPools<T: Config> =
+		StorageValue<_, PriceHistoryPoolsOf<T::AccountId, T::BlockNumber>, ValueQuery>;
+
+	/// Price snapshots of a pool, ring buffer of `T::PriceHistoryLength` slots. [pool_id, slot]
+	#[pallet::storage]
+	#[pallet::getter(fn price_snapshot)]
+	pub type PriceHistory<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, PoolId<T>, Twox64Concat, u32, PriceSnapshot<T::BlockNumber>, OptionQuery>;
+
+	/// Slot of the next price snapshot of a pool and the number of recorded snapshots. [pool_id]
+	#[pallet::storage]
+	#[pallet::getter(fn price_history_cursor)]
+	pub type PriceHistoryCursor<T: Config> = StorageMap<_, Blake2_128Concat, PoolId<T>, (u32, u32), ValueQuery>;
+
+	/// Number of sale rounds created for an asset pair. [pair_account]
+	#[pallet::storage]
+	#[pallet::getter(fn sale_rounds)]
+	pub type SaleRounds<T: Config> = StorageMap<_, Blake2_128Concat, PoolId<T>, u32, ValueQuery>;
+
+	/// Closed sale rounds, the pool of the round was destroyed. [pool_id]
+	#[pallet::storage]
+	#[pallet::getter(fn round_history)]
+	pub type RoundHistory<T: Config> =
+		StorageMap<_, Blake2_128Concat, PoolId<T>, SaleRound<T::AccountId, T::BlockNumber>, OptionQuery>;
+
 	/// Details of a pool.
 	#[pallet::storage]
 	#[pallet::getter(fn pool_data)]
@@ -350,18 +859,91 @@ pub mod pallet {
 	pub type FeeCollectorWithAsset<T: Config> =
 		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, AssetId, bool, ValueQuery>;
 
-	#[pallet::extra_constants]
-	impl<T: Config> Pallet<T> {
-		pub fn repay_fee() -> (u32, u32) {
-			(2, 10)
-		}
-	}
+	/// Net amount of the accumulated asset paid to a pool by trades.
+	#[pallet::storage]
+	#[pallet::getter(fn amount_raised)]
+	pub type AmountRaised<T: Config> = StorageMap<_, Blake2_128Concat, PoolId<T>, Balance, ValueQuery>;
+
+	/// Pools waiting for graduation ordered by the end of the sale.
+	#[pallet::storage]
+	#[pallet::getter(fn scheduled_graduations)]
+	pub type ScheduledGraduations<T: Config> =
+		StorageValue<_, ScheduledGraduationsOf<T::AccountId, T::BlockNumber>, ValueQuery>;
+
+	/// Block at which the shares received on graduation are unlocked. [who, share_token]
+	#[pallet::storage]
+	#[pallet::getter(fn graduation_shares_unlock)]
+	pub type GraduationSharesUnlock<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, AssetId, T::BlockNumber, OptionQuery>;
+
+	/// Block at which the sale was paused and whether it was paused by `T::EmergencyOrigin`.
+	#[pallet::storage]
+	#[pallet::getter(fn paused_sale)]
+	pub type PausedSales<T: Config> = StorageMap<_, Blake2_128Concat, PoolId<T>, (T::BlockNumber, bool), OptionQuery>;
+
+	/// Restrictions on buying the distributed asset from a pool.
+	#[pallet::storage]
+	#[pallet::getter(fn purchase_restrictions)]
+	pub type SaleRestrictions<T: Config> =
+		StorageMap<_, Blake2_128Concat, PoolId<T>, PurchaseRestrictions, OptionQuery>;
+
+	/// Accounts allowed to buy from a pool. [pool_id, who]
+	#[pallet::storage]
+	pub type Allowlist<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, PoolId<T>, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+	/// Number of accounts in the allowlist of a pool.
+	#[pallet::storage]
+	#[pallet::getter(fn allowlist_size)]
+	pub type AllowlistSize<T: Config> = StorageMap<_, Blake2_128Concat, PoolId<T>, u32, ValueQuery>;
+
+	/// Amount of the distributed asset bought by an account from a pool with a max purchase set. [pool_id, who]
+	#[pallet::storage]
+	#[pallet::getter(fn purchased)]
+	pub type Purchased<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, PoolId<T>, Blake2_128Concat, T::AccountId, Balance, ValueQuery>;
+
+	/// Vesting schedule of the distributed asset bought from a pool.
+	#[pallet::storage]
+	#[pallet::getter(fn sale_vesting)]
+	pub type SaleVesting<T: Config> =
+		StorageMap<_, Blake2_128Concat, PoolId<T>, VestingSchedule<T::BlockNumber>, OptionQuery>;
+
+	/// Distributed asset bought by an account from a pool with vesting. Kept after the pool is destroyed until
+	/// everything is claimed. [pool_id, who]
+	#[pallet::storage]
+	#[pallet::getter(fn vesting)]
+	pub type Vesting<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		PoolId<T>,
+		Blake2_128Concat,
+		T::AccountId,
+		VestingInfo<T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	/// Block of the last trade of an account in a pool with trade protection and amount of the distributed asset
+	/// bought by the account in that block. [pool_id, who]
+	#[pallet::storage]
+	#[pallet::getter(fn trade_activity)]
+	pub type TradeActivity<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		PoolId<T>,
+		Blake2_128Concat,
+		T::AccountId,
+		(T::BlockNumber, Balance),
+		OptionQuery,
+	>;
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Create a new liquidity bootstrapping pool for given asset pair.
 		///
-		/// For any asset pair, only one pool can exist at a time.
+		/// Every pool of an asset pair is a new sale round with its own pool account. The new round can be
+		/// created only after the sale of the previous round has ended or its pool was destroyed. Trades of
+		/// the asset pair are executed in the pool of the latest round.
 		///
 		/// The dispatch origin for this call must be `T::CreatePoolOrigin`.
 		/// The pool is created with initial liquidity provided by the `pool_owner` who must have
@@ -379,12 +961,17 @@ pub mod pallet {
 		/// this should be higher than final weight
 		/// - `final_weight`: Final weight of the asset_a. 1_000_000 corresponding to 1% and 100_000_000 to 100%
 		/// this should be lower than initial weight
-		/// - `weight_curve`: The weight function used to update the LBP weights. Currently,
-		/// there is only one weight function implemented, the linear function.
+		/// - `weight_curve`: The weight function used to update the LBP weights. Linear, exponential decay,
+		/// step-wise and piecewise linear weight functions are implemented.
 		/// - `fee`: The trading fee charged on every trade distributed to `fee_collector`.
 		/// - `fee_collector`: The account to which trading fees will be transferred.
 		/// - `repay_target`: The amount of tokens to repay to separate fee_collector account. Until this amount is
-		/// reached, fee will be increased to 20% and taken from the pool
+		/// reached, the repay fee is charged instead of `fee`.
+		/// - `trade_protection`: Protections against trading bots: max amount bought by an account in one block,
+		/// cool-down between trades of an account and elevated fee during the first blocks of the sale. If the
+		/// opening fee applies while the repay fee is still charged, the higher of the two is charged.
+		/// - `repay_fee`: The fee charged until `repay_target` is reached. The fee can step down in tiers as the
+		/// collected fees grow. None of the fees can exceed `T::MaxRepayFee`.
 		///
 		/// Emits `PoolCreated` event when successful.
 		///
@@ -407,6 +994,8 @@ pub mod pallet {
 			fee: (u32, u32),
 			fee_collector: T::AccountId,
 			repay_target: Balance,
+			trade_protection: TradeProtection<T::BlockNumber>,
+			repay_fee: RepayFeeSchedule,
 		) -> DispatchResult {
 			T::CreatePoolOrigin::ensure_origin(origin)?;
 
@@ -417,12 +1006,22 @@ pub mod pallet {
 
 			ensure!(asset_a != asset_b, Error::<T>::CannotCreatePoolWithSameAssets);
 
-			let asset_pair = AssetPair {
-				asset_in: asset_a,
-				asset_out: asset_b,
-			};
-
-			ensure!(!Self::exists(asset_pair), Error::<T>::PoolAlreadyExists);
+			let pair_account = Self::pair_account_from_assets(asset_a, asset_b);
+			let round = Self::sale_rounds(&pair_account);
+
+			// sale of the previous round has to be ended and the pool must not wait for graduation
+			if let Some(previous_round) = round.checked_sub(1) {
+				let previous_id = Self::round_account(asset_a, asset_b, previous_round);
+				if let Some(previous_pool) = <PoolData<T>>::get(&previous_id) {
+					ensure!(
+						Self::sale_status(&previous_id, &previous_pool) == SaleStatus::Ended
+							&& !<ScheduledGraduations<T>>::get()
+								.iter()
+								.any(|(_, scheduled)| *scheduled == previous_id),
+						Error::<T>::PoolAlreadyExists
+					);
+				}
+			}
 
 			ensure!(
 				!<FeeCollectorWithAsset<T>>::contains_key(fee_collector.clone(), asset_a),
@@ -449,12 +1048,15 @@ pub mod pallet {
 				fee,
 				fee_collector.clone(),
 				repay_target,
+				trade_protection,
+				repay_fee,
 			);
 
 			Self::validate_pool_data(&pool_data)?;
 
-			let pool_id = Self::get_pair_id(asset_pair);
+			let pool_id = Self::round_account(asset_a, asset_b, round);
 
+			<SaleRounds<T>>::insert(&pair_account, round.checked_add(1).ok_or(Error::<T>::Overflow)?);
 			<PoolData<T>>::insert(&pool_id, &pool_data);
 			<FeeCollectorWithAsset<T>>::insert(fee_collector, asset_a, true);
 
@@ -488,8 +1090,13 @@ pub mod pallet {
 		/// - `end`: The new ending time of the sale. This parameter is optional.
 		/// - `initial_weight`: The new initial weight. This parameter is optional.
 		/// - `final_weight`: The new final weight. This parameter is optional.
+		/// - `weight_curve`: The new weight function. This parameter is optional.
 		/// - `fee`: The new trading fee charged on every trade. This parameter is optional.
 		/// - `fee_collector`: The new receiver of trading fees. This parameter is optional.
+		/// - `repay_target`: The new repayment target of the fee collector. This parameter is optional.
+		/// - `trade_protection`: The new protections against trading bots. This parameter is optional. The higher of
+		/// the opening fee and the repay fee is charged while both apply.
+		/// - `repay_fee`: The new repay fee schedule. This parameter is optional.
 		///
 		/// Emits `PoolUpdated` event when successful.
 		#[pallet::weight(<T as Config>::WeightInfo::update_pool_data())]
@@ -502,9 +1109,12 @@ pub mod pallet {
 			end: Option<T::BlockNumber>,
 			initial_weight: Option<LBPWeight>,
 			final_weight: Option<LBPWeight>,
+			weight_curve: Option<WeightCurveType>,
 			fee: Option<(u32, u32)>,
 			fee_collector: Option<T::AccountId>,
 			repay_target: Option<Balance>,
+			trade_protection: Option<TradeProtection<T::BlockNumber>>,
+			repay_fee: Option<RepayFeeSchedule>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
@@ -516,8 +1126,11 @@ pub mod pallet {
 					start.is_some()
 						|| end.is_some() || initial_weight.is_some()
 						|| final_weight.is_some()
+						|| weight_curve.is_some()
 						|| fee.is_some() || fee_collector.is_some()
-						|| repay_target.is_some(),
+						|| repay_target.is_some()
+						|| trade_protection.is_some()
+						|| repay_fee.is_some(),
 					Error::<T>::NothingToUpdate
 				);
 
@@ -534,6 +1147,10 @@ pub mod pallet {
 
 				pool.final_weight = final_weight.unwrap_or(pool.final_weight);
 
+				if let Some(weight_curve) = weight_curve {
+					pool.weight_curve = weight_curve;
+				}
+
 				pool.fee = fee.unwrap_or(pool.fee);
 
 				// Handle update of fee collector - validate and replace old fee collector
@@ -556,8 +1173,17 @@ pub mod pallet {
 
 				pool.repay_target = repay_target.unwrap_or(pool.repay_target);
 
+				pool.trade_protection = trade_protection.unwrap_or(pool.trade_protection);
+
+				if let Some(repay_fee) = repay_fee {
+					pool.repay_fee = repay_fee;
+				}
+
 				Self::validate_pool_data(pool)?;
 
+				Self::schedule_graduation(&pool_id, pool)?;
+				Self::schedule_price_history(&pool_id, pool)?;
+
 				Self::deposit_event(Event::PoolUpdated(pool_id, (*pool).clone()));
 				Ok(())
 			})
@@ -587,7 +1213,7 @@ pub mod pallet {
 			let (asset_a, asset_b) = (amount_a.0, amount_b.0);
 			let (amount_a, amount_b) = (amount_a.1, amount_b.1);
 
-			let pool_id = Self::pair_account_from_assets(asset_a, asset_b);
+			let pool_id = Self::current_round_account(asset_a, asset_b);
 			let pool_data = <PoolData<T>>::try_get(&pool_id).map_err(|_| Error::<T>::PoolNotFound)?;
 
 			ensure!(who == pool_data.owner, Error::<T>::NotOwner);
@@ -641,20 +1267,11 @@ pub mod pallet {
 
 			ensure!(!Self::is_pool_running(&pool_data), Error::<T>::SaleNotEnded);
 
-			let (asset_a, asset_b) = pool_data.assets;
-
-			let amount_a = T::MultiCurrency::free_balance(asset_a, &pool_id);
-			let amount_b = T::MultiCurrency::free_balance(asset_b, &pool_id);
-
-			T::MultiCurrency::transfer(asset_a, &pool_id, &who, amount_a)?;
-			T::MultiCurrency::transfer(asset_b, &pool_id, &who, amount_b)?;
+			ensure!(!<PausedSales<T>>::contains_key(&pool_id), Error::<T>::SaleIsPaused);
 
-			if Self::collected_fees(&pool_data) > 0 {
-				T::MultiCurrency::remove_lock(COLLECTOR_LOCK_ID, asset_a, &pool_data.fee_collector)?;
-			}
+			let (asset_a, asset_b) = pool_data.assets;
 
-			<FeeCollectorWithAsset<T>>::remove(pool_data.fee_collector, pool_data.assets.0);
-			<PoolData<T>>::remove(&pool_id);
+			let (amount_a, amount_b) = Self::destroy_pool(&pool_id, pool_data)?;
 
 			Self::deposit_event(Event::LiquidityRemoved(pool_id, asset_a, asset_b, amount_a, amount_b));
 
@@ -720,67 +1337,501 @@ pub mod pallet {
 
 			Ok(())
 		}
-	}
-}
 
-impl<T: Config> Pallet<T> {
-	fn calculate_weights(
-		pool_data: &Pool<T::AccountId, T::BlockNumber>,
-		at: T::BlockNumber,
-	) -> Result<(LBPWeight, LBPWeight), DispatchError> {
-		let weight_a = T::LBPWeightFunction::calculate_weight(
-			pool_data.weight_curve,
-			pool_data.start.unwrap_or_else(Zero::zero),
-			pool_data.end.unwrap_or_else(Zero::zero),
-			pool_data.initial_weight,
-			pool_data.final_weight,
-			at,
-		)
-		.ok_or(Error::<T>::WeightCalculationError)?;
+		/// Set or clear the graduation of a pool.
+		///
+		/// When the sale ends, `fraction` of the remaining reserves is moved into a new pool created by
+		/// `T::GraduationHandler` at the final price of the sale. Shares of the new pool go to the pool owner
+		/// and are locked for `lock_period` blocks. The rest of the reserves stays in the pool.
+		///
+		/// The dispatch origin for this call must be signed by the pool owner.
+		///
+		/// The graduation can be set only if the sale has not already started.
+		///
+		/// Parameters:
+		/// - `pool_id`: The identifier of the pool.
+		/// - `graduation`: The graduation settings, `None` disables the graduation.
+		///
+		/// Emits `PoolUpdated` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::set_graduation())]
+		#[transactional]
+		pub fn set_graduation(
+			origin: OriginFor<T>,
+			pool_id: PoolId<T>,
+			graduation: Option<Graduation<T::BlockNumber>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-		let weight_b = MAX_WEIGHT.saturating_sub(weight_a);
+			<PoolData<T>>::try_mutate(pool_id.clone(), |maybe_pool| -> DispatchResult {
+				let pool = maybe_pool.as_mut().ok_or(Error::<T>::PoolNotFound)?;
 
-		Ok((weight_a, weight_b))
-	}
+				ensure!(who == pool.owner, Error::<T>::NotOwner);
 
-	fn validate_pool_data(pool_data: &Pool<T::AccountId, T::BlockNumber>) -> DispatchResult {
-		let now = T::BlockNumberProvider::current_block_number();
+				ensure!(!Self::has_pool_started(pool), Error::<T>::SaleStarted);
 
-		ensure!(
-			match (pool_data.start, pool_data.end) {
-				(Some(start), Some(end)) => now < start && start < end,
-				(None, None) => true,
-				_ => false,
-			},
-			Error::<T>::InvalidBlockRange
-		);
+				if let Some(graduation) = graduation {
+					ensure!(!graduation.fraction.is_zero(), Error::<T>::InvalidGraduation);
+				}
 
-		// duration of the LBP sale should not exceed 2 weeks (assuming 6 sec blocks)
-		ensure!(
-			pool_data
-				.end
-				.unwrap_or_default()
-				.saturating_sub(pool_data.start.unwrap_or_default())
-				< MAX_SALE_DURATION.into(),
-			Error::<T>::MaxSaleDurationExceeded
-		);
+				pool.graduation = graduation;
 
-		// zero weight at the beginning or at the end of a sale may cause a problem in the price calculation
-		ensure!(
-			!pool_data.initial_weight.is_zero()
-				&& pool_data.initial_weight < MAX_WEIGHT
-				&& !pool_data.final_weight.is_zero()
-				&& pool_data.final_weight < MAX_WEIGHT,
-			// TODO people could leak value out the pool if initial weight is < final weight due to fee structure
-			// && pool_data.initial_weight > pool_data.final_weight,
-			Error::<T>::InvalidWeight
+				Self::schedule_graduation(&pool_id, pool)?;
+
+				Self::deposit_event(Event::PoolUpdated(pool_id, (*pool).clone()));
+				Ok(())
+			})
+		}
+
+		/// Pause trading in a running sale.
+		///
+		/// The dispatch origin for this call must be `T::EmergencyOrigin` or signed by the pool owner.
+		///
+		/// The sale is stopped at its current point. When the sale is resumed, its start and end are shifted by
+		/// the paused period. The pool can't graduate or be destroyed while paused.
+		///
+		/// Parameters:
+		/// - `pool_id`: The identifier of the pool.
+		///
+		/// Emits `SalePaused` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::pause_sale())]
+		#[transactional]
+		pub fn pause_sale(origin: OriginFor<T>, pool_id: PoolId<T>) -> DispatchResult {
+			let pool_data = <PoolData<T>>::try_get(&pool_id).map_err(|_| Error::<T>::PoolNotFound)?;
+
+			let is_emergency = Self::ensure_emergency_or_owner(origin, &pool_data)?;
+
+			ensure!(!<PausedSales<T>>::contains_key(&pool_id), Error::<T>::SaleIsPaused);
+
+			ensure!(Self::is_pool_running(&pool_data), Error::<T>::SaleIsNotRunning);
+
+			let now = T::BlockNumberProvider::current_block_number();
+
+			<PausedSales<T>>::insert(&pool_id, (now, is_emergency));
+			<ScheduledGraduations<T>>::mutate(|scheduled| scheduled.retain(|(_, id)| *id != pool_id));
+
+			Self::deposit_event(Event::SalePaused(pool_id, now));
+
+			Ok(())
+		}
+
+		/// Resume trading in a paused sale.
+		///
+		/// The start and the end of the sale are shifted by the paused period, so the sale continues with the
+		/// weights it had when it was paused.
+		///
+		/// The dispatch origin for this call must be `T::EmergencyOrigin` or signed by the pool owner.
+		/// Sale paused by `T::EmergencyOrigin` can be resumed only by `T::EmergencyOrigin`.
+		///
+		/// Parameters:
+		/// - `pool_id`: The identifier of the pool.
+		///
+		/// Emits `SaleResumed` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::resume_sale())]
+		#[transactional]
+		pub fn resume_sale(origin: OriginFor<T>, pool_id: PoolId<T>) -> DispatchResult {
+			let mut pool_data = <PoolData<T>>::try_get(&pool_id).map_err(|_| Error::<T>::PoolNotFound)?;
+
+			let is_emergency = Self::ensure_emergency_or_owner(origin, &pool_data)?;
+
+			let (paused_at, paused_by_emergency) =
+				<PausedSales<T>>::get(&pool_id).ok_or(Error::<T>::SaleIsNotPaused)?;
+
+			ensure!(
+				is_emergency || !paused_by_emergency,
+				Error::<T>::PausedByEmergencyOrigin
+			);
+
+			let paused_for = T::BlockNumberProvider::current_block_number().saturating_sub(paused_at);
+
+			let start = pool_data
+				.start
+				.ok_or(Error::<T>::SaleIsNotRunning)?
+				.saturating_add(paused_for);
+			let end = pool_data
+				.end
+				.ok_or(Error::<T>::SaleIsNotRunning)?
+				.saturating_add(paused_for);
+
+			pool_data.start = Some(start);
+			pool_data.end = Some(end);
+
+			Self::schedule_graduation(&pool_id, &pool_data)?;
+			Self::schedule_price_history(&pool_id, &pool_data)?;
+
+			<PoolData<T>>::insert(&pool_id, &pool_data);
+			<PausedSales<T>>::remove(&pool_id);
+
+			Self::deposit_event(Event::SaleResumed(pool_id, start, end));
+
+			Ok(())
+		}
+
+		/// Cancel a sale, return all the reserves to the pool owner and destroy the pool.
+		///
+		/// The dispatch origin for this call must be `T::EmergencyOrigin`.
+		///
+		/// The sale is ended immediately whether it is running, paused or not started yet. The pool does not
+		/// graduate.
+		///
+		/// Parameters:
+		/// - `pool_id`: The identifier of the pool.
+		///
+		/// Emits `SaleCancelled` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_sale())]
+		#[transactional]
+		pub fn cancel_sale(origin: OriginFor<T>, pool_id: PoolId<T>) -> DispatchResult {
+			T::EmergencyOrigin::ensure_origin(origin)?;
+
+			let pool_data = <PoolData<T>>::try_get(&pool_id).map_err(|_| Error::<T>::PoolNotFound)?;
+
+			let (amount_a, amount_b) = Self::destroy_pool(&pool_id, pool_data)?;
+
+			Self::deposit_event(Event::SaleCancelled(pool_id, amount_a, amount_b));
+
+			Ok(())
+		}
+
+		/// Set restrictions on buying the distributed asset from the pool.
+		///
+		/// The dispatch origin for this call must be signed by the pool owner.
+		///
+		/// The restrictions can be set only if the sale has not already started.
+		///
+		/// Parameters:
+		/// - `pool_id`: The identifier of the pool.
+		/// - `restrictions`: Whether only allowlisted accounts can buy and the max amount a single account can buy.
+		/// Default restrictions remove all the restrictions.
+		///
+		/// Emits `PurchaseRestrictionsSet` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::set_purchase_restrictions())]
+		#[transactional]
+		pub fn set_purchase_restrictions(
+			origin: OriginFor<T>,
+			pool_id: PoolId<T>,
+			restrictions: PurchaseRestrictions,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let pool_data = <PoolData<T>>::try_get(&pool_id).map_err(|_| Error::<T>::PoolNotFound)?;
+
+			ensure!(who == pool_data.owner, Error::<T>::NotOwner);
+
+			ensure!(!Self::has_pool_started(&pool_data), Error::<T>::SaleStarted);
+
+			if restrictions == PurchaseRestrictions::default() {
+				<SaleRestrictions<T>>::remove(&pool_id);
+			} else {
+				<SaleRestrictions<T>>::insert(&pool_id, restrictions);
+			}
+
+			Self::deposit_event(Event::PurchaseRestrictionsSet(pool_id, restrictions));
+
+			Ok(())
+		}
+
+		/// Add accounts to the allowlist of the pool.
+		///
+		/// The dispatch origin for this call must be signed by the pool owner.
+		///
+		/// The allowlist is enforced only if the purchase restrictions of the pool require it. Accounts already in
+		/// the allowlist are skipped. The allowlist can hold at most `MAX_ALLOWLIST_SIZE` accounts.
+		///
+		/// Parameters:
+		/// - `pool_id`: The identifier of the pool.
+		/// - `accounts`: The accounts to add.
+		///
+		/// Emits `AllowlistAdded` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::add_to_allowlist(accounts.len() as u32))]
+		#[transactional]
+		pub fn add_to_allowlist(
+			origin: OriginFor<T>,
+			pool_id: PoolId<T>,
+			accounts: Vec<T::AccountId>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let pool_data = <PoolData<T>>::try_get(&pool_id).map_err(|_| Error::<T>::PoolNotFound)?;
+
+			ensure!(who == pool_data.owner, Error::<T>::NotOwner);
+
+			<AllowlistSize<T>>::try_mutate(&pool_id, |size| -> DispatchResult {
+				for account in accounts.iter() {
+					if !<Allowlist<T>>::contains_key(&pool_id, account) {
+						*size = size.checked_add(1).ok_or(Error::<T>::Overflow)?;
+						<Allowlist<T>>::insert(&pool_id, account, ());
+					}
+				}
+
+				ensure!(*size <= MAX_ALLOWLIST_SIZE, Error::<T>::AllowlistFull);
+
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::AllowlistAdded(pool_id, accounts));
+
+			Ok(())
+		}
+
+		/// Remove accounts from the allowlist of the pool.
+		///
+		/// The dispatch origin for this call must be signed by the pool owner.
+		///
+		/// Accounts not in the allowlist are skipped.
+		///
+		/// Parameters:
+		/// - `pool_id`: The identifier of the pool.
+		/// - `accounts`: The accounts to remove.
+		///
+		/// Emits `AllowlistRemoved` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_from_allowlist(accounts.len() as u32))]
+		#[transactional]
+		pub fn remove_from_allowlist(
+			origin: OriginFor<T>,
+			pool_id: PoolId<T>,
+			accounts: Vec<T::AccountId>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let pool_data = <PoolData<T>>::try_get(&pool_id).map_err(|_| Error::<T>::PoolNotFound)?;
+
+			ensure!(who == pool_data.owner, Error::<T>::NotOwner);
+
+			<AllowlistSize<T>>::mutate(&pool_id, |size| {
+				for account in accounts.iter() {
+					if <Allowlist<T>>::take(&pool_id, account).is_some() {
+						*size = size.saturating_sub(1);
+					}
+				}
+			});
+
+			Self::deposit_event(Event::AllowlistRemoved(pool_id, accounts));
+
+			Ok(())
+		}
+
+		/// Set vesting of the distributed asset bought from the pool.
+		///
+		/// The dispatch origin for this call must be signed by the pool owner.
+		///
+		/// The vesting can be set only if the sale has not already started. Bought tokens are locked in the buyer's
+		/// account and unlocked by `claim_vested` according to the schedule counted from the end of the sale.
+		///
+		/// Parameters:
+		/// - `pool_id`: The identifier of the pool.
+		/// - `vesting`: The vesting schedule, `None` disables the vesting.
+		///
+		/// Emits `VestingSet` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::set_vesting())]
+		#[transactional]
+		pub fn set_vesting(
+			origin: OriginFor<T>,
+			pool_id: PoolId<T>,
+			vesting: Option<VestingSchedule<T::BlockNumber>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let pool_data = <PoolData<T>>::try_get(&pool_id).map_err(|_| Error::<T>::PoolNotFound)?;
+
+			ensure!(who == pool_data.owner, Error::<T>::NotOwner);
+
+			ensure!(!Self::has_pool_started(&pool_data), Error::<T>::SaleStarted);
+
+			match vesting {
+				Some(schedule) => {
+					ensure!(
+						!schedule.cliff.is_zero() || !schedule.duration.is_zero(),
+						Error::<T>::InvalidVesting
+					);
+					<SaleVesting<T>>::insert(&pool_id, schedule);
+				}
+				None => <SaleVesting<T>>::remove(&pool_id),
+			}
+
+			Self::deposit_event(Event::VestingSet(pool_id, vesting));
+
+			Ok(())
+		}
+
+		/// Unlock the tokens bought from the pool that are vested and not claimed yet.
+		///
+		/// The dispatch origin for this call must be signed by the buyer.
+		///
+		/// Parameters:
+		/// - `pool_id`: The identifier of the pool.
+		///
+		/// Emits `VestedClaimed` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::claim_vested())]
+		#[transactional]
+		pub fn claim_vested(origin: OriginFor<T>, pool_id: PoolId<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut vesting = Self::current_vesting(&pool_id, &who).ok_or(Error::<T>::NothingToClaim)?;
+
+			let claimable = Self::claimable_vested(&vesting, T::BlockNumberProvider::current_block_number());
+			ensure!(!claimable.is_zero(), Error::<T>::NothingToClaim);
+
+			let locked =
+				T::LockedBalance::get_by_lock(VESTING_LOCK_ID, vesting.asset, who.clone()).saturating_sub(claimable);
+			if locked.is_zero() {
+				T::MultiCurrency::remove_lock(VESTING_LOCK_ID, vesting.asset, &who)?;
+			} else {
+				T::MultiCurrency::set_lock(VESTING_LOCK_ID, vesting.asset, &who, locked)?;
+			}
+
+			vesting.claimed = vesting.claimed.saturating_add(claimable);
+			if vesting.claimed >= vesting.total {
+				<Vesting<T>>::remove(&pool_id, &who);
+			} else {
+				<Vesting<T>>::insert(&pool_id, &who, vesting);
+			}
+
+			Self::deposit_event(Event::VestedClaimed(who, pool_id, vesting.asset, claimable));
+
+			Ok(())
+		}
+
+		/// Unlock the shares received on graduation once the lock period is over.
+		///
+		/// The dispatch origin for this call must be signed by any account.
+		///
+		/// Parameters:
+		/// - `who`: The account holding the locked shares.
+		/// - `share_token`: The share token of the pool created on graduation.
+		///
+		/// Emits `GraduationSharesUnlocked` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::unlock_graduation_shares())]
+		#[transactional]
+		pub fn unlock_graduation_shares(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			share_token: AssetId,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let unlock_at = <GraduationSharesUnlock<T>>::get(&who, share_token).ok_or(Error::<T>::SharesNotLocked)?;
+
+			ensure!(
+				unlock_at <= T::BlockNumberProvider::current_block_number(),
+				Error::<T>::SharesStillLocked
+			);
+
+			T::MultiCurrency::remove_lock(GRADUATION_LOCK_ID, share_token, &who)?;
+			<GraduationSharesUnlock<T>>::remove(&who, share_token);
+
+			Self::deposit_event(Event::GraduationSharesUnlocked(who, share_token));
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn calculate_weights(
+		pool_data: &Pool<T::AccountId, T::BlockNumber>,
+		at: T::BlockNumber,
+	) -> Result<(LBPWeight, LBPWeight), DispatchError> {
+		let weight_a = T::LBPWeightFunction::calculate_weight(
+			&pool_data.weight_curve,
+			pool_data.start.unwrap_or_else(Zero::zero),
+			pool_data.end.unwrap_or_else(Zero::zero),
+			pool_data.initial_weight,
+			pool_data.final_weight,
+			at,
+		)
+		.ok_or(Error::<T>::WeightCalculationError)?;
+
+		let weight_b = MAX_WEIGHT.saturating_sub(weight_a);
+
+		Ok((weight_a, weight_b))
+	}
+
+	fn validate_pool_data(pool_data: &Pool<T::AccountId, T::BlockNumber>) -> DispatchResult {
+		let now = T::BlockNumberProvider::current_block_number();
+
+		ensure!(
+			match (pool_data.start, pool_data.end) {
+				(Some(start), Some(end)) => now < start && start < end,
+				(None, None) => true,
+				_ => false,
+			},
+			Error::<T>::InvalidBlockRange
+		);
+
+		// duration of the LBP sale should not exceed 2 weeks (assuming 6 sec blocks)
+		ensure!(
+			pool_data
+				.end
+				.unwrap_or_default()
+				.saturating_sub(pool_data.start.unwrap_or_default())
+				< MAX_SALE_DURATION.into(),
+			Error::<T>::MaxSaleDurationExceeded
+		);
+
+		// zero weight at the beginning or at the end of a sale may cause a problem in the price calculation
+		ensure!(
+			!pool_data.initial_weight.is_zero()
+				&& pool_data.initial_weight < MAX_WEIGHT
+				&& !pool_data.final_weight.is_zero()
+				&& pool_data.final_weight < MAX_WEIGHT,
+			// TODO people could leak value out the pool if initial weight is < final weight due to fee structure
+			// && pool_data.initial_weight > pool_data.final_weight,
+			Error::<T>::InvalidWeight
+		);
+
+		ensure!(
+			Self::is_weight_curve_valid(&pool_data.weight_curve),
+			Error::<T>::InvalidWeightCurve
 		);
 
 		ensure!(!pool_data.fee.1.is_zero(), Error::<T>::FeeAmountInvalid);
 
+		let protection = &pool_data.trade_protection;
+		ensure!(
+			protection.max_buy_per_block != Some(0)
+				&& protection.cooldown.map_or(true, |cooldown| !cooldown.is_zero())
+				&& protection
+					.opening_fee
+					.map_or(true, |(fee, period)| !fee.1.is_zero() && !period.is_zero()),
+			Error::<T>::InvalidTradeProtection
+		);
+
+		let repay_fee = &pool_data.repay_fee;
+		ensure!(
+			!repay_fee.fee.1.is_zero()
+				&& repay_fee
+					.tiers
+					.iter()
+					.all(|(amount, fee)| !amount.is_zero() && !fee.1.is_zero())
+				&& repay_fee.tiers.windows(2).all(|pair| pair[0].0 < pair[1].0),
+			Error::<T>::InvalidRepayFee
+		);
+
+		let max_repay_fee = T::MaxRepayFee::get();
+		ensure!(
+			sp_std::iter::once(&repay_fee.fee)
+				.chain(repay_fee.tiers.iter().map(|(_, fee)| fee))
+				.all(|fee| !fee_exceeds(*fee, max_repay_fee)),
+			Error::<T>::RepayFeeTooHigh
+		);
+
 		Ok(())
 	}
 
+	fn is_weight_curve_valid(weight_curve: &WeightCurveType) -> bool {
+		match weight_curve {
+			WeightCurveType::Linear => true,
+			WeightCurveType::ExponentialDecay { rate } => (1..=MAX_DECAY_RATE).contains(rate),
+			WeightCurveType::Step { steps } => *steps >= 2,
+			// points have to lie strictly inside the sale, be ordered and have valid weights
+			WeightCurveType::PiecewiseLinear(points) => {
+				!points.is_empty()
+					&& points.iter().all(|(progress, weight)| {
+						!progress.is_zero() && *progress < Permill::one() && !weight.is_zero() && *weight < MAX_WEIGHT
+					}) && points.windows(2).all(|pair| pair[0].0 < pair[1].0)
+			}
+		}
+	}
+
 	fn get_sorted_weight(
 		asset_in: AssetId,
 		now: T::BlockNumber,
@@ -799,6 +1850,334 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Put the pool into the graduation schedule, or remove it if the graduation is not set or the end is unknown.
+	fn schedule_graduation(pool_id: &PoolId<T>, pool_data: &Pool<T::AccountId, T::BlockNumber>) -> DispatchResult {
+		<ScheduledGraduations<T>>::try_mutate(|scheduled| -> DispatchResult {
+			scheduled.retain(|(_, id)| id != pool_id);
+
+			if let (Some(_), Some(end)) = (pool_data.graduation, pool_data.end) {
+				let index = scheduled.partition_point(|(scheduled_end, _)| *scheduled_end <= end);
+				scheduled
+					.try_insert(index, (end, pool_id.clone()))
+					.map_err(|_| Error::<T>::TooManyScheduledGraduations)?;
+			}
+
+			Ok(())
+		})
+	}
+
+	/// Record the price history of the pool from the start of its sale until the end of the sale plus the grace
+	/// period.
+	fn schedule_price_history(pool_id: &PoolId<T>, pool_data: &Pool<T::AccountId, T::BlockNumber>) -> DispatchResult {
+		<PriceHistoryPools<T>>::try_mutate(|pools| -> DispatchResult {
+			pools.retain(|(_, id)| id != pool_id);
+
+			if let Some(end) = pool_data.end {
+				let index = pools.partition_point(|(pool_end, _)| *pool_end <= end);
+				pools
+					.try_insert(index, (end, pool_id.clone()))
+					.map_err(|_| Error::<T>::TooManyPriceHistoryPools)?;
+			}
+
+			Ok(())
+		})
+	}
+
+	/// Stop recording the price of a destroyed pool, the recorded history is kept for the grace period.
+	fn close_price_history(pool_id: &PoolId<T>) {
+		let now = T::BlockNumberProvider::current_block_number();
+		let recorded = !Self::price_history_cursor(pool_id).1.is_zero();
+
+		<PriceHistoryPools<T>>::mutate(|pools| {
+			let end = pools.iter().find(|(_, id)| id == pool_id).map(|(end, _)| *end);
+			pools.retain(|(_, id)| id != pool_id);
+
+			if let (true, Some(end)) = (recorded, end) {
+				// sale ends now if it is cancelled, one entry was removed so there is a free slot
+				let end = end.min(now);
+				let index = pools.partition_point(|(pool_end, _)| *pool_end <= end);
+				let _ = pools.try_insert(index, (end, pool_id.clone()));
+			}
+		});
+	}
+
+	/// Record the price of the running sales and prune the price history of the sales which ended before the grace
+	/// period. Returns the number of checked pools and the number of pruned pools.
+	fn update_price_history(now: T::BlockNumber) -> (u32, u32) {
+		let pools = <PriceHistoryPools<T>>::get();
+		let grace_period = T::PriceHistoryGracePeriod::get();
+		let expired = pools
+			.iter()
+			.take_while(|(end, _)| end.saturating_add(grace_period) < now)
+			.count();
+
+		if !expired.is_zero() {
+			for (_, pool_id) in pools.iter().take(expired) {
+				<PriceHistory<T>>::remove_prefix(pool_id, None);
+				<PriceHistoryCursor<T>>::remove(pool_id);
+			}
+
+			<PriceHistoryPools<T>>::mutate(|pools| pools.retain(|(end, _)| end.saturating_add(grace_period) >= now));
+		}
+
+		let mut checked: u32 = 0;
+		for (_, pool_id) in pools.iter().skip(expired) {
+			checked += 1;
+
+			if let Some(pool_data) = <PoolData<T>>::get(pool_id) {
+				if Self::sale_status(pool_id, &pool_data) == SaleStatus::Running {
+					Self::record_price(pool_id, &pool_data, now);
+				}
+			}
+		}
+
+		(checked, expired as u32)
+	}
+
+	/// Write the snapshot of the pool to the next slot of its price history.
+	fn record_price(pool_id: &PoolId<T>, pool_data: &Pool<T::AccountId, T::BlockNumber>, now: T::BlockNumber) {
+		let length = T::PriceHistoryLength::get();
+		let weights = match Self::weights_at(pool_data, now) {
+			Ok(weights) if !length.is_zero() => weights,
+			_ => return,
+		};
+
+		let reserves = (
+			T::MultiCurrency::free_balance(pool_data.assets.0, pool_id),
+			T::MultiCurrency::free_balance(pool_data.assets.1, pool_id),
+		);
+
+		let (slot, recorded) = Self::price_history_cursor(pool_id);
+		let slot = slot % length;
+
+		<PriceHistory<T>>::insert(
+			pool_id,
+			slot,
+			PriceSnapshot {
+				block: now,
+				spot_price: spot_price(reserves, weights),
+				weights,
+				reserves,
+			},
+		);
+		<PriceHistoryCursor<T>>::insert(pool_id, ((slot + 1) % length, recorded.saturating_add(1).min(length)));
+	}
+
+	/// Amounts of the reserves moved into the new pool. The ratio of the amounts is the final price of the sale.
+	fn graduation_amounts(
+		fraction: Permill,
+		reserves: (Balance, Balance),
+		weights: (LBPWeight, LBPWeight),
+	) -> Option<(Balance, Balance)> {
+		let amount_a = fraction.mul_floor(reserves.0);
+		let amount_b = fraction.mul_floor(reserves.1);
+
+		// price of the asset_b is reserve_a * weight_b / (reserve_b * weight_a), reduce the bigger side
+		if weights.1 <= weights.0 {
+			let amount_a = multiply_by_rational(amount_a, weights.1.into(), weights.0.into()).ok()?;
+			Some((amount_a, amount_b))
+		} else {
+			let amount_b = multiply_by_rational(amount_b, weights.0.into(), weights.1.into()).ok()?;
+			Some((amount_a, amount_b))
+		}
+	}
+
+	/// Move the reserves of a finished sale into a new pool owned by the pool owner.
+	#[transactional]
+	fn graduate(pool_id: &PoolId<T>) -> DispatchResult {
+		let pool_data = <PoolData<T>>::try_get(pool_id).map_err(|_| Error::<T>::PoolNotFound)?;
+		let graduation = pool_data.graduation.ok_or(Error::<T>::GraduationNotSet)?;
+		let end = pool_data.end.ok_or(Error::<T>::SaleIsNotRunning)?;
+
+		let (asset_a, asset_b) = pool_data.assets;
+		let reserves = (
+			T::MultiCurrency::free_balance(asset_a, pool_id),
+			T::MultiCurrency::free_balance(asset_b, pool_id),
+		);
+		let weights = Self::calculate_weights(&pool_data, end)?;
+
+		let (amount_a, amount_b) =
+			Self::graduation_amounts(graduation.fraction, reserves, weights).ok_or(Error::<T>::Overflow)?;
+
+		T::MultiCurrency::transfer(asset_a, pool_id, &pool_data.owner, amount_a)?;
+		T::MultiCurrency::transfer(asset_b, pool_id, &pool_data.owner, amount_b)?;
+
+		let (share_token, shares) =
+			T::GraduationHandler::create_pool(&pool_data.owner, asset_a, amount_a, asset_b, amount_b)?;
+
+		if !graduation.lock_period.is_zero() {
+			let locked = T::LockedBalance::get_by_lock(GRADUATION_LOCK_ID, share_token, pool_data.owner.clone());
+			T::MultiCurrency::set_lock(
+				GRADUATION_LOCK_ID,
+				share_token,
+				&pool_data.owner,
+				locked.saturating_add(shares),
+			)?;
+
+			let unlock_at = T::BlockNumberProvider::current_block_number().saturating_add(graduation.lock_period);
+			<GraduationSharesUnlock<T>>::mutate(&pool_data.owner, share_token, |maybe_unlock_at| {
+				*maybe_unlock_at = Some(maybe_unlock_at.map_or(unlock_at, |previous| previous.max(unlock_at)));
+			});
+		}
+
+		Self::deposit_event(Event::PoolGraduated(
+			pool_id.clone(),
+			share_token,
+			amount_a,
+			amount_b,
+			shares,
+		));
+
+		Ok(())
+	}
+
+	/// Transfer all the reserves to the pool owner and remove the pool from the storage.
+	fn destroy_pool(
+		pool_id: &PoolId<T>,
+		pool_data: Pool<T::AccountId, T::BlockNumber>,
+	) -> Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+		let (asset_a, asset_b) = pool_data.assets;
+
+		let amount_a = T::MultiCurrency::free_balance(asset_a, pool_id);
+		let amount_b = T::MultiCurrency::free_balance(asset_b, pool_id);
+
+		T::MultiCurrency::transfer(asset_a, pool_id, &pool_data.owner, amount_a)?;
+		T::MultiCurrency::transfer(asset_b, pool_id, &pool_data.owner, amount_b)?;
+
+		if Self::collected_fees(&pool_data) > 0 {
+			T::MultiCurrency::remove_lock(COLLECTOR_LOCK_ID, asset_a, &pool_data.fee_collector)?;
+		}
+
+		<FeeCollectorWithAsset<T>>::remove(&pool_data.fee_collector, pool_data.assets.0);
+		<RoundHistory<T>>::insert(
+			pool_id,
+			SaleRound {
+				pool: pool_data,
+				amount_raised: Self::amount_raised(pool_id),
+				closed_at: Some(T::BlockNumberProvider::current_block_number()),
+			},
+		);
+		<PoolData<T>>::remove(pool_id);
+		<AmountRaised<T>>::remove(pool_id);
+		<PausedSales<T>>::remove(pool_id);
+		Self::close_price_history(pool_id);
+		<ScheduledGraduations<T>>::mutate(|scheduled| scheduled.retain(|(_, id)| id != pool_id));
+		<SaleRestrictions<T>>::remove(pool_id);
+		<AllowlistSize<T>>::remove(pool_id);
+		<Allowlist<T>>::remove_prefix(pool_id, None);
+		<Purchased<T>>::remove_prefix(pool_id, None);
+		<TradeActivity<T>>::remove_prefix(pool_id, None);
+		<SaleVesting<T>>::remove(pool_id);
+
+		Ok((amount_a, amount_b))
+	}
+
+	/// Ensure that the origin is `T::EmergencyOrigin` or the pool owner. Returns true for the former.
+	fn ensure_emergency_or_owner(
+		origin: T::Origin,
+		pool_data: &Pool<T::AccountId, T::BlockNumber>,
+	) -> Result<bool, DispatchError> {
+		match T::EmergencyOrigin::try_origin(origin) {
+			Ok(_) => Ok(true),
+			Err(origin) => {
+				let who = ensure_signed(origin)?;
+				ensure!(who == pool_data.owner, Error::<T>::NotOwner);
+				Ok(false)
+			}
+		}
+	}
+
+	/// Ensure that the trade cool-down of `who` has passed since its last trade in the pool.
+	fn ensure_cooldown_passed(
+		pool_id: &PoolId<T>,
+		pool_data: &Pool<T::AccountId, T::BlockNumber>,
+		who: &T::AccountId,
+	) -> DispatchResult {
+		if let (Some(cooldown), Some((last_trade, _))) =
+			(pool_data.trade_protection.cooldown, Self::trade_activity(pool_id, who))
+		{
+			ensure!(
+				T::BlockNumberProvider::current_block_number() >= last_trade.saturating_add(cooldown),
+				Error::<T>::TradeCooldownNotPassed
+			);
+		}
+
+		Ok(())
+	}
+
+	/// Ensure that `who` can buy `amount` of the distributed asset from the pool.
+	fn ensure_purchase_allowed(
+		pool_id: &PoolId<T>,
+		pool_data: &Pool<T::AccountId, T::BlockNumber>,
+		who: &T::AccountId,
+		amount: Balance,
+	) -> DispatchResult {
+		if let Some(max_buy_per_block) = pool_data.trade_protection.max_buy_per_block {
+			let bought_in_block = Self::bought_in_current_block(pool_id, who)
+				.checked_add(amount)
+				.ok_or(Error::<T>::Overflow)?;
+			ensure!(bought_in_block <= max_buy_per_block, Error::<T>::MaxBuyPerBlockExceeded);
+		}
+
+		if let Some(restrictions) = <SaleRestrictions<T>>::get(pool_id) {
+			ensure!(
+				!restrictions.allowlist_only || <Allowlist<T>>::contains_key(pool_id, who),
+				Error::<T>::NotAllowlisted
+			);
+
+			if let Some(max_purchase) = restrictions.max_purchase {
+				let purchased = Self::purchased(pool_id, who)
+					.checked_add(amount)
+					.ok_or(Error::<T>::Overflow)?;
+				ensure!(purchased <= max_purchase, Error::<T>::MaxPurchaseExceeded);
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Vesting of `who` in the pool with the start moved to the current end of the sale, which is shifted when
+	/// the sale is paused.
+	fn current_vesting(pool_id: &PoolId<T>, who: &T::AccountId) -> Option<VestingInfo<T::BlockNumber>> {
+		let mut vesting = <Vesting<T>>::get(pool_id, who)?;
+
+		if let Some(end) = <PoolData<T>>::get(pool_id).and_then(|pool_data| pool_data.end) {
+			vesting.start = vesting.start.max(end);
+		}
+
+		Some(vesting)
+	}
+
+	/// Amount of the vested asset unlocked at block `at` and not claimed yet.
+	fn claimable_vested(vesting: &VestingInfo<T::BlockNumber>, at: T::BlockNumber) -> Balance {
+		let cliff_end = vesting.start.saturating_add(vesting.schedule.cliff);
+		if at < cliff_end {
+			return 0;
+		}
+
+		let elapsed = at.saturating_sub(cliff_end);
+		let vested = if elapsed >= vesting.schedule.duration {
+			vesting.total
+		} else {
+			multiply_by_rational(
+				vesting.total,
+				elapsed.saturated_into(),
+				vesting.schedule.duration.saturated_into(),
+			)
+			.unwrap_or(0)
+		};
+
+		vested.saturating_sub(vesting.claimed)
+	}
+
+	/// Amount of the distributed asset bought by `who` from the pool in the current block.
+	fn bought_in_current_block(pool_id: &PoolId<T>, who: &T::AccountId) -> Balance {
+		match Self::trade_activity(pool_id, who) {
+			Some((last_trade, bought)) if last_trade == T::BlockNumberProvider::current_block_number() => bought,
+			_ => 0,
+		}
+	}
+
 	/// return true if now is in interval <pool.start, pool.end>
 	fn is_pool_running(pool_data: &Pool<T::AccountId, T::BlockNumber>) -> bool {
 		let now = T::BlockNumberProvider::current_block_number();
@@ -828,11 +2207,19 @@ impl<T: Config> Pallet<T> {
 		Self::collected_fees(pool) < pool.repay_target
 	}
 
+	/// Executes the trade and returns the fee rate applied to it.
 	#[transactional]
-	fn execute_trade(transfer: &AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>) -> DispatchResult {
+	fn execute_trade(
+		transfer: &AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>,
+	) -> Result<(u32, u32), DispatchError> {
 		let pool_account = Self::get_pair_id(transfer.assets);
 		let pool = <PoolData<T>>::try_get(&pool_account).map_err(|_| Error::<T>::PoolNotFound)?;
 
+		// collected fees determine the repay fee tier, the rate has to be read before the fee is collected
+		let fee_rate = Self::current_fee_rate(&pool);
+
+		let accumulated_reserve = T::MultiCurrency::free_balance(pool.assets.0, &pool_account);
+
 		// Transfer assets between pool and user
 		T::MultiCurrency::transfer(
 			transfer.assets.asset_in,
@@ -861,7 +2248,64 @@ impl<T: Config> Pallet<T> {
 		let collected_fee_total = Self::collected_fees(&pool) + fee_amount;
 		T::MultiCurrency::set_lock(COLLECTOR_LOCK_ID, fee_asset, &pool.fee_collector, collected_fee_total)?;
 
-		Ok(())
+		let updated_accumulated_reserve = T::MultiCurrency::free_balance(pool.assets.0, &pool_account);
+		<AmountRaised<T>>::mutate(&pool_account, |raised| {
+			*raised = raised
+				.saturating_add(updated_accumulated_reserve)
+				.saturating_sub(accumulated_reserve)
+		});
+
+		// Track purchases of the distributed asset only if they are limited
+		if transfer.assets.asset_out == pool.assets.1
+			&& Self::purchase_restrictions(&pool_account)
+				.map_or(false, |restrictions| restrictions.max_purchase.is_some())
+		{
+			<Purchased<T>>::mutate(&pool_account, &transfer.origin, |purchased| {
+				*purchased = purchased.saturating_add(transfer.amount_out)
+			});
+		}
+
+		// Bought distributed asset is locked until vested
+		if transfer.assets.asset_out == pool.assets.1 {
+			if let (Some(schedule), Some(end)) = (Self::sale_vesting(&pool_account), pool.end) {
+				<Vesting<T>>::mutate(&pool_account, &transfer.origin, |maybe_vesting| {
+					let vesting = maybe_vesting.get_or_insert(VestingInfo {
+						asset: pool.assets.1,
+						total: 0,
+						claimed: 0,
+						start: end,
+						schedule,
+					});
+					vesting.total = vesting.total.saturating_add(transfer.amount_out);
+					vesting.start = vesting.start.max(end);
+				});
+
+				let locked = T::LockedBalance::get_by_lock(VESTING_LOCK_ID, pool.assets.1, transfer.origin.clone())
+					.saturating_add(transfer.amount_out);
+				T::MultiCurrency::set_lock(VESTING_LOCK_ID, pool.assets.1, &transfer.origin, locked)?;
+			}
+		}
+
+		// Track trades of the account only if the pool is protected by a cool-down or a per block limit
+		let protection = pool.trade_protection;
+		if protection.cooldown.is_some() || protection.max_buy_per_block.is_some() {
+			let bought = if transfer.assets.asset_out == pool.assets.1 {
+				transfer.amount_out
+			} else {
+				0
+			};
+			let bought_in_block = Self::bought_in_current_block(&pool_account, &transfer.origin);
+			<TradeActivity<T>>::insert(
+				&pool_account,
+				&transfer.origin,
+				(
+					T::BlockNumberProvider::current_block_number(),
+					bought_in_block.saturating_add(bought),
+				),
+			);
+		}
+
+		Ok(fee_rate)
 	}
 
 	/// determines fee rate and applies it to the amount
@@ -869,28 +2313,331 @@ impl<T: Config> Pallet<T> {
 		pool: &Pool<T::AccountId, T::BlockNumber>,
 		amount: BalanceOf<T>,
 	) -> Result<BalanceOf<T>, DispatchError> {
-		let fee = if Self::is_repay_fee_applied(pool) {
-			Self::repay_fee()
-		} else {
-			pool.fee
-		};
+		let fee = Self::current_fee_rate(pool);
 		Ok(hydra_dx_math::fee::calculate_pool_trade_fee(amount, (fee.0, fee.1))
 			.ok_or::<Error<T>>(Error::<T>::FeeAmountInvalid)?)
 	}
 
+	/// repay fee rate of the reached tier until repay target amount is reached, opening fee rate during the first
+	/// blocks of the sale, the higher of the two if both apply, pool fee rate afterwards
+	fn current_fee_rate(pool: &Pool<T::AccountId, T::BlockNumber>) -> (u32, u32) {
+		let now = T::BlockNumberProvider::current_block_number();
+		let collected = Self::collected_fees(pool);
+
+		let repay_fee = if collected < pool.repay_target {
+			Some(pool.repay_fee.fee_at(collected))
+		} else {
+			None
+		};
+		let opening_fee = match (pool.trade_protection.opening_fee, pool.start) {
+			(Some((opening_fee, period)), Some(start)) if now < start.saturating_add(period) => Some(opening_fee),
+			_ => None,
+		};
+
+		match (repay_fee, opening_fee) {
+			(Some(repay_fee), Some(opening_fee)) if fee_exceeds(opening_fee, repay_fee) => opening_fee,
+			(Some(repay_fee), _) => repay_fee,
+			(None, Some(opening_fee)) => opening_fee,
+			(None, None) => pool.fee,
+		}
+	}
+
 	pub fn pair_account_from_assets(asset_a: AssetId, asset_b: AssetId) -> PoolId<T> {
 		T::AssetPairAccountId::from_assets(asset_a, asset_b, "lbp")
 	}
+
+	/// Account of the pool of the sale `round` of `asset_a` and `asset_b`.
+	///
+	/// The first round uses the pair account, so pools created before the sale rounds were introduced are
+	/// the first round of their pair.
+	pub fn round_account(asset_a: AssetId, asset_b: AssetId, round: u32) -> PoolId<T> {
+		let pair_account = Self::pair_account_from_assets(asset_a, asset_b);
+		if round.is_zero() {
+			return pair_account;
+		}
+
+		let entropy = (b"lbp/round", pair_account, round).using_encoded(blake2_256);
+		PoolId::<T>::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
+			.expect("infinite length input; no invalid inputs for type; qed")
+	}
+
+	/// Account of the pool of the latest sale round of `asset_a` and `asset_b`.
+	pub fn current_round_account(asset_a: AssetId, asset_b: AssetId) -> PoolId<T> {
+		let pair_account = Self::pair_account_from_assets(asset_a, asset_b);
+		Self::round_account(asset_a, asset_b, Self::sale_rounds(&pair_account).saturating_sub(1))
+	}
+
+	/// Account of the pool of sale `round` of `asset_a` and `asset_b`, of the latest round if `round` is `None`.
+	fn round_account_or_current(asset_a: AssetId, asset_b: AssetId, round: Option<u32>) -> PoolId<T> {
+		match round {
+			Some(round) => Self::round_account(asset_a, asset_b, round),
+			None => Self::current_round_account(asset_a, asset_b),
+		}
+	}
+
+	/// Sale `round` of `asset_a` and `asset_b`, either still open with the current pool data or closed.
+	///
+	/// Returns `None` if the round was not created.
+	pub fn sale_round(
+		asset_a: AssetId,
+		asset_b: AssetId,
+		round: u32,
+	) -> Option<(PoolId<T>, SaleRound<T::AccountId, T::BlockNumber>)> {
+		let pool_account = Self::round_account(asset_a, asset_b, round);
+
+		let sale_round = match <PoolData<T>>::get(&pool_account) {
+			Some(pool) => SaleRound {
+				pool,
+				amount_raised: Self::amount_raised(&pool_account),
+				closed_at: None,
+			},
+			None => Self::round_history(&pool_account)?,
+		};
+
+		Some((pool_account, sale_round))
+	}
+
+	/// return status of the sale at the current block
+	pub fn sale_status(pool_id: &PoolId<T>, pool_data: &Pool<T::AccountId, T::BlockNumber>) -> SaleStatus {
+		let now = T::BlockNumberProvider::current_block_number();
+		if <PausedSales<T>>::contains_key(pool_id) {
+			SaleStatus::Paused
+		} else if Self::is_pool_running(pool_data) {
+			SaleStatus::Running
+		} else if matches!(pool_data.end, Some(end) if end < now) {
+			SaleStatus::Ended
+		} else {
+			SaleStatus::NotStarted
+		}
+	}
+
+	/// Weights of the asset_a and the asset_b at block `at`.
+	///
+	/// Initial weights are returned before the sale or if the sale is not scheduled, final weights are
+	/// returned after the sale.
+	pub fn weights_at(
+		pool_data: &Pool<T::AccountId, T::BlockNumber>,
+		at: T::BlockNumber,
+	) -> Result<(LBPWeight, LBPWeight), DispatchError> {
+		match (pool_data.start, pool_data.end) {
+			(Some(start), Some(end)) => Self::calculate_weights(pool_data, at.max(start).min(end)),
+			_ => Ok((
+				pool_data.initial_weight,
+				MAX_WEIGHT.saturating_sub(pool_data.initial_weight),
+			)),
+		}
+	}
+
+	/// Configuration and current state of the pool of `asset_a` and `asset_b`.
+	///
+	/// Weights and spot price are calculated at block `at` with current reserves, current block or the block
+	/// at which the sale was paused is used if `at` is not specified. Returns `None` if pool does not exist
+	/// or weights cannot be calculated.
+	pub fn sale_info(
+		asset_a: AssetId,
+		asset_b: AssetId,
+		at: Option<T::BlockNumber>,
+	) -> Option<SaleInfo<T::AccountId, T::BlockNumber>> {
+		let pool_account = Self::current_round_account(asset_a, asset_b);
+		let pool = <PoolData<T>>::get(&pool_account)?;
+
+		let at = at
+			.or_else(|| Self::paused_sale(&pool_account).map(|(paused_at, _)| paused_at))
+			.unwrap_or_else(T::BlockNumberProvider::current_block_number);
+		let (weight_a, weight_b) = Self::weights_at(&pool, at).ok()?;
+
+		let asset_a_reserve = T::MultiCurrency::free_balance(pool.assets.0, &pool_account);
+		let asset_b_reserve = T::MultiCurrency::free_balance(pool.assets.1, &pool_account);
+
+		Some(SaleInfo {
+			status: Self::sale_status(&pool_account, &pool),
+			weights: (weight_a, weight_b),
+			asset_a_reserve,
+			asset_b_reserve,
+			spot_price: spot_price((asset_a_reserve, asset_b_reserve), (weight_a, weight_b)),
+			amount_raised: Self::amount_raised(&pool_account),
+			repay_target_remaining: pool.repay_target.saturating_sub(Self::collected_fees(&pool)),
+			fee_rate: Self::current_fee_rate(&pool),
+			pool_account,
+			pool,
+		})
+	}
+
+	/// Amount of the distributed asset `who` can still buy from the pool of sale `round` of `asset_a` and `asset_b`,
+	/// of the latest round if `round` is `None`.
+	///
+	/// Returns `None` if pool does not exist.
+	pub fn remaining_allowance(
+		asset_a: AssetId,
+		asset_b: AssetId,
+		who: &T::AccountId,
+		round: Option<u32>,
+	) -> Option<Allowance> {
+		let pool_account = Self::round_account_or_current(asset_a, asset_b, round);
+		if !<PoolData<T>>::contains_key(&pool_account) {
+			return None;
+		}
+
+		let restrictions = Self::purchase_restrictions(&pool_account).unwrap_or_default();
+
+		Some(Allowance {
+			allowed: !restrictions.allowlist_only || <Allowlist<T>>::contains_key(&pool_account, who),
+			remaining: restrictions
+				.max_purchase
+				.map(|max_purchase| max_purchase.saturating_sub(Self::purchased(&pool_account, who))),
+		})
+	}
+
+	/// Price history of the pool of sale `round` of `asset_a` and `asset_b`, of the latest round if `round` is
+	/// `None`, ordered from the oldest snapshot.
+	pub fn price_history_of(
+		asset_a: AssetId,
+		asset_b: AssetId,
+		round: Option<u32>,
+	) -> Vec<PriceSnapshot<T::BlockNumber>> {
+		let pool_account = Self::round_account_or_current(asset_a, asset_b, round);
+		let length = T::PriceHistoryLength::get();
+		let (next, recorded) = Self::price_history_cursor(&pool_account);
+
+		if length.is_zero() {
+			return Vec::new();
+		}
+
+		let recorded = recorded.min(length);
+		let oldest = next.saturating_add(length).saturating_sub(recorded) % length;
+
+		(0..recorded)
+			.filter_map(|i| Self::price_snapshot(&pool_account, (oldest + i) % length))
+			.collect()
+	}
+
+	/// Vesting of `who` in the pool of sale `round` of `asset_a` and `asset_b`, of the latest round if `round` is
+	/// `None`, and the amount that can be claimed now.
+	///
+	/// Returns `None` if `who` has nothing under vesting in the pool.
+	pub fn vesting_of(
+		asset_a: AssetId,
+		asset_b: AssetId,
+		who: &T::AccountId,
+		round: Option<u32>,
+	) -> Option<(VestingInfo<T::BlockNumber>, Balance)> {
+		let pool_account = Self::round_account_or_current(asset_a, asset_b, round);
+		let vesting = Self::current_vesting(&pool_account, who)?;
+		let claimable = Self::claimable_vested(&vesting, T::BlockNumberProvider::current_block_number());
+
+		Some((vesting, claimable))
+	}
+
+	/// Calculate outcome of selling `amount` of `asset_in` for `asset_out` in current state of the pool.
+	///
+	/// Returns `None` if pool does not exist, the sale is not running or is paused, or the trade cannot be
+	/// calculated.
+	pub fn quote_sell(asset_in: AssetId, asset_out: AssetId, amount: Balance) -> Option<TradeQuote> {
+		let assets = AssetPair { asset_in, asset_out };
+		let pool_id = Self::get_pair_id(assets);
+		let pool_data = <PoolData<T>>::get(&pool_id)?;
+
+		if !Self::is_pool_running(&pool_data) || <PausedSales<T>>::contains_key(&pool_id) {
+			return None;
+		}
+
+		let now = T::BlockNumberProvider::current_block_number();
+		let (weight_in, weight_out) = Self::get_sorted_weight(asset_in, now, &pool_data).ok()?;
+		let asset_in_reserve = T::MultiCurrency::free_balance(asset_in, &pool_id);
+		let asset_out_reserve = T::MultiCurrency::free_balance(asset_out, &pool_id);
+
+		let calculated_out = hydra_dx_math::lbp::calculate_out_given_in(
+			asset_in_reserve,
+			asset_out_reserve,
+			weight_in,
+			weight_out,
+			amount,
+		)
+		.ok()?;
+
+		// fee is charged from the sold amount if accumulated asset is sold, from the bought amount otherwise
+		let fee_asset = pool_data.assets.0;
+		let (amount_out, fee) = if fee_asset == asset_in {
+			(calculated_out, Self::calculate_fees(&pool_data, amount).ok()?)
+		} else {
+			let fee = Self::calculate_fees(&pool_data, calculated_out).ok()?;
+			(calculated_out.checked_sub(fee)?, fee)
+		};
+
+		Some(TradeQuote {
+			amount: amount_out,
+			fee,
+			fee_asset,
+			fee_rate: Self::current_fee_rate(&pool_data),
+			repay_fee_applied: Self::is_repay_fee_applied(&pool_data),
+			max_in_ratio_exceeded: amount > asset_in_reserve.checked_div(MAX_IN_RATIO)?,
+			max_out_ratio_exceeded: calculated_out > asset_out_reserve.checked_div(MAX_OUT_RATIO)?,
+		})
+	}
+
+	/// Calculate outcome of buying `amount` of `asset_out` for `asset_in` in current state of the pool.
+	///
+	/// Returns `None` if pool does not exist, the sale is not running or is paused, or the trade cannot be
+	/// calculated.
+	pub fn quote_buy(asset_out: AssetId, asset_in: AssetId, amount: Balance) -> Option<TradeQuote> {
+		let assets = AssetPair { asset_in, asset_out };
+		let pool_id = Self::get_pair_id(assets);
+		let pool_data = <PoolData<T>>::get(&pool_id)?;
+
+		if !Self::is_pool_running(&pool_data) || <PausedSales<T>>::contains_key(&pool_id) {
+			return None;
+		}
+
+		let now = T::BlockNumberProvider::current_block_number();
+		let (weight_in, weight_out) = Self::get_sorted_weight(asset_in, now, &pool_data).ok()?;
+		let asset_in_reserve = T::MultiCurrency::free_balance(asset_in, &pool_id);
+		let asset_out_reserve = T::MultiCurrency::free_balance(asset_out, &pool_id);
+
+		// fee is charged from the bought amount if accumulated asset is bought, from the sold amount otherwise
+		let fee_asset = pool_data.assets.0;
+		let (amount_in, fee) = if fee_asset == asset_out {
+			let fee = Self::calculate_fees(&pool_data, amount).ok()?;
+			let calculated_in = hydra_dx_math::lbp::calculate_in_given_out(
+				asset_in_reserve,
+				asset_out_reserve,
+				weight_in,
+				weight_out,
+				amount.checked_add(fee)?,
+			)
+			.ok()?;
+			(calculated_in, fee)
+		} else {
+			let calculated_in = hydra_dx_math::lbp::calculate_in_given_out(
+				asset_in_reserve,
+				asset_out_reserve,
+				weight_in,
+				weight_out,
+				amount,
+			)
+			.ok()?;
+			(calculated_in, Self::calculate_fees(&pool_data, calculated_in).ok()?)
+		};
+
+		Some(TradeQuote {
+			amount: amount_in,
+			fee,
+			fee_asset,
+			fee_rate: Self::current_fee_rate(&pool_data),
+			repay_fee_applied: Self::is_repay_fee_applied(&pool_data),
+			max_in_ratio_exceeded: amount_in > asset_in_reserve.checked_div(MAX_IN_RATIO)?,
+			max_out_ratio_exceeded: amount > asset_out_reserve.checked_div(MAX_OUT_RATIO)?,
+		})
+	}
 }
 
 impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, BalanceOf<T>> for Pallet<T> {
 	fn exists(assets: AssetPair) -> bool {
-		let pair_account = Self::pair_account_from_assets(assets.asset_in, assets.asset_out);
-		<PoolData<T>>::contains_key(&pair_account)
+		let pool_account = Self::current_round_account(assets.asset_in, assets.asset_out);
+		<PoolData<T>>::contains_key(&pool_account)
 	}
 
 	fn get_pair_id(assets: AssetPair) -> T::AccountId {
-		Self::pair_account_from_assets(assets.asset_in, assets.asset_out)
+		Self::current_round_account(assets.asset_in, assets.asset_out)
 	}
 
 	fn get_share_token(_assets: AssetPair) -> AssetId {
@@ -956,6 +2703,10 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, BalanceOf<T>> for Pallet<T
 
 		ensure!(Self::is_pool_running(&pool_data), Error::<T>::SaleIsNotRunning);
 
+		ensure!(!<PausedSales<T>>::contains_key(&pool_id), Error::<T>::SaleIsPaused);
+
+		Self::ensure_cooldown_passed(&pool_id, &pool_data, who)?;
+
 		let now = T::BlockNumberProvider::current_block_number();
 		let (weight_in, weight_out) = Self::get_sorted_weight(assets.asset_in, now, &pool_data)?;
 		let asset_in_reserve = T::MultiCurrency::free_balance(assets.asset_in, &pool_id);
@@ -996,6 +2747,8 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, BalanceOf<T>> for Pallet<T
 
 			ensure!(min_bought <= amount_out, Error::<T>::TradingLimitReached);
 
+			Self::ensure_purchase_allowed(&pool_id, &pool_data, who, amount_out)?;
+
 			let amount_without_fee = amount.checked_sub(fee).ok_or(Error::<T>::Overflow)?;
 
 			Ok(AMMTransfer {
@@ -1049,7 +2802,7 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, BalanceOf<T>> for Pallet<T
 	}
 
 	fn execute_sell(transfer: &AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>) -> DispatchResult {
-		Self::execute_trade(transfer)?;
+		let fee_rate = Self::execute_trade(transfer)?;
 
 		Self::deposit_event(Event::<T>::SellExecuted(
 			transfer.origin.clone(),
@@ -1059,6 +2812,7 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, BalanceOf<T>> for Pallet<T
 			transfer.amount_out,
 			transfer.fee.0,
 			transfer.fee.1,
+			fee_rate,
 		));
 
 		Ok(())
@@ -1082,6 +2836,10 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, BalanceOf<T>> for Pallet<T
 
 		ensure!(Self::is_pool_running(&pool_data), Error::<T>::SaleIsNotRunning);
 
+		ensure!(!<PausedSales<T>>::contains_key(&pool_id), Error::<T>::SaleIsPaused);
+
+		Self::ensure_cooldown_passed(&pool_id, &pool_data, who)?;
+
 		let now = T::BlockNumberProvider::current_block_number();
 		let (weight_in, weight_out) = Self::get_sorted_weight(assets.asset_in, now, &pool_data)?;
 		let asset_in_reserve = T::MultiCurrency::free_balance(assets.asset_in, &pool_id);
@@ -1158,6 +2916,8 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, BalanceOf<T>> for Pallet<T
 
 			ensure!(max_sold >= calculated_in, Error::<T>::TradingLimitReached);
 
+			Self::ensure_purchase_allowed(&pool_id, &pool_data, who, amount)?;
+
 			Ok(AMMTransfer {
 				origin: who.clone(),
 				assets,
@@ -1171,7 +2931,7 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, BalanceOf<T>> for Pallet<T
 	}
 
 	fn execute_buy(transfer: &AMMTransfer<T::AccountId, AssetId, AssetPair, BalanceOf<T>>) -> DispatchResult {
-		Self::execute_trade(transfer)?;
+		let fee_rate = Self::execute_trade(transfer)?;
 
 		Self::deposit_event(Event::<T>::BuyExecuted(
 			transfer.origin.clone(),
@@ -1181,6 +2941,7 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, BalanceOf<T>> for Pallet<T
 			transfer.amount_out,
 			transfer.fee.0,
 			transfer.fee.1,
+			fee_rate,
 		));
 		Ok(())
 	}
@@ -1214,15 +2975,21 @@ pub struct DisallowWhenLBPPoolRunning<T>(sp_std::marker::PhantomData<T>);
 
 impl<T: Config> CanCreatePool<AssetId> for DisallowWhenLBPPoolRunning<T> {
 	fn can_create(asset_a: AssetId, asset_b: AssetId) -> bool {
-		let pool_id = Pallet::<T>::pair_account_from_assets(asset_a, asset_b);
+		let pool_id = Pallet::<T>::current_round_account(asset_a, asset_b);
 		let now = T::BlockNumberProvider::current_block_number();
-		match <PoolData<T>>::try_get(&pool_id) {
-			// returns true if the pool exists and the sale ended
-			Ok(data) => match data.end {
-				Some(end) => end < now,
-				None => false,
-			},
+
+		// sale of the latest round ended and the pool is not paused, previous rounds have always ended
+		let latest_round_ended = match <PoolData<T>>::try_get(&pool_id) {
+			Ok(data) => matches!(data.end, Some(end) if end < now) && !<PausedSales<T>>::contains_key(&pool_id),
 			_ => true,
-		}
+		};
+
+		// no round of the pair is waiting for graduation
+		latest_round_ended
+			&& !<ScheduledGraduations<T>>::get().iter().any(|(_, scheduled)| {
+				<PoolData<T>>::get(scheduled).map_or(false, |data| {
+					data.assets == (asset_a, asset_b) || data.assets == (asset_b, asset_a)
+				})
+			})
 	}
 }
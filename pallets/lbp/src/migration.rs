@@ -0,0 +1,443 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2021  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use frame_support::{
+	storage::unhashed,
+	traits::{GetStorageVersion, StorageVersion},
+	weights::Weight,
+};
+
+/// Pool data before the graduation was added.
+#[derive(Encode, Decode)]
+pub struct OldPool<AccountId, BlockNumber: AtLeast32BitUnsigned + Copy> {
+	pub owner: AccountId,
+	pub start: Option<BlockNumber>,
+	pub end: Option<BlockNumber>,
+	pub assets: (AssetId, AssetId),
+	pub initial_weight: LBPWeight,
+	pub final_weight: LBPWeight,
+	pub weight_curve: WeightCurveType,
+	pub fee: (u32, u32),
+	pub fee_collector: AccountId,
+	pub repay_target: Balance,
+}
+
+/// Pool data before the trade protection was added.
+#[derive(Encode, Decode)]
+pub struct PoolV1<AccountId, BlockNumber: AtLeast32BitUnsigned + Copy> {
+	pub owner: AccountId,
+	pub start: Option<BlockNumber>,
+	pub end: Option<BlockNumber>,
+	pub assets: (AssetId, AssetId),
+	pub initial_weight: LBPWeight,
+	pub final_weight: LBPWeight,
+	pub weight_curve: WeightCurveType,
+	pub fee: (u32, u32),
+	pub fee_collector: AccountId,
+	pub repay_target: Balance,
+	pub graduation: Option<Graduation<BlockNumber>>,
+}
+
+/// Pool data before the repay fee schedule was added.
+#[derive(Encode, Decode)]
+pub struct PoolV2<AccountId, BlockNumber: AtLeast32BitUnsigned + Copy> {
+	pub owner: AccountId,
+	pub start: Option<BlockNumber>,
+	pub end: Option<BlockNumber>,
+	pub assets: (AssetId, AssetId),
+	pub initial_weight: LBPWeight,
+	pub final_weight: LBPWeight,
+	pub weight_curve: WeightCurveType,
+	pub fee: (u32, u32),
+	pub fee_collector: AccountId,
+	pub repay_target: Balance,
+	pub graduation: Option<Graduation<BlockNumber>>,
+	pub trade_protection: TradeProtection<BlockNumber>,
+}
+
+/// Migrate the storage to the current storage version step by step.
+///
+/// Storage versions: 1 - graduation, 2 - trade protection, 3 - sale rounds, 4 - repay fee schedule,
+/// 5 - price history.
+pub fn migrate<T: Config>() -> Weight {
+	let mut weight: Weight = 0;
+
+	if Pallet::<T>::on_chain_storage_version() < 1 {
+		weight = weight.saturating_add(migrate_to_v1::<T>());
+	}
+	if Pallet::<T>::on_chain_storage_version() < 2 {
+		weight = weight.saturating_add(migrate_to_v2::<T>());
+	}
+	if Pallet::<T>::on_chain_storage_version() < 3 {
+		weight = weight.saturating_add(migrate_to_v3::<T>());
+	}
+	if Pallet::<T>::on_chain_storage_version() < 4 {
+		weight = weight.saturating_add(migrate_to_v4::<T>());
+	}
+	if Pallet::<T>::on_chain_storage_version() < 5 {
+		weight = weight.saturating_add(migrate_to_v5::<T>());
+	}
+
+	weight.saturating_add(T::DbWeight::get().reads(5))
+}
+
+/// Add the graduation to the pool data.
+fn migrate_to_v1<T: Config>() -> Weight {
+	let mut translated: Weight = 0;
+
+	translate_pool_data::<T, OldPool<T::AccountId, T::BlockNumber>, PoolV1<T::AccountId, T::BlockNumber>>(|old| {
+		translated += 1;
+
+		PoolV1 {
+			owner: old.owner,
+			start: old.start,
+			end: old.end,
+			assets: old.assets,
+			initial_weight: old.initial_weight,
+			final_weight: old.final_weight,
+			weight_curve: old.weight_curve,
+			fee: old.fee,
+			fee_collector: old.fee_collector,
+			repay_target: old.repay_target,
+			graduation: None,
+		}
+	});
+
+	StorageVersion::new(1).put::<Pallet<T>>();
+
+	T::DbWeight::get().reads_writes(translated, translated + 1)
+}
+
+/// Add the trade protection to the pool data.
+fn migrate_to_v2<T: Config>() -> Weight {
+	let mut translated: Weight = 0;
+
+	translate_pool_data::<T, PoolV1<T::AccountId, T::BlockNumber>, PoolV2<T::AccountId, T::BlockNumber>>(|old| {
+		translated += 1;
+
+		PoolV2 {
+			owner: old.owner,
+			start: old.start,
+			end: old.end,
+			assets: old.assets,
+			initial_weight: old.initial_weight,
+			final_weight: old.final_weight,
+			weight_curve: old.weight_curve,
+			fee: old.fee,
+			fee_collector: old.fee_collector,
+			repay_target: old.repay_target,
+			graduation: old.graduation,
+			trade_protection: TradeProtection::default(),
+		}
+	});
+
+	StorageVersion::new(2).put::<Pallet<T>>();
+
+	T::DbWeight::get().reads_writes(translated, translated + 1)
+}
+
+/// Record the existing pools as the first sale round of their asset pair.
+fn migrate_to_v3<T: Config>() -> Weight {
+	let mut read: Weight = 0;
+	let mut rounds: Weight = 0;
+
+	let pool_ids: Vec<T::AccountId> = <PoolData<T>>::iter_keys().collect();
+
+	for pool_id in pool_ids {
+		read += 1;
+
+		let pool_data =
+			match unhashed::get::<PoolV2<T::AccountId, T::BlockNumber>>(&<PoolData<T>>::hashed_key_for(&pool_id)) {
+				Some(pool_data) => pool_data,
+				None => continue,
+			};
+
+		let pair_account = Pallet::<T>::pair_account_from_assets(pool_data.assets.0, pool_data.assets.1);
+		// pools created before the sale rounds use the pair account
+		if pool_id == pair_account {
+			rounds += 1;
+			<SaleRounds<T>>::insert(&pair_account, 1);
+		}
+	}
+
+	StorageVersion::new(3).put::<Pallet<T>>();
+
+	T::DbWeight::get().reads_writes(2 * read, rounds + 1)
+}
+
+/// Add the repay fee schedule to the pool data.
+fn migrate_to_v4<T: Config>() -> Weight {
+	let mut translated: Weight = 0;
+
+	<PoolData<T>>::translate::<PoolV2<T::AccountId, T::BlockNumber>, _>(|_, old| {
+		translated += 1;
+
+		Some(Pool {
+			owner: old.owner,
+			start: old.start,
+			end: old.end,
+			assets: old.assets,
+			initial_weight: old.initial_weight,
+			final_weight: old.final_weight,
+			weight_curve: old.weight_curve,
+			fee: old.fee,
+			fee_collector: old.fee_collector,
+			repay_target: old.repay_target,
+			repay_fee: RepayFeeSchedule::default(),
+			graduation: old.graduation,
+			trade_protection: old.trade_protection,
+		})
+	});
+
+	StorageVersion::new(4).put::<Pallet<T>>();
+
+	T::DbWeight::get().reads_writes(translated, translated + 1)
+}
+
+/// Record the price history of the sales which have not ended yet.
+fn migrate_to_v5<T: Config>() -> Weight {
+	let now = T::BlockNumberProvider::current_block_number();
+	let mut read: Weight = 0;
+	let mut recorded: Weight = 0;
+
+	for (pool_id, pool_data) in <PoolData<T>>::iter() {
+		read += 1;
+
+		if matches!(pool_data.end, Some(end) if end >= now)
+			&& Pallet::<T>::schedule_price_history(&pool_id, &pool_data).is_ok()
+		{
+			recorded += 1;
+		}
+	}
+
+	StorageVersion::new(5).put::<Pallet<T>>();
+
+	T::DbWeight::get().reads_writes(read + recorded, recorded + 1)
+}
+
+/// Translate the pool data between two formats which are not the current `Pool`.
+fn translate_pool_data<T: Config, Old: Decode, New: Encode>(mut f: impl FnMut(Old) -> New) {
+	let pool_ids: Vec<T::AccountId> = <PoolData<T>>::iter_keys().collect();
+
+	for pool_id in pool_ids {
+		let key = <PoolData<T>>::hashed_key_for(&pool_id);
+
+		match unhashed::get::<Old>(&key) {
+			Some(old) => unhashed::put(&key, &f(old)),
+			// same as `translate`, data which can't be decoded is removed
+			None => unhashed::kill(&key),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{ExtBuilder, Test, ALICE, BSX, CHARLIE, KUSD, KUSD_BSX_POOL_ID, SALE_END, SALE_START};
+
+	fn old_pool() -> OldPool<u64, u64> {
+		OldPool {
+			owner: ALICE,
+			start: SALE_START,
+			end: SALE_END,
+			assets: (KUSD, BSX),
+			initial_weight: 10_000_000,
+			final_weight: 90_000_000,
+			weight_curve: WeightCurveType::Linear,
+			fee: (2, 1_000),
+			fee_collector: CHARLIE,
+			repay_target: 0,
+		}
+	}
+
+	fn pool(old: OldPool<u64, u64>) -> Pool<u64, u64> {
+		Pool {
+			owner: old.owner,
+			start: old.start,
+			end: old.end,
+			assets: old.assets,
+			initial_weight: old.initial_weight,
+			final_weight: old.final_weight,
+			weight_curve: old.weight_curve,
+			fee: old.fee,
+			fee_collector: old.fee_collector,
+			repay_target: old.repay_target,
+			repay_fee: RepayFeeSchedule::default(),
+			graduation: None,
+			trade_protection: TradeProtection::default(),
+		}
+	}
+
+	#[test]
+	fn migration_from_v0_should_work() {
+		ExtBuilder::default().build().execute_with(|| {
+			frame_support::storage::unhashed::put(&<PoolData<Test>>::hashed_key_for(KUSD_BSX_POOL_ID), &old_pool());
+
+			migrate::<Test>();
+
+			let pool_data = <PoolData<Test>>::get(KUSD_BSX_POOL_ID).unwrap();
+			assert_eq!(pool_data.owner, ALICE);
+			assert_eq!(pool_data.end, SALE_END);
+			assert_eq!(pool_data.fee_collector, CHARLIE);
+			assert_eq!(pool_data.graduation, None);
+			assert_eq!(pool_data.trade_protection, TradeProtection::default());
+			assert_eq!(pool_data.repay_fee, RepayFeeSchedule::default());
+			assert_eq!(<SaleRounds<Test>>::get(KUSD_BSX_POOL_ID), 1);
+			assert_eq!(Pallet::<Test>::current_round_account(KUSD, BSX), KUSD_BSX_POOL_ID);
+			assert_eq!(StorageVersion::get::<Pallet<Test>>(), 5);
+		});
+	}
+
+	#[test]
+	fn migration_from_v1_should_work() {
+		ExtBuilder::default().build().execute_with(|| {
+			let graduation = Graduation {
+				fraction: Permill::from_percent(50),
+				lock_period: 10,
+			};
+			let old = old_pool();
+			let pool_v1 = PoolV1 {
+				owner: old.owner,
+				start: old.start,
+				end: old.end,
+				assets: old.assets,
+				initial_weight: old.initial_weight,
+				final_weight: old.final_weight,
+				weight_curve: old.weight_curve,
+				fee: old.fee,
+				fee_collector: old.fee_collector,
+				repay_target: old.repay_target,
+				graduation: Some(graduation),
+			};
+			frame_support::storage::unhashed::put(&<PoolData<Test>>::hashed_key_for(KUSD_BSX_POOL_ID), &pool_v1);
+			StorageVersion::new(1).put::<Pallet<Test>>();
+
+			migrate::<Test>();
+
+			let pool_data = <PoolData<Test>>::get(KUSD_BSX_POOL_ID).unwrap();
+			assert_eq!(pool_data.owner, ALICE);
+			assert_eq!(pool_data.graduation, Some(graduation));
+			assert_eq!(pool_data.trade_protection, TradeProtection::default());
+			assert_eq!(pool_data.repay_fee, RepayFeeSchedule::default());
+			assert_eq!(<SaleRounds<Test>>::get(KUSD_BSX_POOL_ID), 1);
+			assert_eq!(StorageVersion::get::<Pallet<Test>>(), 5);
+		});
+	}
+
+	#[test]
+	fn migration_of_pool_v2_data_from_v3_should_work() {
+		ExtBuilder::default().build().execute_with(|| {
+			let trade_protection = TradeProtection {
+				max_buy_per_block: Some(1_000),
+				cooldown: Some(2),
+				opening_fee: None,
+			};
+			let old = old_pool();
+			let pool_v2 = PoolV2 {
+				owner: old.owner,
+				start: old.start,
+				end: old.end,
+				assets: old.assets,
+				initial_weight: old.initial_weight,
+				final_weight: old.final_weight,
+				weight_curve: old.weight_curve,
+				fee: old.fee,
+				fee_collector: old.fee_collector,
+				repay_target: old.repay_target,
+				graduation: None,
+				trade_protection,
+			};
+			frame_support::storage::unhashed::put(&<PoolData<Test>>::hashed_key_for(KUSD_BSX_POOL_ID), &pool_v2);
+			StorageVersion::new(3).put::<Pallet<Test>>();
+
+			migrate::<Test>();
+
+			let pool_data = <PoolData<Test>>::get(KUSD_BSX_POOL_ID).unwrap();
+			assert_eq!(pool_data.owner, ALICE);
+			assert_eq!(pool_data.trade_protection, trade_protection);
+			assert_eq!(pool_data.repay_fee, RepayFeeSchedule::default());
+			// sale rounds were already recorded
+			assert_eq!(<SaleRounds<Test>>::get(KUSD_BSX_POOL_ID), 0);
+			assert_eq!(
+				<PriceHistoryPools<Test>>::get().into_inner(),
+				vec![(SALE_END.unwrap(), KUSD_BSX_POOL_ID)]
+			);
+			assert_eq!(StorageVersion::get::<Pallet<Test>>(), 5);
+		});
+	}
+
+	#[test]
+	fn migration_from_v4_should_record_price_history() {
+		ExtBuilder::default().build().execute_with(|| {
+			let pool_data = pool(old_pool());
+			<PoolData<Test>>::insert(KUSD_BSX_POOL_ID, &pool_data);
+			StorageVersion::new(4).put::<Pallet<Test>>();
+
+			migrate::<Test>();
+
+			assert_eq!(<PoolData<Test>>::get(KUSD_BSX_POOL_ID), Some(pool_data));
+			assert_eq!(
+				<PriceHistoryPools<Test>>::get().into_inner(),
+				vec![(SALE_END.unwrap(), KUSD_BSX_POOL_ID)]
+			);
+			assert_eq!(StorageVersion::get::<Pallet<Test>>(), 5);
+		});
+	}
+
+	#[test]
+	fn migration_should_not_translate_pools_of_current_version() {
+		ExtBuilder::default().build().execute_with(|| {
+			let mut pool_data = pool(old_pool());
+			pool_data.graduation = Some(Graduation {
+				fraction: Permill::from_percent(50),
+				lock_period: 10,
+			});
+			pool_data.trade_protection = TradeProtection {
+				max_buy_per_block: Some(1_000),
+				cooldown: Some(2),
+				opening_fee: None,
+			};
+			<PoolData<Test>>::insert(KUSD_BSX_POOL_ID, &pool_data);
+			Pallet::<Test>::current_storage_version().put::<Pallet<Test>>();
+
+			assert_eq!(
+				migrate::<Test>(),
+				<Test as frame_system::Config>::DbWeight::get().reads(5)
+			);
+
+			assert_eq!(<PoolData<Test>>::get(KUSD_BSX_POOL_ID), Some(pool_data));
+			assert_eq!(<SaleRounds<Test>>::get(KUSD_BSX_POOL_ID), 0);
+			assert!(<PriceHistoryPools<Test>>::get().is_empty());
+		});
+	}
+
+	#[test]
+	fn second_migration_should_do_nothing_work() {
+		ExtBuilder::default().build().execute_with(|| {
+			migrate::<Test>();
+
+			frame_support::storage::unhashed::put(&<PoolData<Test>>::hashed_key_for(KUSD_BSX_POOL_ID), &old_pool());
+
+			assert_eq!(
+				migrate::<Test>(),
+				<Test as frame_system::Config>::DbWeight::get().reads(5)
+			);
+			assert_eq!(<PoolData<Test>>::get(KUSD_BSX_POOL_ID), None);
+		});
+	}
+}
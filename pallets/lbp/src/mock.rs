@@ -33,6 +33,7 @@ pub const HDX: AssetId = CORE_ASSET_ID;
 pub const KUSD: AssetId = 2_000;
 pub const BSX: AssetId = 3_000;
 pub const ETH: AssetId = 4_000;
+pub const SHARE_TOKEN: AssetId = 5_000;
 
 pub const EXISTENTIAL_DEPOSIT: Balance = 100;
 pub const SALE_START: Option<BlockNumber> = Some(10);
@@ -40,21 +41,27 @@ pub const SALE_END: Option<BlockNumber> = Some(40);
 
 pub const HDX_BSX_POOL_ID: AccountId = 3_000;
 pub const KUSD_BSX_POOL_ID: AccountId = 2_003_000;
+pub const XYK_POOL_ID: AccountId = 99;
 
 pub const DEFAULT_FEE: (u32, u32) = (2, 1_000);
 
-pub const SAMPLE_POOL_DATA: Pool<AccountId, BlockNumber> = Pool {
-	owner: ALICE,
-	start: SALE_START,
-	end: SALE_END,
-	assets: (KUSD, BSX),
-	initial_weight: 10_000_000,
-	final_weight: 90_000_000,
-	weight_curve: WeightCurveType::Linear,
-	fee: DEFAULT_FEE,
-	fee_collector: CHARLIE,
-	repay_target: 0,
-};
+pub fn sample_pool_data() -> Pool<AccountId, BlockNumber> {
+	Pool {
+		owner: ALICE,
+		start: SALE_START,
+		end: SALE_END,
+		assets: (KUSD, BSX),
+		initial_weight: 10_000_000,
+		final_weight: 90_000_000,
+		weight_curve: WeightCurveType::Linear,
+		fee: DEFAULT_FEE,
+		fee_collector: CHARLIE,
+		repay_target: 0,
+		repay_fee: RepayFeeSchedule::default(),
+		graduation: None,
+		trade_protection: TradeProtection::default(),
+	}
+}
 
 pub const SAMPLE_AMM_TRANSFER: AMMTransfer<AccountId, AssetId, AssetPair, Balance> = AMMTransfer {
 	origin: ALICE,
@@ -155,6 +162,9 @@ parameter_types! {
 	pub const MinPoolLiquidity: Balance = MIN_POOL_LIQUIDITY;
 	pub const MaxInRatio: u128 = MAX_IN_RATIO;
 	pub const MaxOutRatio: u128 = MAX_OUT_RATIO;
+	pub const MaxRepayFee: (u32, u32) = (2, 10);
+	pub const PriceHistoryLength: u32 = 5;
+	pub const PriceHistoryGracePeriod: BlockNumber = 10;
 }
 
 pub struct MultiLockedBalance();
@@ -181,11 +191,33 @@ impl LockedBalance<AssetId, AccountId, Balance> for MultiLockedBalance {
 	}
 }
 
+pub struct GraduationHandlerMock;
+
+impl GraduationHandler<AccountId> for GraduationHandlerMock {
+	fn create_pool(
+		who: &AccountId,
+		asset_a: AssetId,
+		amount_a: Balance,
+		asset_b: AssetId,
+		amount_b: Balance,
+	) -> Result<(AssetId, Balance), DispatchError> {
+		// pools with ETH can't graduate
+		ensure!(asset_a != ETH && asset_b != ETH, Error::<Test>::PoolAlreadyExists);
+
+		Currency::transfer(asset_a, who, &XYK_POOL_ID, amount_a)?;
+		Currency::transfer(asset_b, who, &XYK_POOL_ID, amount_b)?;
+		Currency::deposit(SHARE_TOKEN, who, amount_a)?;
+
+		Ok((SHARE_TOKEN, amount_a))
+	}
+}
+
 impl Config for Test {
 	type Event = Event;
 	type MultiCurrency = Currency;
 	type LockedBalance = MultiLockedBalance;
 	type CreatePoolOrigin = frame_system::EnsureRoot<u64>;
+	type EmergencyOrigin = frame_system::EnsureRoot<u64>;
 	type LBPWeightFunction = lbp::LBPWeightFunction;
 	type AssetPairAccountId = AssetPairAccountIdTest;
 	type WeightInfo = ();
@@ -193,7 +225,11 @@ impl Config for Test {
 	type MinPoolLiquidity = MinPoolLiquidity;
 	type MaxInRatio = MaxInRatio;
 	type MaxOutRatio = MaxOutRatio;
+	type MaxRepayFee = MaxRepayFee;
+	type PriceHistoryLength = PriceHistoryLength;
+	type PriceHistoryGracePeriod = PriceHistoryGracePeriod;
 	type BlockNumberProvider = System;
+	type GraduationHandler = GraduationHandlerMock;
 }
 
 pub struct ExtBuilder {
@@ -18,14 +18,14 @@
 #![allow(clippy::bool_assert_comparison)]
 use super::*;
 use crate::mock::{
-	generate_trades, run_to_sale_end, run_to_sale_start, DEFAULT_FEE, EXISTENTIAL_DEPOSIT, HDX_BSX_POOL_ID,
-	INITIAL_BALANCE, KUSD_BSX_POOL_ID, SALE_END, SALE_START, SAMPLE_AMM_TRANSFER, SAMPLE_POOL_DATA,
+	generate_trades, run_to_sale_end, run_to_sale_start, sample_pool_data, DEFAULT_FEE, EXISTENTIAL_DEPOSIT,
+	HDX_BSX_POOL_ID, INITIAL_BALANCE, KUSD_BSX_POOL_ID, SALE_END, SALE_START, SAMPLE_AMM_TRANSFER,
 };
 pub use crate::mock::{
 	set_block_number, Currency, Event as TestEvent, ExtBuilder, LBPPallet, Origin, Test, ALICE, BOB, BSX, CHARLIE, ETH,
-	HDX, KUSD,
+	HDX, KUSD, SHARE_TOKEN, XYK_POOL_ID,
 };
-use frame_support::{assert_err, assert_noop, assert_ok};
+use frame_support::{assert_err, assert_noop, assert_ok, traits::Hooks};
 use hydradx_traits::{AMMTransfer, LockedBalance};
 use sp_runtime::traits::BadOrigin;
 use sp_std::convert::TryInto;
@@ -57,6 +57,8 @@ pub fn predefined_test_ext() -> sp_io::TestExternalities {
 			DEFAULT_FEE,
 			CHARLIE,
 			0,
+			TradeProtection::default(),
+			RepayFeeSchedule::default(),
 		));
 
 		assert_ok!(LBPPallet::update_pool_data(
@@ -70,6 +72,9 @@ pub fn predefined_test_ext() -> sp_io::TestExternalities {
 			None,
 			None,
 			None,
+			None,
+			None,
+			None,
 		));
 
 		let pool_data2 = Pool {
@@ -83,6 +88,9 @@ pub fn predefined_test_ext() -> sp_io::TestExternalities {
 			fee: DEFAULT_FEE,
 			fee_collector: CHARLIE,
 			repay_target: 0,
+			repay_fee: RepayFeeSchedule::default(),
+			graduation: None,
+			trade_protection: TradeProtection::default(),
 		};
 
 		assert_eq!(<PoolData<Test>>::get(KUSD_BSX_POOL_ID).unwrap(), pool_data2);
@@ -113,6 +121,8 @@ pub fn predefined_test_ext_with_repay_target() -> sp_io::TestExternalities {
 			DEFAULT_FEE,
 			CHARLIE,
 			initial_liquidity,
+			TradeProtection::default(),
+			RepayFeeSchedule::default(),
 		));
 
 		assert_ok!(LBPPallet::update_pool_data(
@@ -126,6 +136,9 @@ pub fn predefined_test_ext_with_repay_target() -> sp_io::TestExternalities {
 			None,
 			None,
 			None,
+			None,
+			None,
+			None,
 		));
 
 		assert_ok!(LBPPallet::add_liquidity(
@@ -176,6 +189,9 @@ fn validate_pool_data_should_work() {
 			fee: DEFAULT_FEE,
 			fee_collector: CHARLIE,
 			repay_target: 0,
+			repay_fee: RepayFeeSchedule::default(),
+			graduation: None,
+			trade_protection: TradeProtection::default(),
 		};
 		assert_ok!(LBPPallet::validate_pool_data(&pool_data));
 
@@ -191,6 +207,9 @@ fn validate_pool_data_should_work() {
 			fee: DEFAULT_FEE,
 			fee_collector: CHARLIE,
 			repay_target: 0,
+			repay_fee: RepayFeeSchedule::default(),
+			graduation: None,
+			trade_protection: TradeProtection::default(),
 		};
 		assert_ok!(LBPPallet::validate_pool_data(&pool_data));
 
@@ -205,6 +224,9 @@ fn validate_pool_data_should_work() {
 			fee: DEFAULT_FEE,
 			fee_collector: CHARLIE,
 			repay_target: 0,
+			repay_fee: RepayFeeSchedule::default(),
+			graduation: None,
+			trade_protection: TradeProtection::default(),
 		};
 		assert_noop!(
 			LBPPallet::validate_pool_data(&pool_data),
@@ -222,6 +244,9 @@ fn validate_pool_data_should_work() {
 			fee: DEFAULT_FEE,
 			fee_collector: CHARLIE,
 			repay_target: 0,
+			repay_fee: RepayFeeSchedule::default(),
+			graduation: None,
+			trade_protection: TradeProtection::default(),
 		};
 		assert_noop!(
 			LBPPallet::validate_pool_data(&pool_data),
@@ -244,6 +269,9 @@ fn max_sale_duration_ckeck() {
 			fee: DEFAULT_FEE,
 			fee_collector: CHARLIE,
 			repay_target: 0,
+			repay_fee: RepayFeeSchedule::default(),
+			graduation: None,
+			trade_protection: TradeProtection::default(),
 		}));
 		assert_noop!(
 			LBPPallet::validate_pool_data(&Pool {
@@ -257,6 +285,9 @@ fn max_sale_duration_ckeck() {
 				fee: DEFAULT_FEE,
 				fee_collector: CHARLIE,
 				repay_target: 0,
+				repay_fee: RepayFeeSchedule::default(),
+				graduation: None,
+				trade_protection: TradeProtection::default(),
 			}),
 			Error::<Test>::MaxSaleDurationExceeded
 		);
@@ -277,6 +308,9 @@ fn calculate_weights_should_work() {
 			fee: DEFAULT_FEE,
 			fee_collector: CHARLIE,
 			repay_target: 0,
+			repay_fee: RepayFeeSchedule::default(),
+			graduation: None,
+			trade_protection: TradeProtection::default(),
 		};
 		assert_eq!(LBPPallet::calculate_weights(&pool_data, 170), Ok((38333333, 61666667)));
 
@@ -322,6 +356,175 @@ fn calculate_weights_should_work() {
 	});
 }
 
+#[test]
+fn calculate_weights_with_exponential_decay_curve_should_work() {
+	new_test_ext().execute_with(|| {
+		let mut pool_data = Pool {
+			owner: ALICE,
+			start: Some(100),
+			end: Some(200),
+			assets: (KUSD, BSX),
+			initial_weight: 80_000_000,
+			final_weight: 20_000_000,
+			weight_curve: WeightCurveType::ExponentialDecay { rate: 4 },
+			fee: DEFAULT_FEE,
+			fee_collector: CHARLIE,
+			repay_target: 0,
+			repay_fee: RepayFeeSchedule::default(),
+			graduation: None,
+			trade_protection: TradeProtection::default(),
+		};
+		assert_eq!(
+			LBPPallet::calculate_weights(&pool_data, 100),
+			Ok((80_000_000, 20_000_000))
+		);
+		assert_eq!(
+			LBPPallet::calculate_weights(&pool_data, 110),
+			Ok((64_502_930, 35_497_070))
+		);
+		assert_eq!(
+			LBPPallet::calculate_weights(&pool_data, 150),
+			Ok((32_000_000, 68_000_000))
+		);
+		assert_eq!(
+			LBPPallet::calculate_weights(&pool_data, 170),
+			Ok((25_189_586, 74_810_414))
+		);
+		assert_eq!(
+			LBPPallet::calculate_weights(&pool_data, 200),
+			Ok((20_000_000, 80_000_000))
+		);
+
+		// increasing weight
+		pool_data.initial_weight = 20_000_000;
+		pool_data.final_weight = 80_000_000;
+		pool_data.weight_curve = WeightCurveType::ExponentialDecay { rate: 1 };
+		assert_eq!(
+			LBPPallet::calculate_weights(&pool_data, 150),
+			Ok((55_147_186, 44_852_814))
+		);
+
+		// out of bound
+		assert_eq!(
+			LBPPallet::calculate_weights(&pool_data, 99),
+			Err(Error::<Test>::WeightCalculationError.into())
+		);
+		assert_eq!(
+			LBPPallet::calculate_weights(&pool_data, 201),
+			Err(Error::<Test>::WeightCalculationError.into())
+		);
+	});
+}
+
+#[test]
+fn calculate_weights_with_step_curve_should_work() {
+	new_test_ext().execute_with(|| {
+		let pool_data = Pool {
+			owner: ALICE,
+			start: Some(100),
+			end: Some(200),
+			assets: (KUSD, BSX),
+			initial_weight: 80_000_000,
+			final_weight: 20_000_000,
+			weight_curve: WeightCurveType::Step { steps: 4 },
+			fee: DEFAULT_FEE,
+			fee_collector: CHARLIE,
+			repay_target: 0,
+			repay_fee: RepayFeeSchedule::default(),
+			graduation: None,
+			trade_protection: TradeProtection::default(),
+		};
+		assert_eq!(
+			LBPPallet::calculate_weights(&pool_data, 100),
+			Ok((80_000_000, 20_000_000))
+		);
+		assert_eq!(
+			LBPPallet::calculate_weights(&pool_data, 124),
+			Ok((80_000_000, 20_000_000))
+		);
+		assert_eq!(
+			LBPPallet::calculate_weights(&pool_data, 125),
+			Ok((60_000_000, 40_000_000))
+		);
+		assert_eq!(
+			LBPPallet::calculate_weights(&pool_data, 150),
+			Ok((40_000_000, 60_000_000))
+		);
+		assert_eq!(
+			LBPPallet::calculate_weights(&pool_data, 175),
+			Ok((20_000_000, 80_000_000))
+		);
+		assert_eq!(
+			LBPPallet::calculate_weights(&pool_data, 200),
+			Ok((20_000_000, 80_000_000))
+		);
+
+		// out of bound
+		assert_eq!(
+			LBPPallet::calculate_weights(&pool_data, 201),
+			Err(Error::<Test>::WeightCalculationError.into())
+		);
+	});
+}
+
+#[test]
+fn calculate_weights_with_piecewise_linear_curve_should_work() {
+	new_test_ext().execute_with(|| {
+		let pool_data = Pool {
+			owner: ALICE,
+			start: Some(100),
+			end: Some(200),
+			assets: (KUSD, BSX),
+			initial_weight: 80_000_000,
+			final_weight: 20_000_000,
+			weight_curve: WeightCurveType::PiecewiseLinear(
+				vec![
+					(Permill::from_percent(20), 40_000_000),
+					(Permill::from_percent(50), 30_000_000),
+				]
+				.try_into()
+				.unwrap(),
+			),
+			fee: DEFAULT_FEE,
+			fee_collector: CHARLIE,
+			repay_target: 0,
+			repay_fee: RepayFeeSchedule::default(),
+			graduation: None,
+			trade_protection: TradeProtection::default(),
+		};
+		assert_eq!(
+			LBPPallet::calculate_weights(&pool_data, 100),
+			Ok((80_000_000, 20_000_000))
+		);
+		assert_eq!(
+			LBPPallet::calculate_weights(&pool_data, 110),
+			Ok((60_000_000, 40_000_000))
+		);
+		assert_eq!(
+			LBPPallet::calculate_weights(&pool_data, 120),
+			Ok((40_000_000, 60_000_000))
+		);
+		assert_eq!(
+			LBPPallet::calculate_weights(&pool_data, 150),
+			Ok((30_000_000, 70_000_000))
+		);
+		assert_eq!(
+			LBPPallet::calculate_weights(&pool_data, 170),
+			Ok((26_000_000, 74_000_000))
+		);
+		assert_eq!(
+			LBPPallet::calculate_weights(&pool_data, 200),
+			Ok((20_000_000, 80_000_000))
+		);
+
+		// out of bound
+		assert_eq!(
+			LBPPallet::calculate_weights(&pool_data, 99),
+			Err(Error::<Test>::WeightCalculationError.into())
+		);
+	});
+}
+
 #[test]
 fn create_pool_should_work() {
 	new_test_ext().execute_with(|| {
@@ -338,6 +541,8 @@ fn create_pool_should_work() {
 			DEFAULT_FEE,
 			CHARLIE,
 			0,
+			TradeProtection::default(),
+			RepayFeeSchedule::default(),
 		));
 
 		assert_eq!(Currency::free_balance(KUSD, &KUSD_BSX_POOL_ID), 1_000_000_000);
@@ -393,6 +598,8 @@ fn create_pool_from_basic_origin_should_not_work() {
 				DEFAULT_FEE,
 				CHARLIE,
 				0,
+				TradeProtection::default(),
+				RepayFeeSchedule::default(),
 			),
 			BadOrigin
 		);
@@ -415,6 +622,8 @@ fn create_same_pool_should_not_work() {
 			DEFAULT_FEE,
 			CHARLIE,
 			0,
+			TradeProtection::default(),
+			RepayFeeSchedule::default(),
 		));
 
 		assert_noop!(
@@ -431,6 +640,8 @@ fn create_same_pool_should_not_work() {
 				DEFAULT_FEE,
 				CHARLIE,
 				0,
+				TradeProtection::default(),
+				RepayFeeSchedule::default(),
 			),
 			Error::<Test>::PoolAlreadyExists
 		);
@@ -463,6 +674,8 @@ fn create_pool_with_same_assets_should_not_work() {
 				DEFAULT_FEE,
 				CHARLIE,
 				0,
+				TradeProtection::default(),
+				RepayFeeSchedule::default(),
 			),
 			Error::<Test>::CannotCreatePoolWithSameAssets
 		);
@@ -485,6 +698,8 @@ fn create_pool_with_non_existing_fee_collector_with_asset_should_work() {
 			DEFAULT_FEE,
 			CHARLIE,
 			0,
+			TradeProtection::default(),
+			RepayFeeSchedule::default(),
 		));
 
 		assert_ok!(LBPPallet::create_pool(
@@ -500,6 +715,8 @@ fn create_pool_with_non_existing_fee_collector_with_asset_should_work() {
 			DEFAULT_FEE,
 			CHARLIE,
 			0,
+			TradeProtection::default(),
+			RepayFeeSchedule::default(),
 		),);
 	});
 }
@@ -520,6 +737,8 @@ fn create_pool_with_existing_fee_collector_with_asset_should_not_work() {
 			DEFAULT_FEE,
 			CHARLIE,
 			0,
+			TradeProtection::default(),
+			RepayFeeSchedule::default(),
 		));
 
 		assert_noop!(
@@ -536,6 +755,8 @@ fn create_pool_with_existing_fee_collector_with_asset_should_not_work() {
 				DEFAULT_FEE,
 				CHARLIE,
 				0,
+				TradeProtection::default(),
+				RepayFeeSchedule::default(),
 			),
 			Error::<Test>::FeeCollectorWithAssetAlreadyUsed
 		);
@@ -559,6 +780,8 @@ fn create_pool_with_insufficient_liquidity_should_not_work() {
 				DEFAULT_FEE,
 				CHARLIE,
 				0,
+				TradeProtection::default(),
+				RepayFeeSchedule::default(),
 			),
 			Error::<Test>::InsufficientLiquidity
 		);
@@ -577,6 +800,8 @@ fn create_pool_with_insufficient_liquidity_should_not_work() {
 				DEFAULT_FEE,
 				CHARLIE,
 				0,
+				TradeProtection::default(),
+				RepayFeeSchedule::default(),
 			),
 			Error::<Test>::InsufficientLiquidity
 		);
@@ -595,6 +820,8 @@ fn create_pool_with_insufficient_liquidity_should_not_work() {
 				DEFAULT_FEE,
 				CHARLIE,
 				0,
+				TradeProtection::default(),
+				RepayFeeSchedule::default(),
 			),
 			Error::<Test>::InsufficientLiquidity
 		);
@@ -618,12 +845,82 @@ fn create_pool_with_insufficient_balance_should_not_work() {
 				DEFAULT_FEE,
 				CHARLIE,
 				0,
+				TradeProtection::default(),
+				RepayFeeSchedule::default(),
 			),
 			Error::<Test>::InsufficientAssetBalance
 		);
 	});
 }
 
+#[test]
+fn create_pool_with_invalid_weight_curve_should_not_work() {
+	new_test_ext().execute_with(|| {
+		let points = |points: Vec<(Permill, LBPWeight)>| WeightCurveType::PiecewiseLinear(points.try_into().unwrap());
+
+		for weight_curve in [
+			WeightCurveType::ExponentialDecay { rate: 0 },
+			WeightCurveType::ExponentialDecay {
+				rate: MAX_DECAY_RATE + 1,
+			},
+			WeightCurveType::Step { steps: 1 },
+			points(vec![]),
+			points(vec![(Permill::zero(), 40_000_000)]),
+			points(vec![(Permill::one(), 40_000_000)]),
+			points(vec![(Permill::from_percent(20), 0)]),
+			points(vec![(Permill::from_percent(20), MAX_WEIGHT)]),
+			points(vec![
+				(Permill::from_percent(50), 40_000_000),
+				(Permill::from_percent(20), 30_000_000),
+			]),
+			points(vec![
+				(Permill::from_percent(20), 40_000_000),
+				(Permill::from_percent(20), 30_000_000),
+			]),
+		] {
+			assert_noop!(
+				LBPPallet::create_pool(
+					Origin::root(),
+					ALICE,
+					KUSD,
+					1_000_000_000,
+					BSX,
+					2_000_000_000,
+					80_000_000u32,
+					10_000_000u32,
+					weight_curve,
+					DEFAULT_FEE,
+					CHARLIE,
+					0,
+					TradeProtection::default(),
+					RepayFeeSchedule::default(),
+				),
+				Error::<Test>::InvalidWeightCurve
+			);
+		}
+
+		assert_ok!(LBPPallet::create_pool(
+			Origin::root(),
+			ALICE,
+			KUSD,
+			1_000_000_000,
+			BSX,
+			2_000_000_000,
+			80_000_000u32,
+			10_000_000u32,
+			points(vec![
+				(Permill::from_percent(20), 40_000_000),
+				(Permill::from_percent(50), 30_000_000),
+			]),
+			DEFAULT_FEE,
+			CHARLIE,
+			0,
+			TradeProtection::default(),
+			RepayFeeSchedule::default(),
+		));
+	});
+}
+
 #[test]
 fn update_pool_data_should_work() {
 	predefined_test_ext().execute_with(|| {
@@ -636,9 +933,12 @@ fn update_pool_data_should_work() {
 			Some(18),
 			Some(10_000_000),
 			Some(80_000_000),
+			None,
 			Some((5, 100)),
 			Some(BOB),
 			None,
+			None,
+			None,
 		));
 
 		// verify changes
@@ -667,6 +967,9 @@ fn update_pool_data_should_work() {
 			None,
 			None,
 			None,
+			None,
+			None,
+			None,
 		));
 
 		// verify changes
@@ -690,6 +993,9 @@ fn update_pool_data_should_work() {
 			None,
 			None,
 			None,
+			None,
+			None,
+			None,
 		));
 
 		// verify changes
@@ -711,8 +1017,11 @@ fn update_pool_data_should_work() {
 			None,
 			None,
 			None,
+			None,
 			Some(ALICE),
 			None,
+			None,
+			None,
 		));
 
 		// verify changes
@@ -733,9 +1042,12 @@ fn update_pool_data_should_work() {
 			Some(18),
 			Some(10_000_000),
 			Some(80_000_000),
+			None,
 			Some((6, 1_000)),
 			None,
 			None,
+			None,
+			None,
 		));
 
 		// verify changes
@@ -760,7 +1072,10 @@ fn update_pool_data_should_work() {
 			None,
 			None,
 			None,
+			None,
 			Some(repayment),
+			None,
+			None,
 		));
 		let updated_pool_data_6 = LBPPallet::pool_data(KUSD_BSX_POOL_ID).unwrap();
 		assert_eq!(updated_pool_data_6.repay_target, repayment);
@@ -788,9 +1103,12 @@ fn update_non_existing_pool_data_should_not_work() {
 				Some(18),
 				Some(10_000_000),
 				Some(80_000_000),
+				None,
 				Some((5, 100)),
 				None,
 				None,
+				None,
+				None,
 			),
 			Error::<Test>::PoolNotFound
 		);
@@ -809,10 +1127,13 @@ fn update_pool_with_invalid_data_should_not_work() {
 				Some(20),
 				Some(10),
 				Some(10_000_000),
+				None,
 				Some(80_000_000),
 				Some((5, 100)),
 				None,
 				None,
+				None,
+				None,
 			),
 			Error::<Test>::InvalidBlockRange
 		);
@@ -828,9 +1149,12 @@ fn update_pool_with_invalid_data_should_not_work() {
 				Some(20),
 				Some(10_000_000),
 				Some(80_000_000),
+				None,
 				Some((5, 100)),
 				None,
 				None,
+				None,
+				None,
 			),
 			Error::<Test>::InvalidBlockRange
 		);
@@ -844,9 +1168,12 @@ fn update_pool_with_invalid_data_should_not_work() {
 				Some(20),
 				Some(10_000_000),
 				Some(80_000_000),
+				None,
 				Some((5, 100)),
 				None,
 				None,
+				None,
+				None,
 			),
 			Error::<Test>::InvalidBlockRange
 		);
@@ -860,9 +1187,12 @@ fn update_pool_with_invalid_data_should_not_work() {
 				Some(0),
 				Some(10_000_000),
 				Some(80_000_000),
+				None,
 				Some((5, 100)),
 				None,
 				None,
+				None,
+				None,
 			),
 			Error::<Test>::InvalidBlockRange
 		);
@@ -884,12 +1214,64 @@ fn update_pool_data_without_changes_should_not_work() {
 				None,
 				None,
 				None,
+				None,
+				None,
+				None,
 			),
 			Error::<Test>::NothingToUpdate
 		);
 	});
 }
 
+#[test]
+fn update_pool_weight_curve_should_work() {
+	predefined_test_ext().execute_with(|| {
+		assert_ok!(LBPPallet::update_pool_data(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			None,
+			None,
+			None,
+			None,
+			None,
+			Some(WeightCurveType::Step { steps: 3 }),
+			None,
+			None,
+			None,
+			None,
+			None,
+		));
+
+		let pool_data = LBPPallet::pool_data(KUSD_BSX_POOL_ID).unwrap();
+		assert_eq!(pool_data.weight_curve, WeightCurveType::Step { steps: 3 });
+		assert_eq!(
+			LBPPallet::calculate_weights(&pool_data, 25),
+			Ok((50_000_000, 50_000_000))
+		);
+
+		expect_events(vec![Event::PoolUpdated(KUSD_BSX_POOL_ID, pool_data).into()]);
+
+		assert_noop!(
+			LBPPallet::update_pool_data(
+				Origin::signed(ALICE),
+				KUSD_BSX_POOL_ID,
+				None,
+				None,
+				None,
+				None,
+				None,
+				Some(WeightCurveType::Step { steps: 0 }),
+				None,
+				None,
+				None,
+				None,
+				None,
+			),
+			Error::<Test>::InvalidWeightCurve
+		);
+	});
+}
+
 #[test]
 fn update_pool_data_by_non_owner_should_not_work() {
 	predefined_test_ext().execute_with(|| {
@@ -905,6 +1287,9 @@ fn update_pool_data_by_non_owner_should_not_work() {
 				None,
 				None,
 				None,
+				None,
+				None,
+				None,
 			),
 			Error::<Test>::NotOwner
 		);
@@ -925,6 +1310,9 @@ fn update_pool_owner_by_new_owner_should_work() {
 			None,
 			None,
 			None,
+			None,
+			None,
+			None,
 		));
 
 		let pool_data1 = LBPPallet::pool_data(KUSD_BSX_POOL_ID).unwrap();
@@ -940,6 +1328,9 @@ fn update_pool_owner_by_new_owner_should_work() {
 			None,
 			None,
 			None,
+			None,
+			None,
+			None,
 		));
 
 		let pool_data2 = LBPPallet::pool_data(KUSD_BSX_POOL_ID).unwrap();
@@ -965,6 +1356,9 @@ fn update_pool_data_for_running_lbp_should_not_work() {
 			None,
 			None,
 			None,
+			None,
+			None,
+			None,
 		));
 
 		set_block_number::<Test>(16);
@@ -979,9 +1373,12 @@ fn update_pool_data_for_running_lbp_should_not_work() {
 				Some(30),
 				Some(10_000_000),
 				Some(80_000_000),
+				None,
 				Some((5, 100)),
 				Some(BOB),
 				None,
+				None,
+				None,
 			),
 			Error::<Test>::SaleStarted
 		);
@@ -1008,6 +1405,8 @@ fn update_pool_with_existing_fee_collector_should_not_work() {
 			DEFAULT_FEE,
 			BOB,
 			0,
+			TradeProtection::default(),
+			RepayFeeSchedule::default(),
 		));
 
 		assert_noop!(
@@ -1019,9 +1418,12 @@ fn update_pool_with_existing_fee_collector_should_not_work() {
 				Some(18),
 				Some(10_000_000),
 				Some(80_000_000),
+				None,
 				Some((5, 100)),
 				Some(BOB),
 				None,
+				None,
+				None,
 			),
 			Error::<Test>::FeeCollectorWithAssetAlreadyUsed
 		);
@@ -1044,6 +1446,8 @@ fn update_pool_interval_should_work() {
 			DEFAULT_FEE,
 			CHARLIE,
 			0,
+			TradeProtection::default(),
+			RepayFeeSchedule::default(),
 		));
 
 		set_block_number::<Test>(15);
@@ -1060,6 +1464,9 @@ fn update_pool_interval_should_work() {
 				None,
 				None,
 				None,
+				None,
+				None,
+				None,
 			),
 			Error::<Test>::InvalidBlockRange
 		);
@@ -1075,6 +1482,9 @@ fn update_pool_interval_should_work() {
 			None,
 			None,
 			None,
+			None,
+			None,
+			None,
 		));
 
 		// verify changes
@@ -1421,6 +1831,8 @@ fn remove_liquidity_from_not_started_pool_should_work() {
 			DEFAULT_FEE,
 			CHARLIE,
 			0,
+			TradeProtection::default(),
+			RepayFeeSchedule::default(),
 		));
 
 		let user_balance_a_before = Currency::free_balance(HDX, &ALICE);
@@ -1709,7 +2121,14 @@ fn execute_sell_should_work() {
 		assert_ok!(LBPPallet::execute_sell(&t));
 
 		expect_events(vec![Event::SellExecuted(
-			ALICE, asset_in, asset_out, amount_in, amount_out, asset_in, 1_000,
+			ALICE,
+			asset_in,
+			asset_out,
+			amount_in,
+			amount_out,
+			asset_in,
+			1_000,
+			DEFAULT_FEE,
 		)
 		.into()]);
 
@@ -1721,7 +2140,14 @@ fn execute_sell_should_work() {
 		assert_eq!(Currency::free_balance(asset_out, &pool_id), 1_980_000_000);
 
 		expect_events(vec![Event::SellExecuted(
-			ALICE, asset_in, asset_out, 8_000_000, 20_000_000, asset_in, 1_000,
+			ALICE,
+			asset_in,
+			asset_out,
+			8_000_000,
+			20_000_000,
+			asset_in,
+			1_000,
+			DEFAULT_FEE,
 		)
 		.into()]);
 	});
@@ -1779,6 +2205,8 @@ fn zero_weight_should_not_work() {
 				DEFAULT_FEE,
 				CHARLIE,
 				0,
+				TradeProtection::default(),
+				RepayFeeSchedule::default(),
 			),
 			Error::<Test>::InvalidWeight
 		);
@@ -1792,9 +2220,12 @@ fn zero_weight_should_not_work() {
 				Some(18),
 				Some(0),
 				Some(80),
+				None,
 				Some((5, 100)),
 				Some(BOB),
 				Some(0),
+				None,
+				None,
 			),
 			Error::<Test>::InvalidWeight
 		);
@@ -1839,7 +2270,14 @@ fn execute_buy_should_work() {
 		assert_eq!(Currency::free_balance(asset_out, &pool_id), 1_980_000_000);
 
 		expect_events(vec![Event::BuyExecuted(
-			ALICE, asset_out, asset_in, 8_000_000, 20_000_000, asset_in, 1_000,
+			ALICE,
+			asset_out,
+			asset_in,
+			8_000_000,
+			20_000_000,
+			asset_in,
+			1_000,
+			DEFAULT_FEE,
 		)
 		.into()]);
 	});
@@ -2152,6 +2590,8 @@ fn buy_should_work() {
 			DEFAULT_FEE,
 			CHARLIE,
 			0,
+			TradeProtection::default(),
+			RepayFeeSchedule::default(),
 		));
 
 		let pool_data1 = LBPPallet::pool_data(pool_id2).unwrap();
@@ -2166,8 +2606,11 @@ fn buy_should_work() {
 			None,
 			None,
 			None,
-			None
-		));
+			None,
+			None,
+			None,
+			None,
+		));
 
 		let pool_data2 = LBPPallet::pool_data(pool_id2).unwrap();
 
@@ -2193,7 +2636,7 @@ fn buy_should_work() {
 				amount: 35_860,
 			}
 			.into(),
-			Event::BuyExecuted(buyer, BSX, KUSD, 17_894_737, 10_000_000, KUSD, 35_860).into(),
+			Event::BuyExecuted(buyer, BSX, KUSD, 17_894_737, 10_000_000, KUSD, 35_860, DEFAULT_FEE).into(),
 			Event::PoolCreated(pool_id2, pool_data1).into(),
 			frame_system::Event::NewAccount { account: pool_id2 }.into(),
 			orml_tokens::Event::Endowed {
@@ -2216,7 +2659,7 @@ fn buy_should_work() {
 				amount: 3710,
 			}
 			.into(),
-			Event::BuyExecuted(buyer, asset_out, asset_in, 1_851_962, 10_000_000, 0, 3710).into(),
+			Event::BuyExecuted(buyer, asset_out, asset_in, 1_851_962, 10_000_000, 0, 3710, DEFAULT_FEE).into(),
 		]);
 	});
 }
@@ -2248,7 +2691,14 @@ fn update_pool_data_after_sale_should_not_work() {
 		set_block_number::<Test>(41);
 
 		expect_events(vec![Event::BuyExecuted(
-			buyer, BSX, KUSD, 17_894_737, 10_000_000, KUSD, 35_860,
+			buyer,
+			BSX,
+			KUSD,
+			17_894_737,
+			10_000_000,
+			KUSD,
+			35_860,
+			DEFAULT_FEE,
 		)
 		.into()]);
 
@@ -2264,6 +2714,9 @@ fn update_pool_data_after_sale_should_not_work() {
 				None,
 				None,
 				None,
+				None,
+				None,
+				None,
 			),
 			Error::<Test>::SaleStarted
 		);
@@ -2311,6 +2764,8 @@ fn sell_should_work() {
 			DEFAULT_FEE,
 			CHARLIE,
 			0,
+			TradeProtection::default(),
+			RepayFeeSchedule::default(),
 		));
 
 		let pool_data1 = LBPPallet::pool_data(pool_id2).unwrap();
@@ -2325,7 +2780,10 @@ fn sell_should_work() {
 			None,
 			None,
 			None,
-			None
+			None,
+			None,
+			None,
+			None,
 		));
 
 		let pool_data2 = LBPPallet::pool_data(pool_id2).unwrap();
@@ -2352,7 +2810,7 @@ fn sell_should_work() {
 				amount: 20_000,
 			}
 			.into(),
-			Event::SellExecuted(buyer, KUSD, BSX, 9_980_000, 5_605_137, KUSD, 20_000).into(),
+			Event::SellExecuted(buyer, KUSD, BSX, 9_980_000, 5_605_137, KUSD, 20_000, DEFAULT_FEE).into(),
 			Event::PoolCreated(pool_id2, pool_data1).into(),
 			frame_system::Event::NewAccount { account: pool_id2 }.into(),
 			orml_tokens::Event::Endowed {
@@ -2375,7 +2833,7 @@ fn sell_should_work() {
 				amount: 3_686,
 			}
 			.into(),
-			Event::SellExecuted(buyer, asset_out, asset_in, 10_000_000, 1_839_319, 0, 3_686).into(),
+			Event::SellExecuted(buyer, asset_out, asset_in, 10_000_000, 1_839_319, 0, 3_686, DEFAULT_FEE).into(),
 		]);
 	});
 }
@@ -2396,6 +2854,8 @@ fn zero_fee_should_work() {
 			(0, 100),
 			CHARLIE,
 			0,
+			TradeProtection::default(),
+			RepayFeeSchedule::default(),
 		));
 
 		assert_ok!(LBPPallet::update_pool_data(
@@ -2408,7 +2868,10 @@ fn zero_fee_should_work() {
 			None,
 			None,
 			None,
-			None
+			None,
+			None,
+			None,
+			None,
 		));
 
 		//start sale
@@ -2435,6 +2898,8 @@ fn invalid_fee_should_not_work() {
 				(10, 0),
 				CHARLIE,
 				0,
+				TradeProtection::default(),
+				RepayFeeSchedule::default(),
 			),
 			Error::<Test>::FeeAmountInvalid
 		);
@@ -2535,6 +3000,8 @@ fn amm_trait_should_work() {
 			(400, 1_000),
 			CHARLIE,
 			0,
+			TradeProtection::default(),
+			RepayFeeSchedule::default(),
 		));
 
 		let pool_id = LBPPallet::get_pair_id(AssetPair {
@@ -2564,6 +3031,8 @@ fn get_spot_price_should_work() {
 			DEFAULT_FEE,
 			CHARLIE,
 			0,
+			TradeProtection::default(),
+			RepayFeeSchedule::default(),
 		));
 
 		assert_ok!(LBPPallet::update_pool_data(
@@ -2576,7 +3045,10 @@ fn get_spot_price_should_work() {
 			None,
 			None,
 			None,
-			None
+			None,
+			None,
+			None,
+			None,
 		));
 
 		set_block_number::<Test>(10);
@@ -2718,6 +3190,8 @@ fn simulate_lbp_event_should_work() {
 			fee,
 			fee_collector,
 			0,
+			TradeProtection::default(),
+			RepayFeeSchedule::default(),
 		));
 
 		assert_ok!(LBPPallet::update_pool_data(
@@ -2730,7 +3204,10 @@ fn simulate_lbp_event_should_work() {
 			None,
 			None,
 			None,
-			None
+			None,
+			None,
+			None,
+			None,
 		));
 
 		set_block_number::<Test>(sale_start.checked_sub(1).unwrap());
@@ -3005,6 +3482,8 @@ fn validate_trade_should_not_work() {
 			(10, 1),
 			CHARLIE,
 			0,
+			TradeProtection::default(),
+			RepayFeeSchedule::default(),
 		));
 		let pool_id2 = LBPPallet::get_pair_id(AssetPair {
 			asset_in: KUSD,
@@ -3020,7 +3499,10 @@ fn validate_trade_should_not_work() {
 			None,
 			None,
 			None,
-			None
+			None,
+			None,
+			None,
+			None,
 		));
 	});
 }
@@ -3082,6 +3564,8 @@ fn calculate_fees_should_work() {
 			(10, 1),
 			CHARLIE,
 			0,
+			TradeProtection::default(),
+			RepayFeeSchedule::default(),
 		));
 
 		assert_ok!(LBPPallet::update_pool_data(
@@ -3095,6 +3579,9 @@ fn calculate_fees_should_work() {
 			None,
 			None,
 			None,
+			None,
+			None,
+			None,
 		));
 
 		let pool = LBPPallet::pool_data(HDX_BSX_POOL_ID).unwrap();
@@ -3132,6 +3619,8 @@ fn can_create_should_work() {
 			DEFAULT_FEE,
 			CHARLIE,
 			0,
+			TradeProtection::default(),
+			RepayFeeSchedule::default(),
 		));
 		// pool is not initialized
 		assert!(!DisallowWhenLBPPoolRunning::<Test>::can_create(
@@ -3150,6 +3639,9 @@ fn can_create_should_work() {
 			None,
 			None,
 			None,
+			None,
+			None,
+			None,
 		));
 		// pool is initialized but is not running
 		assert!(!DisallowWhenLBPPoolRunning::<Test>::can_create(
@@ -3185,7 +3677,9 @@ fn repay_fee_not_applied_when_set_to_zero() {
 	new_test_ext().execute_with(|| {
 		let pool = Pool {
 			repay_target: 0,
-			..SAMPLE_POOL_DATA
+			graduation: None,
+			trade_protection: TradeProtection::default(),
+			..sample_pool_data()
 		};
 		assert_eq!(LBPPallet::is_repay_fee_applied(&pool), false);
 	});
@@ -3196,7 +3690,9 @@ fn repay_fee_applied_when_set() {
 	new_test_ext().execute_with(|| {
 		let pool = Pool {
 			repay_target: 10_000_000,
-			..SAMPLE_POOL_DATA
+			graduation: None,
+			trade_protection: TradeProtection::default(),
+			..sample_pool_data()
 		};
 		assert_eq!(LBPPallet::is_repay_fee_applied(&pool), true);
 	});
@@ -3208,7 +3704,9 @@ fn repay_fee_not_applied_when_target_reached() {
 		let pool = Pool {
 			fee_collector: ALICE,
 			repay_target: INITIAL_BALANCE,
-			..SAMPLE_POOL_DATA
+			graduation: None,
+			trade_protection: TradeProtection::default(),
+			..sample_pool_data()
 		};
 		assert_ok!(Currency::set_lock(
 			COLLECTOR_LOCK_ID,
@@ -3248,7 +3746,17 @@ fn calculate_repay_fee() {
 #[test]
 fn repay_fee_should_work() {
 	new_test_ext().execute_with(|| {
-		assert_eq!(LBPPallet::repay_fee(), (2, 10));
+		assert_eq!(RepayFeeSchedule::default().fee, (2, 10));
+
+		let schedule = RepayFeeSchedule {
+			fee: (2, 10),
+			tiers: vec![(1_000, (1, 10)), (2_000, (5, 100))].try_into().unwrap(),
+		};
+		assert_eq!(schedule.fee_at(0), (2, 10));
+		assert_eq!(schedule.fee_at(999), (2, 10));
+		assert_eq!(schedule.fee_at(1_000), (1, 10));
+		assert_eq!(schedule.fee_at(1_999), (1, 10));
+		assert_eq!(schedule.fee_at(5_000), (5, 100));
 	});
 }
 
@@ -3349,6 +3857,8 @@ fn simulate_lbp_event_with_repayment() {
 			fee,
 			fee_collector,
 			0,
+			TradeProtection::default(),
+			RepayFeeSchedule::default(),
 		));
 
 		assert_ok!(LBPPallet::update_pool_data(
@@ -3361,7 +3871,10 @@ fn simulate_lbp_event_with_repayment() {
 			None,
 			None,
 			None,
-			None
+			None,
+			None,
+			None,
+			None,
 		));
 
 		set_block_number::<Test>(sale_start.checked_sub(1).unwrap());
@@ -3429,3 +3942,1771 @@ fn simulate_lbp_event_with_repayment() {
 		assert_eq!(Currency::free_balance(sold_asset, &fee_collector), 0);
 	});
 }
+
+#[test]
+fn sale_info_should_work() {
+	predefined_test_ext().execute_with(|| {
+		let pool = LBPPallet::pool_data(KUSD_BSX_POOL_ID).unwrap();
+
+		assert_eq!(
+			LBPPallet::sale_info(KUSD, BSX, None),
+			Some(SaleInfo {
+				pool_account: KUSD_BSX_POOL_ID,
+				pool: pool.clone(),
+				status: SaleStatus::NotStarted,
+				weights: (20_000_000, 80_000_000),
+				asset_a_reserve: 1_000_000_000,
+				asset_b_reserve: 2_000_000_000,
+				spot_price: Some(Price::saturating_from_integer(2)),
+				amount_raised: 0,
+				repay_target_remaining: 0,
+				fee_rate: DEFAULT_FEE,
+			})
+		);
+
+		// weights at the given block
+		let sale_info = LBPPallet::sale_info(BSX, KUSD, Some(25)).unwrap();
+		assert_eq!(sale_info.weights, (50_000_000, 50_000_000));
+		assert_eq!(sale_info.spot_price, Some(Price::from_float(0.5)));
+
+		set_block_number::<Test>(11);
+		assert_ok!(LBPPallet::buy(
+			Origin::signed(BOB),
+			BSX,
+			KUSD,
+			10_000_000_u128,
+			2_000_000_000_u128
+		));
+
+		let sale_info = LBPPallet::sale_info(KUSD, BSX, None).unwrap();
+		assert_eq!(sale_info.status, SaleStatus::Running);
+		assert_eq!(sale_info.weights, (22_000_000, 78_000_000));
+		assert_eq!(sale_info.asset_a_reserve, 1_017_894_737);
+		assert_eq!(sale_info.asset_b_reserve, 1_990_000_000);
+		assert_eq!(sale_info.amount_raised, 17_894_737);
+
+		run_to_sale_end();
+
+		let sale_info = LBPPallet::sale_info(KUSD, BSX, None).unwrap();
+		assert_eq!(sale_info.status, SaleStatus::Ended);
+		assert_eq!(sale_info.weights, (80_000_000, 20_000_000));
+
+		assert_ok!(LBPPallet::remove_liquidity(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+		assert_eq!(LBPPallet::sale_info(KUSD, BSX, None), None);
+		assert_eq!(LBPPallet::amount_raised(KUSD_BSX_POOL_ID), 0);
+	});
+}
+
+#[test]
+fn sale_info_should_return_remaining_repay_target() {
+	predefined_test_ext_with_repay_target().execute_with(|| {
+		run_to_sale_start();
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 10_000_000_u128, 1_u128));
+
+		let sale_info = LBPPallet::sale_info(KUSD, BSX, None).unwrap();
+		assert_eq!(sale_info.repay_target_remaining, 1_000_000_000 - 2_000_000);
+		assert_eq!(sale_info.fee_rate, (2, 10));
+		assert_eq!(sale_info.amount_raised, 8_000_000);
+	});
+}
+
+#[test]
+fn quote_sell_should_match_executed_trade() {
+	predefined_test_ext_with_repay_target().execute_with(|| {
+		assert_eq!(LBPPallet::quote_sell(KUSD, BSX, 10_000_000), None);
+
+		run_to_sale_start();
+
+		// accumulated asset sold, repay fee applied
+		let quote = LBPPallet::quote_sell(KUSD, BSX, 10_000_000).unwrap();
+		assert_eq!(quote.fee, 2_000_000);
+		assert_eq!(quote.fee_asset, KUSD);
+		assert_eq!(quote.fee_rate, (2, 10));
+		assert!(quote.repay_fee_applied);
+		assert!(!quote.max_in_ratio_exceeded);
+		assert!(!quote.max_out_ratio_exceeded);
+
+		let bsx_balance = Currency::free_balance(BSX, &BOB);
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 10_000_000_u128, 1_u128));
+		assert_eq!(Currency::free_balance(BSX, &BOB) - bsx_balance, quote.amount);
+
+		// distributed asset sold
+		let quote = LBPPallet::quote_sell(BSX, KUSD, 10_000_000).unwrap();
+		let kusd_balance = Currency::free_balance(KUSD, &BOB);
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), BSX, KUSD, 10_000_000_u128, 1_u128));
+		assert_eq!(Currency::free_balance(KUSD, &BOB) - kusd_balance, quote.amount);
+		expect_events(vec![Event::SellExecuted(
+			BOB,
+			BSX,
+			KUSD,
+			10_000_000,
+			quote.amount,
+			KUSD,
+			quote.fee,
+			quote.fee_rate,
+		)
+		.into()]);
+
+		assert!(
+			LBPPallet::quote_sell(KUSD, BSX, 11_000_000_000)
+				.unwrap()
+				.max_in_ratio_exceeded
+		);
+	});
+}
+
+#[test]
+fn quote_buy_should_match_executed_trade() {
+	predefined_test_ext().execute_with(|| {
+		set_block_number::<Test>(11);
+
+		// distributed asset bought
+		assert_eq!(
+			LBPPallet::quote_buy(BSX, KUSD, 10_000_000),
+			Some(TradeQuote {
+				amount: 17_930_597,
+				fee: 35_860,
+				fee_asset: KUSD,
+				fee_rate: DEFAULT_FEE,
+				repay_fee_applied: false,
+				max_in_ratio_exceeded: false,
+				max_out_ratio_exceeded: false,
+			})
+		);
+
+		assert_ok!(LBPPallet::buy(
+			Origin::signed(BOB),
+			BSX,
+			KUSD,
+			10_000_000_u128,
+			2_000_000_000_u128
+		));
+		assert_eq!(Currency::free_balance(KUSD, &BOB), INITIAL_BALANCE - 17_930_597);
+
+		// accumulated asset bought
+		let quote = LBPPallet::quote_buy(KUSD, BSX, 1_000_000).unwrap();
+		let bsx_balance = Currency::free_balance(BSX, &BOB);
+		assert_ok!(LBPPallet::buy(
+			Origin::signed(BOB),
+			KUSD,
+			BSX,
+			1_000_000_u128,
+			2_000_000_000_u128
+		));
+		assert_eq!(bsx_balance - Currency::free_balance(BSX, &BOB), quote.amount);
+		expect_events(vec![Event::BuyExecuted(
+			BOB,
+			KUSD,
+			BSX,
+			quote.amount,
+			1_000_000,
+			KUSD,
+			quote.fee,
+			quote.fee_rate,
+		)
+		.into()]);
+
+		assert!(
+			LBPPallet::quote_buy(BSX, KUSD, 1_000_000_000)
+				.unwrap()
+				.max_out_ratio_exceeded
+		);
+		assert_eq!(LBPPallet::quote_buy(BSX, HDX, 1_000_000), None);
+	});
+}
+
+#[test]
+fn set_graduation_should_work() {
+	predefined_test_ext().execute_with(|| {
+		let graduation = Graduation {
+			fraction: Permill::from_percent(50),
+			lock_period: 10,
+		};
+
+		assert_ok!(LBPPallet::set_graduation(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			Some(graduation)
+		));
+
+		let pool_data = LBPPallet::pool_data(KUSD_BSX_POOL_ID).unwrap();
+		assert_eq!(pool_data.graduation, Some(graduation));
+		assert_eq!(
+			LBPPallet::scheduled_graduations().into_inner(),
+			vec![(SALE_END.unwrap(), KUSD_BSX_POOL_ID)]
+		);
+		expect_events(vec![Event::PoolUpdated(KUSD_BSX_POOL_ID, pool_data).into()]);
+
+		// graduation is rescheduled when the end of the sale changes
+		assert_ok!(LBPPallet::update_pool_data(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			None,
+			None,
+			Some(50),
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+		));
+		assert_eq!(
+			LBPPallet::scheduled_graduations().into_inner(),
+			vec![(50, KUSD_BSX_POOL_ID)]
+		);
+
+		assert_ok!(LBPPallet::set_graduation(Origin::signed(ALICE), KUSD_BSX_POOL_ID, None));
+		assert_eq!(LBPPallet::pool_data(KUSD_BSX_POOL_ID).unwrap().graduation, None);
+		assert!(LBPPallet::scheduled_graduations().is_empty());
+
+		// removed pool is not graduated
+		assert_ok!(LBPPallet::set_graduation(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			Some(graduation)
+		));
+		assert_ok!(LBPPallet::remove_liquidity(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+		assert!(LBPPallet::scheduled_graduations().is_empty());
+	});
+}
+
+#[test]
+fn set_graduation_should_not_work() {
+	predefined_test_ext().execute_with(|| {
+		let graduation = Graduation {
+			fraction: Permill::from_percent(50),
+			lock_period: 0,
+		};
+
+		assert_noop!(
+			LBPPallet::set_graduation(Origin::signed(BOB), KUSD_BSX_POOL_ID, Some(graduation)),
+			Error::<Test>::NotOwner
+		);
+
+		assert_noop!(
+			LBPPallet::set_graduation(Origin::signed(ALICE), HDX_BSX_POOL_ID, Some(graduation)),
+			Error::<Test>::PoolNotFound
+		);
+
+		assert_noop!(
+			LBPPallet::set_graduation(
+				Origin::signed(ALICE),
+				KUSD_BSX_POOL_ID,
+				Some(Graduation {
+					fraction: Permill::zero(),
+					lock_period: 0,
+				})
+			),
+			Error::<Test>::InvalidGraduation
+		);
+
+		run_to_sale_start();
+
+		assert_noop!(
+			LBPPallet::set_graduation(Origin::signed(ALICE), KUSD_BSX_POOL_ID, Some(graduation)),
+			Error::<Test>::SaleStarted
+		);
+	});
+}
+
+#[test]
+fn pool_should_graduate_when_sale_ends() {
+	predefined_test_ext().execute_with(|| {
+		assert_ok!(LBPPallet::set_graduation(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			Some(Graduation {
+				fraction: Permill::from_percent(50),
+				lock_period: 0,
+			})
+		));
+
+		let kusd_balance = Currency::free_balance(KUSD, &ALICE);
+		let bsx_balance = Currency::free_balance(BSX, &ALICE);
+
+		// nothing happens at the end block
+		set_block_number::<Test>(SALE_END.unwrap());
+		LBPPallet::on_initialize(SALE_END.unwrap());
+		assert_eq!(LBPPallet::scheduled_graduations().len(), 1);
+		assert_eq!(Currency::free_balance(KUSD, &KUSD_BSX_POOL_ID), 1_000_000_000);
+
+		run_to_sale_end();
+		assert!(!DisallowWhenLBPPoolRunning::<Test>::can_create(KUSD, BSX));
+
+		LBPPallet::on_initialize(SALE_END.unwrap() + 1);
+
+		// final weights are 80% and 20%, so half of the BSX reserve is paired with a quarter of the KUSD half
+		assert_eq!(Currency::free_balance(KUSD, &XYK_POOL_ID), 125_000_000);
+		assert_eq!(Currency::free_balance(BSX, &XYK_POOL_ID), 1_000_000_000);
+		assert_eq!(Currency::free_balance(KUSD, &KUSD_BSX_POOL_ID), 875_000_000);
+		assert_eq!(Currency::free_balance(BSX, &KUSD_BSX_POOL_ID), 1_000_000_000);
+
+		assert_eq!(Currency::free_balance(KUSD, &ALICE), kusd_balance);
+		assert_eq!(Currency::free_balance(BSX, &ALICE), bsx_balance);
+		assert_eq!(Currency::free_balance(SHARE_TOKEN, &ALICE), 125_000_000);
+		assert!(Currency::locks(ALICE, SHARE_TOKEN).is_empty());
+
+		assert!(LBPPallet::scheduled_graduations().is_empty());
+		assert!(DisallowWhenLBPPoolRunning::<Test>::can_create(KUSD, BSX));
+
+		expect_events(vec![Event::PoolGraduated(
+			KUSD_BSX_POOL_ID,
+			SHARE_TOKEN,
+			125_000_000,
+			1_000_000_000,
+			125_000_000,
+		)
+		.into()]);
+
+		// rest of the reserves goes back to the owner
+		assert_ok!(LBPPallet::remove_liquidity(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+		assert_eq!(Currency::free_balance(KUSD, &ALICE), kusd_balance + 875_000_000);
+		assert_eq!(Currency::free_balance(BSX, &ALICE), bsx_balance + 1_000_000_000);
+	});
+}
+
+#[test]
+fn graduation_shares_should_be_locked_for_lock_period() {
+	predefined_test_ext().execute_with(|| {
+		assert_ok!(LBPPallet::set_graduation(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			Some(Graduation {
+				fraction: Permill::one(),
+				lock_period: 10,
+			})
+		));
+
+		run_to_sale_end();
+		LBPPallet::on_initialize(SALE_END.unwrap() + 1);
+
+		let shares = Currency::free_balance(SHARE_TOKEN, &ALICE);
+		assert_eq!(shares, 250_000_000);
+		assert_eq!(
+			<Test as pallet::Config>::LockedBalance::get_by_lock(GRADUATION_LOCK_ID, SHARE_TOKEN, ALICE),
+			shares
+		);
+		assert_eq!(
+			LBPPallet::graduation_shares_unlock(ALICE, SHARE_TOKEN),
+			Some(SALE_END.unwrap() + 11)
+		);
+
+		set_block_number::<Test>(SALE_END.unwrap() + 10);
+		assert_noop!(
+			LBPPallet::unlock_graduation_shares(Origin::signed(BOB), ALICE, SHARE_TOKEN),
+			Error::<Test>::SharesStillLocked
+		);
+
+		set_block_number::<Test>(SALE_END.unwrap() + 11);
+		assert_ok!(LBPPallet::unlock_graduation_shares(
+			Origin::signed(BOB),
+			ALICE,
+			SHARE_TOKEN
+		));
+		assert!(Currency::locks(ALICE, SHARE_TOKEN).is_empty());
+		assert_eq!(LBPPallet::graduation_shares_unlock(ALICE, SHARE_TOKEN), None);
+		expect_events(vec![Event::GraduationSharesUnlocked(ALICE, SHARE_TOKEN).into()]);
+
+		assert_noop!(
+			LBPPallet::unlock_graduation_shares(Origin::signed(BOB), ALICE, SHARE_TOKEN),
+			Error::<Test>::SharesNotLocked
+		);
+	});
+}
+
+#[test]
+fn failed_graduation_should_keep_reserves_in_pool() {
+	new_test_ext().execute_with(|| {
+		let pool_id = LBPPallet::pair_account_from_assets(ETH, BSX);
+
+		assert_ok!(LBPPallet::create_pool(
+			Origin::root(),
+			ALICE,
+			ETH,
+			1_000_000_000,
+			BSX,
+			2_000_000_000,
+			20_000_000,
+			80_000_000,
+			WeightCurveType::Linear,
+			DEFAULT_FEE,
+			CHARLIE,
+			0,
+			TradeProtection::default(),
+			RepayFeeSchedule::default(),
+		));
+		assert_ok!(LBPPallet::update_pool_data(
+			Origin::signed(ALICE),
+			pool_id,
+			None,
+			SALE_START,
+			SALE_END,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+		));
+		assert_ok!(LBPPallet::set_graduation(
+			Origin::signed(ALICE),
+			pool_id,
+			Some(Graduation {
+				fraction: Permill::from_percent(50),
+				lock_period: 0,
+			})
+		));
+
+		run_to_sale_end();
+		LBPPallet::on_initialize(SALE_END.unwrap() + 1);
+
+		expect_events(vec![Event::GraduationFailed(
+			pool_id,
+			Error::<Test>::PoolAlreadyExists.into(),
+		)
+		.into()]);
+		assert_eq!(Currency::free_balance(ETH, &pool_id), 1_000_000_000);
+		assert_eq!(Currency::free_balance(BSX, &pool_id), 2_000_000_000);
+		assert!(LBPPallet::scheduled_graduations().is_empty());
+		assert!(DisallowWhenLBPPoolRunning::<Test>::can_create(ETH, BSX));
+	});
+}
+
+#[test]
+fn pause_sale_should_work() {
+	predefined_test_ext().execute_with(|| {
+		set_block_number::<Test>(20);
+
+		assert_ok!(LBPPallet::pause_sale(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+
+		assert_eq!(LBPPallet::paused_sale(KUSD_BSX_POOL_ID), Some((20, false)));
+		expect_events(vec![Event::SalePaused(KUSD_BSX_POOL_ID, 20).into()]);
+
+		assert_noop!(
+			LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 10_000_000, 1),
+			Error::<Test>::SaleIsPaused
+		);
+		assert_noop!(
+			LBPPallet::buy(Origin::signed(BOB), BSX, KUSD, 10_000_000, 2_000_000_000),
+			Error::<Test>::SaleIsPaused
+		);
+		assert_eq!(LBPPallet::quote_sell(KUSD, BSX, 10_000_000), None);
+		assert_eq!(LBPPallet::quote_buy(BSX, KUSD, 10_000_000), None);
+
+		// weights stay at the point the sale was paused
+		set_block_number::<Test>(30);
+		let sale_info = LBPPallet::sale_info(KUSD, BSX, None).unwrap();
+		assert_eq!(sale_info.status, SaleStatus::Paused);
+		assert_eq!(sale_info.weights, (40_000_000, 60_000_000));
+
+		// paused sale can't be destroyed or replaced by another pool after its end
+		set_block_number::<Test>(50);
+		assert_noop!(
+			LBPPallet::remove_liquidity(Origin::signed(ALICE), KUSD_BSX_POOL_ID),
+			Error::<Test>::SaleIsPaused
+		);
+		assert!(!DisallowWhenLBPPoolRunning::<Test>::can_create(KUSD, BSX));
+	});
+}
+
+#[test]
+fn resume_sale_should_shift_sale() {
+	predefined_test_ext().execute_with(|| {
+		assert_ok!(LBPPallet::set_graduation(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			Some(Graduation {
+				fraction: Permill::from_percent(50),
+				lock_period: 0,
+			})
+		));
+
+		set_block_number::<Test>(20);
+		assert_ok!(LBPPallet::pause_sale(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+		assert!(LBPPallet::scheduled_graduations().is_empty());
+
+		set_block_number::<Test>(30);
+		assert_ok!(LBPPallet::resume_sale(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+
+		let pool_data = LBPPallet::pool_data(KUSD_BSX_POOL_ID).unwrap();
+		assert_eq!(pool_data.start, Some(20));
+		assert_eq!(pool_data.end, Some(50));
+		assert_eq!(LBPPallet::paused_sale(KUSD_BSX_POOL_ID), None);
+		assert_eq!(
+			LBPPallet::scheduled_graduations().into_inner(),
+			vec![(50, KUSD_BSX_POOL_ID)]
+		);
+		expect_events(vec![Event::SaleResumed(KUSD_BSX_POOL_ID, 20, 50).into()]);
+
+		let sale_info = LBPPallet::sale_info(KUSD, BSX, None).unwrap();
+		assert_eq!(sale_info.status, SaleStatus::Running);
+		assert_eq!(sale_info.weights, (40_000_000, 60_000_000));
+
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 10_000_000, 1));
+
+		// sale is running after its original end
+		set_block_number::<Test>(45);
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 10_000_000, 1));
+	});
+}
+
+#[test]
+fn pause_and_resume_sale_should_not_work() {
+	predefined_test_ext().execute_with(|| {
+		assert_noop!(
+			LBPPallet::pause_sale(Origin::signed(ALICE), KUSD_BSX_POOL_ID),
+			Error::<Test>::SaleIsNotRunning
+		);
+
+		run_to_sale_start();
+
+		assert_noop!(
+			LBPPallet::pause_sale(Origin::signed(ALICE), HDX_BSX_POOL_ID),
+			Error::<Test>::PoolNotFound
+		);
+		assert_noop!(
+			LBPPallet::pause_sale(Origin::signed(BOB), KUSD_BSX_POOL_ID),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			LBPPallet::resume_sale(Origin::signed(ALICE), KUSD_BSX_POOL_ID),
+			Error::<Test>::SaleIsNotPaused
+		);
+
+		assert_ok!(LBPPallet::pause_sale(Origin::root(), KUSD_BSX_POOL_ID));
+		assert_eq!(
+			LBPPallet::paused_sale(KUSD_BSX_POOL_ID),
+			Some((SALE_START.unwrap(), true))
+		);
+
+		assert_noop!(
+			LBPPallet::pause_sale(Origin::signed(ALICE), KUSD_BSX_POOL_ID),
+			Error::<Test>::SaleIsPaused
+		);
+		assert_noop!(
+			LBPPallet::resume_sale(Origin::signed(ALICE), KUSD_BSX_POOL_ID),
+			Error::<Test>::PausedByEmergencyOrigin
+		);
+
+		assert_ok!(LBPPallet::resume_sale(Origin::root(), KUSD_BSX_POOL_ID));
+	});
+}
+
+#[test]
+fn cancel_sale_should_work() {
+	predefined_test_ext().execute_with(|| {
+		run_to_sale_start();
+
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 10_000_000, 1));
+		assert!(!Currency::locks(CHARLIE, KUSD).is_empty());
+
+		assert_ok!(LBPPallet::pause_sale(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+
+		assert_noop!(
+			LBPPallet::cancel_sale(Origin::signed(ALICE), KUSD_BSX_POOL_ID),
+			BadOrigin
+		);
+
+		let kusd_reserve = Currency::free_balance(KUSD, &KUSD_BSX_POOL_ID);
+		let bsx_reserve = Currency::free_balance(BSX, &KUSD_BSX_POOL_ID);
+		let kusd_balance = Currency::free_balance(KUSD, &ALICE);
+		let bsx_balance = Currency::free_balance(BSX, &ALICE);
+
+		assert_ok!(LBPPallet::cancel_sale(Origin::root(), KUSD_BSX_POOL_ID));
+
+		assert_eq!(Currency::free_balance(KUSD, &ALICE), kusd_balance + kusd_reserve);
+		assert_eq!(Currency::free_balance(BSX, &ALICE), bsx_balance + bsx_reserve);
+		assert_eq!(Currency::free_balance(KUSD, &KUSD_BSX_POOL_ID), 0);
+		assert_eq!(Currency::free_balance(BSX, &KUSD_BSX_POOL_ID), 0);
+
+		assert_eq!(LBPPallet::pool_data(KUSD_BSX_POOL_ID), None);
+		assert_eq!(LBPPallet::paused_sale(KUSD_BSX_POOL_ID), None);
+		assert!(Currency::locks(CHARLIE, KUSD).is_empty());
+		assert!(!FeeCollectorWithAsset::<Test>::contains_key(CHARLIE, KUSD));
+
+		expect_events(vec![
+			Event::SaleCancelled(KUSD_BSX_POOL_ID, kusd_reserve, bsx_reserve).into()
+		]);
+
+		assert_noop!(
+			LBPPallet::cancel_sale(Origin::root(), KUSD_BSX_POOL_ID),
+			Error::<Test>::PoolNotFound
+		);
+	});
+}
+
+#[test]
+fn set_purchase_restrictions_should_work() {
+	predefined_test_ext().execute_with(|| {
+		let restrictions = PurchaseRestrictions {
+			allowlist_only: true,
+			max_purchase: Some(1_000_000),
+		};
+
+		assert_ok!(LBPPallet::set_purchase_restrictions(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			restrictions
+		));
+
+		assert_eq!(LBPPallet::purchase_restrictions(KUSD_BSX_POOL_ID), Some(restrictions));
+
+		assert_ok!(LBPPallet::set_purchase_restrictions(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			PurchaseRestrictions::default()
+		));
+
+		assert_eq!(LBPPallet::purchase_restrictions(KUSD_BSX_POOL_ID), None);
+
+		expect_events(vec![
+			Event::PurchaseRestrictionsSet(KUSD_BSX_POOL_ID, restrictions).into(),
+			Event::PurchaseRestrictionsSet(KUSD_BSX_POOL_ID, PurchaseRestrictions::default()).into(),
+		]);
+	});
+}
+
+#[test]
+fn set_purchase_restrictions_should_not_work() {
+	predefined_test_ext().execute_with(|| {
+		let restrictions = PurchaseRestrictions {
+			allowlist_only: true,
+			max_purchase: None,
+		};
+
+		assert_noop!(
+			LBPPallet::set_purchase_restrictions(Origin::signed(BOB), KUSD_BSX_POOL_ID, restrictions),
+			Error::<Test>::NotOwner
+		);
+
+		assert_noop!(
+			LBPPallet::set_purchase_restrictions(Origin::signed(ALICE), ALICE, restrictions),
+			Error::<Test>::PoolNotFound
+		);
+
+		run_to_sale_start();
+
+		assert_noop!(
+			LBPPallet::set_purchase_restrictions(Origin::signed(ALICE), KUSD_BSX_POOL_ID, restrictions),
+			Error::<Test>::SaleStarted
+		);
+	});
+}
+
+#[test]
+fn allowlist_should_work() {
+	predefined_test_ext().execute_with(|| {
+		assert_noop!(
+			LBPPallet::add_to_allowlist(Origin::signed(BOB), KUSD_BSX_POOL_ID, vec![BOB]),
+			Error::<Test>::NotOwner
+		);
+
+		assert_ok!(LBPPallet::add_to_allowlist(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			vec![BOB, CHARLIE]
+		));
+		assert_ok!(LBPPallet::add_to_allowlist(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			vec![BOB]
+		));
+
+		assert_eq!(LBPPallet::allowlist_size(KUSD_BSX_POOL_ID), 2);
+		assert!(Allowlist::<Test>::contains_key(KUSD_BSX_POOL_ID, BOB));
+		assert!(Allowlist::<Test>::contains_key(KUSD_BSX_POOL_ID, CHARLIE));
+
+		assert_noop!(
+			LBPPallet::remove_from_allowlist(Origin::signed(BOB), KUSD_BSX_POOL_ID, vec![BOB]),
+			Error::<Test>::NotOwner
+		);
+
+		assert_ok!(LBPPallet::remove_from_allowlist(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			vec![BOB, ALICE]
+		));
+
+		assert_eq!(LBPPallet::allowlist_size(KUSD_BSX_POOL_ID), 1);
+		assert!(!Allowlist::<Test>::contains_key(KUSD_BSX_POOL_ID, BOB));
+
+		let accounts: Vec<u64> = (100..100 + MAX_ALLOWLIST_SIZE as u64).collect();
+		assert_noop!(
+			LBPPallet::add_to_allowlist(Origin::signed(ALICE), KUSD_BSX_POOL_ID, accounts),
+			Error::<Test>::AllowlistFull
+		);
+
+		expect_events(vec![
+			Event::AllowlistAdded(KUSD_BSX_POOL_ID, vec![BOB, CHARLIE]).into(),
+			Event::AllowlistAdded(KUSD_BSX_POOL_ID, vec![BOB]).into(),
+			Event::AllowlistRemoved(KUSD_BSX_POOL_ID, vec![BOB, ALICE]).into(),
+		]);
+	});
+}
+
+#[test]
+fn allowlist_should_restrict_purchases() {
+	predefined_test_ext().execute_with(|| {
+		assert_ok!(LBPPallet::set_purchase_restrictions(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			PurchaseRestrictions {
+				allowlist_only: true,
+				max_purchase: None,
+			}
+		));
+		assert_ok!(LBPPallet::add_to_allowlist(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			vec![ALICE]
+		));
+
+		assert_eq!(
+			LBPPallet::remaining_allowance(KUSD, BSX, &BOB, None),
+			Some(Allowance {
+				allowed: false,
+				remaining: None,
+			})
+		);
+
+		run_to_sale_start();
+
+		assert_noop!(
+			LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 1_000_000, 1),
+			Error::<Test>::NotAllowlisted
+		);
+		assert_noop!(
+			LBPPallet::buy(Origin::signed(BOB), BSX, KUSD, 1_000_000, 10_000_000),
+			Error::<Test>::NotAllowlisted
+		);
+
+		// selling the distributed asset back to the pool is not restricted
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), BSX, KUSD, 1_000_000, 1));
+
+		assert_ok!(LBPPallet::sell(Origin::signed(ALICE), KUSD, BSX, 1_000_000, 1));
+
+		assert_ok!(LBPPallet::add_to_allowlist(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			vec![BOB]
+		));
+
+		assert_eq!(
+			LBPPallet::remaining_allowance(KUSD, BSX, &BOB, None),
+			Some(Allowance {
+				allowed: true,
+				remaining: None,
+			})
+		);
+
+		assert_ok!(LBPPallet::buy(Origin::signed(BOB), BSX, KUSD, 1_000_000, 10_000_000));
+	});
+}
+
+#[test]
+fn max_purchase_should_be_enforced() {
+	predefined_test_ext().execute_with(|| {
+		let max_purchase = 3_000_000;
+
+		assert_ok!(LBPPallet::set_purchase_restrictions(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			PurchaseRestrictions {
+				allowlist_only: false,
+				max_purchase: Some(max_purchase),
+			}
+		));
+
+		run_to_sale_start();
+
+		assert_ok!(LBPPallet::buy(Origin::signed(BOB), BSX, KUSD, 2_000_000, 10_000_000));
+
+		assert_eq!(LBPPallet::purchased(KUSD_BSX_POOL_ID, BOB), 2_000_000);
+		assert_eq!(
+			LBPPallet::remaining_allowance(KUSD, BSX, &BOB, None),
+			Some(Allowance {
+				allowed: true,
+				remaining: Some(1_000_000),
+			})
+		);
+
+		assert_noop!(
+			LBPPallet::buy(Origin::signed(BOB), BSX, KUSD, 1_000_001, 10_000_000),
+			Error::<Test>::MaxPurchaseExceeded
+		);
+
+		// sold amount of the accumulated asset yields more than the remaining allowance
+		assert_noop!(
+			LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 3_000_000, 1),
+			Error::<Test>::MaxPurchaseExceeded
+		);
+
+		assert_ok!(LBPPallet::buy(Origin::signed(BOB), BSX, KUSD, 1_000_000, 10_000_000));
+		assert_eq!(
+			LBPPallet::remaining_allowance(KUSD, BSX, &BOB, None),
+			Some(Allowance {
+				allowed: true,
+				remaining: Some(0),
+			})
+		);
+
+		// allowance is per account
+		assert_ok!(LBPPallet::buy(Origin::signed(ALICE), BSX, KUSD, 2_000_000, 10_000_000));
+
+		run_to_sale_end();
+
+		assert_ok!(LBPPallet::remove_liquidity(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+
+		assert_eq!(LBPPallet::purchased(KUSD_BSX_POOL_ID, BOB), 0);
+		assert_eq!(LBPPallet::purchase_restrictions(KUSD_BSX_POOL_ID), None);
+		assert_eq!(LBPPallet::remaining_allowance(KUSD, BSX, &BOB, None), None);
+	});
+}
+
+#[test]
+fn invalid_trade_protection_should_not_work() {
+	predefined_test_ext().execute_with(|| {
+		let invalid_protections = vec![
+			TradeProtection {
+				max_buy_per_block: Some(0),
+				..Default::default()
+			},
+			TradeProtection {
+				cooldown: Some(0),
+				..Default::default()
+			},
+			TradeProtection {
+				opening_fee: Some(((10, 0), 5)),
+				..Default::default()
+			},
+			TradeProtection {
+				opening_fee: Some(((10, 100), 0)),
+				..Default::default()
+			},
+		];
+
+		for protection in invalid_protections {
+			assert_noop!(
+				LBPPallet::create_pool(
+					Origin::root(),
+					ALICE,
+					HDX,
+					1_000_000_000,
+					ETH,
+					2_000_000_000,
+					20_000_000,
+					80_000_000,
+					WeightCurveType::Linear,
+					DEFAULT_FEE,
+					BOB,
+					0,
+					protection,
+					RepayFeeSchedule::default(),
+				),
+				Error::<Test>::InvalidTradeProtection
+			);
+
+			assert_noop!(
+				LBPPallet::update_pool_data(
+					Origin::signed(ALICE),
+					KUSD_BSX_POOL_ID,
+					None,
+					None,
+					None,
+					None,
+					None,
+					None,
+					None,
+					None,
+					None,
+					Some(protection),
+					None,
+				),
+				Error::<Test>::InvalidTradeProtection
+			);
+		}
+	});
+}
+
+#[test]
+fn update_trade_protection_should_work() {
+	predefined_test_ext().execute_with(|| {
+		let protection = TradeProtection {
+			max_buy_per_block: Some(1_000_000),
+			cooldown: Some(2),
+			opening_fee: Some(((10, 100), 5)),
+		};
+
+		assert_ok!(LBPPallet::update_pool_data(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			Some(protection),
+			None,
+		));
+
+		let pool_data = LBPPallet::pool_data(KUSD_BSX_POOL_ID).unwrap();
+		assert_eq!(pool_data.trade_protection, protection);
+
+		expect_events(vec![Event::PoolUpdated(KUSD_BSX_POOL_ID, pool_data).into()]);
+	});
+}
+
+#[test]
+fn max_buy_per_block_should_be_enforced() {
+	predefined_test_ext().execute_with(|| {
+		assert_ok!(LBPPallet::update_pool_data(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			Some(TradeProtection {
+				max_buy_per_block: Some(3_000_000),
+				..Default::default()
+			}),
+			None,
+		));
+
+		run_to_sale_start();
+
+		assert_ok!(LBPPallet::buy(Origin::signed(BOB), BSX, KUSD, 2_000_000, 10_000_000));
+
+		assert_noop!(
+			LBPPallet::buy(Origin::signed(BOB), BSX, KUSD, 1_000_001, 10_000_000),
+			Error::<Test>::MaxBuyPerBlockExceeded
+		);
+
+		// selling the distributed asset is not limited
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), BSX, KUSD, 5_000_000, 1));
+
+		// limit is per account
+		assert_ok!(LBPPallet::buy(Origin::signed(ALICE), BSX, KUSD, 3_000_000, 10_000_000));
+
+		set_block_number::<Test>(SALE_START.unwrap() + 1);
+
+		assert_ok!(LBPPallet::buy(Origin::signed(BOB), BSX, KUSD, 3_000_000, 10_000_000));
+		assert_eq!(
+			LBPPallet::trade_activity(KUSD_BSX_POOL_ID, BOB),
+			Some((SALE_START.unwrap() + 1, 3_000_000))
+		);
+	});
+}
+
+#[test]
+fn trade_cooldown_should_be_enforced() {
+	predefined_test_ext().execute_with(|| {
+		assert_ok!(LBPPallet::update_pool_data(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			Some(TradeProtection {
+				cooldown: Some(3),
+				..Default::default()
+			}),
+			None,
+		));
+
+		run_to_sale_start();
+
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 1_000_000, 1));
+
+		assert_noop!(
+			LBPPallet::sell(Origin::signed(BOB), BSX, KUSD, 1_000_000, 1),
+			Error::<Test>::TradeCooldownNotPassed
+		);
+
+		set_block_number::<Test>(SALE_START.unwrap() + 2);
+
+		assert_noop!(
+			LBPPallet::buy(Origin::signed(BOB), BSX, KUSD, 1_000_000, 10_000_000),
+			Error::<Test>::TradeCooldownNotPassed
+		);
+
+		// cool-down is per account
+		assert_ok!(LBPPallet::sell(Origin::signed(ALICE), KUSD, BSX, 1_000_000, 1));
+
+		set_block_number::<Test>(SALE_START.unwrap() + 3);
+
+		assert_ok!(LBPPallet::buy(Origin::signed(BOB), BSX, KUSD, 1_000_000, 10_000_000));
+	});
+}
+
+#[test]
+fn opening_fee_should_be_applied_during_first_blocks() {
+	predefined_test_ext().execute_with(|| {
+		assert_ok!(LBPPallet::update_pool_data(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			Some(TradeProtection {
+				opening_fee: Some(((10, 100), 5)),
+				..Default::default()
+			}),
+			None,
+		));
+
+		run_to_sale_start();
+
+		assert_eq!(LBPPallet::quote_sell(KUSD, BSX, 1_000_000).unwrap().fee_rate, (10, 100));
+
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 1_000_000, 1));
+		assert_eq!(Currency::free_balance(KUSD, &CHARLIE), 100_000);
+
+		set_block_number::<Test>(SALE_START.unwrap() + 4);
+
+		assert_eq!(LBPPallet::quote_sell(KUSD, BSX, 1_000_000).unwrap().fee_rate, (10, 100));
+
+		set_block_number::<Test>(SALE_START.unwrap() + 5);
+
+		assert_eq!(
+			LBPPallet::quote_sell(KUSD, BSX, 1_000_000).unwrap().fee_rate,
+			DEFAULT_FEE
+		);
+
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 1_000_000, 1));
+		assert_eq!(Currency::free_balance(KUSD, &CHARLIE), 102_000);
+	});
+}
+
+#[test]
+fn higher_of_opening_fee_and_repay_fee_should_be_applied() {
+	let update_fees = |opening_fee: (u32, u32), repay_fee: (u32, u32)| {
+		assert_ok!(LBPPallet::update_pool_data(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			Some(1_000_000_000),
+			Some(TradeProtection {
+				opening_fee: Some((opening_fee, 5)),
+				..Default::default()
+			}),
+			Some(RepayFeeSchedule {
+				fee: repay_fee,
+				tiers: Default::default(),
+			}),
+		));
+	};
+
+	predefined_test_ext().execute_with(|| {
+		update_fees((10, 100), (5, 100));
+
+		run_to_sale_start();
+
+		// opening fee is higher
+		assert_eq!(LBPPallet::quote_sell(KUSD, BSX, 1_000_000).unwrap().fee_rate, (10, 100));
+
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 1_000_000, 1));
+		assert_eq!(Currency::free_balance(KUSD, &CHARLIE), 100_000);
+
+		// repay fee is still charged after the opening blocks
+		set_block_number::<Test>(SALE_START.unwrap() + 5);
+
+		assert_eq!(LBPPallet::quote_sell(KUSD, BSX, 1_000_000).unwrap().fee_rate, (5, 100));
+
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 1_000_000, 1));
+		assert_eq!(Currency::free_balance(KUSD, &CHARLIE), 150_000);
+	});
+
+	predefined_test_ext().execute_with(|| {
+		update_fees((3, 100), (5, 100));
+
+		run_to_sale_start();
+
+		// repay fee is higher
+		assert_eq!(LBPPallet::quote_sell(KUSD, BSX, 1_000_000).unwrap().fee_rate, (5, 100));
+
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 1_000_000, 1));
+		assert_eq!(Currency::free_balance(KUSD, &CHARLIE), 50_000);
+	});
+}
+
+#[test]
+fn set_vesting_should_work() {
+	predefined_test_ext().execute_with(|| {
+		let vesting = VestingSchedule {
+			cliff: 10,
+			duration: 20,
+		};
+
+		assert_ok!(LBPPallet::set_vesting(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			Some(vesting)
+		));
+		assert_eq!(LBPPallet::sale_vesting(KUSD_BSX_POOL_ID), Some(vesting));
+
+		assert_ok!(LBPPallet::set_vesting(Origin::signed(ALICE), KUSD_BSX_POOL_ID, None));
+		assert_eq!(LBPPallet::sale_vesting(KUSD_BSX_POOL_ID), None);
+
+		expect_events(vec![
+			Event::VestingSet(KUSD_BSX_POOL_ID, Some(vesting)).into(),
+			Event::VestingSet(KUSD_BSX_POOL_ID, None).into(),
+		]);
+	});
+}
+
+#[test]
+fn set_vesting_should_not_work() {
+	predefined_test_ext().execute_with(|| {
+		let vesting = VestingSchedule {
+			cliff: 10,
+			duration: 20,
+		};
+
+		assert_noop!(
+			LBPPallet::set_vesting(Origin::signed(BOB), KUSD_BSX_POOL_ID, Some(vesting)),
+			Error::<Test>::NotOwner
+		);
+
+		assert_noop!(
+			LBPPallet::set_vesting(Origin::signed(ALICE), ALICE, Some(vesting)),
+			Error::<Test>::PoolNotFound
+		);
+
+		assert_noop!(
+			LBPPallet::set_vesting(
+				Origin::signed(ALICE),
+				KUSD_BSX_POOL_ID,
+				Some(VestingSchedule { cliff: 0, duration: 0 })
+			),
+			Error::<Test>::InvalidVesting
+		);
+
+		run_to_sale_start();
+
+		assert_noop!(
+			LBPPallet::set_vesting(Origin::signed(ALICE), KUSD_BSX_POOL_ID, Some(vesting)),
+			Error::<Test>::SaleStarted
+		);
+	});
+}
+
+#[test]
+fn bought_tokens_should_vest() {
+	predefined_test_ext().execute_with(|| {
+		let vesting = VestingSchedule {
+			cliff: 10,
+			duration: 20,
+		};
+		assert_ok!(LBPPallet::set_vesting(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			Some(vesting)
+		));
+
+		run_to_sale_start();
+
+		assert_ok!(LBPPallet::buy(Origin::signed(BOB), BSX, KUSD, 1_000_000, 10_000_000));
+
+		// selling the distributed asset does not vest anything
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), BSX, KUSD, 1_000_000, 1));
+
+		let vesting_info = VestingInfo {
+			asset: BSX,
+			total: 1_000_000,
+			claimed: 0,
+			start: SALE_END.unwrap(),
+			schedule: vesting,
+		};
+		assert_eq!(LBPPallet::vesting(KUSD_BSX_POOL_ID, BOB), Some(vesting_info));
+		assert_eq!(
+			<Test as pallet::Config>::LockedBalance::get_by_lock(VESTING_LOCK_ID, BSX, BOB),
+			1_000_000
+		);
+
+		run_to_sale_end();
+
+		// vesting is kept after the pool is destroyed
+		assert_ok!(LBPPallet::remove_liquidity(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+
+		set_block_number::<Test>(SALE_END.unwrap() + 9);
+
+		assert_eq!(LBPPallet::vesting_of(KUSD, BSX, &BOB, None), Some((vesting_info, 0)));
+		assert_noop!(
+			LBPPallet::claim_vested(Origin::signed(BOB), KUSD_BSX_POOL_ID),
+			Error::<Test>::NothingToClaim
+		);
+
+		set_block_number::<Test>(SALE_END.unwrap() + 20);
+
+		assert_eq!(
+			LBPPallet::vesting_of(KUSD, BSX, &BOB, None),
+			Some((vesting_info, 500_000))
+		);
+		assert_ok!(LBPPallet::claim_vested(Origin::signed(BOB), KUSD_BSX_POOL_ID));
+		assert_eq!(
+			<Test as pallet::Config>::LockedBalance::get_by_lock(VESTING_LOCK_ID, BSX, BOB),
+			500_000
+		);
+		assert_eq!(LBPPallet::vesting(KUSD_BSX_POOL_ID, BOB).unwrap().claimed, 500_000);
+
+		assert_noop!(
+			LBPPallet::claim_vested(Origin::signed(BOB), KUSD_BSX_POOL_ID),
+			Error::<Test>::NothingToClaim
+		);
+
+		set_block_number::<Test>(SALE_END.unwrap() + 40);
+
+		assert_ok!(LBPPallet::claim_vested(Origin::signed(BOB), KUSD_BSX_POOL_ID));
+		assert_eq!(
+			<Test as pallet::Config>::LockedBalance::get_by_lock(VESTING_LOCK_ID, BSX, BOB),
+			0
+		);
+		assert_eq!(LBPPallet::vesting(KUSD_BSX_POOL_ID, BOB), None);
+		assert_eq!(LBPPallet::vesting_of(KUSD, BSX, &BOB, None), None);
+
+		expect_events(vec![
+			Event::VestedClaimed(BOB, KUSD_BSX_POOL_ID, BSX, 500_000).into(),
+			Event::VestedClaimed(BOB, KUSD_BSX_POOL_ID, BSX, 500_000).into(),
+		]);
+	});
+}
+
+#[test]
+fn vesting_should_start_at_shifted_sale_end() {
+	predefined_test_ext().execute_with(|| {
+		assert_ok!(LBPPallet::set_vesting(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			Some(VestingSchedule { cliff: 10, duration: 0 })
+		));
+
+		run_to_sale_start();
+
+		assert_ok!(LBPPallet::buy(Origin::signed(BOB), BSX, KUSD, 1_000_000, 10_000_000));
+
+		assert_ok!(LBPPallet::pause_sale(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+		set_block_number::<Test>(SALE_START.unwrap() + 5);
+		assert_ok!(LBPPallet::resume_sale(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+
+		set_block_number::<Test>(SALE_END.unwrap() + 10);
+
+		assert_noop!(
+			LBPPallet::claim_vested(Origin::signed(BOB), KUSD_BSX_POOL_ID),
+			Error::<Test>::NothingToClaim
+		);
+
+		set_block_number::<Test>(SALE_END.unwrap() + 15);
+
+		assert_ok!(LBPPallet::claim_vested(Origin::signed(BOB), KUSD_BSX_POOL_ID));
+		assert_eq!(LBPPallet::vesting(KUSD_BSX_POOL_ID, BOB), None);
+	});
+}
+
+#[test]
+fn sale_rounds_should_work() {
+	predefined_test_ext().execute_with(|| {
+		assert_eq!(LBPPallet::sale_rounds(KUSD_BSX_POOL_ID), 1);
+		assert_eq!(LBPPallet::current_round_account(KUSD, BSX), KUSD_BSX_POOL_ID);
+
+		let create_next_round = || {
+			LBPPallet::create_pool(
+				Origin::root(),
+				ALICE,
+				KUSD,
+				1_000_000_000,
+				BSX,
+				2_000_000_000,
+				20_000_000,
+				80_000_000,
+				WeightCurveType::Linear,
+				DEFAULT_FEE,
+				BOB,
+				0,
+				TradeProtection::default(),
+				RepayFeeSchedule::default(),
+			)
+		};
+
+		// sale of the first round has not ended yet
+		assert_noop!(create_next_round(), Error::<Test>::PoolAlreadyExists);
+
+		run_to_sale_start();
+		assert_noop!(create_next_round(), Error::<Test>::PoolAlreadyExists);
+
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 1_000_000_u128, 1_u128));
+		let amount_raised = LBPPallet::amount_raised(KUSD_BSX_POOL_ID);
+
+		run_to_sale_end();
+		assert_ok!(create_next_round());
+
+		let round_id = LBPPallet::round_account(KUSD, BSX, 1);
+		assert_ne!(round_id, KUSD_BSX_POOL_ID);
+		assert_eq!(LBPPallet::sale_rounds(KUSD_BSX_POOL_ID), 2);
+		assert_eq!(LBPPallet::current_round_account(KUSD, BSX), round_id);
+		assert_eq!(
+			LBPPallet::get_pair_id(AssetPair {
+				asset_in: KUSD,
+				asset_out: BSX
+			}),
+			round_id
+		);
+		assert_eq!(Currency::free_balance(KUSD, &round_id), 1_000_000_000);
+		assert_eq!(Currency::free_balance(BSX, &round_id), 2_000_000_000);
+
+		// the first round is still open until the pool is destroyed
+		let (pool_account, first_round) = LBPPallet::sale_round(KUSD, BSX, 0).unwrap();
+		assert_eq!(pool_account, KUSD_BSX_POOL_ID);
+		assert_eq!(first_round.amount_raised, amount_raised);
+		assert_eq!(first_round.closed_at, None);
+
+		assert_ok!(LBPPallet::update_pool_data(
+			Origin::signed(ALICE),
+			round_id,
+			None,
+			Some(50),
+			Some(80),
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+		));
+
+		// trades are executed in the pool of the latest round
+		set_block_number::<Test>(50);
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 1_000_000_u128, 1_u128));
+		assert!(LBPPallet::amount_raised(round_id) > 0);
+		assert_eq!(LBPPallet::amount_raised(KUSD_BSX_POOL_ID), amount_raised);
+
+		assert_ok!(LBPPallet::remove_liquidity(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+
+		let (_, first_round) = LBPPallet::sale_round(KUSD, BSX, 0).unwrap();
+		assert_eq!(first_round.pool.fee_collector, CHARLIE);
+		assert_eq!(first_round.amount_raised, amount_raised);
+		assert_eq!(first_round.closed_at, Some(50));
+
+		let (pool_account, second_round) = LBPPallet::sale_round(KUSD, BSX, 1).unwrap();
+		assert_eq!(pool_account, round_id);
+		assert_eq!(second_round.pool.fee_collector, BOB);
+		assert_eq!(second_round.closed_at, None);
+
+		assert_eq!(LBPPallet::sale_round(KUSD, BSX, 2), None);
+	});
+}
+
+#[test]
+fn queries_should_read_given_sale_round() {
+	predefined_test_ext().execute_with(|| {
+		let vesting = VestingSchedule {
+			cliff: 10,
+			duration: 20,
+		};
+		assert_ok!(LBPPallet::set_vesting(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			Some(vesting)
+		));
+
+		run_to_sale_start();
+		LBPPallet::on_initialize(SALE_START.unwrap());
+
+		assert_ok!(LBPPallet::buy(Origin::signed(BOB), BSX, KUSD, 1_000_000, 10_000_000));
+
+		run_to_sale_end();
+		assert_ok!(LBPPallet::create_pool(
+			Origin::root(),
+			ALICE,
+			KUSD,
+			1_000_000_000,
+			BSX,
+			2_000_000_000,
+			20_000_000,
+			80_000_000,
+			WeightCurveType::Linear,
+			DEFAULT_FEE,
+			BOB,
+			0,
+			TradeProtection::default(),
+			RepayFeeSchedule::default(),
+		));
+
+		// latest round is read if no round is given
+		assert_eq!(LBPPallet::vesting_of(KUSD, BSX, &BOB, None), None);
+		assert!(LBPPallet::price_history_of(KUSD, BSX, None).is_empty());
+		assert_eq!(
+			LBPPallet::remaining_allowance(KUSD, BSX, &BOB, None),
+			LBPPallet::remaining_allowance(KUSD, BSX, &BOB, Some(1))
+		);
+
+		// previous round is still available
+		assert_eq!(
+			LBPPallet::vesting_of(KUSD, BSX, &BOB, Some(0)).map(|(vesting, _)| vesting.total),
+			Some(1_000_000)
+		);
+		assert_eq!(LBPPallet::price_history_of(KUSD, BSX, Some(0)).len(), 1);
+		assert_eq!(
+			LBPPallet::remaining_allowance(KUSD, BSX, &BOB, Some(0)),
+			Some(Allowance {
+				allowed: true,
+				remaining: None,
+			})
+		);
+
+		// round which was not created
+		assert_eq!(LBPPallet::vesting_of(KUSD, BSX, &BOB, Some(2)), None);
+		assert!(LBPPallet::price_history_of(KUSD, BSX, Some(2)).is_empty());
+		assert_eq!(LBPPallet::remaining_allowance(KUSD, BSX, &BOB, Some(2)), None);
+	});
+}
+
+#[test]
+fn can_create_should_consider_all_sale_rounds() {
+	predefined_test_ext().execute_with(|| {
+		run_to_sale_end();
+		assert!(DisallowWhenLBPPoolRunning::<Test>::can_create(KUSD, BSX));
+
+		assert_ok!(LBPPallet::create_pool(
+			Origin::root(),
+			ALICE,
+			KUSD,
+			1_000_000_000,
+			BSX,
+			2_000_000_000,
+			20_000_000,
+			80_000_000,
+			WeightCurveType::Linear,
+			DEFAULT_FEE,
+			BOB,
+			0,
+			TradeProtection::default(),
+			RepayFeeSchedule::default(),
+		));
+
+		// sale of the second round is not scheduled
+		assert!(!DisallowWhenLBPPoolRunning::<Test>::can_create(KUSD, BSX));
+		assert!(!DisallowWhenLBPPoolRunning::<Test>::can_create(BSX, KUSD));
+
+		let round_id = LBPPallet::current_round_account(KUSD, BSX);
+		assert_ok!(LBPPallet::update_pool_data(
+			Origin::signed(ALICE),
+			round_id,
+			None,
+			Some(50),
+			Some(80),
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+		));
+
+		set_block_number::<Test>(81);
+		assert!(DisallowWhenLBPPoolRunning::<Test>::can_create(KUSD, BSX));
+	});
+}
+
+#[test]
+fn invalid_repay_fee_should_not_work() {
+	predefined_test_ext().execute_with(|| {
+		let schedule = |fee: (u32, u32), tiers: Vec<(Balance, (u32, u32))>| RepayFeeSchedule {
+			fee,
+			tiers: tiers.try_into().unwrap(),
+		};
+
+		let invalid_schedules: Vec<(RepayFeeSchedule, DispatchError)> = vec![
+			(schedule((2, 0), vec![]), Error::<Test>::InvalidRepayFee.into()),
+			(
+				schedule((2, 10), vec![(0, (1, 10))]),
+				Error::<Test>::InvalidRepayFee.into(),
+			),
+			(
+				schedule((2, 10), vec![(1_000, (1, 0))]),
+				Error::<Test>::InvalidRepayFee.into(),
+			),
+			(
+				schedule((2, 10), vec![(2_000, (1, 10)), (1_000, (5, 100))]),
+				Error::<Test>::InvalidRepayFee.into(),
+			),
+			(schedule((3, 10), vec![]), Error::<Test>::RepayFeeTooHigh.into()),
+			(
+				schedule((1, 10), vec![(1_000, (21, 100))]),
+				Error::<Test>::RepayFeeTooHigh.into(),
+			),
+		];
+
+		for (repay_fee, error) in invalid_schedules {
+			assert_noop!(
+				LBPPallet::create_pool(
+					Origin::root(),
+					ALICE,
+					HDX,
+					1_000_000_000,
+					ETH,
+					2_000_000_000,
+					20_000_000,
+					80_000_000,
+					WeightCurveType::Linear,
+					DEFAULT_FEE,
+					BOB,
+					0,
+					TradeProtection::default(),
+					repay_fee.clone(),
+				),
+				error
+			);
+
+			assert_noop!(
+				LBPPallet::update_pool_data(
+					Origin::signed(ALICE),
+					KUSD_BSX_POOL_ID,
+					None,
+					None,
+					None,
+					None,
+					None,
+					None,
+					None,
+					None,
+					None,
+					None,
+					Some(repay_fee),
+				),
+				error
+			);
+		}
+	});
+}
+
+#[test]
+fn repay_fee_tiers_should_be_applied() {
+	predefined_test_ext_with_repay_target().execute_with(|| {
+		let repay_fee = RepayFeeSchedule {
+			fee: (2, 10),
+			tiers: vec![(1_000_000, (1, 10))].try_into().unwrap(),
+		};
+
+		assert_ok!(LBPPallet::update_pool_data(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			Some(repay_fee.clone()),
+		));
+		assert_eq!(LBPPallet::pool_data(KUSD_BSX_POOL_ID).unwrap().repay_fee, repay_fee);
+
+		run_to_sale_start();
+
+		// fee of the first tier was not reached yet
+		assert_eq!(LBPPallet::sale_info(KUSD, BSX, None).unwrap().fee_rate, (2, 10));
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 10_000_000_u128, 1_u128));
+		let amount_out = Currency::free_balance(BSX, &BOB) - INITIAL_BALANCE;
+		expect_events(vec![Event::SellExecuted(
+			BOB,
+			KUSD,
+			BSX,
+			8_000_000,
+			amount_out,
+			KUSD,
+			2_000_000,
+			(2, 10),
+		)
+		.into()]);
+
+		// collected fees reached the first tier
+		assert_eq!(LBPPallet::sale_info(KUSD, BSX, None).unwrap().fee_rate, (1, 10));
+		let quote = LBPPallet::quote_sell(KUSD, BSX, 10_000_000).unwrap();
+		assert_eq!(quote.fee, 1_000_000);
+		assert_eq!(quote.fee_rate, (1, 10));
+		assert!(quote.repay_fee_applied);
+
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 10_000_000_u128, 1_u128));
+		expect_events(vec![Event::SellExecuted(
+			BOB,
+			KUSD,
+			BSX,
+			9_000_000,
+			quote.amount,
+			KUSD,
+			1_000_000,
+			(1, 10),
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn price_history_should_be_recorded_while_sale_runs() {
+	predefined_test_ext().execute_with(|| {
+		assert_eq!(
+			LBPPallet::price_history_pools().into_inner(),
+			vec![(SALE_END.unwrap(), KUSD_BSX_POOL_ID)]
+		);
+
+		// nothing is recorded before the sale
+		set_block_number::<Test>(5);
+		LBPPallet::on_initialize(5);
+		assert!(LBPPallet::price_history_of(KUSD, BSX, None).is_empty());
+
+		run_to_sale_start();
+		LBPPallet::on_initialize(SALE_START.unwrap());
+
+		assert_eq!(
+			LBPPallet::price_history_of(KUSD, BSX, None),
+			vec![PriceSnapshot {
+				block: 10,
+				spot_price: Some(Price::saturating_from_integer(2)),
+				weights: (20_000_000, 80_000_000),
+				reserves: (1_000_000_000, 2_000_000_000),
+			}]
+		);
+
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 10_000_000, 1));
+		let kusd_reserve = Currency::free_balance(KUSD, &KUSD_BSX_POOL_ID);
+		let bsx_reserve = Currency::free_balance(BSX, &KUSD_BSX_POOL_ID);
+
+		set_block_number::<Test>(11);
+		LBPPallet::on_initialize(11);
+
+		let history = LBPPallet::price_history_of(KUSD, BSX, None);
+		assert_eq!(history.len(), 2);
+		assert_eq!(history[1].block, 11);
+		assert_eq!(history[1].reserves, (kusd_reserve, bsx_reserve));
+		assert_eq!(history[1].weights, (22_000_000, 78_000_000));
+
+		// nothing is recorded while the sale is paused
+		set_block_number::<Test>(12);
+		assert_ok!(LBPPallet::pause_sale(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+		LBPPallet::on_initialize(12);
+		assert_eq!(LBPPallet::price_history_of(KUSD, BSX, None).len(), 2);
+	});
+}
+
+#[test]
+fn price_history_should_keep_latest_snapshots() {
+	predefined_test_ext().execute_with(|| {
+		let length = <Test as Config>::PriceHistoryLength::get() as u64;
+
+		for block in SALE_START.unwrap()..SALE_START.unwrap() + length + 3 {
+			set_block_number::<Test>(block);
+			LBPPallet::on_initialize(block);
+		}
+
+		let blocks: Vec<BlockNumber> = LBPPallet::price_history_of(KUSD, BSX, None)
+			.iter()
+			.map(|snapshot| snapshot.block)
+			.collect();
+		assert_eq!(blocks, vec![13, 14, 15, 16, 17]);
+		assert_eq!(LBPPallet::price_history_cursor(KUSD_BSX_POOL_ID), (3, 5));
+	});
+}
+
+#[test]
+fn price_history_should_be_pruned_after_grace_period() {
+	predefined_test_ext().execute_with(|| {
+		for block in SALE_START.unwrap()..SALE_START.unwrap() + 3 {
+			set_block_number::<Test>(block);
+			LBPPallet::on_initialize(block);
+		}
+
+		run_to_sale_end();
+		LBPPallet::on_initialize(SALE_END.unwrap() + 1);
+		assert_eq!(LBPPallet::price_history_of(KUSD, BSX, None).len(), 3);
+
+		let grace_period_end = SALE_END.unwrap() + <Test as Config>::PriceHistoryGracePeriod::get();
+		set_block_number::<Test>(grace_period_end);
+		LBPPallet::on_initialize(grace_period_end);
+		assert_eq!(LBPPallet::price_history_of(KUSD, BSX, None).len(), 3);
+
+		set_block_number::<Test>(grace_period_end + 1);
+		LBPPallet::on_initialize(grace_period_end + 1);
+
+		assert!(LBPPallet::price_history_of(KUSD, BSX, None).is_empty());
+		assert!(LBPPallet::price_history_pools().is_empty());
+		assert_eq!(LBPPallet::price_history_cursor(KUSD_BSX_POOL_ID), (0, 0));
+	});
+}
+
+#[test]
+fn price_history_of_cancelled_sale_should_be_pruned_after_grace_period() {
+	predefined_test_ext().execute_with(|| {
+		for block in SALE_START.unwrap()..SALE_START.unwrap() + 3 {
+			set_block_number::<Test>(block);
+			LBPPallet::on_initialize(block);
+		}
+
+		assert_ok!(LBPPallet::pause_sale(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+		assert_ok!(LBPPallet::cancel_sale(Origin::root(), KUSD_BSX_POOL_ID));
+
+		// history of the cancelled sale is kept for the grace period from the cancellation
+		let cancelled_at = SALE_START.unwrap() + 2;
+		assert_eq!(
+			LBPPallet::price_history_pools().into_inner(),
+			vec![(cancelled_at, KUSD_BSX_POOL_ID)]
+		);
+		assert_eq!(LBPPallet::price_history_of(KUSD, BSX, None).len(), 3);
+
+		let grace_period_end = cancelled_at + <Test as Config>::PriceHistoryGracePeriod::get();
+		set_block_number::<Test>(grace_period_end + 1);
+		LBPPallet::on_initialize(grace_period_end + 1);
+
+		assert!(LBPPallet::price_history_of(KUSD, BSX, None).is_empty());
+		assert!(LBPPallet::price_history_pools().is_empty());
+	});
+}
+
+#[test]
+fn price_history_should_not_be_kept_for_pool_destroyed_before_sale() {
+	predefined_test_ext().execute_with(|| {
+		assert_ok!(LBPPallet::remove_liquidity(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+
+		assert!(LBPPallet::price_history_pools().is_empty());
+	});
+}
@@ -23,7 +23,7 @@ use frame_support::{
 	PalletId,
 };
 use frame_system as system;
-use frame_system::EnsureSigned;
+use frame_system::{EnsureRoot, EnsureSigned};
 use hydradx_traits::AssetPairAccountIdFor;
 use orml_traits::parameter_type_with_key;
 use primitives::{
@@ -82,6 +82,7 @@ parameter_types! {
 	pub static MockBlockNumberProvider: u64 = 0;
 	pub const BSXAssetId: AssetId = BSX;
 	pub ExchangeFeeRate: (u32, u32) = (2, 1_000);
+	pub FeeTiers: Vec<(u32, u32)> = vec![(1, 10_000), (2, 1_000), (1, 100)];
 	pub RegistryStringLimit: u32 = 100;
 }
 
@@ -253,22 +254,34 @@ impl pallet_asset_registry::Config for Test {
 parameter_types! {
 	pub const MinTradingLimit: Balance = MIN_TRADING_LIMIT;
 	pub const MinPoolLiquidity: Balance = MIN_POOL_LIQUIDITY;
+	pub const LockedLiquidity: Balance = 1_000;
 	pub const MaxInRatio: u128 = MAX_IN_RATIO;
 	pub const MaxOutRatio: u128 = MAX_OUT_RATIO;
+	pub const MaxRouteHops: u32 = 5;
+	pub const MaxPriceObservations: u32 = 10;
+	pub const ProtocolFeeAccount: AccountId = 99;
 }
 
 impl pallet_xyk::Config for Test {
 	type Event = Event;
 	type AssetRegistry = AssetRegistry;
+	type ShareTokenCleanup = ();
 	type AssetPairAccountId = AssetPairAccountIdTest;
 	type Currency = Currency;
 	type NativeAssetId = BSXAssetId;
 	type WeightInfo = ();
 	type GetExchangeFee = ExchangeFeeRate;
+	type FeeTiers = FeeTiers;
+	type UpdatePoolFeeOrigin = EnsureRoot<AccountId>;
+	type UpdatePoolStateOrigin = EnsureRoot<AccountId>;
+	type ProtocolFeeAccount = ProtocolFeeAccount;
 	type MinTradingLimit = MinTradingLimit;
 	type MinPoolLiquidity = MinPoolLiquidity;
+	type LockedLiquidity = LockedLiquidity;
 	type MaxInRatio = MaxInRatio;
 	type MaxOutRatio = MaxOutRatio;
+	type MaxRouteHops = MaxRouteHops;
+	type MaxPriceObservations = MaxPriceObservations;
 	type CanCreatePool = pallet_xyk::AllowAllPools;
 	type AMMHandler = ();
 }
@@ -0,0 +1,70 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2021  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for stableswap pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::{Codec, Decode, Encode};
+use sp_std::prelude::*;
+
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct TradeQuote<Balance> {
+	/// Amount received in sell or amount paid in buy, fee included.
+	pub amount: Balance,
+
+	/// Fee charged for the trade.
+	pub fee: Balance,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait StableswapApi<AccountId, AssetId, Balance> where
+		AccountId: Codec,
+		AssetId: Codec,
+		Balance: Codec,
+	{
+		fn get_pool_account(
+			pool_id: u32,
+		) -> AccountId;
+
+		fn get_reserves(
+			pool_id: u32,
+		) -> Vec<(AssetId, Balance)>;
+
+		fn get_amplification(
+			pool_id: u32,
+		) -> Option<u16>;
+
+		fn quote_sell(
+			pool_id: u32,
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount: Balance,
+		) -> Option<TradeQuote<Balance>>;
+
+		fn quote_buy(
+			pool_id: u32,
+			asset_out: AssetId,
+			asset_in: AssetId,
+			amount: Balance,
+		) -> Option<TradeQuote<Balance>>;
+	}
+}
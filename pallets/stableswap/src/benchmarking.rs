@@ -0,0 +1,153 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2021  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks};
+use frame_system::RawOrigin;
+use sp_std::prelude::*;
+
+use crate::Pallet as Stableswap;
+
+const SEED: u32 = 1;
+
+const ASSETS: [AssetId; 3] = [1_000, 2_000, 3_000];
+const AMPLIFICATION: u16 = 100;
+const FEE: Permill = Permill::from_parts(3_000);
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let caller: T::AccountId = account(name, index, SEED);
+	for asset in ASSETS.iter() {
+		T::Currency::update_balance(*asset, &caller, 1_000_000_000_000_000).unwrap();
+	}
+	caller
+}
+
+fn pool_with_liquidity<T: Config>(maker: &T::AccountId) -> Result<PoolId, DispatchError> {
+	Stableswap::<T>::create_pool(RawOrigin::Root.into(), ASSETS.to_vec(), AMPLIFICATION, FEE)?;
+
+	let pool_id = Stableswap::<T>::pool_count();
+
+	Stableswap::<T>::add_liquidity(
+		RawOrigin::Signed(maker.clone()).into(),
+		pool_id,
+		ASSETS.iter().map(|asset| (*asset, 100_000_000_000_000)).collect(),
+	)?;
+
+	Ok(pool_id)
+}
+
+benchmarks! {
+	create_pool {
+		let pool_id = Stableswap::<T>::pool_count() + 1;
+	}: _(RawOrigin::Root, ASSETS.to_vec(), AMPLIFICATION, FEE)
+	verify {
+		assert!(<Pools<T>>::contains_key(pool_id));
+	}
+
+	add_liquidity {
+		let maker = funded_account::<T>("maker", 0);
+		let caller = funded_account::<T>("caller", 0);
+
+		let pool_id = pool_with_liquidity::<T>(&maker)?;
+		let share_token = Stableswap::<T>::pools(pool_id).unwrap().share_token;
+
+		let amounts: Vec<(AssetId, Balance)> = ASSETS.iter().map(|asset| (*asset, 1_000_000_000_000)).collect();
+
+	}: _(RawOrigin::Signed(caller.clone()), pool_id, amounts)
+	verify {
+		assert_eq!(T::Currency::free_balance(ASSETS[0], &caller), 999_000_000_000_000);
+		assert_eq!(T::Currency::free_balance(share_token, &caller), 3_000_000_000_000);
+	}
+
+	remove_liquidity {
+		let maker = funded_account::<T>("maker", 0);
+		let caller = funded_account::<T>("caller", 0);
+
+		let pool_id = pool_with_liquidity::<T>(&maker)?;
+		let share_token = Stableswap::<T>::pools(pool_id).unwrap().share_token;
+
+		Stableswap::<T>::add_liquidity(
+			RawOrigin::Signed(caller.clone()).into(),
+			pool_id,
+			ASSETS.iter().map(|asset| (*asset, 1_000_000_000_000)).collect(),
+		)?;
+
+	}: _(RawOrigin::Signed(caller.clone()), pool_id, 1_000_000_000_000)
+	verify {
+		assert_eq!(T::Currency::free_balance(ASSETS[0], &caller), 999_333_333_333_333);
+		assert_eq!(T::Currency::free_balance(share_token, &caller), 2_000_000_000_000);
+	}
+
+	sell {
+		let maker = funded_account::<T>("maker", 0);
+		let caller = funded_account::<T>("caller", 0);
+
+		let pool_id = pool_with_liquidity::<T>(&maker)?;
+
+	}: _(RawOrigin::Signed(caller.clone()), pool_id, ASSETS[0], ASSETS[1], 10_000_000_000, 0)
+	verify {
+		assert_eq!(T::Currency::free_balance(ASSETS[0], &caller), 999_990_000_000_000);
+		assert_eq!(T::Currency::free_balance(ASSETS[1], &caller), 1_000_009_969_998_894);
+	}
+
+	buy {
+		let maker = funded_account::<T>("maker", 0);
+		let caller = funded_account::<T>("caller", 0);
+
+		let pool_id = pool_with_liquidity::<T>(&maker)?;
+
+	}: _(RawOrigin::Signed(caller.clone()), pool_id, ASSETS[1], ASSETS[0], 10_000_000_000, 20_000_000_000)
+	verify {
+		assert_eq!(T::Currency::free_balance(ASSETS[0], &caller), 999_989_969_998_887);
+		assert_eq!(T::Currency::free_balance(ASSETS[1], &caller), 1_000_010_000_000_000);
+	}
+
+	update_amplification {
+		let maker = funded_account::<T>("maker", 0);
+
+		let pool_id = pool_with_liquidity::<T>(&maker)?;
+
+		let now = <frame_system::Pallet<T>>::block_number();
+		let final_block = now + 1_000u32.into();
+
+	}: _(RawOrigin::Root, pool_id, 1_000, now, final_block)
+	verify {
+		assert_eq!(Stableswap::<T>::pools(pool_id).unwrap().final_amplification, 1_000);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Pallet::<Test>::test_benchmark_create_pool());
+			assert_ok!(Pallet::<Test>::test_benchmark_add_liquidity());
+			assert_ok!(Pallet::<Test>::test_benchmark_remove_liquidity());
+			assert_ok!(Pallet::<Test>::test_benchmark_sell());
+			assert_ok!(Pallet::<Test>::test_benchmark_buy());
+			assert_ok!(Pallet::<Test>::test_benchmark_update_amplification());
+		});
+	}
+}
@@ -0,0 +1,41 @@
+use crate::PoolId;
+use frame_support::traits::Get;
+use hydradx_traits::pools::SpotPriceProvider;
+use orml_traits::MultiCurrency;
+use primitives::asset::AssetPair;
+use primitives::{AssetId, Price};
+use sp_runtime::FixedPointNumber;
+use sp_std::marker::PhantomData;
+
+/// Fraction of the pool reserve sold to derive spot price.
+const PRICE_SAMPLE_RATIO: u128 = 1_000_000;
+
+pub struct StableswapSpotPrice<T>(PhantomData<T>);
+
+impl<T: crate::Config> StableswapSpotPrice<T> {
+	/// Price of `asset_a` denominated in `asset_b` in given pool, trading fee not included.
+	///
+	/// Price is derived from selling a millionth of the pool reserve of `asset_a`, but at least `MinTradingLimit`.
+	pub fn pool_spot_price(pool_id: PoolId, asset_a: AssetId, asset_b: AssetId) -> Option<Price> {
+		let reserve = T::Currency::free_balance(asset_a, &<crate::Pallet<T>>::pool_account(pool_id));
+		let amount = (reserve / PRICE_SAMPLE_RATIO).max(T::MinTradingLimit::get()).max(1);
+		let (amount_out, _) = <crate::Pallet<T>>::calculate_sell(pool_id, asset_a, asset_b, amount).ok()?;
+
+		Price::checked_from_rational(amount_out, amount)
+	}
+}
+
+impl<T: crate::Config> SpotPriceProvider<AssetId> for StableswapSpotPrice<T> {
+	type Price = Price;
+
+	fn pair_exists(asset_a: AssetId, asset_b: AssetId) -> bool {
+		<crate::Pallet<T>>::pool_for_pair(AssetPair::new(asset_b, asset_a)).is_some()
+	}
+
+	/// Price of `asset_a` denominated in `asset_b` in the pool used for the pair by AMM Api.
+	fn spot_price(asset_a: AssetId, asset_b: AssetId) -> Option<Self::Price> {
+		let pool_id = <crate::Pallet<T>>::pool_for_pair(AssetPair::new(asset_b, asset_a))?;
+
+		Self::pool_spot_price(pool_id, asset_a, asset_b)
+	}
+}
@@ -0,0 +1,944 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2021  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Stableswap Pallet
+//!
+//! ## Overview
+//!
+//! Stableswap pallet provides Curve-style pools of N correlated assets, such as stablecoins or bridged
+//! representations of the same asset. Trades close to the balanced state of the pool have much lower
+//! slippage than in constant product pools. The shape of the curve is given by the amplification
+//! of the pool which can be changed gradually by governance.
+//!
+//! This pallet implements AMM Api trait therefore it is possible to plug this pool implementation
+//! into the exchange pallet. Trades of an asset pair through the AMM Api are routed to the first pool
+//! created with both assets.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+#![allow(clippy::upper_case_acronyms)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::sp_runtime::{
+	traits::{AccountIdConversion, UniqueSaturatedInto, Zero},
+	DispatchError, Permill,
+};
+use frame_support::{
+	dispatch::DispatchResult,
+	ensure,
+	traits::{ConstU32, Get},
+	transactional, BoundedVec, PalletId, RuntimeDebug,
+};
+use frame_system::ensure_signed;
+use hydradx_traits::{AMMTransfer, AMM};
+use orml_traits::{MultiCurrency, MultiCurrencyExtended};
+use primitives::{asset::AssetPair, Amount, AssetId, Balance};
+use sp_std::{vec, vec::Vec};
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+mod benchmarking;
+mod impls;
+pub mod math;
+pub mod weights;
+
+pub use impls::StableswapSpotPrice;
+use weights::WeightInfo;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+
+/// Identifier of a stableswap pool.
+pub type PoolId = u32;
+
+/// Max number of assets in a pool, `MaxAssetsInPool` can't exceed it
+pub const MAX_ASSETS_IN_POOL: u32 = 8;
+
+pub type PoolAssets = BoundedVec<AssetId, ConstU32<MAX_ASSETS_IN_POOL>>;
+
+/// Stableswap pool of correlated assets.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo, MaxEncodedLen)]
+pub struct PoolInfo<BlockNumber> {
+	/// Assets of the pool, sorted.
+	pub assets: PoolAssets,
+	/// Share token of the pool.
+	pub share_token: AssetId,
+	/// Amplification at `initial_block`.
+	pub initial_amplification: u16,
+	/// Amplification at `final_block`.
+	pub final_amplification: u16,
+	/// Block at which amplification starts changing.
+	pub initial_block: BlockNumber,
+	/// Block at which amplification reaches `final_amplification`.
+	pub final_block: BlockNumber,
+	/// Trading fee rate.
+	pub fee: Permill,
+}
+
+impl<BlockNumber> PoolInfo<BlockNumber> {
+	/// Index of `asset` in the pool.
+	pub fn find_asset(&self, asset: AssetId) -> Option<usize> {
+		self.assets.iter().position(|a| *a == asset)
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::OriginFor;
+	use hydradx_traits::ShareTokenRegistry;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn integrity_test() {
+			assert!(
+				T::MaxAssetsInPool::get() <= MAX_ASSETS_IN_POOL,
+				"`MaxAssetsInPool` must not exceed `MAX_ASSETS_IN_POOL`"
+			);
+		}
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Registry support
+		type AssetRegistry: ShareTokenRegistry<AssetId, Vec<u8>, Balance, DispatchError>;
+
+		/// Multi currency for transfer of currencies
+		type Currency: MultiCurrencyExtended<Self::AccountId, CurrencyId = AssetId, Balance = Balance, Amount = Amount>;
+
+		/// Pallet id used to derive pool accounts
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Origin allowed to create pools and change their amplification
+		type AuthorityOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Minimum amplification of a pool
+		#[pallet::constant]
+		type MinAmplification: Get<u16>;
+
+		/// Maximum amplification of a pool
+		#[pallet::constant]
+		type MaxAmplification: Get<u16>;
+
+		/// Maximum number of assets in a pool, at most `MAX_ASSETS_IN_POOL`
+		#[pallet::constant]
+		type MaxAssetsInPool: Get<u32>;
+
+		/// Minimum trading limit
+		#[pallet::constant]
+		type MinTradingLimit: Get<Balance>;
+
+		/// Minimum pool liquidity
+		#[pallet::constant]
+		type MinPoolLiquidity: Get<Balance>;
+
+		/// Max fraction of pool to sell in single transaction
+		#[pallet::constant]
+		type MaxInRatio: Get<u128>;
+
+		/// Max fraction of pool to buy in single transaction
+		#[pallet::constant]
+		type MaxOutRatio: Get<u128>;
+
+		/// Weight information for the extrinsics.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	#[cfg_attr(test, derive(PartialEq))]
+	pub enum Error<T> {
+		/// Pool must consist of at least two distinct assets.
+		IncorrectAssets,
+
+		/// Number of assets exceeds `MaxAssetsInPool`.
+		MaxAssetsExceeded,
+
+		/// Amplification is outside of allowed range.
+		InvalidAmplification,
+
+		/// Fee rate must be lower than 100%.
+		InvalidFee,
+
+		/// Pool does not exist.
+		PoolNotFound,
+
+		/// Asset is not in the pool.
+		AssetNotInPool,
+
+		/// Cannot trade asset for itself.
+		SameAssetTrade,
+
+		/// Account does not have enough balance.
+		InsufficientBalance,
+
+		/// Traded amount is lower than `MinTradingLimit`.
+		InsufficientTradingAmount,
+
+		/// Liquidity has not reached the required minimum.
+		InsufficientLiquidity,
+
+		/// Account does not have enough shares.
+		InsufficientShares,
+
+		/// First liquidity of a pool must contain all assets of the pool.
+		InvalidInitialLiquidity,
+
+		/// Bought amount is lower than the limit.
+		BuyLimitNotReached,
+
+		/// Sold amount is higher than the limit.
+		SellLimitExceeded,
+
+		/// Max fraction of pool to sell in single transaction has been exceeded.
+		MaxInRatioExceeded,
+
+		/// Max fraction of pool to buy in single transaction has been exceeded.
+		MaxOutRatioExceeded,
+
+		/// Trading fee discount is not supported by stableswap pools.
+		DiscountNotSupported,
+
+		/// Amplification change must end after it starts and must not start in the past.
+		InvalidAmplificationChange,
+
+		/// Calculation of the invariant overflowed or did not converge.
+		MathError,
+
+		/// Overflow
+		Overflow,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// New pool was created. [pool id, assets, amplification, fee, share token, pool account id]
+		PoolCreated(PoolId, Vec<AssetId>, u16, Permill, AssetId, T::AccountId),
+
+		/// Liquidity was added to a pool. [who, pool id, added amounts, shares issued]
+		LiquidityAdded(T::AccountId, PoolId, Vec<(AssetId, Balance)>, Balance),
+
+		/// Liquidity was removed from a pool. [who, pool id, shares burned, removed amounts]
+		LiquidityRemoved(T::AccountId, PoolId, Balance, Vec<(AssetId, Balance)>),
+
+		/// Asset sale executed. [who, pool id, asset in, asset out, amount in, amount out, fee]
+		SellExecuted(T::AccountId, PoolId, AssetId, AssetId, Balance, Balance, Balance),
+
+		/// Asset purchase executed. [who, pool id, asset out, asset in, amount out, amount in, fee]
+		BuyExecuted(T::AccountId, PoolId, AssetId, AssetId, Balance, Balance, Balance),
+
+		/// Amplification of a pool is scheduled to change. [pool id, current amplification, final amplification, initial block, final block]
+		AmplificationChangeScheduled(PoolId, u16, u16, T::BlockNumber, T::BlockNumber),
+	}
+
+	/// Id of the last created pool.
+	#[pallet::storage]
+	#[pallet::getter(fn pool_count)]
+	pub(crate) type PoolCount<T: Config> = StorageValue<_, PoolId, ValueQuery>;
+
+	/// Existing pools.
+	#[pallet::storage]
+	#[pallet::getter(fn pools)]
+	pub(crate) type Pools<T: Config> = StorageMap<_, Blake2_128Concat, PoolId, PoolInfo<T::BlockNumber>, OptionQuery>;
+
+	/// Pool used for trades of an asset pair through the AMM Api. Assets of the pair are ordered.
+	#[pallet::storage]
+	#[pallet::getter(fn pair_pool)]
+	pub(crate) type PairPools<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, AssetId, Blake2_128Concat, AssetId, PoolId, OptionQuery>;
+
+	/// Pool identified by its account.
+	#[pallet::storage]
+	#[pallet::getter(fn pool_id_by_account)]
+	pub(crate) type PoolAccounts<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, PoolId, OptionQuery>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create new pool of given assets.
+		///
+		/// Can be called only by `AuthorityOrigin`. Pool is created empty, first liquidity must be
+		/// provided by `add_liquidity` and must contain all assets of the pool.
+		///
+		/// `assets` - at least two distinct assets, at most `MaxAssetsInPool`.
+		///
+		/// `amplification` - amplification of the pool, must be within `MinAmplification` and `MaxAmplification`.
+		///
+		/// `fee` - trading fee rate of the pool.
+		///
+		/// Emits `PoolCreated` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::create_pool())]
+		#[transactional]
+		pub fn create_pool(
+			origin: OriginFor<T>,
+			assets: Vec<AssetId>,
+			amplification: u16,
+			fee: Permill,
+		) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin)?;
+
+			let mut assets = assets;
+			assets.sort_unstable();
+			assets.dedup();
+
+			ensure!(assets.len() >= 2, Error::<T>::IncorrectAssets);
+			ensure!(
+				assets.len() <= T::MaxAssetsInPool::get() as usize,
+				Error::<T>::MaxAssetsExceeded
+			);
+			let pool_assets: PoolAssets = assets.clone().try_into().map_err(|_| Error::<T>::MaxAssetsExceeded)?;
+			ensure!(
+				(T::MinAmplification::get()..=T::MaxAmplification::get()).contains(&amplification),
+				Error::<T>::InvalidAmplification
+			);
+			ensure!(fee < Permill::one(), Error::<T>::InvalidFee);
+
+			let pool_id = Self::pool_count().checked_add(1).ok_or(Error::<T>::Overflow)?;
+			let pool_account = Self::pool_account(pool_id);
+
+			let share_token = T::AssetRegistry::get_or_create_shared_asset(
+				Self::share_token_name(pool_id),
+				assets.clone(),
+				T::MinPoolLiquidity::get(),
+			)?;
+
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			for (idx, asset_a) in assets.iter().enumerate() {
+				for asset_b in assets[idx + 1..].iter() {
+					if !<PairPools<T>>::contains_key(asset_a, asset_b) {
+						<PairPools<T>>::insert(asset_a, asset_b, pool_id);
+					}
+				}
+			}
+
+			<Pools<T>>::insert(
+				pool_id,
+				PoolInfo {
+					assets: pool_assets,
+					share_token,
+					initial_amplification: amplification,
+					final_amplification: amplification,
+					initial_block: now,
+					final_block: now,
+					fee,
+				},
+			);
+			<PoolAccounts<T>>::insert(&pool_account, pool_id);
+			<PoolCount<T>>::put(pool_id);
+
+			Self::deposit_event(Event::PoolCreated(
+				pool_id,
+				assets,
+				amplification,
+				fee,
+				share_token,
+				pool_account,
+			));
+
+			Ok(())
+		}
+
+		/// Add liquidity to a pool.
+		///
+		/// Any subset of pool assets can be provided, except for the first liquidity which must contain
+		/// all assets of the pool. Liquidity which changes ratio of the pool reserves pays trading fee
+		/// on the difference from a balanced deposit.
+		///
+		/// Shares are issued in proportion to the increase of the pool invariant.
+		///
+		/// Emits `LiquidityAdded` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::add_liquidity())]
+		#[transactional]
+		pub fn add_liquidity(origin: OriginFor<T>, pool_id: PoolId, assets: Vec<(AssetId, Balance)>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			let pool_account = Self::pool_account(pool_id);
+
+			let mut amounts = vec![Balance::zero(); pool.assets.len()];
+
+			for (asset, amount) in assets.iter() {
+				let idx = pool.find_asset(*asset).ok_or(Error::<T>::AssetNotInPool)?;
+
+				ensure!(
+					T::Currency::free_balance(*asset, &who) >= *amount,
+					Error::<T>::InsufficientBalance
+				);
+
+				amounts[idx] = amounts[idx].checked_add(*amount).ok_or(Error::<T>::Overflow)?;
+			}
+
+			let total_shares = T::Currency::total_issuance(pool.share_token);
+
+			if total_shares.is_zero() {
+				ensure!(
+					amounts.iter().all(|amount| !amount.is_zero()),
+					Error::<T>::InvalidInitialLiquidity
+				);
+			}
+
+			let reserves = Self::reserves(&pool, &pool_account);
+
+			let shares = math::calculate_shares_for_amounts(
+				&reserves,
+				&amounts,
+				total_shares,
+				Self::current_amplification(&pool),
+				pool.fee,
+			)
+			.ok_or(Error::<T>::MathError)?;
+
+			ensure!(!shares.is_zero(), Error::<T>::InsufficientLiquidity);
+			ensure!(
+				T::Currency::free_balance(pool.share_token, &who).saturating_add(shares) >= T::MinPoolLiquidity::get(),
+				Error::<T>::InsufficientLiquidity
+			);
+
+			let added = pool
+				.assets
+				.iter()
+				.zip(amounts.iter())
+				.filter(|(_, amount)| !amount.is_zero())
+				.map(|(asset, amount)| (*asset, *amount))
+				.collect::<Vec<_>>();
+
+			for (asset, amount) in added.iter() {
+				T::Currency::transfer(*asset, &who, &pool_account, *amount)?;
+			}
+
+			T::Currency::deposit(pool.share_token, &who, shares)?;
+
+			Self::deposit_event(Event::LiquidityAdded(who, pool_id, added, shares));
+
+			Ok(())
+		}
+
+		/// Remove liquidity from a pool in the form of burning shares.
+		///
+		/// All assets of the pool are withdrawn in proportion to the pool reserves.
+		///
+		/// Emits `LiquidityRemoved` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_liquidity())]
+		#[transactional]
+		pub fn remove_liquidity(origin: OriginFor<T>, pool_id: PoolId, share_amount: Balance) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!share_amount.is_zero(), Error::<T>::InsufficientShares);
+
+			let pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			let pool_account = Self::pool_account(pool_id);
+
+			let account_shares = T::Currency::free_balance(pool.share_token, &who);
+
+			ensure!(account_shares >= share_amount, Error::<T>::InsufficientShares);
+
+			// Account's liquidity left should be either 0 or at least MinPoolLiquidity
+			ensure!(
+				account_shares.saturating_sub(share_amount) >= T::MinPoolLiquidity::get()
+					|| account_shares == share_amount,
+				Error::<T>::InsufficientLiquidity
+			);
+
+			let total_shares = T::Currency::total_issuance(pool.share_token);
+
+			let mut removed = Vec::with_capacity(pool.assets.len());
+
+			for asset in pool.assets.iter() {
+				let reserve = T::Currency::free_balance(*asset, &pool_account);
+				let amount = sp_runtime::helpers_128bit::multiply_by_rational(reserve, share_amount, total_shares)
+					.map_err(|_| Error::<T>::Overflow)?;
+
+				removed.push((*asset, amount));
+			}
+
+			T::Currency::withdraw(pool.share_token, &who, share_amount)?;
+
+			for (asset, amount) in removed.iter() {
+				T::Currency::transfer(*asset, &pool_account, &who, *amount)?;
+			}
+
+			Self::deposit_event(Event::LiquidityRemoved(who, pool_id, share_amount, removed));
+
+			Ok(())
+		}
+
+		/// Trade `amount_in` of `asset_in` for `asset_out` in a pool.
+		///
+		/// Trading fee is deducted from the bought amount.
+		///
+		/// `min_buy_amount` - minimum amount of `asset_out` to receive.
+		///
+		/// Emits `SellExecuted` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::sell())]
+		#[transactional]
+		pub fn sell(
+			origin: OriginFor<T>,
+			pool_id: PoolId,
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount_in: Balance,
+			min_buy_amount: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let transfer = Self::validate_pool_sell(&who, pool_id, asset_in, asset_out, amount_in, min_buy_amount)?;
+
+			Self::execute_pool_sell(pool_id, &transfer)
+		}
+
+		/// Trade `asset_in` for `amount_out` of `asset_out` in a pool.
+		///
+		/// Trading fee is added to the sold amount.
+		///
+		/// `max_sell_amount` - maximum amount of `asset_in` to pay.
+		///
+		/// Emits `BuyExecuted` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::buy())]
+		#[transactional]
+		pub fn buy(
+			origin: OriginFor<T>,
+			pool_id: PoolId,
+			asset_out: AssetId,
+			asset_in: AssetId,
+			amount_out: Balance,
+			max_sell_amount: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let transfer = Self::validate_pool_buy(&who, pool_id, asset_out, asset_in, amount_out, max_sell_amount)?;
+
+			Self::execute_pool_buy(pool_id, &transfer)
+		}
+
+		/// Change amplification of a pool linearly from its current value to `final_amplification`
+		/// between `initial_block` and `final_block`.
+		///
+		/// Can be called only by `AuthorityOrigin`.
+		///
+		/// Emits `AmplificationChangeScheduled` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::update_amplification())]
+		pub fn update_amplification(
+			origin: OriginFor<T>,
+			pool_id: PoolId,
+			final_amplification: u16,
+			initial_block: T::BlockNumber,
+			final_block: T::BlockNumber,
+		) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				(T::MinAmplification::get()..=T::MaxAmplification::get()).contains(&final_amplification),
+				Error::<T>::InvalidAmplification
+			);
+			ensure!(
+				initial_block >= <frame_system::Pallet<T>>::block_number() && final_block > initial_block,
+				Error::<T>::InvalidAmplificationChange
+			);
+
+			<Pools<T>>::try_mutate(pool_id, |maybe_pool| -> DispatchResult {
+				let pool = maybe_pool.as_mut().ok_or(Error::<T>::PoolNotFound)?;
+
+				let current_amplification = Self::current_amplification(pool) as u16;
+
+				pool.initial_amplification = current_amplification;
+				pool.final_amplification = final_amplification;
+				pool.initial_block = initial_block;
+				pool.final_block = final_block;
+
+				Self::deposit_event(Event::AmplificationChangeScheduled(
+					pool_id,
+					current_amplification,
+					final_amplification,
+					initial_block,
+					final_block,
+				));
+
+				Ok(())
+			})
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Account of a pool.
+	pub fn pool_account(pool_id: PoolId) -> T::AccountId {
+		T::PalletId::get().into_sub_account(pool_id)
+	}
+
+	fn share_token_name(pool_id: PoolId) -> Vec<u8> {
+		let mut name = b"SSP".to_vec();
+		name.extend_from_slice(&pool_id.to_le_bytes());
+		name
+	}
+
+	/// Amplification of a pool in current block.
+	pub fn current_amplification(pool: &PoolInfo<T::BlockNumber>) -> u128 {
+		math::calculate_amplification(
+			pool.initial_amplification.into(),
+			pool.final_amplification.into(),
+			pool.initial_block.unique_saturated_into(),
+			pool.final_block.unique_saturated_into(),
+			<frame_system::Pallet<T>>::block_number().unique_saturated_into(),
+		)
+	}
+
+	/// Reserves of a pool in the order of pool assets.
+	pub fn reserves(pool: &PoolInfo<T::BlockNumber>, pool_account: &T::AccountId) -> Vec<Balance> {
+		pool.assets
+			.iter()
+			.map(|asset| T::Currency::free_balance(*asset, pool_account))
+			.collect()
+	}
+
+	fn calculate_fee(pool: &PoolInfo<T::BlockNumber>, amount: Balance) -> Balance {
+		pool.fee.mul_floor(amount)
+	}
+
+	/// Amount of `asset_out` received for `amount_in` of `asset_in` and the fee, fee not included.
+	pub fn calculate_sell(
+		pool_id: PoolId,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_in: Balance,
+	) -> Result<(Balance, Balance), DispatchError> {
+		ensure!(asset_in != asset_out, Error::<T>::SameAssetTrade);
+
+		let pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+		let pool_account = Self::pool_account(pool_id);
+
+		let index_in = pool.find_asset(asset_in).ok_or(Error::<T>::AssetNotInPool)?;
+		let index_out = pool.find_asset(asset_out).ok_or(Error::<T>::AssetNotInPool)?;
+
+		let reserves = Self::reserves(&pool, &pool_account);
+
+		ensure!(
+			amount_in
+				<= reserves[index_in]
+					.checked_div(T::MaxInRatio::get())
+					.ok_or(Error::<T>::Overflow)?,
+			Error::<T>::MaxInRatioExceeded
+		);
+
+		let amount_out = math::calculate_out_given_in(
+			&reserves,
+			index_in,
+			index_out,
+			amount_in,
+			Self::current_amplification(&pool),
+		)
+		.ok_or(Error::<T>::MathError)?;
+
+		Ok((amount_out, Self::calculate_fee(&pool, amount_out)))
+	}
+
+	/// Amount of `asset_in` needed to receive `amount_out` of `asset_out` and the fee, fee not included.
+	pub fn calculate_buy(
+		pool_id: PoolId,
+		asset_out: AssetId,
+		asset_in: AssetId,
+		amount_out: Balance,
+	) -> Result<(Balance, Balance), DispatchError> {
+		ensure!(asset_in != asset_out, Error::<T>::SameAssetTrade);
+
+		let pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+		let pool_account = Self::pool_account(pool_id);
+
+		let index_in = pool.find_asset(asset_in).ok_or(Error::<T>::AssetNotInPool)?;
+		let index_out = pool.find_asset(asset_out).ok_or(Error::<T>::AssetNotInPool)?;
+
+		let reserves = Self::reserves(&pool, &pool_account);
+
+		ensure!(
+			amount_out
+				<= reserves[index_out]
+					.checked_div(T::MaxOutRatio::get())
+					.ok_or(Error::<T>::Overflow)?,
+			Error::<T>::MaxOutRatioExceeded
+		);
+
+		let amount_in = math::calculate_in_given_out(
+			&reserves,
+			index_in,
+			index_out,
+			amount_out,
+			Self::current_amplification(&pool),
+		)
+		.ok_or(Error::<T>::MathError)?;
+
+		Ok((amount_in, Self::calculate_fee(&pool, amount_in)))
+	}
+
+	fn validate_pool_sell(
+		who: &T::AccountId,
+		pool_id: PoolId,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_in: Balance,
+		min_buy_amount: Balance,
+	) -> Result<AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>, DispatchError> {
+		ensure!(
+			amount_in >= T::MinTradingLimit::get(),
+			Error::<T>::InsufficientTradingAmount
+		);
+
+		ensure!(
+			T::Currency::free_balance(asset_in, who) >= amount_in,
+			Error::<T>::InsufficientBalance
+		);
+
+		let (amount_out, fee) = Self::calculate_sell(pool_id, asset_in, asset_out, amount_in)?;
+
+		let amount_out_without_fee = amount_out.checked_sub(fee).ok_or(Error::<T>::Overflow)?;
+
+		ensure!(amount_out_without_fee >= min_buy_amount, Error::<T>::BuyLimitNotReached);
+
+		Ok(AMMTransfer {
+			origin: who.clone(),
+			assets: AssetPair { asset_in, asset_out },
+			amount: amount_in,
+			amount_out: amount_out_without_fee,
+			discount: false,
+			discount_amount: Balance::zero(),
+			fee: (asset_out, fee),
+		})
+	}
+
+	fn validate_pool_buy(
+		who: &T::AccountId,
+		pool_id: PoolId,
+		asset_out: AssetId,
+		asset_in: AssetId,
+		amount_out: Balance,
+		max_sell_amount: Balance,
+	) -> Result<AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>, DispatchError> {
+		ensure!(
+			amount_out >= T::MinTradingLimit::get(),
+			Error::<T>::InsufficientTradingAmount
+		);
+
+		let (amount_in, fee) = Self::calculate_buy(pool_id, asset_out, asset_in, amount_out)?;
+
+		let amount_in_with_fee = amount_in.checked_add(fee).ok_or(Error::<T>::Overflow)?;
+
+		ensure!(amount_in_with_fee <= max_sell_amount, Error::<T>::SellLimitExceeded);
+
+		ensure!(
+			T::Currency::free_balance(asset_in, who) >= amount_in_with_fee,
+			Error::<T>::InsufficientBalance
+		);
+
+		Ok(AMMTransfer {
+			origin: who.clone(),
+			assets: AssetPair { asset_in, asset_out },
+			amount: amount_out,
+			amount_out: amount_in,
+			discount: false,
+			discount_amount: Balance::zero(),
+			fee: (asset_in, fee),
+		})
+	}
+
+	fn execute_pool_sell(
+		pool_id: PoolId,
+		transfer: &AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>,
+	) -> DispatchResult {
+		let pool_account = Self::pool_account(pool_id);
+
+		T::Currency::transfer(
+			transfer.assets.asset_in,
+			&transfer.origin,
+			&pool_account,
+			transfer.amount,
+		)?;
+		T::Currency::transfer(
+			transfer.assets.asset_out,
+			&pool_account,
+			&transfer.origin,
+			transfer.amount_out,
+		)?;
+
+		Self::deposit_event(Event::SellExecuted(
+			transfer.origin.clone(),
+			pool_id,
+			transfer.assets.asset_in,
+			transfer.assets.asset_out,
+			transfer.amount,
+			transfer.amount_out,
+			transfer.fee.1,
+		));
+
+		Ok(())
+	}
+
+	fn execute_pool_buy(
+		pool_id: PoolId,
+		transfer: &AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>,
+	) -> DispatchResult {
+		let pool_account = Self::pool_account(pool_id);
+
+		let amount_in = transfer
+			.amount_out
+			.checked_add(transfer.fee.1)
+			.ok_or(Error::<T>::Overflow)?;
+
+		T::Currency::transfer(transfer.assets.asset_in, &transfer.origin, &pool_account, amount_in)?;
+		T::Currency::transfer(
+			transfer.assets.asset_out,
+			&pool_account,
+			&transfer.origin,
+			transfer.amount,
+		)?;
+
+		Self::deposit_event(Event::BuyExecuted(
+			transfer.origin.clone(),
+			pool_id,
+			transfer.assets.asset_out,
+			transfer.assets.asset_in,
+			transfer.amount,
+			amount_in,
+			transfer.fee.1,
+		));
+
+		Ok(())
+	}
+
+	/// Pool used for trades of an asset pair through the AMM Api.
+	pub(crate) fn pool_for_pair(assets: AssetPair) -> Option<PoolId> {
+		let (asset_a, asset_b) = assets.ordered_pair();
+		Self::pair_pool(asset_a, asset_b)
+	}
+}
+
+impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
+	fn exists(assets: AssetPair) -> bool {
+		Self::pool_for_pair(assets).is_some()
+	}
+
+	fn get_pair_id(assets: AssetPair) -> T::AccountId {
+		Self::pool_account(Self::pool_for_pair(assets).unwrap_or_default())
+	}
+
+	fn get_share_token(assets: AssetPair) -> AssetId {
+		Self::pool_for_pair(assets)
+			.and_then(Self::pools)
+			.map(|pool| pool.share_token)
+			.unwrap_or_default()
+	}
+
+	fn get_pool_assets(pool_account_id: &T::AccountId) -> Option<Vec<AssetId>> {
+		Self::pool_id_by_account(pool_account_id)
+			.and_then(Self::pools)
+			.map(|pool| pool.assets.into_inner())
+	}
+
+	fn get_spot_price_unchecked(asset_a: AssetId, asset_b: AssetId, amount: Balance) -> Balance {
+		Self::pool_for_pair(AssetPair {
+			asset_in: asset_a,
+			asset_out: asset_b,
+		})
+		.and_then(|pool_id| Self::calculate_sell(pool_id, asset_a, asset_b, amount).ok())
+		.map(|(amount_out, _)| amount_out)
+		.unwrap_or_else(Balance::zero)
+	}
+
+	/// Validate a sell. Perform all necessary checks and calculations.
+	/// No storage changes are performed yet.
+	///
+	/// Return `AMMTransfer` with all info needed to execute the transaction.
+	fn validate_sell(
+		who: &T::AccountId,
+		assets: AssetPair,
+		amount: Balance,
+		min_bought: Balance,
+		discount: bool,
+	) -> Result<AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>, DispatchError> {
+		ensure!(!discount, Error::<T>::DiscountNotSupported);
+
+		let pool_id = Self::pool_for_pair(assets).ok_or(Error::<T>::PoolNotFound)?;
+
+		Self::validate_pool_sell(who, pool_id, assets.asset_in, assets.asset_out, amount, min_bought)
+	}
+
+	/// Execute sell. validate_sell must be called first.
+	/// Perform necessary storage/state changes.
+	#[transactional]
+	fn execute_sell(transfer: &AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>) -> DispatchResult {
+		let pool_id = Self::pool_for_pair(transfer.assets).ok_or(Error::<T>::PoolNotFound)?;
+
+		Self::execute_pool_sell(pool_id, transfer)
+	}
+
+	/// Validate a buy. Perform all necessary checks and calculations.
+	/// No storage changes are performed yet.
+	///
+	/// Return `AMMTransfer` with all info needed to execute the transaction.
+	fn validate_buy(
+		who: &T::AccountId,
+		assets: AssetPair,
+		amount: Balance,
+		max_limit: Balance,
+		discount: bool,
+	) -> Result<AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>, DispatchError> {
+		ensure!(!discount, Error::<T>::DiscountNotSupported);
+
+		let pool_id = Self::pool_for_pair(assets).ok_or(Error::<T>::PoolNotFound)?;
+
+		Self::validate_pool_buy(who, pool_id, assets.asset_out, assets.asset_in, amount, max_limit)
+	}
+
+	/// Execute buy. validate_buy must be called first.
+	/// Perform necessary storage/state changes.
+	#[transactional]
+	fn execute_buy(transfer: &AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>) -> DispatchResult {
+		let pool_id = Self::pool_for_pair(transfer.assets).ok_or(Error::<T>::PoolNotFound)?;
+
+		Self::execute_pool_buy(pool_id, transfer)
+	}
+
+	fn get_min_trading_limit() -> Balance {
+		T::MinTradingLimit::get()
+	}
+
+	fn get_min_pool_liquidity() -> Balance {
+		T::MinPoolLiquidity::get()
+	}
+
+	fn get_max_in_ratio() -> u128 {
+		T::MaxInRatio::get()
+	}
+
+	fn get_max_out_ratio() -> u128 {
+		T::MaxOutRatio::get()
+	}
+
+	fn get_fee(pool_account_id: &T::AccountId) -> (u32, u32) {
+		Self::pool_id_by_account(pool_account_id)
+			.and_then(Self::pools)
+			.map(|pool| (pool.fee.deconstruct(), Permill::one().deconstruct()))
+			.unwrap_or((0, 1))
+	}
+}
@@ -0,0 +1,252 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2021  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Stableswap invariant math.
+//!
+//! Pool of `n` assets with reserves `x_i` and amplification `A` keeps the invariant `D` given by
+//!
+//! `A * n^n * sum(x_i) + D = A * D * n^n + D^(n+1) / (n^n * prod(x_i))`
+//!
+//! Both the invariant and the reserve of an asset for given invariant are found by Newton's method.
+//! All functions return `None` if the calculation overflows or does not converge.
+
+use primitives::Balance;
+use sp_core::U256;
+use sp_std::prelude::*;
+
+/// Maximum number of Newton's method iterations.
+const MAX_ITERATIONS: usize = 64;
+
+/// Invariant `D` of a pool with given reserves.
+pub fn calculate_d(reserves: &[Balance], amplification: u128) -> Option<Balance> {
+	let n = U256::from(reserves.len());
+	let sum = reserves
+		.iter()
+		.try_fold(U256::zero(), |acc, reserve| acc.checked_add(U256::from(*reserve)))?;
+
+	if sum.is_zero() {
+		return Some(0);
+	}
+
+	if reserves.iter().any(|reserve| *reserve == 0) {
+		return None;
+	}
+
+	let ann = U256::from(amplification).checked_mul(n.checked_pow(n)?)?;
+
+	let mut d = sum;
+
+	for _ in 0..MAX_ITERATIONS {
+		let mut d_p = d;
+		for reserve in reserves.iter() {
+			d_p = d_p.checked_mul(d)?.checked_div(U256::from(*reserve).checked_mul(n)?)?;
+		}
+
+		let d_prev = d;
+
+		let numerator = ann.checked_mul(sum)?.checked_add(d_p.checked_mul(n)?)?.checked_mul(d)?;
+		let denominator = ann
+			.checked_sub(U256::one())?
+			.checked_mul(d)?
+			.checked_add(n.checked_add(U256::one())?.checked_mul(d_p)?)?;
+
+		d = numerator.checked_div(denominator)?;
+
+		if has_converged(d, d_prev) {
+			return d.try_into().ok();
+		}
+	}
+
+	None
+}
+
+/// Reserve of the asset at `index` which keeps invariant `d` given reserves of other assets.
+///
+/// Reserve at `index` in `reserves` is ignored.
+pub fn calculate_y(reserves: &[Balance], index: usize, d: Balance, amplification: u128) -> Option<Balance> {
+	let n = U256::from(reserves.len());
+	let d = U256::from(d);
+	let ann = U256::from(amplification).checked_mul(n.checked_pow(n)?)?;
+
+	let mut c = d;
+	let mut sum = U256::zero();
+
+	for (_, reserve) in reserves.iter().enumerate().filter(|(i, _)| *i != index) {
+		sum = sum.checked_add(U256::from(*reserve))?;
+		c = c.checked_mul(d)?.checked_div(U256::from(*reserve).checked_mul(n)?)?;
+	}
+
+	c = c.checked_mul(d)?.checked_div(ann.checked_mul(n)?)?;
+
+	let b = sum.checked_add(d.checked_div(ann)?)?;
+
+	let mut y = d;
+
+	for _ in 0..MAX_ITERATIONS {
+		let y_prev = y;
+
+		y = y
+			.checked_mul(y)?
+			.checked_add(c)?
+			.checked_div(y.checked_mul(U256::from(2u32))?.checked_add(b)?.checked_sub(d)?)?;
+
+		if has_converged(y, y_prev) {
+			return y.try_into().ok();
+		}
+	}
+
+	None
+}
+
+/// Amount of asset at `index_out` received for `amount_in` of asset at `index_in`, fee not included.
+pub fn calculate_out_given_in(
+	reserves: &[Balance],
+	index_in: usize,
+	index_out: usize,
+	amount_in: Balance,
+	amplification: u128,
+) -> Option<Balance> {
+	let d = calculate_d(reserves, amplification)?;
+
+	let mut new_reserves = reserves.to_vec();
+	new_reserves[index_in] = new_reserves[index_in].checked_add(amount_in)?;
+
+	let y = calculate_y(&new_reserves, index_out, d, amplification)?;
+
+	// Round in favour of the pool.
+	reserves[index_out].checked_sub(y)?.checked_sub(1)
+}
+
+/// Amount of asset at `index_in` needed to receive `amount_out` of asset at `index_out`, fee not included.
+pub fn calculate_in_given_out(
+	reserves: &[Balance],
+	index_in: usize,
+	index_out: usize,
+	amount_out: Balance,
+	amplification: u128,
+) -> Option<Balance> {
+	let d = calculate_d(reserves, amplification)?;
+
+	let mut new_reserves = reserves.to_vec();
+	new_reserves[index_out] = new_reserves[index_out].checked_sub(amount_out)?;
+
+	let y = calculate_y(&new_reserves, index_in, d, amplification)?;
+
+	// Round in favour of the pool.
+	y.checked_sub(reserves[index_in])?.checked_add(1)
+}
+
+/// Amount of shares issued for adding `amounts` to a pool with `reserves` and `total_shares`.
+///
+/// Deposits which change the ratio of reserves pay `fee` on the difference from a balanced deposit,
+/// scaled by `n / (4 * (n - 1))`. The fee stays in the pool.
+pub fn calculate_shares_for_amounts(
+	reserves: &[Balance],
+	amounts: &[Balance],
+	total_shares: Balance,
+	amplification: u128,
+	fee: sp_runtime::Permill,
+) -> Option<Balance> {
+	let new_reserves = reserves
+		.iter()
+		.zip(amounts.iter())
+		.map(|(reserve, amount)| reserve.checked_add(*amount))
+		.collect::<Option<Vec<Balance>>>()?;
+
+	let d1 = calculate_d(&new_reserves, amplification)?;
+
+	if total_shares == 0 {
+		return Some(d1);
+	}
+
+	let d0 = calculate_d(reserves, amplification)?;
+
+	if d1 <= d0 {
+		return None;
+	}
+
+	let n = reserves.len() as u128;
+	let imbalance_fee = fee.deconstruct() as u128 * n / (4 * (n - 1));
+
+	let adjusted_reserves = reserves
+		.iter()
+		.zip(new_reserves.iter())
+		.map(|(reserve, new_reserve)| {
+			let ideal = U256::from(d1)
+				.checked_mul(U256::from(*reserve))?
+				.checked_div(U256::from(d0))?;
+			let new_reserve = U256::from(*new_reserve);
+			let difference = if ideal > new_reserve {
+				ideal - new_reserve
+			} else {
+				new_reserve - ideal
+			};
+			let fee_amount = difference
+				.checked_mul(U256::from(imbalance_fee))?
+				.checked_div(U256::from(1_000_000u32))?;
+
+			new_reserve.checked_sub(fee_amount)?.try_into().ok()
+		})
+		.collect::<Option<Vec<Balance>>>()?;
+
+	let d2 = calculate_d(&adjusted_reserves, amplification)?;
+
+	if d2 <= d0 {
+		return Some(0);
+	}
+
+	U256::from(total_shares)
+		.checked_mul(U256::from(d2 - d0))?
+		.checked_div(U256::from(d0))?
+		.try_into()
+		.ok()
+}
+
+/// Amplification of a pool at block `now` while it changes linearly from `initial` at `initial_block`
+/// to `final` at `final_block`.
+pub fn calculate_amplification(
+	initial: u128,
+	final_amplification: u128,
+	initial_block: u128,
+	final_block: u128,
+	now: u128,
+) -> u128 {
+	if now >= final_block || final_block <= initial_block {
+		return final_amplification;
+	}
+
+	if now <= initial_block {
+		return initial;
+	}
+
+	let elapsed = now - initial_block;
+	let duration = final_block - initial_block;
+
+	if final_amplification > initial {
+		initial + (final_amplification - initial) * elapsed / duration
+	} else {
+		initial - (initial - final_amplification) * elapsed / duration
+	}
+}
+
+fn has_converged(value: U256, previous: U256) -> bool {
+	if value > previous {
+		value - previous <= U256::one()
+	} else {
+		previous - value <= U256::one()
+	}
+}
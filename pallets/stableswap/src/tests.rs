@@ -0,0 +1,404 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2021  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+pub use crate::mock::{
+	Currency, Event as TestEvent, ExtBuilder, Origin, Stableswap, System, Test, ALICE, BOB, DAI, HDX, ONE, USDC, USDT,
+};
+use frame_support::{assert_noop, assert_ok};
+use hydradx_traits::pools::SpotPriceProvider;
+use hydradx_traits::AMM as AmmPool;
+use primitives::Price;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext = ExtBuilder::default().build();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+fn expect_events(e: Vec<TestEvent>) {
+	e.into_iter().for_each(frame_system::Pallet::<Test>::assert_has_event);
+}
+
+const FEE: Permill = Permill::from_parts(3_000);
+
+/// Creates pool of DAI, USDC and USDT with amplification 100 and 1000 ONE of each asset provided by ALICE.
+fn create_funded_pool() -> PoolId {
+	assert_ok!(Stableswap::create_pool(Origin::root(), vec![USDT, DAI, USDC], 100, FEE));
+
+	let pool_id = Stableswap::pool_count();
+
+	assert_ok!(Stableswap::add_liquidity(
+		Origin::signed(ALICE),
+		pool_id,
+		vec![(DAI, 1_000 * ONE), (USDC, 1_000 * ONE), (USDT, 1_000 * ONE)]
+	));
+
+	pool_id
+}
+
+#[test]
+fn create_pool_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Stableswap::create_pool(Origin::root(), vec![USDT, DAI, USDC], 100, FEE));
+
+		let pool = Stableswap::pools(1).unwrap();
+		let pool_account = Stableswap::pool_account(1);
+
+		assert_eq!(pool.assets.into_inner(), vec![DAI, USDC, USDT]);
+		assert_eq!(pool.initial_amplification, 100);
+		assert_eq!(pool.final_amplification, 100);
+		assert_eq!(pool.fee, FEE);
+
+		assert_eq!(Stableswap::pool_count(), 1);
+		assert_eq!(Stableswap::pool_id_by_account(&pool_account), Some(1));
+		assert_eq!(Stableswap::pair_pool(DAI, USDC), Some(1));
+		assert_eq!(Stableswap::pair_pool(DAI, USDT), Some(1));
+		assert_eq!(Stableswap::pair_pool(USDC, USDT), Some(1));
+
+		expect_events(vec![Event::PoolCreated(
+			1,
+			vec![DAI, USDC, USDT],
+			100,
+			FEE,
+			pool.share_token,
+			pool_account,
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn create_pool_with_invalid_params_should_not_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Stableswap::create_pool(Origin::signed(ALICE), vec![DAI, USDC], 100, FEE),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Stableswap::create_pool(Origin::root(), vec![DAI, DAI], 100, FEE),
+			Error::<Test>::IncorrectAssets
+		);
+		assert_noop!(
+			Stableswap::create_pool(Origin::root(), vec![HDX, DAI, USDC, USDT, 5_000], 100, FEE),
+			Error::<Test>::MaxAssetsExceeded
+		);
+		assert_noop!(
+			Stableswap::create_pool(Origin::root(), vec![DAI, USDC], 0, FEE),
+			Error::<Test>::InvalidAmplification
+		);
+		assert_noop!(
+			Stableswap::create_pool(Origin::root(), vec![DAI, USDC], 10_001, FEE),
+			Error::<Test>::InvalidAmplification
+		);
+		assert_noop!(
+			Stableswap::create_pool(Origin::root(), vec![DAI, USDC], 100, Permill::one()),
+			Error::<Test>::InvalidFee
+		);
+	});
+}
+
+#[test]
+fn add_initial_liquidity_should_work() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_funded_pool();
+
+		let pool = Stableswap::pools(pool_id).unwrap();
+		let pool_account = Stableswap::pool_account(pool_id);
+
+		assert_eq!(Currency::free_balance(pool.share_token, &ALICE), 3_000 * ONE);
+		assert_eq!(Currency::free_balance(DAI, &ALICE), 999_000 * ONE);
+		assert_eq!(Currency::free_balance(DAI, &pool_account), 1_000 * ONE);
+		assert_eq!(Currency::free_balance(USDC, &pool_account), 1_000 * ONE);
+		assert_eq!(Currency::free_balance(USDT, &pool_account), 1_000 * ONE);
+
+		expect_events(vec![Event::LiquidityAdded(
+			ALICE,
+			pool_id,
+			vec![(DAI, 1_000 * ONE), (USDC, 1_000 * ONE), (USDT, 1_000 * ONE)],
+			3_000 * ONE,
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn add_initial_liquidity_without_all_assets_should_not_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Stableswap::create_pool(Origin::root(), vec![DAI, USDC, USDT], 100, FEE));
+
+		assert_noop!(
+			Stableswap::add_liquidity(Origin::signed(ALICE), 1, vec![(DAI, 1_000 * ONE), (USDC, 1_000 * ONE)]),
+			Error::<Test>::InvalidInitialLiquidity
+		);
+		assert_noop!(
+			Stableswap::add_liquidity(Origin::signed(ALICE), 1, vec![(DAI, ONE), (HDX, ONE), (USDT, ONE)]),
+			Error::<Test>::AssetNotInPool
+		);
+		assert_noop!(
+			Stableswap::add_liquidity(Origin::signed(ALICE), 2, vec![(DAI, ONE)]),
+			Error::<Test>::PoolNotFound
+		);
+	});
+}
+
+#[test]
+fn add_imbalanced_liquidity_should_pay_fee() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_funded_pool();
+		let share_token = Stableswap::pools(pool_id).unwrap().share_token;
+
+		assert_ok!(Stableswap::add_liquidity(
+			Origin::signed(BOB),
+			pool_id,
+			vec![(DAI, 100 * ONE), (USDC, 100 * ONE), (USDT, 100 * ONE)]
+		));
+		assert_eq!(Currency::free_balance(share_token, &BOB), 300 * ONE);
+
+		assert_ok!(Stableswap::add_liquidity(
+			Origin::signed(ALICE),
+			pool_id,
+			vec![(DAI, 100 * ONE)]
+		));
+		// Less than 100 ONE of shares due to slippage and imbalance fee
+		assert_eq!(
+			Currency::free_balance(share_token, &ALICE),
+			3_000 * ONE + 99_846_794_960_737
+		);
+	});
+}
+
+#[test]
+fn remove_liquidity_should_work() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_funded_pool();
+		let share_token = Stableswap::pools(pool_id).unwrap().share_token;
+
+		assert_ok!(Stableswap::remove_liquidity(Origin::signed(ALICE), pool_id, 300 * ONE));
+
+		assert_eq!(Currency::free_balance(share_token, &ALICE), 2_700 * ONE);
+		assert_eq!(Currency::free_balance(DAI, &ALICE), 999_100 * ONE);
+		assert_eq!(Currency::free_balance(USDC, &ALICE), 999_100 * ONE);
+		assert_eq!(Currency::free_balance(USDT, &ALICE), 999_100 * ONE);
+
+		expect_events(vec![Event::LiquidityRemoved(
+			ALICE,
+			pool_id,
+			300 * ONE,
+			vec![(DAI, 100 * ONE), (USDC, 100 * ONE), (USDT, 100 * ONE)],
+		)
+		.into()]);
+
+		assert_noop!(
+			Stableswap::remove_liquidity(Origin::signed(ALICE), pool_id, 2_700 * ONE - 1),
+			Error::<Test>::InsufficientLiquidity
+		);
+		assert_noop!(
+			Stableswap::remove_liquidity(Origin::signed(BOB), pool_id, ONE),
+			Error::<Test>::InsufficientShares
+		);
+
+		assert_ok!(Stableswap::remove_liquidity(
+			Origin::signed(ALICE),
+			pool_id,
+			2_700 * ONE
+		));
+
+		assert_eq!(Currency::total_issuance(share_token), 0);
+		assert_eq!(Currency::free_balance(DAI, &ALICE), 1_000_000 * ONE);
+	});
+}
+
+#[test]
+fn sell_should_work() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_funded_pool();
+
+		assert_ok!(Stableswap::sell(Origin::signed(BOB), pool_id, DAI, USDC, ONE, 0));
+
+		assert_eq!(Currency::free_balance(DAI, &BOB), 999_999 * ONE);
+		assert_eq!(
+			Currency::free_balance(USDC, &BOB),
+			1_000_000 * ONE - ONE + 996_998_893_452
+		);
+
+		expect_events(vec![Event::SellExecuted(
+			BOB,
+			pool_id,
+			DAI,
+			USDC,
+			ONE,
+			996_998_893_452,
+			2_999_996_670,
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn buy_should_work() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_funded_pool();
+
+		assert_ok!(Stableswap::buy(Origin::signed(BOB), pool_id, USDC, DAI, ONE, 2 * ONE));
+
+		assert_eq!(Currency::free_balance(DAI, &BOB), 1_000_000 * ONE - 1_003_001_113_210);
+		assert_eq!(Currency::free_balance(USDC, &BOB), 1_000_001 * ONE);
+
+		expect_events(vec![Event::BuyExecuted(
+			BOB,
+			pool_id,
+			USDC,
+			DAI,
+			ONE,
+			1_003_001_113_210,
+			3_000_003_329,
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn trade_limits_should_be_respected() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_funded_pool();
+
+		assert_noop!(
+			Stableswap::sell(Origin::signed(BOB), pool_id, DAI, USDC, ONE, ONE),
+			Error::<Test>::BuyLimitNotReached
+		);
+		assert_noop!(
+			Stableswap::buy(Origin::signed(BOB), pool_id, USDC, DAI, ONE, ONE),
+			Error::<Test>::SellLimitExceeded
+		);
+		assert_noop!(
+			Stableswap::sell(Origin::signed(BOB), pool_id, DAI, USDC, 999, 0),
+			Error::<Test>::InsufficientTradingAmount
+		);
+		assert_noop!(
+			Stableswap::sell(Origin::signed(BOB), pool_id, DAI, USDC, 334 * ONE, 0),
+			Error::<Test>::MaxInRatioExceeded
+		);
+		assert_noop!(
+			Stableswap::buy(Origin::signed(BOB), pool_id, USDC, DAI, 334 * ONE, 1_000 * ONE),
+			Error::<Test>::MaxOutRatioExceeded
+		);
+		assert_noop!(
+			Stableswap::sell(Origin::signed(BOB), pool_id, DAI, DAI, ONE, 0),
+			Error::<Test>::SameAssetTrade
+		);
+		assert_noop!(
+			Stableswap::sell(Origin::signed(BOB), pool_id, HDX, DAI, ONE, 0),
+			Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn amm_trait_should_route_trades_to_pool() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_funded_pool();
+		let pool_account = Stableswap::pool_account(pool_id);
+
+		let assets = AssetPair {
+			asset_in: DAI,
+			asset_out: USDC,
+		};
+
+		assert!(Stableswap::exists(assets));
+		assert!(!Stableswap::exists(AssetPair {
+			asset_in: HDX,
+			asset_out: USDC,
+		}));
+		assert_eq!(Stableswap::get_pair_id(assets), pool_account);
+		assert_eq!(Stableswap::get_pool_assets(&pool_account), Some(vec![DAI, USDC, USDT]));
+		assert_eq!(Stableswap::get_fee(&pool_account), (3_000, 1_000_000));
+
+		let transfer = Stableswap::validate_sell(&BOB, assets, ONE, 0, false).unwrap();
+
+		assert_eq!(transfer.amount_out, 996_998_893_452);
+		assert_eq!(transfer.fee, (USDC, 2_999_996_670));
+
+		assert_ok!(Stableswap::execute_sell(&transfer));
+		assert_eq!(
+			Currency::free_balance(USDC, &BOB),
+			1_000_000 * ONE - ONE + 996_998_893_452
+		);
+
+		let transfer = Stableswap::validate_buy(&BOB, assets, ONE, 2 * ONE, false).unwrap();
+
+		assert_eq!(transfer.amount_out, 1_000_003_326_320);
+		assert_eq!(transfer.fee, (DAI, 3_000_009_978));
+
+		assert_noop!(
+			Stableswap::validate_sell(&BOB, assets, ONE, 0, true),
+			Error::<Test>::DiscountNotSupported
+		);
+	});
+}
+
+#[test]
+fn spot_price_should_work() {
+	new_test_ext().execute_with(|| {
+		create_funded_pool();
+
+		assert!(StableswapSpotPrice::<Test>::pair_exists(DAI, USDT));
+		assert_eq!(
+			StableswapSpotPrice::<Test>::spot_price(DAI, USDT),
+			Some(Price::from_inner(999_999_998_000_000_000))
+		);
+		assert_eq!(StableswapSpotPrice::<Test>::spot_price(DAI, HDX), None);
+	});
+}
+
+#[test]
+fn update_amplification_should_change_amplification_gradually() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_funded_pool();
+
+		assert_noop!(
+			Stableswap::update_amplification(Origin::root(), pool_id, 10, 0, 100),
+			Error::<Test>::InvalidAmplificationChange
+		);
+		assert_noop!(
+			Stableswap::update_amplification(Origin::root(), pool_id, 10, 10, 10),
+			Error::<Test>::InvalidAmplificationChange
+		);
+
+		assert_ok!(Stableswap::update_amplification(Origin::root(), pool_id, 10, 1, 101));
+
+		expect_events(vec![
+			Event::AmplificationChangeScheduled(pool_id, 100, 10, 1, 101).into()
+		]);
+
+		let pool = Stableswap::pools(pool_id).unwrap();
+
+		System::set_block_number(51);
+		assert_eq!(Stableswap::current_amplification(&pool), 55);
+		assert_eq!(
+			Stableswap::calculate_sell(pool_id, DAI, USDC, ONE).unwrap().0,
+			999_997_983_873
+		);
+
+		System::set_block_number(101);
+		assert_eq!(Stableswap::current_amplification(&pool), 10);
+		assert_eq!(
+			Stableswap::calculate_sell(pool_id, DAI, USDC, ONE).unwrap().0,
+			999_989_011_098
+		);
+	});
+}
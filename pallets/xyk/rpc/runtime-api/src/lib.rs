@@ -27,6 +27,7 @@ use codec::{Codec, Decode, Encode};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use sp_runtime::traits::{MaybeDisplay, MaybeFromStr};
+use sp_runtime::{FixedU128, Permill};
 use sp_std::prelude::*;
 
 #[derive(Eq, PartialEq, Encode, Decode, Default)]
@@ -42,6 +43,127 @@ pub struct BalanceInfo<AssetId, Balance> {
 	pub asset: Option<AssetId>,
 }
 
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct TradeQuote<Balance> {
+	/// Amount received in sell or amount paid in buy, fee included.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub amount: Balance,
+
+	/// Fee charged for the trade.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub fee: Balance,
+
+	/// Relative difference between the spot price and the execution price of the trade.
+	pub price_impact: Permill,
+
+	/// Trade would be rejected because traded amount is lower than `MinTradingLimit`.
+	pub below_min_trading_limit: bool,
+
+	/// Trade would be rejected because sold amount exceeds `MaxInRatio` of the pool reserve.
+	pub max_in_ratio_exceeded: bool,
+
+	/// Trade would be rejected because bought amount exceeds `MaxOutRatio` of the pool reserve.
+	pub max_out_ratio_exceeded: bool,
+
+	/// Trading fee rate of the pool applied to the trade.
+	pub fee_rate: (u32, u32),
+}
+
+#[derive(Eq, PartialEq, Encode, Decode, Clone, Copy)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum PoolState {
+	/// All operations are allowed.
+	Active,
+
+	/// Trading is not allowed, liquidity can be added and removed.
+	TradingPaused,
+
+	/// Only removing liquidity is allowed.
+	WithdrawOnly,
+}
+
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PoolInfo<AccountId, AssetId, Balance> {
+	/// Account of the pool.
+	pub pool_account: AccountId,
+
+	/// First asset of the pool.
+	pub asset_a: AssetId,
+
+	/// Second asset of the pool.
+	pub asset_b: AssetId,
+
+	/// Reserve of the first asset.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub asset_a_reserve: Balance,
+
+	/// Reserve of the second asset.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub asset_b_reserve: Balance,
+
+	/// Share token of the pool.
+	pub share_token: AssetId,
+
+	/// Total amount of issued shares.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub total_shares: Balance,
+}
+
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct LiquidityPosition<AccountId, AssetId, Balance> {
+	/// Account of the pool.
+	pub pool_account: AccountId,
+
+	/// First asset of the pool.
+	pub asset_a: AssetId,
+
+	/// Second asset of the pool.
+	pub asset_b: AssetId,
+
+	/// Amount of shares held by the account.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub shares: Balance,
+
+	/// Amount of the first asset the shares are currently worth.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub asset_a_amount: Balance,
+
+	/// Amount of the second asset the shares are currently worth.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub asset_b_amount: Balance,
+}
+
 #[cfg(feature = "std")]
 fn serialize_as_string<S: Serializer, T: std::fmt::Display>(t: &T, serializer: S) -> Result<S::Ok, S::Error> {
 	serializer.serialize_str(&t.to_string())
@@ -68,5 +190,37 @@ sp_api::decl_runtime_apis! {
 			asset_a: AssetId,
 			asset_b: AssetId
 		) -> AccountId;
+
+		fn quote_sell(
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount: Balance,
+		) -> Option<TradeQuote<Balance>>;
+
+		fn quote_buy(
+			asset_out: AssetId,
+			asset_in: AssetId,
+			amount: Balance,
+		) -> Option<TradeQuote<Balance>>;
+
+		fn get_twap(
+			asset_a: AssetId,
+			asset_b: AssetId,
+			period: u32,
+		) -> Option<FixedU128>;
+
+		fn get_pool_state(
+			asset_a: AssetId,
+			asset_b: AssetId,
+		) -> Option<PoolState>;
+
+		fn get_pools(
+			start_after: Option<AccountId>,
+			limit: u32,
+		) -> Vec<PoolInfo<AccountId, AssetId, Balance>>;
+
+		fn get_liquidity_positions(
+			who: AccountId,
+		) -> Vec<LiquidityPosition<AccountId, AssetId, Balance>>;
 	}
 }
@@ -20,13 +20,14 @@
 use codec::Codec;
 use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
 use jsonrpc_derive::rpc;
-use pallet_xyk_rpc_runtime_api::BalanceInfo;
+use pallet_xyk_rpc_runtime_api::{BalanceInfo, LiquidityPosition, PoolInfo, PoolState, TradeQuote};
 use serde::{Deserialize, Serialize};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_runtime::{
 	generic::BlockId,
 	traits::{Block as BlockT, MaybeDisplay, MaybeFromStr},
+	FixedU128,
 };
 use std::sync::Arc;
 
@@ -41,12 +42,57 @@ pub struct BalanceRequest<Balance> {
 }
 
 #[rpc]
-pub trait XYKApi<BlockHash, AccountId, AssetId, Balance, ResponseType> {
+pub trait XYKApi<BlockHash, AccountId, AssetId, Balance, ResponseType, QuoteType> {
 	#[rpc(name = "xyk_getPoolBalances")]
 	fn get_pool_balances(&self, pool_address: AccountId, at: Option<BlockHash>) -> Result<Vec<ResponseType>>;
 
 	#[rpc(name = "xyk_getPoolAccount")]
 	fn get_pool_id(&self, asset_a: AssetId, asset_b: AssetId) -> Result<AccountId>;
+
+	#[rpc(name = "xyk_quoteSell")]
+	fn quote_sell(
+		&self,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount: Balance,
+		at: Option<BlockHash>,
+	) -> Result<Option<QuoteType>>;
+
+	#[rpc(name = "xyk_quoteBuy")]
+	fn quote_buy(
+		&self,
+		asset_out: AssetId,
+		asset_in: AssetId,
+		amount: Balance,
+		at: Option<BlockHash>,
+	) -> Result<Option<QuoteType>>;
+
+	#[rpc(name = "xyk_getTwap")]
+	fn get_twap(
+		&self,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		period: u32,
+		at: Option<BlockHash>,
+	) -> Result<Option<FixedU128>>;
+
+	#[rpc(name = "xyk_getPoolState")]
+	fn get_pool_state(&self, asset_a: AssetId, asset_b: AssetId, at: Option<BlockHash>) -> Result<Option<PoolState>>;
+
+	#[rpc(name = "xyk_getPools")]
+	fn get_pools(
+		&self,
+		start_after: Option<AccountId>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> Result<Vec<PoolInfo<AccountId, AssetId, Balance>>>;
+
+	#[rpc(name = "xyk_getLiquidityPositions")]
+	fn get_liquidity_positions(
+		&self,
+		who: AccountId,
+		at: Option<BlockHash>,
+	) -> Result<Vec<LiquidityPosition<AccountId, AssetId, Balance>>>;
 }
 
 /// A struct that implements the [`XYKApi`].
@@ -79,7 +125,8 @@ impl From<Error> for i64 {
 }
 
 impl<C, Block, AccountId, AssetId, Balance>
-	XYKApi<<Block as BlockT>::Hash, AccountId, AssetId, Balance, BalanceInfo<AssetId, Balance>> for XYK<C, Block>
+	XYKApi<<Block as BlockT>::Hash, AccountId, AssetId, Balance, BalanceInfo<AssetId, Balance>, TradeQuote<Balance>>
+	for XYK<C, Block>
 where
 	Block: BlockT,
 	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
@@ -115,4 +162,114 @@ where
 			data: Some(format!("{:?}", e).into()),
 		})
 	}
+
+	fn quote_sell(
+		&self,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<TradeQuote<Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.quote_sell(&at, asset_in, asset_out, amount).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to quote sell.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn quote_buy(
+		&self,
+		asset_out: AssetId,
+		asset_in: AssetId,
+		amount: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<TradeQuote<Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.quote_buy(&at, asset_out, asset_in, amount).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to quote buy.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_twap(
+		&self,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		period: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<FixedU128>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_twap(&at, asset_a, asset_b, period).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to retrieve time-weighted average price.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_pool_state(
+		&self,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<PoolState>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_pool_state(&at, asset_a, asset_b).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to retrieve pool state.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_pools(
+		&self,
+		start_after: Option<AccountId>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<PoolInfo<AccountId, AssetId, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_pools(&at, start_after, limit).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to retrieve pools.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_liquidity_positions(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<LiquidityPosition<AccountId, AssetId, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_liquidity_positions(&at, who).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to retrieve liquidity positions.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
 }
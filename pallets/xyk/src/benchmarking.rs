@@ -36,6 +36,32 @@ fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
 	caller
 }
 
+fn funded_route<T: Config>(
+	maker: &T::AccountId,
+	caller: &T::AccountId,
+	hops: u32,
+) -> Result<Vec<AssetId>, DispatchError> {
+	let route: Vec<AssetId> = (0..=hops).map(|i| 1_000 + i).collect();
+
+	for asset in route.iter() {
+		T::Currency::update_balance(*asset, maker, 1_000_000_000_000_000)?;
+		T::Currency::update_balance(*asset, caller, 1_000_000_000_000_000)?;
+	}
+
+	for pair in route.windows(2) {
+		XYK::<T>::create_pool(
+			RawOrigin::Signed(maker.clone()).into(),
+			pair[0],
+			pair[1],
+			1_000_000_000_000,
+			Price::from(1),
+			None,
+		)?;
+	}
+
+	Ok(route)
+}
+
 benchmarks! {
 	create_pool {
 		let caller = funded_account::<T>("caller", 0);
@@ -45,7 +71,7 @@ benchmarks! {
 		let amount : Balance = 10 * 1_000_000_000;
 		let initial_price : Price = Price::from(2);
 
-	}: _(RawOrigin::Signed(caller.clone()), asset_a, asset_b, amount, initial_price)
+	}: _(RawOrigin::Signed(caller.clone()), asset_a, asset_b, amount, initial_price, None)
 	verify {
 		assert_eq!(T::Currency::free_balance(asset_a, &caller), 999990000000000);
 	}
@@ -59,7 +85,7 @@ benchmarks! {
 		let amount : Balance = 10 * 1_000_000_000;
 		let max_limit : Balance = 10 * 1_000_000_000_000;
 
-		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), asset_a,asset_b, 1_000_000_000, Price::from(1))?;
+		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), asset_a,asset_b, 1_000_000_000, Price::from(1), None)?;
 
 	}: _(RawOrigin::Signed(caller.clone()), asset_a, asset_b, amount, max_limit)
 	verify {
@@ -75,7 +101,7 @@ benchmarks! {
 		let asset_b: AssetId = 2;
 		let amount : Balance = 1_000_000_000;
 
-		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), 1, 2, 10_000_000_000, Price::from(2))?;
+		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), 1, 2, 10_000_000_000, Price::from(2), None)?;
 		XYK::<T>::add_liquidity(RawOrigin::Signed(caller.clone()).into(), 1, 2, 5_000_000_000, 10_000_000_000)?;
 
 		assert_eq!(T::Currency::free_balance(asset_a, &caller), 999995000000000);
@@ -87,6 +113,42 @@ benchmarks! {
 		assert_eq!(T::Currency::free_balance(asset_b, &caller), 999992000000000);
 	}
 
+	add_liquidity_single_asset {
+		let maker = funded_account::<T>("maker", 0);
+		let caller = funded_account::<T>("caller", 0);
+
+		let asset_a: AssetId = 1;
+		let asset_b: AssetId = 2;
+		let amount : Balance = 10 * 1_000_000_000;
+
+		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), asset_a, asset_b, 1_000_000_000_000, Price::from(1), None)?;
+
+		let pair_account = XYK::<T>::pair_account_from_assets(asset_a, asset_b);
+		let share_token = XYK::<T>::share_token(&pair_account);
+
+	}: _(RawOrigin::Signed(caller.clone()), asset_a, asset_b, amount, 0)
+	verify {
+		assert!(T::Currency::free_balance(asset_a, &caller) < 1_000_000_000_000_000);
+		assert!(!T::Currency::free_balance(share_token, &caller).is_zero());
+	}
+
+	remove_liquidity_to_single_asset {
+		let maker = funded_account::<T>("maker", 0);
+		let caller = funded_account::<T>("caller", 0);
+
+		let asset_a: AssetId = 1;
+		let asset_b: AssetId = 2;
+		let amount : Balance = 1_000_000_000;
+
+		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), asset_a, asset_b, 10_000_000_000, Price::from(2), None)?;
+		XYK::<T>::add_liquidity(RawOrigin::Signed(caller.clone()).into(), asset_a, asset_b, 5_000_000_000, 10_000_000_000)?;
+
+	}: _(RawOrigin::Signed(caller.clone()), asset_a, asset_b, amount, 0)
+	verify {
+		assert!(T::Currency::free_balance(asset_a, &caller) > 999995000000000);
+		assert_eq!(T::Currency::free_balance(asset_b, &caller), 999990000000000);
+	}
+
 	sell {
 		let maker = funded_account::<T>("maker", 0);
 		let caller = funded_account::<T>("caller", 0);
@@ -98,7 +160,7 @@ benchmarks! {
 
 		let min_bought: Balance = 10 * 1_000;
 
-		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), asset_a, asset_b, 1_000_000_000_000, Price::from(3))?;
+		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), asset_a, asset_b, 1_000_000_000_000, Price::from(3), None)?;
 
 	}: _(RawOrigin::Signed(caller.clone()), asset_a, asset_b, amount, min_bought, discount)
 	verify{
@@ -117,13 +179,115 @@ benchmarks! {
 
 		let max_sold: Balance = 6_000_000_000;
 
-		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), asset_a, asset_b, 1_000_000_000_000, Price::from(3))?;
+		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), asset_a, asset_b, 1_000_000_000_000, Price::from(3), None)?;
 
 	}: _(RawOrigin::Signed(caller.clone()), asset_a, asset_b, amount, max_sold, discount)
 	verify{
 		assert_eq!(T::Currency::free_balance(asset_a, &caller), 1000001000000000);
 		assert_eq!(T::Currency::free_balance(asset_b, &caller), 999996990990990);
 	}
+
+	set_pool_fee {
+		let maker = funded_account::<T>("maker", 0);
+
+		let asset_a: AssetId = 1;
+		let asset_b: AssetId = 2;
+		let fee = (3, 1_000);
+
+		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), asset_a, asset_b, 1_000_000_000_000, Price::from(3), None)?;
+
+		let pair_account = XYK::<T>::pair_account_from_assets(asset_a, asset_b);
+
+	}: _(RawOrigin::Root, asset_a, asset_b, fee)
+	verify{
+		assert_eq!(XYK::<T>::get_fee(&pair_account), fee);
+	}
+
+	set_dynamic_fee {
+		let maker = funded_account::<T>("maker", 0);
+
+		let asset_a: AssetId = 1;
+		let asset_b: AssetId = 2;
+		let params = DynamicFeeParams {
+			min_fee: Permill::from_parts(1_000),
+			max_fee: Permill::from_percent(1),
+			amplification: 2,
+			decay: Permill::from_percent(10),
+		};
+
+		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), asset_a, asset_b, 1_000_000_000_000, Price::from(3), None)?;
+
+		let pair_account = XYK::<T>::pair_account_from_assets(asset_a, asset_b);
+
+	}: _(RawOrigin::Root, asset_a, asset_b, Some(params))
+	verify{
+		assert_eq!(XYK::<T>::dynamic_fee(&pair_account), Some(params));
+	}
+
+	set_pool_state {
+		let maker = funded_account::<T>("maker", 0);
+
+		let asset_a: AssetId = 1;
+		let asset_b: AssetId = 2;
+
+		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), asset_a, asset_b, 1_000_000_000_000, Price::from(3), None)?;
+
+		let pair_account = XYK::<T>::pair_account_from_assets(asset_a, asset_b);
+
+	}: _(RawOrigin::Root, asset_a, asset_b, PoolState::TradingPaused)
+	verify{
+		assert_eq!(XYK::<T>::pool_state(&pair_account), PoolState::TradingPaused);
+	}
+
+	set_circuit_breaker_limits {
+		let max_price_change = Some(Permill::from_percent(1));
+		let max_liquidity_removal = Some(Permill::from_percent(10));
+	}: _(RawOrigin::Root, max_price_change, max_liquidity_removal)
+	verify{
+		assert_eq!(XYK::<T>::max_price_change_per_block(), max_price_change);
+		assert_eq!(XYK::<T>::max_liquidity_removal_per_block(), max_liquidity_removal);
+	}
+
+	set_protocol_fee_share {
+		let share = Permill::from_percent(20);
+	}: _(RawOrigin::Root, share)
+	verify{
+		assert_eq!(XYK::<T>::protocol_fee_share(), share);
+	}
+
+	sell_route {
+		let n in 1 .. T::MaxRouteHops::get();
+
+		let maker = funded_account::<T>("maker", 0);
+		let caller = funded_account::<T>("caller", 0);
+
+		let route = funded_route::<T>(&maker, &caller, n)?;
+
+		let amount : Balance = 1_000_000_000;
+		let min_bought: Balance = 10 * 1_000;
+
+	}: _(RawOrigin::Signed(caller.clone()), route.clone(), amount, min_bought)
+	verify{
+		assert_eq!(T::Currency::free_balance(route[0], &caller), 999999000000000);
+		assert!(T::Currency::free_balance(route[n as usize], &caller) > 1_000_000_000_000_000);
+	}
+
+	buy_route {
+		let n in 1 .. T::MaxRouteHops::get();
+
+		let maker = funded_account::<T>("maker", 0);
+		let caller = funded_account::<T>("caller", 0);
+
+		let route = funded_route::<T>(&maker, &caller, n)?;
+
+		let amount : Balance = 1_000_000_000;
+		let max_sold: Balance = 6_000_000_000;
+
+	}: _(RawOrigin::Signed(caller.clone()), route.clone(), amount, max_sold)
+	verify{
+		assert_eq!(T::Currency::free_balance(route[n as usize], &caller), 1000001000000000);
+		assert!(T::Currency::free_balance(route[0], &caller) < 1_000_000_000_000_000);
+	}
 }
 
 #[cfg(test)]
@@ -138,8 +302,17 @@ mod tests {
 			assert_ok!(Pallet::<Test>::test_benchmark_create_pool());
 			assert_ok!(Pallet::<Test>::test_benchmark_add_liquidity());
 			assert_ok!(Pallet::<Test>::test_benchmark_remove_liquidity());
+			assert_ok!(Pallet::<Test>::test_benchmark_add_liquidity_single_asset());
+			assert_ok!(Pallet::<Test>::test_benchmark_remove_liquidity_to_single_asset());
 			assert_ok!(Pallet::<Test>::test_benchmark_sell());
 			assert_ok!(Pallet::<Test>::test_benchmark_buy());
+			assert_ok!(Pallet::<Test>::test_benchmark_set_pool_fee());
+			assert_ok!(Pallet::<Test>::test_benchmark_set_dynamic_fee());
+			assert_ok!(Pallet::<Test>::test_benchmark_set_pool_state());
+			assert_ok!(Pallet::<Test>::test_benchmark_set_circuit_breaker_limits());
+			assert_ok!(Pallet::<Test>::test_benchmark_set_protocol_fee_share());
+			assert_ok!(Pallet::<Test>::test_benchmark_sell_route());
+			assert_ok!(Pallet::<Test>::test_benchmark_buy_route());
 		});
 	}
 }
@@ -1,8 +1,11 @@
+use crate::TwapProvider;
+use frame_support::traits::Get;
 use hydradx_traits::pools::SpotPriceProvider;
 use hydradx_traits::AMM;
 use orml_traits::MultiCurrency;
 use primitives::asset::AssetPair;
 use primitives::{AssetId, Price};
+use sp_runtime::traits::{CheckedMul, Zero};
 use sp_std::marker::PhantomData;
 
 pub struct XYKSpotPrice<T>(PhantomData<T>);
@@ -29,3 +32,59 @@ impl<T: crate::Config> SpotPriceProvider<AssetId> for XYKSpotPrice<T> {
 		}
 	}
 }
+
+/// Spot price provider which includes trading fee of the pool in the price.
+pub struct XYKSpotPriceWithFee<T>(PhantomData<T>);
+
+impl<T: crate::Config> SpotPriceProvider<AssetId> for XYKSpotPriceWithFee<T> {
+	type Price = Price;
+
+	fn pair_exists(asset_a: AssetId, asset_b: AssetId) -> bool {
+		XYKSpotPrice::<T>::pair_exists(asset_a, asset_b)
+	}
+
+	/// Price of `asset_a` denominated in `asset_b`, including trading fee of the pool.
+	fn spot_price(asset_a: AssetId, asset_b: AssetId) -> Option<Self::Price> {
+		let price = XYKSpotPrice::<T>::spot_price(asset_a, asset_b)?;
+
+		with_pool_fee::<T>(price, &<crate::Pallet<T>>::pair_account_from_assets(asset_a, asset_b))
+	}
+}
+
+/// Spot price provider which uses time-weighted average price over last `Period` blocks
+/// instead of current reserves of the pool.
+pub struct XYKTwapSpotPrice<T, Period>(PhantomData<(T, Period)>);
+
+impl<T: crate::Config, Period: Get<T::BlockNumber>> SpotPriceProvider<AssetId> for XYKTwapSpotPrice<T, Period> {
+	type Price = Price;
+
+	fn pair_exists(asset_a: AssetId, asset_b: AssetId) -> bool {
+		XYKSpotPrice::<T>::pair_exists(asset_a, asset_b)
+	}
+
+	/// Average price of `asset_a` denominated in `asset_b` over last `Period` blocks.
+	fn spot_price(asset_a: AssetId, asset_b: AssetId) -> Option<Self::Price> {
+		<crate::Pallet<T>>::get_twap(asset_a, asset_b, Period::get())
+	}
+}
+
+impl<T: crate::Config> TwapProvider<AssetId, T::BlockNumber> for crate::Pallet<T> {
+	type Price = Price;
+
+	fn twap(asset_a: AssetId, asset_b: AssetId, period: T::BlockNumber) -> Option<Self::Price> {
+		Self::get_twap(asset_a, asset_b, period)
+	}
+}
+
+fn with_pool_fee<T: crate::Config>(price: Price, pair_account: &T::AccountId) -> Option<Price> {
+	let (fee_numerator, fee_denominator) = <crate::Pallet<T>>::get_fee(pair_account);
+
+	if fee_numerator.is_zero() || fee_denominator.is_zero() {
+		Some(price)
+	} else {
+		price.checked_mul(&Price::from((
+			fee_denominator.saturating_add(fee_numerator),
+			fee_denominator,
+		)))
+	}
+}
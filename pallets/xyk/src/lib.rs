@@ -28,11 +28,18 @@
 #![allow(clippy::unused_unit)]
 #![allow(clippy::upper_case_acronyms)]
 
-use frame_support::sp_runtime::{traits::Zero, DispatchError};
-use frame_support::{dispatch::DispatchResult, ensure, traits::Get, transactional};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::sp_runtime::{
+	helpers_128bit::multiply_by_rational,
+	traits::{CheckedSub, Saturating, UniqueSaturatedInto, Zero},
+	DispatchError, Permill,
+};
+use frame_support::{dispatch::DispatchResult, ensure, traits::Get, transactional, RuntimeDebug};
 use frame_system::ensure_signed;
 use hydradx_traits::{AMMTransfer, AssetPairAccountIdFor, CanCreatePool, OnCreatePoolHandler, OnTradeHandler, AMM};
 use primitives::{asset::AssetPair, AssetId, Balance, Price};
+use sp_core::U256;
+use sp_std::convert::TryInto;
 use sp_std::{vec, vec::Vec};
 
 use frame_support::sp_runtime::FixedPointNumber;
@@ -48,15 +55,164 @@ mod tests;
 mod benchmarking;
 
 mod impls;
+mod signed_extensions;
 pub mod weights;
 
-pub use impls::XYKSpotPrice;
+pub use impls::{XYKSpotPrice, XYKSpotPriceWithFee, XYKTwapSpotPrice};
+pub use signed_extensions::{ValidateTradeExpiry, TRADE_EXPIRED};
 
 use weights::WeightInfo;
 
 // Re-export pallet items so that they can be accessed from the crate namespace.
 pub use pallet::*;
 
+/// Outcome of a trade calculated without executing it.
+#[derive(Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct TradeQuote {
+	/// Amount received in sell or amount paid in buy, fee included.
+	pub amount: Balance,
+	/// Fee charged for the trade.
+	pub fee: Balance,
+	/// Relative difference between the spot price and the execution price of the trade.
+	pub price_impact: Permill,
+	/// Traded amount is lower than min trading limit.
+	pub below_min_trading_limit: bool,
+	/// Sold amount exceeds max fraction of the pool reserve.
+	pub max_in_ratio_exceeded: bool,
+	/// Bought amount exceeds max fraction of the pool reserve.
+	pub max_out_ratio_exceeded: bool,
+	/// Trading fee rate of the pool applied to the trade.
+	pub fee_rate: (u32, u32),
+}
+
+/// Description of a pool and its current reserves.
+#[derive(Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct PoolInfo<AccountId> {
+	/// Account of the pool.
+	pub pool_account: AccountId,
+	/// First asset of the pool.
+	pub asset_a: AssetId,
+	/// Second asset of the pool.
+	pub asset_b: AssetId,
+	/// Reserve of the first asset.
+	pub asset_a_reserve: Balance,
+	/// Reserve of the second asset.
+	pub asset_b_reserve: Balance,
+	/// Share token of the pool.
+	pub share_token: AssetId,
+	/// Total amount of issued shares.
+	pub total_shares: Balance,
+}
+
+/// Liquidity provided by an account to a pool.
+#[derive(Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct LiquidityPosition<AccountId> {
+	/// Account of the pool.
+	pub pool_account: AccountId,
+	/// First asset of the pool.
+	pub asset_a: AssetId,
+	/// Second asset of the pool.
+	pub asset_b: AssetId,
+	/// Amount of shares held by the account.
+	pub shares: Balance,
+	/// Amount of the first asset the shares are currently worth.
+	pub asset_a_amount: Balance,
+	/// Amount of the second asset the shares are currently worth.
+	pub asset_b_amount: Balance,
+}
+
+/// Cumulative prices of a pool recorded at given block.
+///
+/// Cumulative price is a sum of prices at the end of each block since pool creation. Prices are
+/// accumulated as inner values of `Price` and are allowed to overflow, only differences
+/// between two observations are meaningful.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo, MaxEncodedLen)]
+pub struct PriceObservation<BlockNumber> {
+	/// Block in which the observation was recorded.
+	pub block: BlockNumber,
+	/// Cumulative price of first pool asset denominated in second pool asset.
+	pub price_a_cumulative: u128,
+	/// Cumulative price of second pool asset denominated in first pool asset.
+	pub price_b_cumulative: u128,
+}
+
+/// Operational state of a pool.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo, MaxEncodedLen)]
+pub enum PoolState {
+	/// All operations are allowed.
+	Active,
+	/// Trading is not allowed, liquidity can be added and removed.
+	TradingPaused,
+	/// Only removing liquidity is allowed.
+	WithdrawOnly,
+}
+
+impl Default for PoolState {
+	fn default() -> Self {
+		PoolState::Active
+	}
+}
+
+/// Parameters of dynamic trading fee of a pool.
+///
+/// Fee rate is `min_fee` increased by `amplification` multiple of the pool volatility and capped at `max_fee`.
+#[derive(Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo, MaxEncodedLen)]
+pub struct DynamicFeeParams {
+	/// Fee rate charged when price of the pool does not move.
+	pub min_fee: Permill,
+	/// Maximum fee rate.
+	pub max_fee: Permill,
+	/// Multiplier of the volatility added to the minimum fee rate.
+	pub amplification: u32,
+	/// Fraction of the volatility forgotten each block.
+	pub decay: Permill,
+}
+
+/// Volatility of a pool price tracked for dynamic fee.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo, MaxEncodedLen)]
+pub struct Volatility<BlockNumber> {
+	/// Block the state belongs to.
+	pub block: BlockNumber,
+	/// Price of first pool asset denominated in second pool asset at the start of the block.
+	pub price: Price,
+	/// Volatility of the pool at the start of the block.
+	pub volatility: Permill,
+}
+
+/// State of a pool at the start of a block tracked by the circuit breaker.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo, MaxEncodedLen)]
+pub struct BlockStartState<BlockNumber> {
+	/// Block the state belongs to.
+	pub block: BlockNumber,
+	/// Price of first pool asset denominated in second pool asset at the start of the block.
+	pub price: Price,
+	/// Total liquidity of the pool at the start of the block.
+	pub liquidity: Balance,
+	/// Amount of liquidity removed from the pool in the block.
+	pub liquidity_removed: Balance,
+}
+
+/// Provider of time-weighted average price of an asset pair.
+pub trait TwapProvider<AssetId, BlockNumber> {
+	type Price;
+
+	/// Average price of `asset_a` denominated in `asset_b` over last `period` blocks.
+	fn twap(asset_a: AssetId, asset_b: AssetId, period: BlockNumber) -> Option<Self::Price>;
+}
+
+/// Handler of share tokens of destroyed pools.
+pub trait ShareTokenCleanup<AssetId> {
+	/// Release `share_token` of destroyed pool of `asset_a` and `asset_b` in the asset registry,
+	/// so a new share token is registered if the pool is created again.
+	fn deregister_share_token(asset_a: AssetId, asset_b: AssetId, share_token: AssetId) -> DispatchResult;
+}
+
+impl<AssetId> ShareTokenCleanup<AssetId> for () {
+	fn deregister_share_token(_asset_a: AssetId, _asset_b: AssetId, _share_token: AssetId) -> DispatchResult {
+		Ok(())
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -77,6 +233,9 @@ pub mod pallet {
 		/// Registry support
 		type AssetRegistry: ShareTokenRegistry<AssetId, Vec<u8>, Balance, DispatchError>;
 
+		/// Called to release share token of destroyed pool
+		type ShareTokenCleanup: ShareTokenCleanup<AssetId>;
+
 		/// Share token support
 		type AssetPairAccountId: AssetPairAccountIdFor<AssetId, Self::AccountId>;
 
@@ -90,10 +249,23 @@ pub mod pallet {
 		/// Weight information for the extrinsics.
 		type WeightInfo: WeightInfo;
 
-		/// Trading fee rate
+		/// Trading fee rate of pools without explicitly set fee
 		#[pallet::constant]
 		type GetExchangeFee: Get<(u32, u32)>;
 
+		/// Trading fee rates which can be chosen for new pools
+		#[pallet::constant]
+		type FeeTiers: Get<Vec<(u32, u32)>>;
+
+		/// Origin allowed to change trading fee rate of a pool and protocol fee share
+		type UpdatePoolFeeOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Origin allowed to change state of a pool and circuit breaker limits
+		type UpdatePoolStateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Account receiving protocol share of trade fees
+		type ProtocolFeeAccount: Get<Self::AccountId>;
+
 		/// Minimum trading limit
 		#[pallet::constant]
 		type MinTradingLimit: Get<Balance>;
@@ -102,6 +274,10 @@ pub mod pallet {
 		#[pallet::constant]
 		type MinPoolLiquidity: Get<Balance>;
 
+		/// Amount of shares permanently locked in the pool account on pool creation
+		#[pallet::constant]
+		type LockedLiquidity: Get<Balance>;
+
 		/// Max fraction of pool to sell in single transaction
 		#[pallet::constant]
 		type MaxInRatio: Get<u128>;
@@ -110,6 +286,14 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxOutRatio: Get<u128>;
 
+		/// Max number of trades executed in single routed trade
+		#[pallet::constant]
+		type MaxRouteHops: Get<u32>;
+
+		/// Max number of price observations stored for a pool
+		#[pallet::constant]
+		type MaxPriceObservations: Get<u32>;
+
 		/// Called to ensure that pool can be created
 		type CanCreatePool: CanCreatePool<AssetId>;
 
@@ -189,6 +373,33 @@ pub mod pallet {
 
 		/// Pool cannot be created due to outside factors.
 		CannotCreatePool,
+
+		/// Trade route must consist of at least two assets and must not contain any asset more than once.
+		InvalidRoute,
+
+		/// Trade route contains more trades than allowed.
+		MaxRouteHopsExceeded,
+
+		/// Trading fee rate is not allowed.
+		InvalidFee,
+
+		/// Last liquidity of the pool cannot be removed to single asset.
+		CannotRemoveAllLiquidityToSingleAsset,
+
+		/// Trading in the pool is not allowed in its current state.
+		TradingNotAllowed,
+
+		/// Adding liquidity to the pool is not allowed in its current state.
+		AddLiquidityNotAllowed,
+
+		/// Trade was not executed before its expiry block.
+		TradeExpired,
+
+		/// Trade would move price of the pool more than allowed in a single block.
+		MaxPriceChangeExceeded,
+
+		/// Removal would exceed the maximum amount of liquidity removed from the pool in a single block.
+		MaxLiquidityRemovalExceeded,
 	}
 
 	#[pallet::event]
@@ -229,6 +440,39 @@ pub mod pallet {
 			Balance,
 			T::AccountId,
 		),
+
+		/// Routed asset sale executed. [who, route, amount sold, amount received]
+		RouteSellExecuted(T::AccountId, Vec<AssetId>, Balance, Balance),
+
+		/// Routed asset purchase executed. [who, route, amount bought, amount paid]
+		RouteBuyExecuted(T::AccountId, Vec<AssetId>, Balance, Balance),
+
+		/// Trading fee rate of a pool was changed. [pool account id, fee]
+		PoolFeeUpdated(T::AccountId, (u32, u32)),
+
+		/// Dynamic fee parameters of a pool were changed. [pool account id, parameters]
+		DynamicFeeUpdated(T::AccountId, Option<DynamicFeeParams>),
+
+		/// Dynamic fee rate was applied to a trade. [pool account id, fee]
+		DynamicFeeApplied(T::AccountId, (u32, u32)),
+
+		/// Liquidity was provided to the pool in single asset. [who, asset, other asset, amount, shares]
+		SingleAssetLiquidityAdded(T::AccountId, AssetId, AssetId, Balance, Balance),
+
+		/// Liquidity was removed from the pool to single asset. [who, asset, other asset, shares, amount]
+		SingleAssetLiquidityRemoved(T::AccountId, AssetId, AssetId, Balance, Balance),
+
+		/// State of a pool was changed. [pool account id, state]
+		PoolStateUpdated(T::AccountId, PoolState),
+
+		/// Circuit breaker limits were changed. [max price change per block, max liquidity removal per block]
+		CircuitBreakerLimitsUpdated(Option<Permill>, Option<Permill>),
+
+		/// Protocol share of trade fees was changed. [protocol fee share]
+		ProtocolFeeShareUpdated(Permill),
+
+		/// Protocol share of trade fee was transferred. [pool account id, fee asset, pool fee, protocol fee]
+		TradeFeeSplit(T::AccountId, AssetId, Balance, Balance),
 	}
 
 	/// Asset id storage for shared pool tokens
@@ -247,6 +491,65 @@ pub mod pallet {
 	pub(crate) type PoolAssets<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AccountId, (AssetId, AssetId), OptionQuery>;
 
+	/// Trading fee rate of a pool. Pools without explicitly set fee use `GetExchangeFee`.
+	#[pallet::storage]
+	#[pallet::getter(fn pool_fee)]
+	pub(crate) type PoolFee<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, (u32, u32), OptionQuery>;
+
+	/// Dynamic fee parameters of a pool. Dynamic fee rate takes precedence over `PoolFee` if set.
+	#[pallet::storage]
+	#[pallet::getter(fn dynamic_fee)]
+	pub(crate) type DynamicFees<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, DynamicFeeParams, OptionQuery>;
+
+	/// Volatility of pools with dynamic fee.
+	#[pallet::storage]
+	#[pallet::getter(fn pool_volatility)]
+	pub(crate) type PoolVolatility<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, Volatility<T::BlockNumber>, OptionQuery>;
+
+	/// Operational state of a pool identified by asset pair account id.
+	#[pallet::storage]
+	#[pallet::getter(fn pool_state)]
+	pub(crate) type PoolStates<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, PoolState, ValueQuery>;
+
+	/// Maximum change of pool price allowed in a single block. Not limited if not set.
+	#[pallet::storage]
+	#[pallet::getter(fn max_price_change_per_block)]
+	pub(crate) type MaxPriceChangePerBlock<T: Config> = StorageValue<_, Permill, OptionQuery>;
+
+	/// Maximum fraction of pool liquidity which can be removed in a single block. Not limited if not set.
+	#[pallet::storage]
+	#[pallet::getter(fn max_liquidity_removal_per_block)]
+	pub(crate) type MaxLiquidityRemovalPerBlock<T: Config> = StorageValue<_, Permill, OptionQuery>;
+
+	/// State of a pool at the start of the block in which it was last changed.
+	#[pallet::storage]
+	#[pallet::getter(fn block_start_states)]
+	pub(crate) type BlockStartStates<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BlockStartState<T::BlockNumber>, OptionQuery>;
+
+	/// Share of each trade fee transferred to the protocol account.
+	#[pallet::storage]
+	#[pallet::getter(fn protocol_fee_share)]
+	pub(crate) type ProtocolFeeShare<T: Config> = StorageValue<_, Permill, ValueQuery>;
+
+	/// Total amount of protocol fees collected per asset.
+	#[pallet::storage]
+	#[pallet::getter(fn protocol_fees_collected)]
+	pub(crate) type ProtocolFeesCollected<T: Config> = StorageMap<_, Blake2_128Concat, AssetId, Balance, ValueQuery>;
+
+	/// Cumulative price observations of a pool, oldest first.
+	#[pallet::storage]
+	#[pallet::getter(fn price_observations)]
+	pub(crate) type PriceObservations<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<PriceObservation<T::BlockNumber>, T::MaxPriceObservations>,
+		ValueQuery,
+	>;
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Create new pool for given asset pair.
@@ -256,6 +559,9 @@ pub mod pallet {
 		///
 		/// Pool is created with initial liquidity provided by `origin`.
 		/// Shares are issued with specified initial price and represents proportion of asset in the pool.
+		/// `LockedLiquidity` of the issued shares is locked in the pool account forever, the rest is issued to `origin`.
+		///
+		/// `fee` - trading fee rate of the pool, must be one of `FeeTiers`. `GetExchangeFee` is used if not provided.
 		///
 		/// Emits `PoolCreated` event when successful.
 		#[pallet::weight(<T as Config>::WeightInfo::create_pool())]
@@ -266,6 +572,7 @@ pub mod pallet {
 			asset_b: AssetId,
 			amount: Balance,
 			initial_price: Price,
+			fee: Option<(u32, u32)>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
@@ -280,6 +587,10 @@ pub mod pallet {
 
 			ensure!(asset_a != asset_b, Error::<T>::CannotCreatePoolWithSameAssets);
 
+			if let Some(fee) = fee {
+				ensure!(T::FeeTiers::get().contains(&fee), Error::<T>::InvalidFee);
+			}
+
 			let asset_pair = AssetPair {
 				asset_in: asset_a,
 				asset_out: asset_b,
@@ -298,6 +609,10 @@ pub mod pallet {
 
 			let shares_added = if asset_a < asset_b { amount } else { asset_b_amount };
 
+			let locked_shares = T::LockedLiquidity::get();
+
+			ensure!(shares_added > locked_shares, Error::<T>::InsufficientLiquidity);
+
 			ensure!(
 				T::Currency::free_balance(asset_a, &who) >= amount,
 				Error::<T>::InsufficientAssetBalance
@@ -323,6 +638,10 @@ pub mod pallet {
 			<ShareToken<T>>::insert(&pair_account, &share_token);
 			<PoolAssets<T>>::insert(&pair_account, (asset_a, asset_b));
 
+			if let Some(fee) = fee {
+				<PoolFee<T>>::insert(&pair_account, fee);
+			}
+
 			Self::deposit_event(Event::PoolCreated(
 				who.clone(),
 				asset_a,
@@ -335,10 +654,13 @@ pub mod pallet {
 			T::Currency::transfer(asset_a, &who, &pair_account, amount)?;
 			T::Currency::transfer(asset_b, &who, &pair_account, asset_b_amount)?;
 
-			T::Currency::deposit(share_token, &who, shares_added)?;
+			T::Currency::deposit(share_token, &pair_account, locked_shares)?;
+			T::Currency::deposit(share_token, &who, shares_added.saturating_sub(locked_shares))?;
 
 			<TotalLiquidity<T>>::insert(&pair_account, shares_added);
 
+			Self::update_price_accumulator(&pair_account);
+
 			Ok(())
 		}
 
@@ -358,170 +680,163 @@ pub mod pallet {
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
+			Self::do_add_liquidity(&who, asset_a, asset_b, amount_a, amount_b_max_limit)?;
+
+			Ok(())
+		}
+
+		/// Remove liquidity from specific liquidity pool in the form of burning shares.
+		///
+		/// If only locked liquidity is left in the pool, it is destroyed. Locked shares are burned
+		/// and any assets left in the pool are transferred to `ProtocolFeeAccount`.
+		///
+		/// Emits 'LiquidityRemoved' when successful.
+		/// Emits 'PoolDestroyed' when pool is destroyed.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_liquidity())]
+		#[transactional]
+		pub fn remove_liquidity(
+			origin: OriginFor<T>,
+			asset_a: AssetId,
+			asset_b: AssetId,
+			liquidity_amount: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::do_remove_liquidity(&who, asset_a, asset_b, liquidity_amount)?;
+
+			Ok(())
+		}
+
+		/// Add liquidity to the pool providing only `asset`.
+		///
+		/// Part of `amount` is sold for `asset_other` first so that the rest can be added to the pool
+		/// together with the bought amount in the current pool ratio. Amounts which could not be added
+		/// due to rounding stay with the caller.
+		///
+		/// `min_shares` - minimum amount of shares to be received.
+		///
+		/// Emits `SellExecuted`, `LiquidityAdded` and `SingleAssetLiquidityAdded` events when successful.
+		#[pallet::weight(
+			<T as Config>::WeightInfo::add_liquidity_single_asset()
+				.saturating_add(<T as Config>::AMMHandler::on_trade_weight())
+		)]
+		#[transactional]
+		pub fn add_liquidity_single_asset(
+			origin: OriginFor<T>,
+			asset: AssetId,
+			asset_other: AssetId,
+			amount: Balance,
+			min_shares: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
 			let asset_pair = AssetPair {
-				asset_in: asset_a,
-				asset_out: asset_b,
+				asset_in: asset,
+				asset_out: asset_other,
 			};
 
 			ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
 
 			ensure!(
-				amount_a >= T::MinTradingLimit::get(),
+				amount >= T::MinTradingLimit::get(),
 				Error::<T>::InsufficientTradingAmount
 			);
 
-			ensure!(!amount_b_max_limit.is_zero(), Error::<T>::ZeroLiquidity);
-
-			ensure!(
-				T::Currency::free_balance(asset_a, &who) >= amount_a,
-				Error::<T>::InsufficientAssetBalance
-			);
-
 			ensure!(
-				T::Currency::free_balance(asset_b, &who) >= amount_b_max_limit,
+				T::Currency::free_balance(asset, &who) >= amount,
 				Error::<T>::InsufficientAssetBalance
 			);
 
 			let pair_account = Self::get_pair_id(asset_pair);
 
-			let share_token = Self::share_token(&pair_account);
-
-			let account_shares = T::Currency::free_balance(share_token, &who);
-
-			let asset_a_reserve = T::Currency::free_balance(asset_a, &pair_account);
-			let asset_b_reserve = T::Currency::free_balance(asset_b, &pair_account);
-			let total_liquidity = Self::total_liquidity(&pair_account);
-
-			let amount_b_required =
-				hydra_dx_math::xyk::calculate_liquidity_in(asset_a_reserve, asset_b_reserve, amount_a)
-					.map_err(|_| Error::<T>::AddAssetAmountInvalid)?;
-
-			let shares_added = if asset_a < asset_b { amount_a } else { amount_b_required };
-
-			ensure!(
-				amount_b_required <= amount_b_max_limit,
-				Error::<T>::AssetAmountExceededLimit
-			);
-
-			ensure!(!shares_added.is_zero(), Error::<T>::InvalidMintedLiquidity);
+			let amount_to_sell = Self::calculate_single_asset_sell_amount(
+				T::Currency::free_balance(asset, &pair_account),
+				amount,
+				Self::get_fee(&pair_account),
+			)
+			.ok_or(Error::<T>::Overflow)?;
 
-			// Make sure that account share liquidity is at least MinPoolLiquidity
-			ensure!(
-				account_shares
-					.checked_add(shares_added)
-					.ok_or(Error::<T>::InvalidMintedLiquidity)?
-					>= T::MinPoolLiquidity::get(),
-				Error::<T>::InsufficientLiquidity
-			);
+			let transfer = Self::validate_sell(&who, asset_pair, amount_to_sell, Balance::zero(), false)?;
+			Self::execute_sell(&transfer)?;
 
-			let liquidity_amount = total_liquidity
-				.checked_add(shares_added)
-				.ok_or(Error::<T>::InvalidLiquidityAmount)?;
+			let amount_matched = multiply_by_rational(
+				transfer.amount_out,
+				T::Currency::free_balance(asset, &pair_account),
+				T::Currency::free_balance(asset_other, &pair_account),
+			)
+			.map_err(|_| Error::<T>::Overflow)?;
 
-			T::Currency::transfer(asset_a, &who, &pair_account, amount_a)?;
-			T::Currency::transfer(asset_b, &who, &pair_account, amount_b_required)?;
+			let amount_to_add = amount.saturating_sub(amount_to_sell).min(amount_matched);
 
-			T::Currency::deposit(share_token, &who, shares_added)?;
+			let shares = Self::do_add_liquidity(&who, asset, asset_other, amount_to_add, transfer.amount_out)?;
 
-			<TotalLiquidity<T>>::insert(&pair_account, liquidity_amount);
+			ensure!(shares >= min_shares, Error::<T>::AssetAmountNotReachedLimit);
 
-			Self::deposit_event(Event::LiquidityAdded(
+			Self::deposit_event(Event::SingleAssetLiquidityAdded(
 				who,
-				asset_a,
-				asset_b,
-				amount_a,
-				amount_b_required,
+				asset,
+				asset_other,
+				amount,
+				shares,
 			));
 
 			Ok(())
 		}
 
-		/// Remove liquidity from specific liquidity pool in the form of burning shares.
+		/// Remove liquidity from the pool receiving only `asset`.
 		///
-		/// If liquidity in the pool reaches 0, it is destroyed.
+		/// Shares are burned and received amount of `asset_other` is sold for `asset`. The sale is not limited
+		/// by the max in ratio. Last liquidity of the pool cannot be removed to single asset.
 		///
-		/// Emits 'LiquidityRemoved' when successful.
-		/// Emits 'PoolDestroyed' when pool is destroyed.
-		#[pallet::weight(<T as Config>::WeightInfo::remove_liquidity())]
+		/// `min_amount` - minimum total amount of `asset` to be received.
+		///
+		/// Emits `LiquidityRemoved`, `SellExecuted` and `SingleAssetLiquidityRemoved` events when successful.
+		#[pallet::weight(
+			<T as Config>::WeightInfo::remove_liquidity_to_single_asset()
+				.saturating_add(<T as Config>::AMMHandler::on_trade_weight())
+		)]
 		#[transactional]
-		pub fn remove_liquidity(
+		pub fn remove_liquidity_to_single_asset(
 			origin: OriginFor<T>,
-			asset_a: AssetId,
-			asset_b: AssetId,
+			asset: AssetId,
+			asset_other: AssetId,
 			liquidity_amount: Balance,
+			min_amount: Balance,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
+			let (amount_removed, amount_other_removed) =
+				Self::do_remove_liquidity(&who, asset, asset_other, liquidity_amount)?;
+
 			let asset_pair = AssetPair {
-				asset_in: asset_a,
-				asset_out: asset_b,
+				asset_in: asset_other,
+				asset_out: asset,
 			};
 
-			ensure!(!liquidity_amount.is_zero(), Error::<T>::ZeroLiquidity);
-
-			ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
-
-			let pair_account = Self::get_pair_id(asset_pair);
-
-			let share_token = Self::share_token(&pair_account);
-
-			let total_shares = Self::total_liquidity(&pair_account);
-
-			let account_shares = T::Currency::free_balance(share_token, &who);
-
-			ensure!(total_shares >= liquidity_amount, Error::<T>::InsufficientLiquidity);
-
-			ensure!(account_shares >= liquidity_amount, Error::<T>::InsufficientAssetBalance);
-
-			// Account's liquidity left should be either 0 or at least MinPoolLiquidity
-			ensure!(
-				(account_shares.saturating_sub(liquidity_amount)) >= T::MinPoolLiquidity::get()
-					|| (account_shares == liquidity_amount),
-				Error::<T>::InsufficientLiquidity
-			);
-
-			let asset_a_reserve = T::Currency::free_balance(asset_a, &pair_account);
-			let asset_b_reserve = T::Currency::free_balance(asset_b, &pair_account);
-
-			let liquidity_out = hydra_dx_math::xyk::calculate_liquidity_out(
-				asset_a_reserve,
-				asset_b_reserve,
-				liquidity_amount,
-				total_shares,
-			)
-			.map_err(|_| Error::<T>::RemoveAssetAmountInvalid)?;
-
-			let (remove_amount_a, remove_amount_b) = liquidity_out;
-
-			ensure!(
-				T::Currency::free_balance(asset_a, &pair_account) >= remove_amount_a,
-				Error::<T>::InsufficientPoolAssetBalance
-			);
+			// removal of the last liquidity destroys the pool, there is nothing to sell to then
 			ensure!(
-				T::Currency::free_balance(asset_b, &pair_account) >= remove_amount_b,
-				Error::<T>::InsufficientPoolAssetBalance
+				Self::exists(asset_pair),
+				Error::<T>::CannotRemoveAllLiquidityToSingleAsset
 			);
 
-			let liquidity_left = total_shares
-				.checked_sub(liquidity_amount)
-				.ok_or(Error::<T>::InvalidLiquidityAmount)?;
-
-			T::Currency::transfer(asset_a, &pair_account, &who, remove_amount_a)?;
-			T::Currency::transfer(asset_b, &pair_account, &who, remove_amount_b)?;
+			// sold amount is a share of the pool which was just removed, so the max in ratio does not apply
+			let transfer =
+				Self::do_validate_sell(&who, asset_pair, amount_other_removed, Balance::zero(), false, false)?;
+			Self::execute_sell(&transfer)?;
 
-			T::Currency::withdraw(share_token, &who, liquidity_amount)?;
+			let amount = amount_removed
+				.checked_add(transfer.amount_out)
+				.ok_or(Error::<T>::Overflow)?;
 
-			<TotalLiquidity<T>>::insert(&pair_account, liquidity_left);
+			ensure!(amount >= min_amount, Error::<T>::AssetAmountNotReachedLimit);
 
-			Self::deposit_event(Event::LiquidityRemoved(who.clone(), asset_a, asset_b, liquidity_amount));
-
-			if liquidity_left == 0 {
-				<ShareToken<T>>::remove(&pair_account);
-				<PoolAssets<T>>::remove(&pair_account);
-				<TotalLiquidity<T>>::remove(&pair_account);
-
-				Self::deposit_event(Event::PoolDestroyed(who, asset_a, asset_b, share_token, pair_account));
-			}
+			Self::deposit_event(Event::SingleAssetLiquidityRemoved(
+				who,
+				asset,
+				asset_other,
+				liquidity_amount,
+				amount,
+			));
 
 			Ok(())
 		}
@@ -571,85 +886,1175 @@ pub mod pallet {
 
 			Ok(())
 		}
-	}
-}
 
-impl<T: Config> Pallet<T> {
-	/// Return balance of each asset in selected liquidity pool.
-	pub fn get_pool_balances(pool_address: T::AccountId) -> Option<Vec<(AssetId, Balance)>> {
-		let mut balances = Vec::new();
+		/// Trade asset in for asset out if execution price is not lower than `min_price`.
+		///
+		/// `min_price` - minimum amount of `asset_out` received for one unit of `asset_in`, fee included.
+		///
+		/// `valid_until` - last block in which the trade can be executed.
+		///
+		/// Emits `SellExecuted` when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::sell() + <T as Config>::AMMHandler::on_trade_weight())]
+		pub fn sell_at_price(
+			origin: OriginFor<T>,
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount: Balance,
+			min_price: Price,
+			valid_until: T::BlockNumber,
+			discount: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-		if let Some(assets) = Self::get_pool_assets(&pool_address) {
-			for item in &assets {
-				let reserve = T::Currency::free_balance(*item, &pool_address);
-				balances.push((*item, reserve));
-			}
-		}
-		Some(balances)
-	}
-	/// Calculate discounted trade fee
-	fn calculate_discounted_fee(amount: Balance) -> Result<Balance, DispatchError> {
-		Ok(
-			hydra_dx_math::fee::calculate_pool_trade_fee(amount, (7, 10_000)) // 0.07%
-				.ok_or::<Error<T>>(Error::<T>::FeeAmountInvalid)?,
-		)
-	}
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() <= valid_until,
+				Error::<T>::TradeExpired
+			);
 
-	/// Calculate trade fee
-	fn calculate_fee(amount: Balance) -> Result<Balance, DispatchError> {
-		let fee = T::GetExchangeFee::get();
-		Ok(hydra_dx_math::fee::calculate_pool_trade_fee(amount, (fee.0, fee.1))
-			.ok_or::<Error<T>>(Error::<T>::FeeAmountInvalid)?)
-	}
+			let min_bought = min_price.checked_mul_int(amount).ok_or(Error::<T>::Overflow)?;
 
-	pub fn pair_account_from_assets(asset_a: AssetId, asset_b: AssetId) -> T::AccountId {
-		T::AssetPairAccountId::from_assets(asset_a, asset_b, "xyk")
-	}
-}
+			<Self as AMM<_, _, _, _>>::sell(&who, AssetPair { asset_in, asset_out }, amount, min_bought, discount)?;
 
-// Implementation of AMM API which makes possible to plug the AMM pool into the exchange pallet.
-impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
-	fn exists(assets: AssetPair) -> bool {
-		<ShareToken<T>>::contains_key(&Self::get_pair_id(assets))
-	}
+			Ok(())
+		}
 
-	fn get_pair_id(assets: AssetPair) -> T::AccountId {
-		Self::pair_account_from_assets(assets.asset_in, assets.asset_out)
-	}
+		/// Trade asset in for asset out if execution price is not higher than `max_price`.
+		///
+		/// `max_price` - maximum amount of `asset_in` paid for one unit of `asset_out`, fee included.
+		///
+		/// `valid_until` - last block in which the trade can be executed.
+		///
+		/// Emits `BuyExecuted` when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::buy() + <T as Config>::AMMHandler::on_trade_weight())]
+		pub fn buy_at_price(
+			origin: OriginFor<T>,
+			asset_out: AssetId,
+			asset_in: AssetId,
+			amount: Balance,
+			max_price: Price,
+			valid_until: T::BlockNumber,
+			discount: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-	fn get_share_token(assets: AssetPair) -> AssetId {
-		let pair_account = Self::get_pair_id(assets);
-		Self::share_token(&pair_account)
-	}
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() <= valid_until,
+				Error::<T>::TradeExpired
+			);
 
-	fn get_pool_assets(pool_account_id: &T::AccountId) -> Option<Vec<AssetId>> {
-		let maybe_assets = <PoolAssets<T>>::get(pool_account_id);
-		maybe_assets.map(|assets| vec![assets.0, assets.1])
-	}
+			let max_sold = max_price.checked_mul_int(amount).ok_or(Error::<T>::Overflow)?;
 
-	fn get_spot_price_unchecked(asset_a: AssetId, asset_b: AssetId, amount: Balance) -> Balance {
-		let pair_account = Self::get_pair_id(AssetPair {
-			asset_out: asset_a,
-			asset_in: asset_b,
+			<Self as AMM<_, _, _, _>>::buy(&who, AssetPair { asset_in, asset_out }, amount, max_sold, discount)?;
+
+			Ok(())
+		}
+
+		/// Change trading fee rate of a pool.
+		///
+		/// Can be called only by `UpdatePoolFeeOrigin`. Fee rate does not have to be one of `FeeTiers`
+		/// but it must be lower than 100% and its denominator must not be zero.
+		///
+		/// Emits `PoolFeeUpdated` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::set_pool_fee())]
+		pub fn set_pool_fee(
+			origin: OriginFor<T>,
+			asset_a: AssetId,
+			asset_b: AssetId,
+			fee: (u32, u32),
+		) -> DispatchResult {
+			T::UpdatePoolFeeOrigin::ensure_origin(origin)?;
+
+			let asset_pair = AssetPair {
+				asset_in: asset_a,
+				asset_out: asset_b,
+			};
+
+			ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
+
+			ensure!(!fee.1.is_zero() && fee.0 < fee.1, Error::<T>::InvalidFee);
+
+			let pair_account = Self::get_pair_id(asset_pair);
+
+			<PoolFee<T>>::insert(&pair_account, fee);
+
+			Self::deposit_event(Event::PoolFeeUpdated(pair_account, fee));
+
+			Ok(())
+		}
+
+		/// Enable, change or disable dynamic trading fee of a pool.
+		///
+		/// Can be called only by `UpdatePoolFeeOrigin`. Fee rate of the pool is derived from recent
+		/// movement of its price and kept between `min_fee` and `max_fee`. Dynamic fee is disabled
+		/// and `PoolFee` is used again if `None` is provided.
+		///
+		/// Emits `DynamicFeeUpdated` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::set_dynamic_fee())]
+		pub fn set_dynamic_fee(
+			origin: OriginFor<T>,
+			asset_a: AssetId,
+			asset_b: AssetId,
+			params: Option<DynamicFeeParams>,
+		) -> DispatchResult {
+			T::UpdatePoolFeeOrigin::ensure_origin(origin)?;
+
+			let asset_pair = AssetPair {
+				asset_in: asset_a,
+				asset_out: asset_b,
+			};
+
+			ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
+
+			let pair_account = Self::get_pair_id(asset_pair);
+
+			match params {
+				Some(p) => {
+					ensure!(
+						p.min_fee <= p.max_fee && p.max_fee < Permill::one(),
+						Error::<T>::InvalidFee
+					);
+
+					if !<PoolVolatility<T>>::contains_key(&pair_account) {
+						let volatility = Volatility {
+							block: <frame_system::Pallet<T>>::block_number(),
+							price: Self::pool_price(&pair_account).ok_or(Error::<T>::TokenPoolNotFound)?,
+							volatility: Permill::zero(),
+						};
+						<PoolVolatility<T>>::insert(&pair_account, volatility);
+					}
+
+					<DynamicFees<T>>::insert(&pair_account, p);
+				}
+				None => {
+					<DynamicFees<T>>::remove(&pair_account);
+					<PoolVolatility<T>>::remove(&pair_account);
+				}
+			}
+
+			Self::deposit_event(Event::DynamicFeeUpdated(pair_account, params));
+
+			Ok(())
+		}
+
+		/// Change operational state of a pool.
+		///
+		/// Can be called only by `UpdatePoolStateOrigin`. Trading is not allowed unless the pool is `Active`,
+		/// adding liquidity is not allowed in `WithdrawOnly` state. Removing liquidity is always allowed.
+		///
+		/// Emits `PoolStateUpdated` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::set_pool_state())]
+		pub fn set_pool_state(
+			origin: OriginFor<T>,
+			asset_a: AssetId,
+			asset_b: AssetId,
+			state: PoolState,
+		) -> DispatchResult {
+			T::UpdatePoolStateOrigin::ensure_origin(origin)?;
+
+			let asset_pair = AssetPair {
+				asset_in: asset_a,
+				asset_out: asset_b,
+			};
+
+			ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
+
+			let pair_account = Self::get_pair_id(asset_pair);
+
+			<PoolStates<T>>::insert(&pair_account, state);
+
+			Self::deposit_event(Event::PoolStateUpdated(pair_account, state));
+
+			Ok(())
+		}
+
+		/// Change circuit breaker limits applied to all pools.
+		///
+		/// Can be called only by `UpdatePoolStateOrigin`.
+		///
+		/// `max_price_change` - maximum change of pool price caused by trades in a single block.
+		///
+		/// `max_liquidity_removal` - maximum fraction of pool liquidity which can be removed in a single block.
+		///
+		/// Limit is disabled if `None` is provided.
+		///
+		/// Emits `CircuitBreakerLimitsUpdated` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::set_circuit_breaker_limits())]
+		pub fn set_circuit_breaker_limits(
+			origin: OriginFor<T>,
+			max_price_change: Option<Permill>,
+			max_liquidity_removal: Option<Permill>,
+		) -> DispatchResult {
+			T::UpdatePoolStateOrigin::ensure_origin(origin)?;
+
+			<MaxPriceChangePerBlock<T>>::set(max_price_change);
+			<MaxLiquidityRemovalPerBlock<T>>::set(max_liquidity_removal);
+
+			Self::deposit_event(Event::CircuitBreakerLimitsUpdated(
+				max_price_change,
+				max_liquidity_removal,
+			));
+
+			Ok(())
+		}
+
+		/// Change protocol share of trade fees.
+		///
+		/// Can be called only by `UpdatePoolFeeOrigin`. Given share of each trade fee is transferred
+		/// from the pool to `ProtocolFeeAccount`, rest of the fee stays in the pool.
+		///
+		/// Emits `ProtocolFeeShareUpdated` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::set_protocol_fee_share())]
+		pub fn set_protocol_fee_share(origin: OriginFor<T>, share: Permill) -> DispatchResult {
+			T::UpdatePoolFeeOrigin::ensure_origin(origin)?;
+
+			<ProtocolFeeShare<T>>::put(share);
+
+			Self::deposit_event(Event::ProtocolFeeShareUpdated(share));
+
+			Ok(())
+		}
+
+		/// Trade first asset of the route for the last asset of the route.
+		///
+		/// Executes a sell in every pool along the `route`. Amount received in one trade is sold in the next one.
+		/// All trades are executed atomically - if any of them fails, none of them is executed.
+		///
+		/// `route` - ordered list of assets to trade through.
+		///
+		/// `min_bought` - minimum amount of the last asset of the route to be obtained in exchange for `amount`.
+		///
+		/// Emits `SellExecuted` for each trade and `RouteSellExecuted` when successful.
+		#[pallet::weight(
+			<T as Config>::WeightInfo::sell_route(route.len().saturating_sub(1) as u32)
+				.saturating_add(<T as Config>::AMMHandler::on_trade_weight().saturating_mul(route.len() as Weight))
+		)]
+		#[transactional]
+		pub fn sell_route(
+			origin: OriginFor<T>,
+			route: Vec<AssetId>,
+			amount: Balance,
+			min_bought: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let hops = Self::validate_route(&route)?;
+
+			let mut amount_in = amount;
+
+			for assets in hops {
+				let transfer = Self::validate_sell(&who, assets, amount_in, Balance::zero(), false)?;
+				Self::execute_sell(&transfer)?;
+				amount_in = transfer.amount_out;
+			}
+
+			ensure!(amount_in >= min_bought, Error::<T>::AssetAmountNotReachedLimit);
+
+			Self::deposit_event(Event::RouteSellExecuted(who, route, amount, amount_in));
+
+			Ok(())
+		}
+
+		/// Trade first asset of the route for `amount` of the last asset of the route.
+		///
+		/// Executes a buy in every pool along the `route`. Amounts needed in each trade are calculated
+		/// from the end of the route first. All trades are executed atomically.
+		///
+		/// `route` - ordered list of assets to trade through.
+		///
+		/// `max_sold` - maximum amount of the first asset of the route to be sold in exchange for `amount`.
+		///
+		/// Emits `BuyExecuted` for each trade and `RouteBuyExecuted` when successful.
+		#[pallet::weight(
+			<T as Config>::WeightInfo::buy_route(route.len().saturating_sub(1) as u32)
+				.saturating_add(<T as Config>::AMMHandler::on_trade_weight().saturating_mul(route.len() as Weight))
+		)]
+		#[transactional]
+		pub fn buy_route(
+			origin: OriginFor<T>,
+			route: Vec<AssetId>,
+			amount: Balance,
+			max_sold: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let hops = Self::validate_route(&route)?;
+
+			let amounts = Self::calculate_route_buy_amounts(&hops, amount)?;
+
+			ensure!(amounts[0] <= max_sold, Error::<T>::AssetAmountExceededLimit);
+
+			for (idx, assets) in hops.into_iter().enumerate() {
+				let transfer = Self::validate_buy(&who, assets, amounts[idx + 1], amounts[idx], false)?;
+				Self::execute_buy(&transfer)?;
+			}
+
+			Self::deposit_event(Event::RouteBuyExecuted(who, route, amount, amounts[0]));
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Return balance of each asset in selected liquidity pool.
+	pub fn get_pool_balances(pool_address: T::AccountId) -> Option<Vec<(AssetId, Balance)>> {
+		let mut balances = Vec::new();
+
+		if let Some(assets) = Self::get_pool_assets(&pool_address) {
+			for item in &assets {
+				let reserve = T::Currency::free_balance(*item, &pool_address);
+				balances.push((*item, reserve));
+			}
+		}
+		Some(balances)
+	}
+	/// Calculate discounted trade fee
+	fn calculate_discounted_fee(amount: Balance) -> Result<Balance, DispatchError> {
+		Ok(
+			hydra_dx_math::fee::calculate_pool_trade_fee(amount, (7, 10_000)) // 0.07%
+				.ok_or::<Error<T>>(Error::<T>::FeeAmountInvalid)?,
+		)
+	}
+
+	/// Calculate trade fee using trading fee rate of given pool
+	fn calculate_fee(pool_account: &T::AccountId, amount: Balance) -> Result<Balance, DispatchError> {
+		let fee = Self::get_fee(pool_account);
+		Ok(hydra_dx_math::fee::calculate_pool_trade_fee(amount, (fee.0, fee.1))
+			.ok_or::<Error<T>>(Error::<T>::FeeAmountInvalid)?)
+	}
+
+	pub fn pair_account_from_assets(asset_a: AssetId, asset_b: AssetId) -> T::AccountId {
+		T::AssetPairAccountId::from_assets(asset_a, asset_b, "xyk")
+	}
+
+	/// Transfer protocol share of trade fee from the pool to the protocol account.
+	///
+	/// Transfer is skipped if the amount is not sufficient to keep the protocol account alive.
+	fn transfer_protocol_fee(pool_account: &T::AccountId, asset: AssetId, fee: Balance) -> DispatchResult {
+		let protocol_fee = Self::protocol_fee_share().mul_floor(fee);
+
+		if protocol_fee.is_zero() {
+			return Ok(());
+		}
+
+		let protocol_account = T::ProtocolFeeAccount::get();
+
+		if T::Currency::free_balance(asset, &protocol_account).saturating_add(protocol_fee)
+			< T::Currency::minimum_balance(asset)
+		{
+			return Ok(());
+		}
+
+		T::Currency::transfer(asset, pool_account, &protocol_account, protocol_fee)?;
+
+		<ProtocolFeesCollected<T>>::mutate(asset, |collected| *collected = collected.saturating_add(protocol_fee));
+
+		Self::deposit_event(Event::TradeFeeSplit(
+			pool_account.clone(),
+			asset,
+			fee.saturating_sub(protocol_fee),
+			protocol_fee,
+		));
+
+		Ok(())
+	}
+
+	/// Add liquidity to the pool of `asset_a` and `asset_b` and return amount of shares issued.
+	fn do_add_liquidity(
+		who: &T::AccountId,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		amount_a: Balance,
+		amount_b_max_limit: Balance,
+	) -> Result<Balance, DispatchError> {
+		let asset_pair = AssetPair {
+			asset_in: asset_a,
+			asset_out: asset_b,
+		};
+
+		ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
+
+		ensure!(
+			amount_a >= T::MinTradingLimit::get(),
+			Error::<T>::InsufficientTradingAmount
+		);
+
+		ensure!(!amount_b_max_limit.is_zero(), Error::<T>::ZeroLiquidity);
+
+		ensure!(
+			T::Currency::free_balance(asset_a, who) >= amount_a,
+			Error::<T>::InsufficientAssetBalance
+		);
+
+		ensure!(
+			T::Currency::free_balance(asset_b, who) >= amount_b_max_limit,
+			Error::<T>::InsufficientAssetBalance
+		);
+
+		let pair_account = Self::get_pair_id(asset_pair);
+
+		ensure!(
+			Self::pool_state(&pair_account) != PoolState::WithdrawOnly,
+			Error::<T>::AddLiquidityNotAllowed
+		);
+
+		let share_token = Self::share_token(&pair_account);
+
+		let account_shares = T::Currency::free_balance(share_token, who);
+
+		let asset_a_reserve = T::Currency::free_balance(asset_a, &pair_account);
+		let asset_b_reserve = T::Currency::free_balance(asset_b, &pair_account);
+		let total_liquidity = Self::total_liquidity(&pair_account);
+
+		let amount_b_required = hydra_dx_math::xyk::calculate_liquidity_in(asset_a_reserve, asset_b_reserve, amount_a)
+			.map_err(|_| Error::<T>::AddAssetAmountInvalid)?;
+
+		let shares_added = if asset_a < asset_b { amount_a } else { amount_b_required };
+
+		ensure!(
+			amount_b_required <= amount_b_max_limit,
+			Error::<T>::AssetAmountExceededLimit
+		);
+
+		ensure!(!shares_added.is_zero(), Error::<T>::InvalidMintedLiquidity);
+
+		// Make sure that account share liquidity is at least MinPoolLiquidity
+		ensure!(
+			account_shares
+				.checked_add(shares_added)
+				.ok_or(Error::<T>::InvalidMintedLiquidity)?
+				>= T::MinPoolLiquidity::get(),
+			Error::<T>::InsufficientLiquidity
+		);
+
+		let liquidity_amount = total_liquidity
+			.checked_add(shares_added)
+			.ok_or(Error::<T>::InvalidLiquidityAmount)?;
+
+		Self::update_price_accumulator(&pair_account);
+		Self::record_block_start_state(&pair_account);
+
+		T::Currency::transfer(asset_a, who, &pair_account, amount_a)?;
+		T::Currency::transfer(asset_b, who, &pair_account, amount_b_required)?;
+
+		T::Currency::deposit(share_token, who, shares_added)?;
+
+		<TotalLiquidity<T>>::insert(&pair_account, liquidity_amount);
+
+		Self::deposit_event(Event::LiquidityAdded(
+			who.clone(),
+			asset_a,
+			asset_b,
+			amount_a,
+			amount_b_required,
+		));
+
+		Ok(shares_added)
+	}
+
+	/// Remove liquidity from the pool of `asset_a` and `asset_b` and return amounts of both assets received.
+	fn do_remove_liquidity(
+		who: &T::AccountId,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		liquidity_amount: Balance,
+	) -> Result<(Balance, Balance), DispatchError> {
+		let asset_pair = AssetPair {
+			asset_in: asset_a,
+			asset_out: asset_b,
+		};
+
+		ensure!(!liquidity_amount.is_zero(), Error::<T>::ZeroLiquidity);
+
+		ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
+
+		let pair_account = Self::get_pair_id(asset_pair);
+
+		let share_token = Self::share_token(&pair_account);
+
+		let total_shares = Self::total_liquidity(&pair_account);
+
+		let account_shares = T::Currency::free_balance(share_token, who);
+
+		ensure!(total_shares >= liquidity_amount, Error::<T>::InsufficientLiquidity);
+
+		ensure!(account_shares >= liquidity_amount, Error::<T>::InsufficientAssetBalance);
+
+		// Account's liquidity left should be either 0 or at least MinPoolLiquidity
+		ensure!(
+			(account_shares.saturating_sub(liquidity_amount)) >= T::MinPoolLiquidity::get()
+				|| (account_shares == liquidity_amount),
+			Error::<T>::InsufficientLiquidity
+		);
+
+		let asset_a_reserve = T::Currency::free_balance(asset_a, &pair_account);
+		let asset_b_reserve = T::Currency::free_balance(asset_b, &pair_account);
+
+		let liquidity_out = hydra_dx_math::xyk::calculate_liquidity_out(
+			asset_a_reserve,
+			asset_b_reserve,
+			liquidity_amount,
+			total_shares,
+		)
+		.map_err(|_| Error::<T>::RemoveAssetAmountInvalid)?;
+
+		let (remove_amount_a, remove_amount_b) = liquidity_out;
+
+		ensure!(
+			T::Currency::free_balance(asset_a, &pair_account) >= remove_amount_a,
+			Error::<T>::InsufficientPoolAssetBalance
+		);
+		ensure!(
+			T::Currency::free_balance(asset_b, &pair_account) >= remove_amount_b,
+			Error::<T>::InsufficientPoolAssetBalance
+		);
+
+		let liquidity_left = total_shares
+			.checked_sub(liquidity_amount)
+			.ok_or(Error::<T>::InvalidLiquidityAmount)?;
+
+		Self::ensure_liquidity_removal_within_limit(&pair_account, liquidity_amount)?;
+
+		Self::update_price_accumulator(&pair_account);
+
+		T::Currency::transfer(asset_a, &pair_account, who, remove_amount_a)?;
+		T::Currency::transfer(asset_b, &pair_account, who, remove_amount_b)?;
+
+		T::Currency::withdraw(share_token, who, liquidity_amount)?;
+
+		<TotalLiquidity<T>>::insert(&pair_account, liquidity_left);
+
+		Self::deposit_event(Event::LiquidityRemoved(who.clone(), asset_a, asset_b, liquidity_amount));
+
+		let locked_shares = T::Currency::free_balance(share_token, &pair_account);
+
+		if liquidity_left == locked_shares {
+			Self::destroy_pool(&pair_account, asset_a, asset_b, share_token, locked_shares)?;
+
+			<ShareToken<T>>::remove(&pair_account);
+			<PoolAssets<T>>::remove(&pair_account);
+			<TotalLiquidity<T>>::remove(&pair_account);
+			<PoolFee<T>>::remove(&pair_account);
+			<PriceObservations<T>>::remove(&pair_account);
+			<PoolStates<T>>::remove(&pair_account);
+			<BlockStartStates<T>>::remove(&pair_account);
+			<DynamicFees<T>>::remove(&pair_account);
+			<PoolVolatility<T>>::remove(&pair_account);
+
+			Self::deposit_event(Event::PoolDestroyed(
+				who.clone(),
+				asset_a,
+				asset_b,
+				share_token,
+				pair_account,
+			));
+		}
+
+		Ok((remove_amount_a, remove_amount_b))
+	}
+
+	/// Calculate amount of `amount` which has to be sold to the pool so that the rest can be added
+	/// as liquidity together with the amount received from the sale.
+	///
+	/// Solves `s^2 + s * ((1 + g) * r - f * a) - a * r = 0` for `s`, where `r` is pool reserve of the sold
+	/// asset, `a` is provided amount, `f` is fee rate and `g = 1 - f`.
+	fn calculate_single_asset_sell_amount(reserve: Balance, amount: Balance, fee: (u32, u32)) -> Option<Balance> {
+		let (fee_numerator, fee_denominator) = if fee.1.is_zero() { (0, 1) } else { fee };
+
+		let n = U256::from(fee_numerator);
+		let d = U256::from(fee_denominator);
+		let g = d.checked_sub(n)?;
+		let r = U256::from(reserve);
+		let a = U256::from(amount);
+
+		let b_positive = d.checked_add(g)?.checked_mul(r)?;
+		let b_negative = n.checked_mul(a)?;
+
+		let b = if b_positive >= b_negative {
+			b_positive - b_negative
+		} else {
+			b_negative - b_positive
+		};
+
+		let discriminant = b.checked_mul(b)?.checked_add(
+			U256::from(4u32)
+				.checked_mul(d)?
+				.checked_mul(d)?
+				.checked_mul(a)?
+				.checked_mul(r)?,
+		)?;
+
+		let root = discriminant.integer_sqrt();
+
+		let numerator = if b_positive >= b_negative {
+			root.checked_sub(b)?
+		} else {
+			root.checked_add(b)?
+		};
+
+		let result = numerator.checked_div(U256::from(2u32).checked_mul(d)?)?;
+
+		result.try_into().ok()
+	}
+
+	/// State of a pool at the start of current block.
+	///
+	/// Returns stored state if the pool was already changed in current block, otherwise the state
+	/// is created from current state of the pool.
+	fn block_start_state(pool_account: &T::AccountId) -> Option<BlockStartState<T::BlockNumber>> {
+		let now = <frame_system::Pallet<T>>::block_number();
+
+		match Self::block_start_states(pool_account) {
+			Some(state) if state.block == now => Some(state),
+			_ => {
+				let (asset_a, asset_b) = Self::pool_assets(pool_account)?;
+
+				Some(BlockStartState {
+					block: now,
+					price: Price::checked_from_rational(
+						T::Currency::free_balance(asset_b, pool_account),
+						T::Currency::free_balance(asset_a, pool_account),
+					)
+					.unwrap_or_else(Price::zero),
+					liquidity: Self::total_liquidity(pool_account),
+					liquidity_removed: Balance::zero(),
+				})
+			}
+		}
+	}
+
+	/// Store state of a pool at the start of current block if any circuit breaker limit is set.
+	///
+	/// Has to be called before reserves of the pool are modified.
+	fn record_block_start_state(pool_account: &T::AccountId) {
+		if Self::max_price_change_per_block().is_none() && Self::max_liquidity_removal_per_block().is_none() {
+			return;
+		}
+
+		if let Some(state) = Self::block_start_state(pool_account) {
+			<BlockStartStates<T>>::insert(pool_account, state);
+		}
+	}
+
+	/// Ensure that reserves of the pool after a trade do not move the price of the pool more than
+	/// allowed since the start of current block.
+	fn ensure_price_change_within_limit(
+		pool_account: &T::AccountId,
+		assets: AssetPair,
+		asset_in_reserve: Balance,
+		asset_out_reserve: Balance,
+	) -> DispatchResult {
+		let max_price_change = match Self::max_price_change_per_block() {
+			Some(max_price_change) => max_price_change,
+			None => return Ok(()),
+		};
+
+		let state = Self::block_start_state(pool_account).ok_or(Error::<T>::TokenPoolNotFound)?;
+
+		let (asset_a_reserve, asset_b_reserve) = match Self::pool_assets(pool_account) {
+			Some((asset_a, _)) if asset_a == assets.asset_in => (asset_in_reserve, asset_out_reserve),
+			_ => (asset_out_reserve, asset_in_reserve),
+		};
+
+		let price = Price::checked_from_rational(asset_b_reserve, asset_a_reserve).ok_or(Error::<T>::Overflow)?;
+
+		let price_change = if price > state.price {
+			price.saturating_sub(state.price)
+		} else {
+			state.price.saturating_sub(price)
+		};
+
+		ensure!(
+			Permill::from_rational(price_change.into_inner(), state.price.into_inner()) <= max_price_change,
+			Error::<T>::MaxPriceChangeExceeded
+		);
+
+		Ok(())
+	}
+
+	/// Ensure that removing `liquidity_amount` does not exceed liquidity allowed to be removed from
+	/// the pool in current block and record the removed amount.
+	fn ensure_liquidity_removal_within_limit(pool_account: &T::AccountId, liquidity_amount: Balance) -> DispatchResult {
+		let max_liquidity_removal = match Self::max_liquidity_removal_per_block() {
+			Some(max_liquidity_removal) => max_liquidity_removal,
+			None => {
+				Self::record_block_start_state(pool_account);
+				return Ok(());
+			}
+		};
+
+		let mut state = Self::block_start_state(pool_account).ok_or(Error::<T>::TokenPoolNotFound)?;
+
+		state.liquidity_removed = state
+			.liquidity_removed
+			.checked_add(liquidity_amount)
+			.ok_or(Error::<T>::Overflow)?;
+
+		ensure!(
+			state.liquidity_removed <= max_liquidity_removal.mul_floor(state.liquidity),
+			Error::<T>::MaxLiquidityRemovalExceeded
+		);
+
+		<BlockStartStates<T>>::insert(pool_account, state);
+
+		Ok(())
+	}
+
+	/// Burn locked shares of a pool and sweep assets left in the pool to the protocol account.
+	///
+	/// Assets left are burned instead if the amount is not sufficient to keep the protocol account alive.
+	/// Share token is released in the asset registry, so a new share token is created if the pool is created again.
+	fn destroy_pool(
+		pool_account: &T::AccountId,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		share_token: AssetId,
+		locked_shares: Balance,
+	) -> DispatchResult {
+		if !locked_shares.is_zero() {
+			T::Currency::withdraw(share_token, pool_account, locked_shares)?;
+		}
+
+		let protocol_account = T::ProtocolFeeAccount::get();
+
+		for asset in [asset_a, asset_b] {
+			let left = T::Currency::free_balance(asset, pool_account);
+
+			if left.is_zero() {
+				continue;
+			}
+
+			if T::Currency::free_balance(asset, &protocol_account).saturating_add(left)
+				< T::Currency::minimum_balance(asset)
+			{
+				T::Currency::withdraw(asset, pool_account, left)?;
+			} else {
+				T::Currency::transfer(asset, pool_account, &protocol_account, left)?;
+			}
+		}
+
+		T::ShareTokenCleanup::deregister_share_token(asset_a, asset_b, share_token)
+	}
+
+	/// Price of first pool asset denominated in second pool asset.
+	fn pool_price(pool_account: &T::AccountId) -> Option<Price> {
+		let (asset_a, asset_b) = Self::pool_assets(pool_account)?;
+
+		Price::checked_from_rational(
+			T::Currency::free_balance(asset_b, pool_account),
+			T::Currency::free_balance(asset_a, pool_account),
+		)
+	}
+
+	/// Volatility of a pool in current block and current price of the pool.
+	///
+	/// Relative price change since the start of the tracked block is added to the tracked volatility
+	/// and the result is decayed by `decay` for each block passed since.
+	fn current_volatility(pool_account: &T::AccountId) -> Option<(Permill, Price)> {
+		let params = Self::dynamic_fee(pool_account)?;
+		let state = Self::pool_volatility(pool_account)?;
+
+		let price = Self::pool_price(pool_account)?;
+
+		let price_change = if price > state.price {
+			price.saturating_sub(state.price)
+		} else {
+			state.price.saturating_sub(price)
+		};
+
+		let elapsed: u32 = <frame_system::Pallet<T>>::block_number()
+			.saturating_sub(state.block)
+			.unique_saturated_into();
+
+		let volatility = state.volatility.saturating_add(Permill::from_rational(
+			price_change.into_inner(),
+			state.price.into_inner(),
+		));
+
+		Some((
+			Permill::one()
+				.saturating_sub(params.decay)
+				.saturating_pow(elapsed as usize)
+				.saturating_mul(volatility),
+			price,
+		))
+	}
+
+	/// Dynamic trading fee rate of a pool. Returns `None` if dynamic fee is not enabled for the pool.
+	pub fn dynamic_fee_rate(pool_account: &T::AccountId) -> Option<(u32, u32)> {
+		let params = Self::dynamic_fee(pool_account)?;
+		let (volatility, _) = Self::current_volatility(pool_account)?;
+
+		let fee = params
+			.min_fee
+			.deconstruct()
+			.saturating_add(volatility.deconstruct().saturating_mul(params.amplification))
+			.min(params.max_fee.deconstruct());
+
+		Some((fee, Permill::one().deconstruct()))
+	}
+
+	/// Track volatility of a pool with dynamic fee in current block.
+	///
+	/// Has to be called before reserves of the pool are modified.
+	fn update_volatility(pool_account: &T::AccountId) {
+		let now = <frame_system::Pallet<T>>::block_number();
+
+		match Self::pool_volatility(pool_account) {
+			Some(state) if state.block != now => {}
+			_ => return,
+		}
+
+		if let Some((volatility, price)) = Self::current_volatility(pool_account) {
+			<PoolVolatility<T>>::insert(
+				pool_account,
+				Volatility {
+					block: now,
+					price,
+					volatility,
+				},
+			);
+		}
+	}
+
+	/// Record cumulative prices of a pool for current block.
+	///
+	/// Prices are accumulated from reserves before any change in current block, therefore this
+	/// has to be called before reserves of the pool are modified. Only first call in a block
+	/// records a new observation.
+	fn update_price_accumulator(pool_account: &T::AccountId) {
+		let (asset_a, asset_b) = match Self::pool_assets(pool_account) {
+			Some(assets) => assets,
+			None => return,
+		};
+
+		let now = <frame_system::Pallet<T>>::block_number();
+
+		<PriceObservations<T>>::mutate(pool_account, |observations| {
+			let observation = match observations.last() {
+				Some(last) if last.block == now => return,
+				Some(last) => {
+					let reserve_a = T::Currency::free_balance(asset_a, pool_account);
+					let reserve_b = T::Currency::free_balance(asset_b, pool_account);
+					Self::extrapolate_observation(last, reserve_a, reserve_b, now)
+				}
+				None => PriceObservation {
+					block: now,
+					..Default::default()
+				},
+			};
+
+			if observations.len() >= T::MaxPriceObservations::get() as usize && !observations.is_empty() {
+				observations.remove(0);
+			}
+
+			let _ = observations.try_push(observation);
 		});
+	}
 
-		let asset_a_reserve = T::Currency::free_balance(asset_a, &pair_account);
-		let asset_b_reserve = T::Currency::free_balance(asset_b, &pair_account);
+	/// Accumulate prices given by reserves from the block of `observation` up to `block`.
+	fn extrapolate_observation(
+		observation: &PriceObservation<T::BlockNumber>,
+		reserve_a: Balance,
+		reserve_b: Balance,
+		block: T::BlockNumber,
+	) -> PriceObservation<T::BlockNumber> {
+		let elapsed: u128 = block.saturating_sub(observation.block).unique_saturated_into();
+
+		let price_a = Price::checked_from_rational(reserve_b, reserve_a)
+			.unwrap_or_else(Price::zero)
+			.into_inner();
+		let price_b = Price::checked_from_rational(reserve_a, reserve_b)
+			.unwrap_or_else(Price::zero)
+			.into_inner();
+
+		PriceObservation {
+			block,
+			price_a_cumulative: observation
+				.price_a_cumulative
+				.wrapping_add(price_a.wrapping_mul(elapsed)),
+			price_b_cumulative: observation
+				.price_b_cumulative
+				.wrapping_add(price_b.wrapping_mul(elapsed)),
+		}
+	}
 
-		hydra_dx_math::xyk::calculate_spot_price(asset_a_reserve, asset_b_reserve, amount)
-			.unwrap_or_else(|_| Balance::zero())
+	/// Time-weighted average price of `asset_a` denominated in `asset_b` over last `period` blocks.
+	///
+	/// Observations are recorded only in blocks in which the pool is traded or its liquidity changes.
+	/// Reserves do not change in other blocks, so price is constant since the last observation and
+	/// cumulative prices are extrapolated over such idle blocks up to the current block.
+	///
+	/// Returns `None` if pool does not exist or its price history does not cover the whole period.
+	pub fn get_twap(asset_a: AssetId, asset_b: AssetId, period: T::BlockNumber) -> Option<Price> {
+		if period.is_zero() {
+			return None;
+		}
+
+		let pool_account = Self::pair_account_from_assets(asset_a, asset_b);
+		let (pool_asset_a, pool_asset_b) = Self::pool_assets(&pool_account)?;
+
+		let now = <frame_system::Pallet<T>>::block_number();
+		let start = now.checked_sub(&period)?;
+
+		let mut observations = Self::price_observations(&pool_account).into_inner();
+
+		let last = observations.last()?;
+		if last.block < now {
+			let current = Self::extrapolate_observation(
+				last,
+				T::Currency::free_balance(pool_asset_a, &pool_account),
+				T::Currency::free_balance(pool_asset_b, &pool_account),
+				now,
+			);
+			observations.push(current);
+		}
+
+		let position = observations.iter().rposition(|o| o.block <= start)?;
+		let previous = &observations[position];
+		let current = observations.last()?;
+
+		// Prices are constant between two consecutive observations, cumulative price at the start
+		// of the period is therefore interpolated linearly.
+		let (price_a_start, price_b_start) = match observations.get(position + 1) {
+			Some(next) if previous.block < start => {
+				let offset: u128 = start.saturating_sub(previous.block).unique_saturated_into();
+				let length: u128 = next.block.saturating_sub(previous.block).unique_saturated_into();
+				let interpolate = |from: u128, to: u128| -> Option<u128> {
+					multiply_by_rational(to.wrapping_sub(from), offset, length)
+						.ok()
+						.map(|part| from.wrapping_add(part))
+				};
+				(
+					interpolate(previous.price_a_cumulative, next.price_a_cumulative)?,
+					interpolate(previous.price_b_cumulative, next.price_b_cumulative)?,
+				)
+			}
+			_ => (previous.price_a_cumulative, previous.price_b_cumulative),
+		};
+
+		let period: u128 = period.unique_saturated_into();
+
+		let average = if asset_a == pool_asset_a {
+			current.price_a_cumulative.wrapping_sub(price_a_start) / period
+		} else {
+			current.price_b_cumulative.wrapping_sub(price_b_start) / period
+		};
+
+		Some(Price::from_inner(average))
 	}
 
-	/// Validate a sell. Perform all necessary checks and calculations.
-	/// No storage changes are performed yet.
+	/// Calculate outcome of selling `amount` of `asset_in` for `asset_out` in current state of the pool.
 	///
-	/// Return `AMMTransfer` with all info needed to execute the transaction.
-	fn validate_sell(
+	/// Returns `None` if pool does not exist, trading in the pool is not allowed, the trade would exceed the max
+	/// price change of the pool in current block or the trade cannot be calculated.
+	pub fn quote_sell(asset_in: AssetId, asset_out: AssetId, amount: Balance) -> Option<TradeQuote> {
+		let assets = AssetPair { asset_in, asset_out };
+
+		if !Self::exists(assets) {
+			return None;
+		}
+
+		let pair_account = Self::get_pair_id(assets);
+
+		if Self::pool_state(&pair_account) != PoolState::Active {
+			return None;
+		}
+
+		let asset_in_reserve = T::Currency::free_balance(asset_in, &pair_account);
+		let asset_out_reserve = T::Currency::free_balance(asset_out, &pair_account);
+
+		let amount_out =
+			hydra_dx_math::xyk::calculate_out_given_in(asset_in_reserve, asset_out_reserve, amount).ok()?;
+		let fee = Self::calculate_fee(&pair_account, amount_out).ok()?;
+
+		let spot_amount_out =
+			hydra_dx_math::xyk::calculate_spot_price(asset_in_reserve, asset_out_reserve, amount).ok()?;
+
+		let amount_out_without_fee = amount_out.checked_sub(fee)?;
+
+		Self::ensure_price_change_within_limit(
+			&pair_account,
+			assets,
+			asset_in_reserve.saturating_add(amount),
+			asset_out_reserve.saturating_sub(amount_out_without_fee),
+		)
+		.ok()?;
+
+		Some(TradeQuote {
+			amount: amount_out_without_fee,
+			fee,
+			price_impact: Permill::from_rational(spot_amount_out.saturating_sub(amount_out), spot_amount_out),
+			below_min_trading_limit: amount < T::MinTradingLimit::get(),
+			max_in_ratio_exceeded: amount > asset_in_reserve.checked_div(T::MaxInRatio::get())?,
+			max_out_ratio_exceeded: false,
+			fee_rate: Self::get_fee(&pair_account),
+		})
+	}
+
+	/// Calculate outcome of buying `amount` of `asset_out` for `asset_in` in current state of the pool.
+	///
+	/// Returns `None` if pool does not exist, trading in the pool is not allowed, the trade would exceed the max
+	/// price change of the pool in current block or the trade cannot be calculated.
+	pub fn quote_buy(asset_out: AssetId, asset_in: AssetId, amount: Balance) -> Option<TradeQuote> {
+		let assets = AssetPair { asset_in, asset_out };
+
+		if !Self::exists(assets) {
+			return None;
+		}
+
+		let pair_account = Self::get_pair_id(assets);
+
+		if Self::pool_state(&pair_account) != PoolState::Active {
+			return None;
+		}
+
+		let asset_in_reserve = T::Currency::free_balance(asset_in, &pair_account);
+		let asset_out_reserve = T::Currency::free_balance(asset_out, &pair_account);
+
+		let buy_price = hydra_dx_math::xyk::calculate_in_given_out(asset_out_reserve, asset_in_reserve, amount).ok()?;
+		let fee = Self::calculate_fee(&pair_account, buy_price).ok()?;
+
+		let spot_buy_price =
+			hydra_dx_math::xyk::calculate_spot_price(asset_out_reserve, asset_in_reserve, amount).ok()?;
+
+		let buy_price_with_fee = buy_price.checked_add(fee)?;
+
+		Self::ensure_price_change_within_limit(
+			&pair_account,
+			assets,
+			asset_in_reserve.saturating_add(buy_price_with_fee),
+			asset_out_reserve.saturating_sub(amount),
+		)
+		.ok()?;
+
+		Some(TradeQuote {
+			amount: buy_price_with_fee,
+			fee,
+			price_impact: Permill::from_rational(buy_price.saturating_sub(spot_buy_price), buy_price),
+			below_min_trading_limit: amount < T::MinTradingLimit::get(),
+			max_in_ratio_exceeded: false,
+			max_out_ratio_exceeded: amount > asset_out_reserve.checked_div(T::MaxOutRatio::get())?,
+			fee_rate: Self::get_fee(&pair_account),
+		})
+	}
+
+	/// List at most `limit` pools following the pool `start_after`.
+	///
+	/// Pools are listed in storage order, first page is returned if `start_after` is `None`.
+	pub fn pools(start_after: Option<T::AccountId>, limit: u32) -> Vec<PoolInfo<T::AccountId>> {
+		let iter = match start_after {
+			Some(pool_account) => <PoolAssets<T>>::iter_from(<PoolAssets<T>>::hashed_key_for(pool_account)),
+			None => <PoolAssets<T>>::iter(),
+		};
+
+		iter.take(limit as usize)
+			.map(|(pool_account, (asset_a, asset_b))| PoolInfo {
+				asset_a,
+				asset_b,
+				asset_a_reserve: T::Currency::free_balance(asset_a, &pool_account),
+				asset_b_reserve: T::Currency::free_balance(asset_b, &pool_account),
+				share_token: Self::share_token(&pool_account),
+				total_shares: Self::total_liquidity(&pool_account),
+				pool_account,
+			})
+			.collect()
+	}
+
+	/// Liquidity positions of `who` in all pools with amounts of assets the shares are currently worth.
+	pub fn liquidity_positions(who: &T::AccountId) -> Vec<LiquidityPosition<T::AccountId>> {
+		<ShareToken<T>>::iter()
+			.filter_map(|(pool_account, share_token)| {
+				let shares = T::Currency::free_balance(share_token, who);
+
+				if shares.is_zero() {
+					return None;
+				}
+
+				let (asset_a, asset_b) = Self::pool_assets(&pool_account)?;
+
+				let (asset_a_amount, asset_b_amount) = hydra_dx_math::xyk::calculate_liquidity_out(
+					T::Currency::free_balance(asset_a, &pool_account),
+					T::Currency::free_balance(asset_b, &pool_account),
+					shares,
+					Self::total_liquidity(&pool_account),
+				)
+				.ok()?;
+
+				Some(LiquidityPosition {
+					pool_account,
+					asset_a,
+					asset_b,
+					shares,
+					asset_a_amount,
+					asset_b_amount,
+				})
+			})
+			.collect()
+	}
+
+	/// Validate trade route and split it into asset pairs of individual trades.
+	fn validate_route(route: &[AssetId]) -> Result<Vec<AssetPair>, DispatchError> {
+		ensure!(route.len() >= 2, Error::<T>::InvalidRoute);
+		ensure!(
+			route.len() - 1 <= T::MaxRouteHops::get() as usize,
+			Error::<T>::MaxRouteHopsExceeded
+		);
+
+		for (idx, asset) in route.iter().enumerate() {
+			ensure!(!route[idx + 1..].contains(asset), Error::<T>::InvalidRoute);
+		}
+
+		Ok(route
+			.windows(2)
+			.map(|pair| AssetPair {
+				asset_in: pair[0],
+				asset_out: pair[1],
+			})
+			.collect())
+	}
+
+	/// Calculate amounts of assets needed to buy `amount` of the last asset of the route.
+	///
+	/// Returns amount for each asset of the route, including fees, starting with the amount to be sold.
+	fn calculate_route_buy_amounts(hops: &[AssetPair], amount: Balance) -> Result<Vec<Balance>, DispatchError> {
+		let mut amounts = vec![amount];
+
+		for assets in hops.iter().rev() {
+			ensure!(Self::exists(*assets), Error::<T>::TokenPoolNotFound);
+
+			let pair_account = Self::get_pair_id(*assets);
+
+			let asset_out_reserve = T::Currency::free_balance(assets.asset_out, &pair_account);
+			let asset_in_reserve = T::Currency::free_balance(assets.asset_in, &pair_account);
+
+			let amount_out = *amounts.last().ok_or(Error::<T>::BuyAssetAmountInvalid)?;
+
+			let buy_price = hydra_dx_math::xyk::calculate_in_given_out(asset_out_reserve, asset_in_reserve, amount_out)
+				.map_err(|_| Error::<T>::BuyAssetAmountInvalid)?;
+
+			let buy_price_with_fee = buy_price
+				.checked_add(Self::calculate_fee(&pair_account, buy_price)?)
+				.ok_or(Error::<T>::BuyAssetAmountInvalid)?;
+
+			amounts.push(buy_price_with_fee);
+		}
+
+		amounts.reverse();
+
+		Ok(amounts)
+	}
+
+	/// Validate a sell without storage changes and return `AMMTransfer` with all info needed to execute it.
+	///
+	/// Max in ratio is checked only if `check_in_ratio` is set.
+	fn do_validate_sell(
 		who: &T::AccountId,
 		assets: AssetPair,
 		amount: Balance,
 		min_bought: Balance,
 		discount: bool,
+		check_in_ratio: bool,
 	) -> Result<AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>, sp_runtime::DispatchError> {
 		ensure!(
 			amount >= T::MinTradingLimit::get(),
@@ -658,6 +2063,11 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 
 		ensure!(Self::exists(assets), Error::<T>::TokenPoolNotFound);
 
+		ensure!(
+			Self::pool_state(&Self::get_pair_id(assets)) == PoolState::Active,
+			Error::<T>::TradingNotAllowed
+		);
+
 		ensure!(
 			T::Currency::free_balance(assets.asset_in, who) >= amount,
 			Error::<T>::InsufficientAssetBalance
@@ -679,13 +2089,15 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 		let asset_in_reserve = T::Currency::free_balance(assets.asset_in, &pair_account);
 		let asset_out_reserve = T::Currency::free_balance(assets.asset_out, &pair_account);
 
-		ensure!(
-			amount
-				<= asset_in_reserve
-					.checked_div(T::MaxInRatio::get())
-					.ok_or(Error::<T>::Overflow)?,
-			Error::<T>::MaxInRatioExceeded
-		);
+		if check_in_ratio {
+			ensure!(
+				amount
+					<= asset_in_reserve
+						.checked_div(T::MaxInRatio::get())
+						.ok_or(Error::<T>::Overflow)?,
+				Error::<T>::MaxInRatioExceeded
+			);
+		}
 
 		let amount_out = hydra_dx_math::xyk::calculate_out_given_in(asset_in_reserve, asset_out_reserve, amount)
 			.map_err(|_| Error::<T>::SellAssetAmountInvalid)?;
@@ -693,7 +2105,7 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 		let transfer_fee = if discount {
 			Self::calculate_discounted_fee(amount_out)?
 		} else {
-			Self::calculate_fee(amount_out)?
+			Self::calculate_fee(&pair_account, amount_out)?
 		};
 
 		let amount_out_without_fee = amount_out
@@ -732,6 +2144,13 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 			Balance::zero()
 		};
 
+		Self::ensure_price_change_within_limit(
+			&pair_account,
+			assets,
+			asset_in_reserve.saturating_add(amount),
+			asset_out_reserve.saturating_sub(amount_out_without_fee),
+		)?;
+
 		let transfer = AMMTransfer {
 			origin: who.clone(),
 			assets,
@@ -744,6 +2163,54 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 
 		Ok(transfer)
 	}
+}
+
+// Implementation of AMM API which makes possible to plug the AMM pool into the exchange pallet.
+impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
+	fn exists(assets: AssetPair) -> bool {
+		<ShareToken<T>>::contains_key(&Self::get_pair_id(assets))
+	}
+
+	fn get_pair_id(assets: AssetPair) -> T::AccountId {
+		Self::pair_account_from_assets(assets.asset_in, assets.asset_out)
+	}
+
+	fn get_share_token(assets: AssetPair) -> AssetId {
+		let pair_account = Self::get_pair_id(assets);
+		Self::share_token(&pair_account)
+	}
+
+	fn get_pool_assets(pool_account_id: &T::AccountId) -> Option<Vec<AssetId>> {
+		let maybe_assets = <PoolAssets<T>>::get(pool_account_id);
+		maybe_assets.map(|assets| vec![assets.0, assets.1])
+	}
+
+	fn get_spot_price_unchecked(asset_a: AssetId, asset_b: AssetId, amount: Balance) -> Balance {
+		let pair_account = Self::get_pair_id(AssetPair {
+			asset_out: asset_a,
+			asset_in: asset_b,
+		});
+
+		let asset_a_reserve = T::Currency::free_balance(asset_a, &pair_account);
+		let asset_b_reserve = T::Currency::free_balance(asset_b, &pair_account);
+
+		hydra_dx_math::xyk::calculate_spot_price(asset_a_reserve, asset_b_reserve, amount)
+			.unwrap_or_else(|_| Balance::zero())
+	}
+
+	/// Validate a sell. Perform all necessary checks and calculations.
+	/// No storage changes are performed yet.
+	///
+	/// Return `AMMTransfer` with all info needed to execute the transaction.
+	fn validate_sell(
+		who: &T::AccountId,
+		assets: AssetPair,
+		amount: Balance,
+		min_bought: Balance,
+		discount: bool,
+	) -> Result<AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>, sp_runtime::DispatchError> {
+		Self::do_validate_sell(who, assets, amount, min_bought, discount, true)
+	}
 
 	/// Execute sell. validate_sell must be called first.
 	/// Perform necessary storage/state changes.
@@ -752,6 +2219,12 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 	fn execute_sell(transfer: &AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>) -> DispatchResult {
 		let pair_account = Self::get_pair_id(transfer.assets);
 
+		Self::update_price_accumulator(&pair_account);
+		Self::record_block_start_state(&pair_account);
+		Self::update_volatility(&pair_account);
+
+		let dynamic_fee_rate = Self::dynamic_fee_rate(&pair_account);
+
 		let total_liquidity = Self::total_liquidity(&pair_account);
 		T::AMMHandler::on_trade(
 			transfer.assets.asset_in,
@@ -779,6 +2252,12 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 			transfer.amount_out,
 		)?;
 
+		Self::transfer_protocol_fee(&pair_account, transfer.fee.0, transfer.fee.1)?;
+
+		if let Some(fee_rate) = dynamic_fee_rate {
+			Self::deposit_event(Event::DynamicFeeApplied(pair_account.clone(), fee_rate));
+		}
+
 		Self::deposit_event(Event::<T>::SellExecuted(
 			transfer.origin.clone(),
 			transfer.assets.asset_in,
@@ -811,6 +2290,11 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 
 		ensure!(Self::exists(assets), Error::<T>::TokenPoolNotFound);
 
+		ensure!(
+			Self::pool_state(&Self::get_pair_id(assets)) == PoolState::Active,
+			Error::<T>::TradingNotAllowed
+		);
+
 		let pair_account = Self::get_pair_id(assets);
 
 		let asset_out_reserve = T::Currency::free_balance(assets.asset_out, &pair_account);
@@ -843,7 +2327,7 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 		let transfer_fee = if discount {
 			Self::calculate_discounted_fee(buy_price)?
 		} else {
-			Self::calculate_fee(buy_price)?
+			Self::calculate_fee(&pair_account, buy_price)?
 		};
 
 		let buy_price_with_fee = buy_price
@@ -881,6 +2365,13 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 			Balance::zero()
 		};
 
+		Self::ensure_price_change_within_limit(
+			&pair_account,
+			assets,
+			asset_in_reserve.saturating_add(buy_price_with_fee),
+			asset_out_reserve.saturating_sub(amount),
+		)?;
+
 		let transfer = AMMTransfer {
 			origin: who.clone(),
 			assets,
@@ -901,6 +2392,12 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 	fn execute_buy(transfer: &AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>) -> DispatchResult {
 		let pair_account = Self::get_pair_id(transfer.assets);
 
+		Self::update_price_accumulator(&pair_account);
+		Self::record_block_start_state(&pair_account);
+		Self::update_volatility(&pair_account);
+
+		let dynamic_fee_rate = Self::dynamic_fee_rate(&pair_account);
+
 		let total_liquidity = Self::total_liquidity(&pair_account);
 		T::AMMHandler::on_trade(
 			transfer.assets.asset_in,
@@ -928,6 +2425,12 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 			transfer.amount_out + transfer.fee.1,
 		)?;
 
+		Self::transfer_protocol_fee(&pair_account, transfer.fee.0, transfer.fee.1)?;
+
+		if let Some(fee_rate) = dynamic_fee_rate {
+			Self::deposit_event(Event::DynamicFeeApplied(pair_account.clone(), fee_rate));
+		}
+
 		Self::deposit_event(Event::<T>::BuyExecuted(
 			transfer.origin.clone(),
 			transfer.assets.asset_out,
@@ -958,8 +2461,10 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 		T::MaxOutRatio::get()
 	}
 
-	fn get_fee(_pool_account_id: &T::AccountId) -> (u32, u32) {
-		T::GetExchangeFee::get()
+	fn get_fee(pool_account_id: &T::AccountId) -> (u32, u32) {
+		Self::dynamic_fee_rate(pool_account_id)
+			.or_else(|| Self::pool_fee(pool_account_id))
+			.unwrap_or_else(T::GetExchangeFee::get)
 	}
 }
 
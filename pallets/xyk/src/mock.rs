@@ -16,8 +16,8 @@
 // limitations under the License.
 
 use crate as xyk;
-use crate::Config;
-use frame_support::parameter_types;
+use crate::{Config, ShareTokenCleanup};
+use frame_support::{dispatch::DispatchResult, parameter_types};
 use frame_system as system;
 use orml_traits::parameter_type_with_key;
 use sp_core::H256;
@@ -29,11 +29,12 @@ use sp_runtime::{
 use frame_support::traits::{Everything, GenesisBuild, Get, Nothing};
 use hydradx_traits::{AssetPairAccountIdFor, CanCreatePool};
 use primitives::{
+	asset::AssetPair,
 	constants::chain::{MAX_IN_RATIO, MAX_OUT_RATIO, MIN_POOL_LIQUIDITY, MIN_TRADING_LIMIT},
 	AssetId, Balance,
 };
 
-use frame_system::EnsureSigned;
+use frame_system::EnsureRoot;
 use std::cell::RefCell;
 
 pub type Amount = i128;
@@ -67,6 +68,8 @@ frame_support::construct_runtime!(
 
 thread_local! {
 		static EXCHANGE_FEE: RefCell<(u32, u32)> = RefCell::new((2, 1_000));
+		static LOCKED_LIQUIDITY: RefCell<Balance> = RefCell::new(0);
+		static EXISTENTIAL_DEPOSITS: RefCell<Vec<(AssetId, Balance)>> = RefCell::new(vec![]);
 }
 
 struct ExchangeFee;
@@ -76,17 +79,25 @@ impl Get<(u32, u32)> for ExchangeFee {
 	}
 }
 
+pub struct LockedLiquidity;
+impl Get<Balance> for LockedLiquidity {
+	fn get() -> Balance {
+		LOCKED_LIQUIDITY.with(|v| *v.borrow())
+	}
+}
+
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
 	pub const SS58Prefix: u8 = 63;
 	pub const NativeAssetId: AssetId = HDX;
 	pub ExchangeFeeRate: (u32, u32) = ExchangeFee::get();
+	pub FeeTiers: Vec<(u32, u32)> = vec![(1, 10_000), (2, 1_000), (1, 100)];
 	pub RegistryStringLimit: u32 = 100;
 }
 
 impl pallet_asset_registry::Config for Test {
 	type Event = Event;
-	type RegistryOrigin = EnsureSigned<AccountId>;
+	type RegistryOrigin = EnsureRoot<AccountId>;
 	type AssetId = AssetId;
 	type Balance = Balance;
 	type AssetNativeLocation = u8;
@@ -123,8 +134,13 @@ impl system::Config for Test {
 }
 
 parameter_type_with_key! {
-	pub ExistentialDeposits: |_currency_id: AssetId| -> Balance {
-		One::one()
+	pub ExistentialDeposits: |currency_id: AssetId| -> Balance {
+		EXISTENTIAL_DEPOSITS.with(|v| {
+			v.borrow()
+				.iter()
+				.find(|(asset, _)| asset == currency_id)
+				.map_or(One::one(), |(_, amount)| *amount)
+		})
 	};
 }
 
@@ -158,6 +174,9 @@ parameter_types! {
 	pub const MinPoolLiquidity: Balance = MIN_POOL_LIQUIDITY;
 	pub const MaxInRatio: u128 = MAX_IN_RATIO;
 	pub const MaxOutRatio: u128 = MAX_OUT_RATIO;
+	pub const MaxRouteHops: u32 = 5;
+	pub const MaxPriceObservations: u32 = 10;
+	pub const ProtocolFeeAccount: AccountId = 99;
 }
 
 pub struct Disallow10_10Pool();
@@ -168,18 +187,47 @@ impl CanCreatePool<AssetId> for Disallow10_10Pool {
 	}
 }
 
+pub struct RenameShareToken();
+
+impl ShareTokenCleanup<AssetId> for RenameShareToken {
+	fn deregister_share_token(asset_a: AssetId, asset_b: AssetId, share_token: AssetId) -> DispatchResult {
+		let mut name = AssetPair {
+			asset_in: asset_a,
+			asset_out: asset_b,
+		}
+		.name();
+		name.extend_from_slice(&share_token.to_le_bytes());
+
+		AssetRegistry::update(
+			Origin::root(),
+			share_token,
+			name,
+			pallet_asset_registry::AssetType::PoolShare(asset_a, asset_b),
+			None,
+		)
+	}
+}
+
 impl Config for Test {
 	type Event = Event;
 	type AssetRegistry = AssetRegistry;
+	type ShareTokenCleanup = RenameShareToken;
 	type AssetPairAccountId = AssetPairAccountIdTest;
 	type Currency = Currency;
 	type NativeAssetId = NativeAssetId;
 	type WeightInfo = ();
 	type GetExchangeFee = ExchangeFeeRate;
+	type FeeTiers = FeeTiers;
+	type UpdatePoolFeeOrigin = EnsureRoot<AccountId>;
+	type UpdatePoolStateOrigin = EnsureRoot<AccountId>;
+	type ProtocolFeeAccount = ProtocolFeeAccount;
 	type MinTradingLimit = MinTradingLimit;
 	type MinPoolLiquidity = MinPoolLiquidity;
+	type LockedLiquidity = LockedLiquidity;
 	type MaxInRatio = MaxInRatio;
 	type MaxOutRatio = MaxOutRatio;
+	type MaxRouteHops = MaxRouteHops;
+	type MaxPriceObservations = MaxPriceObservations;
 	type CanCreatePool = Disallow10_10Pool;
 	type AMMHandler = ();
 }
@@ -217,6 +265,16 @@ impl ExtBuilder {
 		self
 	}
 
+	pub fn with_locked_liquidity(self, amount: Balance) -> Self {
+		LOCKED_LIQUIDITY.with(|v| *v.borrow_mut() = amount);
+		self
+	}
+
+	pub fn with_existential_deposit(self, asset: AssetId, amount: Balance) -> Self {
+		EXISTENTIAL_DEPOSITS.with(|v| v.borrow_mut().push((asset, amount)));
+		self
+	}
+
 	pub fn build(self) -> sp_io::TestExternalities {
 		let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 
@@ -0,0 +1,86 @@
+use crate::{Call, Config};
+use codec::{Decode, Encode};
+use frame_support::traits::IsSubType;
+use sp_runtime::traits::{DispatchInfoOf, One, Saturating, SignedExtension, UniqueSaturatedInto};
+use sp_runtime::transaction_validity::{
+	InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+};
+use sp_std::marker::PhantomData;
+
+/// Custom `InvalidTransaction` code of a price bounded trade submitted after its expiry block.
+pub const TRADE_EXPIRED: u8 = 1;
+
+/// Signed extension validating expiry of price bounded trades.
+///
+/// Expired trades are rejected from the transaction pool and valid trades are kept in the pool only
+/// until their expiry block.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, scale_info::TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct ValidateTradeExpiry<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for ValidateTradeExpiry<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "ValidateTradeExpiry")
+	}
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config + Send + Sync> ValidateTradeExpiry<T> {
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T: Config + Send + Sync> Default for ValidateTradeExpiry<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for ValidateTradeExpiry<T>
+where
+	<T as frame_system::Config>::Call: IsSubType<Call<T>>,
+{
+	const IDENTIFIER: &'static str = "ValidateTradeExpiry";
+	type AccountId = T::AccountId;
+	type Call = <T as frame_system::Config>::Call;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		_who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		let valid_until = match call.is_sub_type() {
+			Some(Call::sell_at_price { valid_until, .. }) | Some(Call::buy_at_price { valid_until, .. }) => {
+				*valid_until
+			}
+			_ => return Ok(ValidTransaction::default()),
+		};
+
+		let now = <frame_system::Pallet<T>>::block_number();
+
+		if now > valid_until {
+			return Err(InvalidTransaction::Custom(TRADE_EXPIRED).into());
+		}
+
+		Ok(ValidTransaction {
+			longevity: valid_until
+				.saturating_sub(now)
+				.saturating_add(One::one())
+				.unique_saturated_into(),
+			..Default::default()
+		})
+	}
+}
@@ -17,13 +17,17 @@
 
 use super::*;
 pub use crate::mock::{
-	Currency, Event as TestEvent, ExtBuilder, Origin, System, Test, ACA, ALICE, BOB, DOT, HDX, HDX_DOT_POOL_ID, XYK,
+	Currency, Event as TestEvent, ExtBuilder, MaxPriceObservations, Origin, ProtocolFeeAccount, System, Test, ACA,
+	ALICE, BOB, DOT, HDX, HDX_DOT_POOL_ID, XYK,
 };
 use frame_support::BoundedVec;
 use frame_support::{assert_noop, assert_ok};
 use hydra_dx_math::MathError;
+use hydradx_traits::pools::SpotPriceProvider;
 use hydradx_traits::AMM as AmmPool;
 use pallet_asset_registry::AssetType;
+use sp_runtime::traits::SignedExtension;
+use sp_runtime::transaction_validity::{InvalidTransaction, ValidTransaction};
 use sp_std::convert::TryInto;
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -46,7 +50,8 @@ fn create_pool_should_work() {
 			asset_a,
 			asset_b,
 			100_000_000_000_000,
-			Price::from(10)
+			Price::from(10),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -88,22 +93,23 @@ fn create_same_pool_should_not_work() {
 			asset_b,
 			asset_a,
 			1000,
-			Price::from(2)
+			Price::from(2),
+			None
 		));
 		assert_noop!(
-			XYK::create_pool(Origin::signed(user), asset_b, asset_a, 999, Price::from(2)),
+			XYK::create_pool(Origin::signed(user), asset_b, asset_a, 999, Price::from(2), None),
 			Error::<Test>::InsufficientLiquidity
 		);
 		assert_noop!(
-			XYK::create_pool(Origin::signed(user), asset_b, asset_a, 1000, Price::from(0)),
+			XYK::create_pool(Origin::signed(user), asset_b, asset_a, 1000, Price::from(0), None),
 			Error::<Test>::ZeroInitialPrice
 		);
 		assert_noop!(
-			XYK::create_pool(Origin::signed(user), asset_a, asset_a, 1000, Price::from(2)),
+			XYK::create_pool(Origin::signed(user), asset_a, asset_a, 1000, Price::from(2), None),
 			Error::<Test>::CannotCreatePoolWithSameAssets
 		);
 		assert_noop!(
-			XYK::create_pool(Origin::signed(user), asset_b, asset_a, 1000, Price::from(2)),
+			XYK::create_pool(Origin::signed(user), asset_b, asset_a, 1000, Price::from(2), None),
 			Error::<Test>::TokenPoolAlreadyExists
 		);
 
@@ -138,7 +144,8 @@ fn create_pool_overflowing_amount_should_not_work() {
 				asset_b,
 				asset_a,
 				u128::MAX as u128,
-				Price::from(2)
+				Price::from(2),
+				None
 			),
 			Error::<Test>::CreatePoolAssetAmountInvalid
 		);
@@ -157,7 +164,8 @@ fn create_pool_with_insufficient_balance_should_not_work() {
 				4000,
 				asset_a,
 				100_000_000_000_000,
-				Price::from(10)
+				Price::from(10),
+				None
 			),
 			Error::<Test>::InsufficientAssetBalance
 		);
@@ -168,7 +176,8 @@ fn create_pool_with_insufficient_balance_should_not_work() {
 				asset_a,
 				4000,
 				100_000_000_000_000,
-				Price::from(10)
+				Price::from(10),
+				None
 			),
 			Error::<Test>::InsufficientAssetBalance
 		);
@@ -187,7 +196,8 @@ fn add_liquidity_should_work() {
 			asset_a,
 			asset_b,
 			100_000_000,
-			Price::from(10_000)
+			Price::from(10_000),
+			None
 		));
 
 		assert_ok!(XYK::add_liquidity(
@@ -228,7 +238,8 @@ fn add_liquidity_as_another_user_should_work() {
 			asset_b,
 			asset_a,
 			100_000_000,
-			Price::from(10_000)
+			Price::from(10_000),
+			None
 		));
 		assert_ok!(XYK::add_liquidity(
 			Origin::signed(user),
@@ -292,7 +303,8 @@ fn remove_liquidity_should_work() {
 			asset_a,
 			asset_b,
 			100_000_000,
-			Price::from(10_000)
+			Price::from(10_000),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -333,7 +345,8 @@ fn remove_liquidity_without_shares_should_not_work() {
 			asset_a,
 			asset_b,
 			100_000_000,
-			Price::from(1)
+			Price::from(1),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -382,7 +395,8 @@ fn remove_liquidity_from_reduced_pool_should_not_work() {
 			asset_a,
 			asset_b,
 			100_000_000,
-			Price::from(1)
+			Price::from(1),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -464,7 +478,8 @@ fn add_liquidity_more_than_owner_should_not_work() {
 			HDX,
 			ACA,
 			200_000_000,
-			Price::from(3000000)
+			Price::from(3000000),
+			None
 		));
 
 		assert_eq!(Currency::free_balance(ACA, &ALICE), 400000000000000);
@@ -489,7 +504,8 @@ fn add_insufficient_liquidity_should_not_work() {
 			HDX,
 			ACA,
 			1000,
-			Price::from_float(1.5)
+			Price::from_float(1.5),
+			None
 		));
 
 		assert_noop!(
@@ -517,7 +533,8 @@ fn add_liquidity_exceeding_max_limit_should_not_work() {
 			HDX,
 			ACA,
 			100_000_000_000_000,
-			Price::from(1)
+			Price::from(1),
+			None
 		));
 
 		assert_noop!(
@@ -534,7 +551,8 @@ fn remove_liquidity_should_respect_min_pool_limit() {
 			HDX,
 			ACA,
 			1000,
-			Price::from_float(1.5)
+			Price::from_float(1.5),
+			None
 		));
 
 		assert_ok!(XYK::add_liquidity(Origin::signed(BOB), ACA, HDX, 2000, 2000));
@@ -568,7 +586,8 @@ fn sell_test() {
 			asset_a,
 			asset_b,
 			200_000_000_000,
-			Price::from(3000)
+			Price::from(3000),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -640,7 +659,8 @@ fn work_flow_happy_path_should_work() {
 			asset_a,
 			asset_b,
 			350_000_000_000,
-			Price::from(40)
+			Price::from(40),
+			None
 		));
 
 		// User 1 really tries!
@@ -845,7 +865,8 @@ fn sell_with_correct_fees_should_work() {
 			asset_a,
 			asset_b,
 			10_000_000,
-			Price::from(200)
+			Price::from(200),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -915,14 +936,16 @@ fn discount_sell_fees_should_work() {
 			asset_a,
 			HDX,
 			5_000,
-			Price::from(2)
+			Price::from(2),
+			None
 		));
 		assert_ok!(XYK::create_pool(
 			Origin::signed(user_1),
 			asset_a,
 			asset_b,
 			30_000,
-			Price::from(2)
+			Price::from(2),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -1001,7 +1024,8 @@ fn sell_without_sufficient_balance_should_not_work() {
 			asset_a,
 			asset_b,
 			1_000_000_000,
-			Price::from(1)
+			Price::from(1),
+			None
 		));
 
 		assert_ok!(Currency::transfer(Origin::signed(user), BOB, ACA, 999_998_999_999_999));
@@ -1025,7 +1049,8 @@ fn sell_without_sufficient_discount_balance_should_not_work() {
 			asset_a,
 			asset_b,
 			1_000_000_000_000,
-			Price::from(1)
+			Price::from(1),
+			None
 		));
 
 		assert_ok!(XYK::create_pool(
@@ -1033,7 +1058,8 @@ fn sell_without_sufficient_discount_balance_should_not_work() {
 			asset_a,
 			HDX,
 			1_000_000_000_000,
-			Price::from(1)
+			Price::from(1),
+			None
 		));
 
 		assert_ok!(Currency::transfer(Origin::signed(user), BOB, HDX, 998_999_999_999_999));
@@ -1057,7 +1083,8 @@ fn buy_without_sufficient_balance_should_not_work() {
 			asset_a,
 			asset_b,
 			1_000_000_000,
-			Price::from(1)
+			Price::from(1),
+			None
 		));
 
 		assert_ok!(Currency::transfer(Origin::signed(user), BOB, ACA, 999_998_999_999_999));
@@ -1081,7 +1108,8 @@ fn buy_without_sufficient_discount_balance_should_not_work() {
 			asset_a,
 			asset_b,
 			1_000_000_000_000,
-			Price::from(1)
+			Price::from(1),
+			None
 		));
 
 		assert_ok!(XYK::create_pool(
@@ -1089,7 +1117,8 @@ fn buy_without_sufficient_discount_balance_should_not_work() {
 			asset_b,
 			HDX,
 			1_000_000_000_000,
-			Price::from(1)
+			Price::from(1),
+			None
 		));
 
 		assert_ok!(Currency::transfer(Origin::signed(user), BOB, HDX, 998_999_999_999_999));
@@ -1113,7 +1142,8 @@ fn single_buy_should_work() {
 			asset_a,
 			asset_b,
 			200_000_000,
-			Price::from(3200)
+			Price::from(3200),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -1173,7 +1203,8 @@ fn single_buy_with_discount_should_work() {
 			asset_a,
 			asset_b,
 			200_000_000,
-			Price::from(3200)
+			Price::from(3200),
+			None
 		));
 
 		assert_ok!(XYK::create_pool(
@@ -1181,7 +1212,8 @@ fn single_buy_with_discount_should_work() {
 			asset_a,
 			HDX,
 			50_000_000_000,
-			Price::from(2)
+			Price::from(2),
+			None
 		));
 
 		let native_pair_account = XYK::get_pair_id(AssetPair {
@@ -1281,17 +1313,17 @@ fn single_buy_with_discount_should_work() {
 fn create_pool_with_insufficient_liquidity_should_not_work() {
 	new_test_ext().execute_with(|| {
 		assert_noop!(
-			XYK::create_pool(Origin::signed(ALICE), ACA, HDX, 500, Price::from(3200)),
+			XYK::create_pool(Origin::signed(ALICE), ACA, HDX, 500, Price::from(3200), None),
 			Error::<Test>::InsufficientLiquidity
 		);
 
 		assert_noop!(
-			XYK::create_pool(Origin::signed(ALICE), ACA, HDX, 5000, Price::from_float(0.1f64)),
+			XYK::create_pool(Origin::signed(ALICE), ACA, HDX, 5000, Price::from_float(0.1f64), None),
 			Error::<Test>::InsufficientLiquidity
 		);
 
 		assert_noop!(
-			XYK::create_pool(Origin::signed(ALICE), ACA, HDX, 1000, Price::from(0)),
+			XYK::create_pool(Origin::signed(ALICE), ACA, HDX, 1000, Price::from(0), None),
 			Error::<Test>::ZeroInitialPrice
 		);
 	});
@@ -1335,7 +1367,8 @@ fn discount_sell_with_no_native_pool_should_not_work() {
 			ACA,
 			DOT,
 			1000,
-			Price::from(3200)
+			Price::from(3200),
+			None
 		));
 
 		assert_noop!(
@@ -1363,7 +1396,8 @@ fn discount_buy_with_no_native_pool_should_not_work() {
 			ACA,
 			DOT,
 			10000,
-			Price::from(3200)
+			Price::from(3200),
+			None
 		));
 
 		assert_noop!(
@@ -1384,7 +1418,8 @@ fn create_pool_small_fixed_point_amount_should_work() {
 			asset_a,
 			asset_b,
 			100_000_000_000_000,
-			Price::from_float(0.00001)
+			Price::from_float(0.00001),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -1422,7 +1457,8 @@ fn create_pool_fixed_point_amount_should_work() {
 			asset_a,
 			asset_b,
 			100_000_000_000,
-			Price::from_float(4560.234543)
+			Price::from_float(4560.234543),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -1465,7 +1501,8 @@ fn money_in_sell_money_out_should_leave_the_same_balance() {
 			asset_a,
 			asset_b,
 			200_000_000_000,
-			Price::from(3000)
+			Price::from(3000),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -1529,7 +1566,8 @@ fn money_in_money_out_should_leave_the_same_balance_for_both_accounts() {
 			asset_a,
 			asset_b,
 			100_000_000,
-			Price::from(10_000)
+			Price::from(10_000),
+			None
 		));
 
 		let asset_pair = AssetPair {
@@ -1595,7 +1633,8 @@ fn destroy_pool_on_remove_liquidity_and_recreate_should_work() {
 			asset_a,
 			asset_b,
 			100_000_000,
-			Price::from(10_000)
+			Price::from(10_000),
+			None
 		));
 
 		let asset_pair = AssetPair {
@@ -1626,9 +1665,13 @@ fn destroy_pool_on_remove_liquidity_and_recreate_should_work() {
 			asset_a,
 			asset_b,
 			100_000_000,
-			Price::from(10_000)
+			Price::from(10_000),
+			None
 		));
 
+		let new_share_token = XYK::share_token(pair_account);
+		assert_ne!(new_share_token, share_token);
+
 		expect_events(vec![
 			Event::PoolCreated(user, asset_a, asset_b, 100_000_000, share_token, pair_account).into(),
 			frame_system::Event::KilledAccount { account: pair_account }.into(),
@@ -1648,16 +1691,77 @@ fn destroy_pool_on_remove_liquidity_and_recreate_should_work() {
 			}
 			.into(),
 			orml_tokens::Event::Endowed {
-				currency_id: 0,
+				currency_id: new_share_token,
 				who: 1,
 				amount: 100000000,
 			}
 			.into(),
-			Event::PoolCreated(user, asset_a, asset_b, 100_000_000, share_token, pair_account).into(),
+			Event::PoolCreated(user, asset_a, asset_b, 100_000_000, new_share_token, pair_account).into(),
 		]);
 	});
 }
 
+#[test]
+fn share_token_of_recreated_pool_should_work() {
+	new_test_ext().execute_with(|| {
+		let asset_pair = AssetPair {
+			asset_in: HDX,
+			asset_out: DOT,
+		};
+		let pair_account = XYK::get_pair_id(asset_pair);
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			100_000_000,
+			Price::from(10_000),
+			None
+		));
+		let share_token = XYK::share_token(pair_account);
+
+		assert_ok!(XYK::remove_liquidity(Origin::signed(ALICE), HDX, DOT, 100_000_000));
+		assert!(!XYK::exists(asset_pair));
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			100_000_000_000,
+			Price::from(2),
+			None
+		));
+		let new_share_token = XYK::share_token(pair_account);
+		assert_ne!(new_share_token, share_token);
+		assert_eq!(XYK::get_share_token(asset_pair), new_share_token);
+
+		assert_ok!(XYK::add_liquidity(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			100_000_000_000,
+			300_000_000_000
+		));
+		assert_eq!(Currency::free_balance(new_share_token, &BOB), 100_000_000_000);
+		assert_eq!(Currency::free_balance(share_token, &BOB), 0);
+		assert_eq!(XYK::total_liquidity(&pair_account), 200_000_000_000);
+
+		assert_ok!(XYK::sell(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			1_000_000_000,
+			1_000_000,
+			false
+		));
+
+		assert_ok!(XYK::remove_liquidity(Origin::signed(BOB), HDX, DOT, 100_000_000_000));
+		assert_eq!(Currency::free_balance(new_share_token, &BOB), 0);
+		assert_eq!(XYK::total_liquidity(&pair_account), 100_000_000_000);
+		assert_eq!(Currency::total_issuance(new_share_token), 100_000_000_000);
+	});
+}
+
 #[test]
 fn create_pool_with_same_assets_should_not_be_allowed() {
 	new_test_ext().execute_with(|| {
@@ -1665,7 +1769,14 @@ fn create_pool_with_same_assets_should_not_be_allowed() {
 		let asset_a = HDX;
 
 		assert_noop!(
-			XYK::create_pool(Origin::signed(user), asset_a, asset_a, 100_000_000, Price::from(10_000)),
+			XYK::create_pool(
+				Origin::signed(user),
+				asset_a,
+				asset_a,
+				100_000_000,
+				Price::from(10_000),
+				None
+			),
 			Error::<Test>::CannotCreatePoolWithSameAssets
 		);
 	})
@@ -1683,7 +1794,8 @@ fn sell_test_not_reaching_limit() {
 			asset_a,
 			asset_b,
 			200_000_000_000,
-			Price::from(3000)
+			Price::from(3000),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -1731,7 +1843,8 @@ fn buy_test_exceeding_max_limit() {
 			asset_a,
 			asset_b,
 			200_000_000_000,
-			Price::from(3000)
+			Price::from(3000),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -1779,7 +1892,8 @@ fn single_buy_more_than_ratio_out_should_not_work() {
 			asset_a,
 			asset_b,
 			200_000_000,
-			Price::from(3200)
+			Price::from(3200),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -1821,7 +1935,8 @@ fn single_sell_more_than_ratio_in_should_not_work() {
 			asset_a,
 			asset_b,
 			200_000_000_000,
-			Price::from(3000)
+			Price::from(3000),
+			None
 		));
 
 		let pair_account = XYK::get_pair_id(AssetPair {
@@ -1923,7 +2038,8 @@ fn buy_with_excesive_amount_should_not_work() {
 			HDX,
 			DOT,
 			10_000,
-			Price::from(1)
+			Price::from(1),
+			None
 		));
 
 		assert_noop!(
@@ -1940,29 +2056,32 @@ fn fee_calculation() {
 		assert_eq!(XYK::calculate_discounted_fee(100000), Ok(70));
 		assert_eq!(XYK::calculate_discounted_fee(100000), Ok(70));
 
-		assert_eq!(XYK::calculate_fee(100000), Ok(200));
-		assert_eq!(XYK::calculate_fee(10000), Ok(20));
+		assert_eq!(XYK::calculate_fee(&HDX_DOT_POOL_ID, 100000), Ok(200));
+		assert_eq!(XYK::calculate_fee(&HDX_DOT_POOL_ID, 10000), Ok(20));
 	});
 	ExtBuilder::default()
 		.with_exchange_fee((10, 1000))
 		.build()
 		.execute_with(|| {
-			assert_eq!(XYK::calculate_fee(100000), Ok(1000));
-			assert_eq!(XYK::calculate_fee(10000), Ok(100));
+			assert_eq!(XYK::calculate_fee(&HDX_DOT_POOL_ID, 100000), Ok(1000));
+			assert_eq!(XYK::calculate_fee(&HDX_DOT_POOL_ID, 10000), Ok(100));
 		});
 
 	ExtBuilder::default()
 		.with_exchange_fee((10, 0))
 		.build()
 		.execute_with(|| {
-			assert_eq!(XYK::calculate_fee(100000), Ok(0));
+			assert_eq!(XYK::calculate_fee(&HDX_DOT_POOL_ID, 100000), Ok(0));
 		});
 
 	ExtBuilder::default()
 		.with_exchange_fee((10, 1))
 		.build()
 		.execute_with(|| {
-			assert_noop!(XYK::calculate_fee(u128::MAX), Error::<Test>::FeeAmountInvalid);
+			assert_noop!(
+				XYK::calculate_fee(&HDX_DOT_POOL_ID, u128::MAX),
+				Error::<Test>::FeeAmountInvalid
+			);
 		});
 }
 
@@ -1977,7 +2096,8 @@ fn can_create_pool_should_work() {
 				asset_a,
 				asset_b,
 				100_000_000_000_000,
-				Price::from(10)
+				Price::from(10),
+				None
 			),
 			Error::<Test>::CannotCreatePool
 		);
@@ -1992,7 +2112,8 @@ fn get_fee_should_work() {
 			HDX,
 			DOT,
 			1_000_000_000,
-			Price::from(2)
+			Price::from(2),
+			None
 		),);
 
 		// existing pool
@@ -2003,3 +2124,2020 @@ fn get_fee_should_work() {
 		assert_eq!(fee, (2, 1_000));
 	});
 }
+
+fn create_route_pools() {
+	assert_ok!(XYK::create_pool(
+		Origin::signed(ALICE),
+		HDX,
+		DOT,
+		100_000_000_000_000,
+		Price::from(2),
+		None
+	));
+	assert_ok!(XYK::create_pool(
+		Origin::signed(ALICE),
+		DOT,
+		ACA,
+		100_000_000_000_000,
+		Price::from(3),
+		None
+	));
+}
+
+#[test]
+fn sell_route_should_work() {
+	new_test_ext().execute_with(|| {
+		create_route_pools();
+
+		let hdx_dot_pool = XYK::get_pair_id(AssetPair::new(HDX, DOT));
+		let dot_aca_pool = XYK::get_pair_id(AssetPair::new(DOT, ACA));
+
+		assert_ok!(XYK::sell_route(
+			Origin::signed(BOB),
+			vec![HDX, DOT, ACA],
+			1_000_000_000_000,
+			5_802_190_376_327,
+		));
+
+		assert_eq!(Currency::free_balance(HDX, &BOB), 999_000_000_000_000);
+		assert_eq!(Currency::free_balance(DOT, &BOB), 1_000_000_000_000_000);
+		assert_eq!(Currency::free_balance(ACA, &BOB), 1_005_802_190_376_327);
+
+		assert_eq!(Currency::free_balance(HDX, &hdx_dot_pool), 101_000_000_000_000);
+		assert_eq!(Currency::free_balance(DOT, &hdx_dot_pool), 198_023_762_376_237);
+		assert_eq!(Currency::free_balance(DOT, &dot_aca_pool), 101_976_237_623_763);
+		assert_eq!(Currency::free_balance(ACA, &dot_aca_pool), 294_197_809_623_673);
+
+		expect_events(vec![
+			Event::SellExecuted(
+				BOB,
+				HDX,
+				DOT,
+				1_000_000_000_000,
+				1_976_237_623_763,
+				DOT,
+				3_960_396_038,
+				hdx_dot_pool,
+			)
+			.into(),
+			Event::SellExecuted(
+				BOB,
+				DOT,
+				ACA,
+				1_976_237_623_763,
+				5_802_190_376_327,
+				ACA,
+				11_627_636_024,
+				dot_aca_pool,
+			)
+			.into(),
+			Event::RouteSellExecuted(BOB, vec![HDX, DOT, ACA], 1_000_000_000_000, 5_802_190_376_327).into(),
+		]);
+	});
+}
+
+#[test]
+fn sell_route_not_reaching_limit_should_not_work() {
+	new_test_ext().execute_with(|| {
+		create_route_pools();
+
+		assert_noop!(
+			XYK::sell_route(
+				Origin::signed(BOB),
+				vec![HDX, DOT, ACA],
+				1_000_000_000_000,
+				5_802_190_376_328,
+			),
+			Error::<Test>::AssetAmountNotReachedLimit
+		);
+	});
+}
+
+#[test]
+fn buy_route_should_work() {
+	new_test_ext().execute_with(|| {
+		create_route_pools();
+
+		let hdx_dot_pool = XYK::get_pair_id(AssetPair::new(HDX, DOT));
+		let dot_aca_pool = XYK::get_pair_id(AssetPair::new(DOT, ACA));
+
+		assert_ok!(XYK::buy_route(
+			Origin::signed(BOB),
+			vec![HDX, DOT, ACA],
+			1_000_000_000_000,
+			168_175_437_774,
+		));
+
+		assert_eq!(Currency::free_balance(HDX, &BOB), 999_831_824_562_226);
+		assert_eq!(Currency::free_balance(DOT, &BOB), 1_000_000_000_000_000);
+		assert_eq!(Currency::free_balance(ACA, &BOB), 1_001_000_000_000_000);
+
+		assert_eq!(Currency::free_balance(HDX, &hdx_dot_pool), 100_168_175_437_774);
+		assert_eq!(Currency::free_balance(DOT, &hdx_dot_pool), 199_664_882_943_144);
+		assert_eq!(Currency::free_balance(DOT, &dot_aca_pool), 100_335_117_056_856);
+		assert_eq!(Currency::free_balance(ACA, &dot_aca_pool), 299_000_000_000_000);
+
+		expect_events(vec![
+			Event::BuyExecuted(
+				BOB,
+				DOT,
+				HDX,
+				335_117_056_856,
+				167_839_758_258,
+				HDX,
+				335_679_516,
+				hdx_dot_pool,
+			)
+			.into(),
+			Event::BuyExecuted(
+				BOB,
+				ACA,
+				DOT,
+				1_000_000_000_000,
+				334_448_160_536,
+				DOT,
+				668_896_320,
+				dot_aca_pool,
+			)
+			.into(),
+			Event::RouteBuyExecuted(BOB, vec![HDX, DOT, ACA], 1_000_000_000_000, 168_175_437_774).into(),
+		]);
+	});
+}
+
+#[test]
+fn buy_route_exceeding_limit_should_not_work() {
+	new_test_ext().execute_with(|| {
+		create_route_pools();
+
+		assert_noop!(
+			XYK::buy_route(
+				Origin::signed(BOB),
+				vec![HDX, DOT, ACA],
+				1_000_000_000_000,
+				168_175_437_773,
+			),
+			Error::<Test>::AssetAmountExceededLimit
+		);
+	});
+}
+
+#[test]
+fn trade_route_with_invalid_route_should_not_work() {
+	new_test_ext().execute_with(|| {
+		create_route_pools();
+
+		assert_noop!(
+			XYK::sell_route(Origin::signed(BOB), vec![HDX], 1_000_000_000_000, 0),
+			Error::<Test>::InvalidRoute
+		);
+		assert_noop!(
+			XYK::buy_route(Origin::signed(BOB), vec![], 1_000_000_000_000, u128::MAX),
+			Error::<Test>::InvalidRoute
+		);
+		assert_noop!(
+			XYK::sell_route(Origin::signed(BOB), vec![HDX, DOT, HDX], 1_000_000_000_000, 0),
+			Error::<Test>::InvalidRoute
+		);
+		assert_noop!(
+			XYK::sell_route(
+				Origin::signed(BOB),
+				vec![HDX, DOT, ACA, 4_000, 5_000, 6_000, 7_000],
+				1_000_000_000_000,
+				0
+			),
+			Error::<Test>::MaxRouteHopsExceeded
+		);
+		assert_noop!(
+			XYK::sell_route(Origin::signed(BOB), vec![HDX, ACA], 1_000_000_000_000, 0),
+			Error::<Test>::TokenPoolNotFound
+		);
+		assert_noop!(
+			XYK::buy_route(
+				Origin::signed(BOB),
+				vec![ACA, DOT, HDX, 4_000],
+				1_000_000_000_000,
+				u128::MAX
+			),
+			Error::<Test>::TokenPoolNotFound
+		);
+	});
+}
+
+#[test]
+fn quote_sell_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			ACA,
+			DOT,
+			200_000_000_000,
+			Price::from(3000),
+			None
+		));
+
+		assert_eq!(
+			XYK::quote_sell(ACA, DOT, 456_444_678),
+			Some(TradeQuote {
+				amount: 1_363_483_591_788,
+				fee: 2_732_432_046,
+				price_impact: Permill::from_rational(3_118_010_166u128, 1_369_334_034_000u128),
+				below_min_trading_limit: false,
+				max_in_ratio_exceeded: false,
+				max_out_ratio_exceeded: false,
+				fee_rate: (2, 1_000),
+			})
+		);
+
+		assert_ok!(XYK::sell(Origin::signed(BOB), ACA, DOT, 456_444_678, 0, false));
+
+		assert_eq!(Currency::free_balance(DOT, &BOB), 1_001_363_483_591_788);
+	});
+}
+
+#[test]
+fn quote_buy_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			ACA,
+			DOT,
+			200_000_000_000,
+			Price::from(3000),
+			None
+		));
+
+		assert_eq!(
+			XYK::quote_buy(DOT, ACA, 1_000_000_000),
+			Some(TradeQuote {
+				amount: 334_000,
+				fee: 666,
+				price_impact: Permill::from_rational(1u128, 333_334u128),
+				below_min_trading_limit: false,
+				max_in_ratio_exceeded: false,
+				max_out_ratio_exceeded: false,
+				fee_rate: (2, 1_000),
+			})
+		);
+
+		assert_ok!(XYK::buy(Origin::signed(BOB), DOT, ACA, 1_000_000_000, u128::MAX, false));
+
+		assert_eq!(Currency::free_balance(ACA, &BOB), 999_999_999_666_000);
+	});
+}
+
+#[test]
+fn quote_should_report_trade_limits() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		let quote = XYK::quote_sell(HDX, DOT, 999).unwrap();
+		assert!(quote.below_min_trading_limit);
+		assert!(!quote.max_in_ratio_exceeded);
+
+		let quote = XYK::quote_sell(HDX, DOT, 500_000_000).unwrap();
+		assert!(!quote.below_min_trading_limit);
+		assert!(quote.max_in_ratio_exceeded);
+
+		let quote = XYK::quote_buy(DOT, HDX, 999).unwrap();
+		assert!(quote.below_min_trading_limit);
+		assert!(!quote.max_out_ratio_exceeded);
+
+		let quote = XYK::quote_buy(DOT, HDX, 1_000_000_000).unwrap();
+		assert!(!quote.below_min_trading_limit);
+		assert!(quote.max_out_ratio_exceeded);
+	});
+}
+
+#[test]
+fn quote_with_non_existing_pool_should_return_none() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(XYK::quote_sell(HDX, DOT, 1_000_000), None);
+		assert_eq!(XYK::quote_buy(HDX, DOT, 1_000_000), None);
+	});
+}
+
+#[test]
+fn create_pool_with_fee_tier_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			Some((1, 100))
+		));
+
+		assert_eq!(XYK::get_fee(&HDX_DOT_POOL_ID), (1, 100));
+
+		assert_ok!(XYK::sell(Origin::signed(BOB), HDX, DOT, 1_000_000_000, 0, false));
+
+		assert_eq!(Currency::free_balance(DOT, &BOB), 1_000_001_978_021_979);
+
+		expect_events(vec![Event::SellExecuted(
+			BOB,
+			HDX,
+			DOT,
+			1_000_000_000,
+			1_978_021_979,
+			DOT,
+			19_980_019,
+			HDX_DOT_POOL_ID,
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn create_pool_with_not_allowed_fee_should_not_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			XYK::create_pool(
+				Origin::signed(ALICE),
+				HDX,
+				DOT,
+				1_000_000_000_000,
+				Price::from(2),
+				Some((3, 1_000))
+			),
+			Error::<Test>::InvalidFee
+		);
+	});
+}
+
+#[test]
+fn set_pool_fee_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_eq!(XYK::get_fee(&HDX_DOT_POOL_ID), (2, 1_000));
+
+		assert_ok!(XYK::set_pool_fee(Origin::root(), HDX, DOT, (3, 1_000)));
+
+		assert_eq!(XYK::get_fee(&HDX_DOT_POOL_ID), (3, 1_000));
+		assert_eq!(XYK::quote_sell(HDX, DOT, 1_000_000_000).unwrap().fee, 5_994_003);
+
+		expect_events(vec![Event::PoolFeeUpdated(HDX_DOT_POOL_ID, (3, 1_000)).into()]);
+	});
+}
+
+#[test]
+fn set_pool_fee_should_not_work_when_not_allowed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_noop!(
+			XYK::set_pool_fee(Origin::signed(ALICE), HDX, DOT, (3, 1_000)),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			XYK::set_pool_fee(Origin::root(), HDX, DOT, (1_000, 1_000)),
+			Error::<Test>::InvalidFee
+		);
+		assert_noop!(
+			XYK::set_pool_fee(Origin::root(), HDX, DOT, (0, 0)),
+			Error::<Test>::InvalidFee
+		);
+		assert_noop!(
+			XYK::set_pool_fee(Origin::root(), HDX, DOT, (3, 0)),
+			Error::<Test>::InvalidFee
+		);
+		assert_noop!(
+			XYK::set_pool_fee(Origin::root(), HDX, ACA, (3, 1_000)),
+			Error::<Test>::TokenPoolNotFound
+		);
+	});
+}
+
+#[test]
+fn spot_price_should_not_include_pool_fee() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			Some((1, 100))
+		));
+
+		assert_eq!(XYKSpotPrice::<Test>::spot_price(HDX, DOT), Some(Price::from(2)));
+	});
+}
+
+#[test]
+fn spot_price_with_fee_should_include_pool_fee() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			Some((1, 100))
+		));
+
+		assert_eq!(
+			XYKSpotPriceWithFee::<Test>::spot_price(HDX, DOT),
+			Some(Price::from((202, 100)))
+		);
+
+		assert_ok!(XYK::set_pool_fee(Origin::root(), HDX, DOT, (0, 1_000)));
+
+		assert_eq!(XYKSpotPriceWithFee::<Test>::spot_price(HDX, DOT), Some(Price::from(2)));
+	});
+}
+
+#[test]
+fn set_protocol_fee_share_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(XYK::protocol_fee_share(), Permill::zero());
+
+		assert_ok!(XYK::set_protocol_fee_share(Origin::root(), Permill::from_percent(50)));
+
+		assert_eq!(XYK::protocol_fee_share(), Permill::from_percent(50));
+
+		expect_events(vec![Event::ProtocolFeeShareUpdated(Permill::from_percent(50)).into()]);
+	});
+}
+
+#[test]
+fn set_protocol_fee_share_should_not_work_when_not_allowed() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			XYK::set_protocol_fee_share(Origin::signed(ALICE), Permill::from_percent(50)),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn sell_should_transfer_protocol_fee() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+		assert_ok!(XYK::set_protocol_fee_share(Origin::root(), Permill::from_percent(50)));
+
+		assert_ok!(XYK::sell(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			1_000_000_000,
+			1_000_000,
+			false
+		));
+
+		assert_eq!(Currency::free_balance(DOT, &ProtocolFeeAccount::get()), 1_998_001);
+		assert_eq!(Currency::free_balance(DOT, &HDX_DOT_POOL_ID), 1_998_003_996_003);
+		assert_eq!(XYK::protocol_fees_collected(DOT), 1_998_001);
+
+		expect_events(vec![
+			Event::TradeFeeSplit(HDX_DOT_POOL_ID, DOT, 1_998_001, 1_998_001).into(),
+			Event::SellExecuted(
+				BOB,
+				HDX,
+				DOT,
+				1_000_000_000,
+				1_994_005_996,
+				DOT,
+				3_996_002,
+				HDX_DOT_POOL_ID,
+			)
+			.into(),
+		]);
+	});
+}
+
+#[test]
+fn buy_should_transfer_protocol_fee() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+		assert_ok!(XYK::set_protocol_fee_share(Origin::root(), Permill::from_percent(50)));
+
+		assert_ok!(XYK::buy(
+			Origin::signed(BOB),
+			DOT,
+			HDX,
+			1_000_000_000,
+			1_000_000_000_000,
+			false
+		));
+
+		assert_eq!(Currency::free_balance(HDX, &ProtocolFeeAccount::get()), 500_250);
+		assert_eq!(Currency::free_balance(HDX, &HDX_DOT_POOL_ID), 1_000_500_750_376);
+		assert_eq!(XYK::protocol_fees_collected(HDX), 500_250);
+
+		expect_events(vec![Event::TradeFeeSplit(HDX_DOT_POOL_ID, HDX, 500_250, 500_250).into()]);
+	});
+}
+
+#[test]
+fn protocol_fee_should_not_be_transferred_when_share_is_not_set() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_ok!(XYK::sell(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			1_000_000_000,
+			1_000_000,
+			false
+		));
+
+		assert_eq!(Currency::free_balance(DOT, &ProtocolFeeAccount::get()), 0);
+		assert_eq!(Currency::free_balance(DOT, &HDX_DOT_POOL_ID), 1_998_005_994_004);
+		assert_eq!(XYK::protocol_fees_collected(DOT), 0);
+	});
+}
+
+#[test]
+fn price_observation_should_be_recorded_once_per_block() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_eq!(
+			XYK::price_observations(&HDX_DOT_POOL_ID).into_inner(),
+			vec![PriceObservation {
+				block: 1,
+				price_a_cumulative: 0,
+				price_b_cumulative: 0,
+			}]
+		);
+
+		System::set_block_number(11);
+
+		assert_ok!(XYK::sell(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			1_000_000_000,
+			1_000_000,
+			false
+		));
+		assert_ok!(XYK::sell(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			1_000_000_000,
+			1_000_000,
+			false
+		));
+
+		assert_eq!(
+			XYK::price_observations(&HDX_DOT_POOL_ID).into_inner(),
+			vec![
+				PriceObservation {
+					block: 1,
+					price_a_cumulative: 0,
+					price_b_cumulative: 0,
+				},
+				PriceObservation {
+					block: 11,
+					price_a_cumulative: 20_000_000_000_000_000_000,
+					price_b_cumulative: 5_000_000_000_000_000_000,
+				}
+			]
+		);
+	});
+}
+
+#[test]
+fn price_observations_should_be_bounded() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		for block in 2..=15 {
+			System::set_block_number(block);
+			assert_ok!(XYK::sell(
+				Origin::signed(BOB),
+				HDX,
+				DOT,
+				1_000_000_000,
+				1_000_000,
+				false
+			));
+		}
+
+		let observations = XYK::price_observations(&HDX_DOT_POOL_ID);
+
+		assert_eq!(observations.len(), MaxPriceObservations::get() as usize);
+		assert_eq!(observations.first().map(|o| o.block), Some(6));
+		assert_eq!(observations.last().map(|o| o.block), Some(15));
+	});
+}
+
+#[test]
+fn get_twap_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		System::set_block_number(11);
+
+		assert_ok!(XYK::sell(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			1_000_000_000,
+			1_000_000,
+			false
+		));
+
+		System::set_block_number(21);
+
+		let price_a = Price::checked_from_rational(1_998_005_994_004u128, 1_001_000_000_000u128).unwrap();
+		let price_b = Price::checked_from_rational(1_001_000_000_000u128, 1_998_005_994_004u128).unwrap();
+
+		assert_eq!(XYK::get_twap(HDX, DOT, 10), Some(price_a));
+		assert_eq!(XYK::get_twap(DOT, HDX, 10), Some(price_b));
+		assert_eq!(XYK::get_twap(HDX, DOT, 5), Some(price_a));
+		assert_eq!(
+			XYK::get_twap(HDX, DOT, 20),
+			Some(Price::from_inner(
+				(20_000_000_000_000_000_000 + price_a.into_inner() * 10) / 20
+			))
+		);
+		assert_eq!(
+			XYK::get_twap(HDX, DOT, 15),
+			Some(Price::from_inner(
+				(10_000_000_000_000_000_000 + price_a.into_inner() * 10) / 15
+			))
+		);
+		assert_eq!(
+			XYK::get_twap(DOT, HDX, 15),
+			Some(Price::from_inner(
+				(2_500_000_000_000_000_000 + price_b.into_inner() * 10) / 15
+			))
+		);
+	});
+}
+
+#[test]
+fn get_twap_should_extrapolate_price_over_idle_blocks() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		System::set_block_number(11);
+
+		assert_ok!(XYK::sell(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			1_000_000_000,
+			1_000_000,
+			false
+		));
+
+		// no trades for more blocks than the number of observations kept
+		System::set_block_number(100);
+
+		assert_eq!(XYK::price_observations(HDX_DOT_POOL_ID).len(), 2);
+
+		let price_a = Price::checked_from_rational(1_998_005_994_004u128, 1_001_000_000_000u128).unwrap();
+
+		assert_eq!(XYK::get_twap(HDX, DOT, 1), Some(price_a));
+		assert_eq!(XYK::get_twap(HDX, DOT, 89), Some(price_a));
+		assert_eq!(
+			XYK::get_twap(HDX, DOT, 99),
+			Some(Price::from_inner(
+				(20_000_000_000_000_000_000 + price_a.into_inner() * 89) / 99
+			))
+		);
+
+		// querying does not record any observation
+		assert_eq!(XYK::price_observations(HDX_DOT_POOL_ID).len(), 2);
+	});
+}
+
+#[test]
+fn get_twap_should_return_none_when_history_is_insufficient() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(XYK::get_twap(HDX, DOT, 10), None);
+
+		System::set_block_number(5);
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		System::set_block_number(10);
+
+		assert_eq!(XYK::get_twap(HDX, DOT, 0), None);
+		assert_eq!(XYK::get_twap(HDX, DOT, 6), None);
+		assert_eq!(XYK::get_twap(HDX, DOT, 5), Some(Price::from(2)));
+	});
+}
+
+#[test]
+fn twap_spot_price_should_be_average_price() {
+	struct TwapPeriod;
+	impl Get<u64> for TwapPeriod {
+		fn get() -> u64 {
+			10
+		}
+	}
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		System::set_block_number(11);
+
+		assert_ok!(XYK::sell(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			1_000_000_000,
+			1_000_000,
+			false
+		));
+
+		// price moved by the trade in current block is not included
+		assert_ne!(XYKSpotPrice::<Test>::spot_price(HDX, DOT), Some(Price::from(2)));
+		assert_eq!(
+			XYKTwapSpotPrice::<Test, TwapPeriod>::spot_price(HDX, DOT),
+			Some(Price::from(2))
+		);
+		assert_eq!(
+			<XYK as TwapProvider<AssetId, u64>>::twap(HDX, DOT, 10),
+			Some(Price::from(2))
+		);
+	});
+}
+
+#[test]
+fn add_liquidity_single_asset_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		let share_token = XYK::share_token(HDX_DOT_POOL_ID);
+
+		assert_ok!(XYK::add_liquidity_single_asset(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			10_000_000_000,
+			5_000_000_000
+		));
+
+		assert_eq!(Currency::free_balance(HDX, &BOB), 999_990_000_000_003);
+		assert!(Currency::free_balance(DOT, &BOB) >= 1_000_000_000_000_000);
+		assert_eq!(Currency::free_balance(share_token, &BOB), 5_007_420_418);
+		assert_eq!(Currency::free_balance(HDX, &HDX_DOT_POOL_ID), 1_009_999_999_997);
+		assert_eq!(XYK::total_liquidity(&HDX_DOT_POOL_ID), 1_005_007_420_418);
+
+		expect_events(vec![
+			Event::SellExecuted(
+				BOB,
+				HDX,
+				DOT,
+				4_992_579_579,
+				9_915_683_998,
+				DOT,
+				19_871_110,
+				HDX_DOT_POOL_ID,
+			)
+			.into(),
+			Event::SingleAssetLiquidityAdded(BOB, HDX, DOT, 10_000_000_000, 5_007_420_418).into(),
+		]);
+	});
+}
+
+#[test]
+fn add_liquidity_single_asset_should_not_work_when_limit_is_not_reached() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			XYK::add_liquidity_single_asset(Origin::signed(BOB), HDX, DOT, 10_000_000_000, 0),
+			Error::<Test>::TokenPoolNotFound
+		);
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_noop!(
+			XYK::add_liquidity_single_asset(Origin::signed(BOB), HDX, DOT, 10_000_000_000, 5_007_420_419),
+			Error::<Test>::AssetAmountNotReachedLimit
+		);
+	});
+}
+
+#[test]
+fn remove_liquidity_to_single_asset_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		let share_token = XYK::share_token(HDX_DOT_POOL_ID);
+
+		assert_ok!(XYK::remove_liquidity_to_single_asset(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			100_000_000_000,
+			189_000_000_000
+		));
+
+		assert_eq!(Currency::free_balance(HDX, &ALICE), 999_189_820_000_000);
+		assert_eq!(Currency::free_balance(DOT, &ALICE), 998_000_000_000_000);
+		assert_eq!(Currency::free_balance(share_token, &ALICE), 900_000_000_000);
+		assert_eq!(Currency::free_balance(HDX, &HDX_DOT_POOL_ID), 810_180_000_000);
+		assert_eq!(Currency::free_balance(DOT, &HDX_DOT_POOL_ID), 2_000_000_000_000);
+
+		expect_events(vec![
+			Event::LiquidityRemoved(ALICE, HDX, DOT, 100_000_000_000).into(),
+			Event::SellExecuted(
+				ALICE,
+				DOT,
+				HDX,
+				200_000_000_000,
+				89_820_000_000,
+				HDX,
+				180_000_000,
+				HDX_DOT_POOL_ID,
+			)
+			.into(),
+			Event::SingleAssetLiquidityRemoved(ALICE, HDX, DOT, 100_000_000_000, 189_820_000_000).into(),
+		]);
+	});
+}
+
+#[test]
+fn remove_liquidity_to_single_asset_should_not_work_when_limit_is_not_reached() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_noop!(
+			XYK::remove_liquidity_to_single_asset(Origin::signed(ALICE), HDX, DOT, 100_000_000_000, 189_820_000_001),
+			Error::<Test>::AssetAmountNotReachedLimit
+		);
+	});
+}
+
+#[test]
+fn remove_liquidity_to_single_asset_should_not_be_limited_by_max_in_ratio() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		// removed DOT exceeds max in ratio of the pool left after the removal
+		assert_ok!(XYK::remove_liquidity_to_single_asset(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			500_000_000_000,
+			749_500_000_000
+		));
+
+		assert_eq!(Currency::free_balance(HDX, &ALICE), 999_749_500_000_000);
+		assert_eq!(Currency::free_balance(DOT, &ALICE), 998_000_000_000_000);
+		assert_eq!(Currency::free_balance(HDX, &HDX_DOT_POOL_ID), 250_500_000_000);
+		assert_eq!(Currency::free_balance(DOT, &HDX_DOT_POOL_ID), 2_000_000_000_000);
+
+		expect_events(vec![Event::SingleAssetLiquidityRemoved(
+			ALICE,
+			HDX,
+			DOT,
+			500_000_000_000,
+			749_500_000_000,
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn remove_liquidity_to_single_asset_should_not_work_when_all_liquidity_is_removed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_noop!(
+			XYK::remove_liquidity_to_single_asset(Origin::signed(ALICE), HDX, DOT, 1_000_000_000_000, 0),
+			Error::<Test>::CannotRemoveAllLiquidityToSingleAsset
+		);
+
+		assert_ok!(XYK::remove_liquidity(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000
+		));
+	});
+}
+
+#[test]
+fn set_pool_state_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_eq!(XYK::pool_state(&HDX_DOT_POOL_ID), PoolState::Active);
+
+		assert_ok!(XYK::set_pool_state(Origin::root(), HDX, DOT, PoolState::TradingPaused));
+
+		assert_eq!(XYK::pool_state(&HDX_DOT_POOL_ID), PoolState::TradingPaused);
+
+		expect_events(vec![
+			Event::PoolStateUpdated(HDX_DOT_POOL_ID, PoolState::TradingPaused).into()
+		]);
+	});
+}
+
+#[test]
+fn quote_should_return_none_when_trading_is_not_allowed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		for state in [PoolState::TradingPaused, PoolState::WithdrawOnly] {
+			assert_ok!(XYK::set_pool_state(Origin::root(), HDX, DOT, state));
+
+			assert_eq!(XYK::quote_sell(HDX, DOT, 1_000_000_000), None);
+			assert_eq!(XYK::quote_buy(DOT, HDX, 1_000_000_000), None);
+		}
+
+		assert_ok!(XYK::set_pool_state(Origin::root(), HDX, DOT, PoolState::Active));
+
+		assert!(XYK::quote_sell(HDX, DOT, 1_000_000_000).is_some());
+		assert!(XYK::quote_buy(DOT, HDX, 1_000_000_000).is_some());
+	});
+}
+
+#[test]
+fn set_pool_state_should_not_work_when_not_allowed() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			XYK::set_pool_state(Origin::root(), HDX, DOT, PoolState::TradingPaused),
+			Error::<Test>::TokenPoolNotFound
+		);
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_noop!(
+			XYK::set_pool_state(Origin::signed(ALICE), HDX, DOT, PoolState::TradingPaused),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn trading_should_not_work_when_pool_is_paused() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_ok!(XYK::set_pool_state(Origin::root(), HDX, DOT, PoolState::TradingPaused));
+
+		assert_noop!(
+			XYK::sell(Origin::signed(BOB), HDX, DOT, 1_000_000_000, 0, false),
+			Error::<Test>::TradingNotAllowed
+		);
+		assert_noop!(
+			XYK::buy(Origin::signed(BOB), DOT, HDX, 1_000_000_000, 1_000_000_000_000, false),
+			Error::<Test>::TradingNotAllowed
+		);
+		assert_noop!(
+			XYK::sell_route(Origin::signed(BOB), vec![HDX, DOT], 1_000_000_000, 0),
+			Error::<Test>::TradingNotAllowed
+		);
+		assert_noop!(
+			XYK::add_liquidity_single_asset(Origin::signed(BOB), HDX, DOT, 1_000_000_000, 0),
+			Error::<Test>::TradingNotAllowed
+		);
+
+		assert_ok!(XYK::add_liquidity(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			1_000_000_000,
+			2_000_000_000
+		));
+		assert_ok!(XYK::remove_liquidity(Origin::signed(BOB), HDX, DOT, 1_000_000_000));
+
+		assert_ok!(XYK::set_pool_state(Origin::root(), HDX, DOT, PoolState::Active));
+
+		assert_ok!(XYK::sell(Origin::signed(BOB), HDX, DOT, 1_000_000_000, 0, false));
+	});
+}
+
+#[test]
+fn withdraw_only_pool_should_allow_only_removing_liquidity() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_ok!(XYK::set_pool_state(Origin::root(), HDX, DOT, PoolState::WithdrawOnly));
+
+		assert_noop!(
+			XYK::sell(Origin::signed(BOB), HDX, DOT, 1_000_000_000, 0, false),
+			Error::<Test>::TradingNotAllowed
+		);
+		assert_noop!(
+			XYK::add_liquidity(Origin::signed(BOB), HDX, DOT, 1_000_000_000, 2_000_000_000),
+			Error::<Test>::AddLiquidityNotAllowed
+		);
+
+		assert_ok!(XYK::remove_liquidity(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000
+		));
+
+		assert_eq!(XYK::pool_state(&HDX_DOT_POOL_ID), PoolState::Active);
+	});
+}
+
+#[test]
+fn amm_trade_validation_should_respect_pool_state() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_ok!(XYK::set_pool_state(Origin::root(), HDX, DOT, PoolState::TradingPaused));
+
+		let assets = AssetPair {
+			asset_in: HDX,
+			asset_out: DOT,
+		};
+
+		assert_noop!(
+			XYK::validate_sell(&BOB, assets, 1_000_000_000, 0, false).map(|_| ()),
+			Error::<Test>::TradingNotAllowed
+		);
+		assert_noop!(
+			XYK::validate_buy(&BOB, assets, 1_000_000_000, 1_000_000_000_000, false).map(|_| ()),
+			Error::<Test>::TradingNotAllowed
+		);
+	});
+}
+
+#[test]
+fn sell_at_price_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_noop!(
+			XYK::sell_at_price(Origin::signed(BOB), HDX, DOT, 1_000_000_000, Price::from(2), 1, false),
+			Error::<Test>::AssetAmountNotReachedLimit
+		);
+
+		assert_ok!(XYK::sell_at_price(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			1_000_000_000,
+			Price::from_float(1.99),
+			1,
+			false
+		));
+
+		assert_eq!(Currency::free_balance(DOT, &BOB), 1_000_001_994_005_996);
+	});
+}
+
+#[test]
+fn buy_at_price_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_noop!(
+			XYK::buy_at_price(
+				Origin::signed(BOB),
+				DOT,
+				HDX,
+				1_000_000_000,
+				Price::from_float(0.5),
+				1,
+				false
+			),
+			Error::<Test>::AssetAmountExceededLimit
+		);
+
+		assert_ok!(XYK::buy_at_price(
+			Origin::signed(BOB),
+			DOT,
+			HDX,
+			1_000_000_000,
+			Price::from_float(0.5013),
+			1,
+			false
+		));
+
+		assert_eq!(Currency::free_balance(HDX, &BOB), 999_999_498_749_374);
+	});
+}
+
+#[test]
+fn trade_at_price_should_not_work_when_expired() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		System::set_block_number(10);
+
+		assert_noop!(
+			XYK::sell_at_price(Origin::signed(BOB), HDX, DOT, 1_000_000_000, Price::from(1), 9, false),
+			Error::<Test>::TradeExpired
+		);
+		assert_noop!(
+			XYK::buy_at_price(Origin::signed(BOB), DOT, HDX, 1_000_000_000, Price::from(1), 9, false),
+			Error::<Test>::TradeExpired
+		);
+
+		assert_ok!(XYK::sell_at_price(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			1_000_000_000,
+			Price::from(1),
+			10,
+			false
+		));
+	});
+}
+
+#[test]
+fn validate_trade_expiry_should_reject_expired_trades() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(10);
+
+		let info = frame_support::weights::DispatchInfo::default();
+
+		let trade = |valid_until| {
+			crate::mock::Call::XYK(Call::sell_at_price {
+				asset_in: HDX,
+				asset_out: DOT,
+				amount: 1_000_000_000,
+				min_price: Price::from(1),
+				valid_until,
+				discount: false,
+			})
+		};
+
+		assert_eq!(
+			ValidateTradeExpiry::<Test>::new().validate(&BOB, &trade(9), &info, 0),
+			Err(InvalidTransaction::Custom(TRADE_EXPIRED).into())
+		);
+		assert_eq!(
+			ValidateTradeExpiry::<Test>::new()
+				.validate(&BOB, &trade(15), &info, 0)
+				.map(|v| v.longevity),
+			Ok(6)
+		);
+		assert_eq!(
+			ValidateTradeExpiry::<Test>::new().validate(
+				&BOB,
+				&crate::mock::Call::XYK(Call::sell {
+					asset_in: HDX,
+					asset_out: DOT,
+					amount: 1_000_000_000,
+					max_limit: 0,
+					discount: false,
+				}),
+				&info,
+				0
+			),
+			Ok(ValidTransaction::default())
+		);
+	});
+}
+
+#[test]
+fn set_circuit_breaker_limits_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			XYK::set_circuit_breaker_limits(Origin::signed(ALICE), Some(Permill::from_percent(1)), None),
+			sp_runtime::DispatchError::BadOrigin
+		);
+
+		assert_ok!(XYK::set_circuit_breaker_limits(
+			Origin::root(),
+			Some(Permill::from_percent(1)),
+			Some(Permill::from_percent(10))
+		));
+
+		assert_eq!(XYK::max_price_change_per_block(), Some(Permill::from_percent(1)));
+		assert_eq!(XYK::max_liquidity_removal_per_block(), Some(Permill::from_percent(10)));
+
+		expect_events(vec![Event::CircuitBreakerLimitsUpdated(
+			Some(Permill::from_percent(1)),
+			Some(Permill::from_percent(10)),
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn trade_should_not_work_when_price_change_exceeds_limit_in_block() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_ok!(XYK::set_circuit_breaker_limits(
+			Origin::root(),
+			Some(Permill::from_percent(1)),
+			None
+		));
+
+		assert_noop!(
+			XYK::sell(Origin::signed(BOB), HDX, DOT, 20_000_000_000, 0, false),
+			Error::<Test>::MaxPriceChangeExceeded
+		);
+
+		assert_ok!(XYK::sell(Origin::signed(BOB), HDX, DOT, 3_000_000_000, 0, false));
+
+		assert_noop!(
+			XYK::sell(Origin::signed(BOB), HDX, DOT, 3_000_000_000, 0, false),
+			Error::<Test>::MaxPriceChangeExceeded
+		);
+		assert_noop!(
+			XYK::buy(Origin::signed(BOB), DOT, HDX, 6_000_000_000, 10_000_000_000, false),
+			Error::<Test>::MaxPriceChangeExceeded
+		);
+
+		System::set_block_number(2);
+
+		assert_ok!(XYK::sell(Origin::signed(BOB), HDX, DOT, 3_000_000_000, 0, false));
+	});
+}
+
+#[test]
+fn quote_should_return_none_when_price_change_exceeds_limit_in_block() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_ok!(XYK::set_circuit_breaker_limits(
+			Origin::root(),
+			Some(Permill::from_percent(1)),
+			None
+		));
+
+		assert_eq!(XYK::quote_sell(HDX, DOT, 20_000_000_000), None);
+		assert!(XYK::quote_sell(HDX, DOT, 3_000_000_000).is_some());
+
+		assert_ok!(XYK::sell(Origin::signed(BOB), HDX, DOT, 3_000_000_000, 0, false));
+
+		assert_eq!(XYK::quote_sell(HDX, DOT, 3_000_000_000), None);
+		assert_eq!(XYK::quote_buy(DOT, HDX, 6_000_000_000), None);
+
+		System::set_block_number(2);
+
+		assert!(XYK::quote_sell(HDX, DOT, 3_000_000_000).is_some());
+	});
+}
+
+#[test]
+fn remove_liquidity_should_not_work_when_removal_exceeds_limit_in_block() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_ok!(XYK::set_circuit_breaker_limits(
+			Origin::root(),
+			None,
+			Some(Permill::from_percent(10))
+		));
+
+		assert_noop!(
+			XYK::remove_liquidity(Origin::signed(ALICE), HDX, DOT, 100_000_000_001),
+			Error::<Test>::MaxLiquidityRemovalExceeded
+		);
+
+		assert_ok!(XYK::remove_liquidity(Origin::signed(ALICE), HDX, DOT, 100_000_000_000));
+
+		assert_noop!(
+			XYK::remove_liquidity(Origin::signed(ALICE), HDX, DOT, 1_000),
+			Error::<Test>::MaxLiquidityRemovalExceeded
+		);
+
+		System::set_block_number(2);
+
+		assert_ok!(XYK::remove_liquidity(Origin::signed(ALICE), HDX, DOT, 1_000));
+	});
+}
+
+#[test]
+fn remove_liquidity_limit_should_not_include_liquidity_added_in_block() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_ok!(XYK::set_circuit_breaker_limits(
+			Origin::root(),
+			None,
+			Some(Permill::from_percent(10))
+		));
+
+		System::set_block_number(2);
+
+		assert_ok!(XYK::add_liquidity(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			2_000_000_000_000
+		));
+
+		assert_eq!(
+			XYK::block_start_states(HDX_DOT_POOL_ID).unwrap().liquidity,
+			1_000_000_000_000
+		);
+
+		assert_noop!(
+			XYK::remove_liquidity(Origin::signed(BOB), HDX, DOT, 100_000_000_001),
+			Error::<Test>::MaxLiquidityRemovalExceeded
+		);
+
+		assert_ok!(XYK::remove_liquidity(Origin::signed(BOB), HDX, DOT, 100_000_000_000));
+	});
+}
+
+#[test]
+fn pools_should_be_listed_in_pages() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			ACA,
+			1_000_000_000_000,
+			Price::from(3),
+			None
+		));
+
+		let hdx_dot_pool = XYK::get_pair_id(AssetPair {
+			asset_in: HDX,
+			asset_out: DOT,
+		});
+
+		let pools = XYK::pools(None, 10);
+		assert_eq!(pools.len(), 2);
+		assert!(pools.contains(&PoolInfo {
+			pool_account: hdx_dot_pool,
+			asset_a: HDX,
+			asset_b: DOT,
+			asset_a_reserve: 1_000_000_000_000,
+			asset_b_reserve: 2_000_000_000_000,
+			share_token: HDX_DOT_POOL_ID,
+			total_shares: 1_000_000_000_000,
+		}));
+
+		let first_page = XYK::pools(None, 1);
+		assert_eq!(first_page.len(), 1);
+
+		let second_page = XYK::pools(Some(first_page[0].pool_account), 1);
+		assert_eq!(second_page.len(), 1);
+		assert_ne!(first_page[0], second_page[0]);
+
+		assert!(XYK::pools(Some(second_page[0].pool_account), 1).is_empty());
+	});
+}
+
+#[test]
+fn liquidity_positions_should_report_underlying_amounts() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			ACA,
+			1_000_000_000_000,
+			Price::from(3),
+			None
+		));
+
+		assert_ok!(XYK::add_liquidity(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			100_000_000_000,
+			1_000_000_000_000
+		));
+
+		let pool_account = XYK::get_pair_id(AssetPair {
+			asset_in: HDX,
+			asset_out: DOT,
+		});
+
+		assert_eq!(
+			XYK::liquidity_positions(&BOB),
+			vec![LiquidityPosition {
+				pool_account,
+				asset_a: HDX,
+				asset_b: DOT,
+				shares: 100_000_000_000,
+				asset_a_amount: 100_000_000_000,
+				asset_b_amount: 200_000_000_000,
+			}]
+		);
+
+		assert_eq!(XYK::liquidity_positions(&ALICE).len(), 2);
+		assert!(XYK::liquidity_positions(&ProtocolFeeAccount::get()).is_empty());
+	});
+}
+
+fn new_test_ext_with_locked_liquidity() -> sp_io::TestExternalities {
+	let mut ext = ExtBuilder::default().with_locked_liquidity(1_000).build();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+#[test]
+fn create_pool_should_lock_liquidity_in_pool_account() {
+	new_test_ext_with_locked_liquidity().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: HDX,
+			asset_out: DOT,
+		});
+		let share_token = XYK::share_token(pair_account);
+
+		assert_eq!(XYK::total_liquidity(&pair_account), 1_000_000_000_000);
+		assert_eq!(Currency::free_balance(share_token, &ALICE), 999_999_999_000);
+		assert_eq!(Currency::free_balance(share_token, &pair_account), 1_000);
+
+		expect_events(vec![Event::PoolCreated(
+			ALICE,
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			share_token,
+			pair_account,
+		)
+		.into()]);
+	});
+}
+
+#[test]
+fn create_pool_should_not_work_when_shares_do_not_exceed_locked_liquidity() {
+	new_test_ext_with_locked_liquidity().execute_with(|| {
+		assert_noop!(
+			XYK::create_pool(Origin::signed(ALICE), HDX, DOT, 1_000, Price::from(2), None),
+			Error::<Test>::InsufficientLiquidity
+		);
+	});
+}
+
+#[test]
+fn remove_liquidity_should_destroy_pool_when_only_locked_liquidity_is_left() {
+	new_test_ext_with_locked_liquidity().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: HDX,
+			asset_out: DOT,
+		});
+		let share_token = XYK::share_token(pair_account);
+
+		assert_ok!(XYK::remove_liquidity(Origin::signed(ALICE), HDX, DOT, 999_999_999_000));
+
+		assert!(!XYK::exists(AssetPair {
+			asset_in: HDX,
+			asset_out: DOT,
+		}));
+		assert_eq!(XYK::total_liquidity(&pair_account), 0);
+		assert_eq!(Currency::free_balance(share_token, &pair_account), 0);
+		assert_eq!(Currency::total_issuance(share_token), 0);
+		assert_eq!(Currency::free_balance(HDX, &pair_account), 0);
+		assert_eq!(Currency::free_balance(DOT, &pair_account), 0);
+		assert_eq!(Currency::free_balance(HDX, &ProtocolFeeAccount::get()), 1_000);
+		assert_eq!(Currency::free_balance(DOT, &ProtocolFeeAccount::get()), 2_000);
+
+		expect_events(vec![
+			Event::PoolDestroyed(ALICE, HDX, DOT, share_token, pair_account).into()
+		]);
+
+		// new share token is registered when the pool is created again
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+		let new_share_token = XYK::share_token(pair_account);
+		assert_ne!(new_share_token, share_token);
+		assert_eq!(Currency::free_balance(new_share_token, &pair_account), 1_000);
+		assert_eq!(Currency::total_issuance(share_token), 0);
+	});
+}
+
+#[test]
+fn remove_liquidity_should_burn_assets_left_below_existential_deposit_when_pool_is_destroyed() {
+	let mut ext = ExtBuilder::default()
+		.with_locked_liquidity(1_000)
+		.with_existential_deposit(DOT, 10_000)
+		.build();
+	ext.execute_with(|| System::set_block_number(1));
+	ext.execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: HDX,
+			asset_out: DOT,
+		});
+		let share_token = XYK::share_token(pair_account);
+		let dot_issuance = Currency::total_issuance(DOT);
+
+		assert_eq!(Currency::free_balance(DOT, &ProtocolFeeAccount::get()), 0);
+
+		assert_ok!(XYK::remove_liquidity(Origin::signed(ALICE), HDX, DOT, 999_999_999_000));
+
+		assert!(!XYK::exists(AssetPair {
+			asset_in: HDX,
+			asset_out: DOT,
+		}));
+		assert_eq!(Currency::total_issuance(share_token), 0);
+		assert_eq!(Currency::free_balance(HDX, &pair_account), 0);
+		assert_eq!(Currency::free_balance(DOT, &pair_account), 0);
+		assert_eq!(Currency::free_balance(HDX, &ProtocolFeeAccount::get()), 1_000);
+		// DOT left in the pool is below existential deposit and is burned
+		assert_eq!(Currency::free_balance(DOT, &ProtocolFeeAccount::get()), 0);
+		assert_eq!(Currency::total_issuance(DOT), dot_issuance - 2_000);
+	});
+}
+
+#[test]
+fn share_inflation_attack_should_not_be_profitable() {
+	new_test_ext_with_locked_liquidity().execute_with(|| {
+		let attacker = ALICE;
+		let victim = BOB;
+
+		let attacker_hdx = Currency::free_balance(HDX, &attacker);
+		let attacker_dot = Currency::free_balance(DOT, &attacker);
+		let victim_hdx = Currency::free_balance(HDX, &victim);
+		let victim_dot = Currency::free_balance(DOT, &victim);
+
+		// attacker creates pool with the smallest possible liquidity
+		assert_ok!(XYK::create_pool(
+			Origin::signed(attacker),
+			HDX,
+			DOT,
+			2_000,
+			Price::from(1),
+			None
+		));
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: HDX,
+			asset_out: DOT,
+		});
+		let share_token = XYK::share_token(pair_account);
+
+		assert_eq!(Currency::free_balance(share_token, &attacker), 1_000);
+
+		// attacker can not drain the pool below locked liquidity
+		assert_noop!(
+			XYK::remove_liquidity(Origin::signed(attacker), HDX, DOT, 1_001),
+			Error::<Test>::InsufficientAssetBalance
+		);
+
+		// attacker inflates value of a share by donating to the pool
+		assert_ok!(Currency::transfer(
+			Origin::signed(attacker),
+			pair_account,
+			HDX,
+			1_000_000_000_000
+		));
+		assert_ok!(Currency::transfer(
+			Origin::signed(attacker),
+			pair_account,
+			DOT,
+			1_000_000_000_000
+		));
+
+		// victim provides liquidity and still gets shares
+		assert_ok!(XYK::add_liquidity(
+			Origin::signed(victim),
+			HDX,
+			DOT,
+			1_000_000_000,
+			2_000_000_000
+		));
+		assert!(!Currency::free_balance(share_token, &victim).is_zero());
+
+		// both redeem all their shares which leaves only locked liquidity in the pool
+		assert_ok!(XYK::remove_liquidity(Origin::signed(attacker), HDX, DOT, 1_000));
+		assert_ok!(XYK::remove_liquidity(
+			Origin::signed(victim),
+			HDX,
+			DOT,
+			Currency::free_balance(share_token, &victim)
+		));
+
+		assert!(!XYK::exists(AssetPair {
+			asset_in: HDX,
+			asset_out: DOT,
+		}));
+
+		// attacker lost the donation, victim did not lose anything
+		assert!(attacker_hdx - Currency::free_balance(HDX, &attacker) > 900_000_000_000);
+		assert!(attacker_dot - Currency::free_balance(DOT, &attacker) > 900_000_000_000);
+		assert!(Currency::free_balance(HDX, &victim) >= victim_hdx);
+		assert!(Currency::free_balance(DOT, &victim) >= victim_dot);
+	});
+}
+
+fn dynamic_fee_params() -> DynamicFeeParams {
+	DynamicFeeParams {
+		min_fee: Permill::from_parts(1_000),
+		max_fee: Permill::from_percent(1),
+		amplification: 2,
+		decay: Permill::from_percent(10),
+	}
+}
+
+#[test]
+fn set_dynamic_fee_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: HDX,
+			asset_out: DOT,
+		});
+
+		assert_ok!(XYK::set_dynamic_fee(
+			Origin::root(),
+			HDX,
+			DOT,
+			Some(dynamic_fee_params())
+		));
+
+		assert_eq!(XYK::dynamic_fee(&pair_account), Some(dynamic_fee_params()));
+		assert_eq!(
+			XYK::pool_volatility(&pair_account),
+			Some(Volatility {
+				block: 1,
+				price: Price::from(2),
+				volatility: Permill::zero(),
+			})
+		);
+		assert_eq!(XYK::get_fee(&pair_account), (1_000, 1_000_000));
+
+		expect_events(vec![
+			Event::DynamicFeeUpdated(pair_account, Some(dynamic_fee_params())).into()
+		]);
+
+		assert_ok!(XYK::set_dynamic_fee(Origin::root(), HDX, DOT, None));
+
+		assert_eq!(XYK::dynamic_fee(&pair_account), None);
+		assert_eq!(XYK::pool_volatility(&pair_account), None);
+		assert_eq!(XYK::get_fee(&pair_account), (2, 1_000));
+	});
+}
+
+#[test]
+fn set_dynamic_fee_should_not_work_with_invalid_params() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			XYK::set_dynamic_fee(Origin::root(), HDX, DOT, Some(dynamic_fee_params())),
+			Error::<Test>::TokenPoolNotFound
+		);
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		assert_noop!(
+			XYK::set_dynamic_fee(Origin::signed(ALICE), HDX, DOT, Some(dynamic_fee_params())),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			XYK::set_dynamic_fee(
+				Origin::root(),
+				HDX,
+				DOT,
+				Some(DynamicFeeParams {
+					min_fee: Permill::from_percent(2),
+					..dynamic_fee_params()
+				})
+			),
+			Error::<Test>::InvalidFee
+		);
+		assert_noop!(
+			XYK::set_dynamic_fee(
+				Origin::root(),
+				HDX,
+				DOT,
+				Some(DynamicFeeParams {
+					max_fee: Permill::one(),
+					..dynamic_fee_params()
+				})
+			),
+			Error::<Test>::InvalidFee
+		);
+	});
+}
+
+#[test]
+fn dynamic_fee_should_follow_price_volatility() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			1_000_000_000_000,
+			Price::from(2),
+			None
+		));
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: HDX,
+			asset_out: DOT,
+		});
+
+		assert_ok!(XYK::set_dynamic_fee(
+			Origin::root(),
+			HDX,
+			DOT,
+			Some(dynamic_fee_params())
+		));
+
+		assert_eq!(
+			XYK::quote_sell(HDX, DOT, 1_000_000_000).map(|quote| quote.fee_rate),
+			Some((1_000, 1_000_000))
+		);
+
+		assert_ok!(XYK::sell(Origin::signed(BOB), HDX, DOT, 10_000_000_000, 0, false));
+
+		expect_events(vec![Event::DynamicFeeApplied(pair_account, (1_000, 1_000_000)).into()]);
+
+		// price moved by ~2% which raises the fee up to the maximum
+		assert_eq!(
+			XYK::quote_sell(HDX, DOT, 1_000_000_000).map(|quote| quote.fee_rate),
+			Some((10_000, 1_000_000))
+		);
+		assert_eq!(
+			XYK::quote_buy(DOT, HDX, 1_000_000_000).map(|quote| quote.fee_rate),
+			Some((10_000, 1_000_000))
+		);
+
+		// volatility decays over time
+		System::set_block_number(200);
+
+		assert_eq!(XYK::get_fee(&pair_account), (1_000, 1_000_000));
+
+		assert_ok!(XYK::buy(
+			Origin::signed(BOB),
+			DOT,
+			HDX,
+			1_000_000_000,
+			1_000_000_000_000,
+			false
+		));
+
+		expect_events(vec![Event::DynamicFeeApplied(pair_account, (1_000, 1_000_000)).into()]);
+		assert_eq!(XYK::pool_volatility(&pair_account).map(|v| v.block), Some(200));
+	});
+}
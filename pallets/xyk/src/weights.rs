@@ -52,6 +52,15 @@ pub trait WeightInfo {
 	fn remove_liquidity() -> Weight;
 	fn sell() -> Weight;
 	fn buy() -> Weight;
+	fn sell_route(n: u32) -> Weight;
+	fn buy_route(n: u32) -> Weight;
+	fn set_pool_fee() -> Weight;
+	fn set_protocol_fee_share() -> Weight;
+	fn add_liquidity_single_asset() -> Weight;
+	fn remove_liquidity_to_single_asset() -> Weight;
+	fn set_pool_state() -> Weight;
+	fn set_circuit_breaker_limits() -> Weight;
+	fn set_dynamic_fee() -> Weight;
 }
 
 /// Weights for amm using the hydraDX node and recommended hardware.
@@ -83,6 +92,49 @@ impl<T: frame_system::Config> WeightInfo for HydraWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(5 as Weight))
 			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
+	fn sell_route(n: u32) -> Weight {
+		(13_410_000 as Weight) // Standard Error: 41_000
+			.saturating_add((119_832_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(n as Weight)))
+	}
+	fn buy_route(n: u32) -> Weight {
+		(15_982_000 as Weight) // Standard Error: 45_000
+			.saturating_add((126_310_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads((7 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(n as Weight)))
+	}
+	fn set_pool_fee() -> Weight {
+		(22_417_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_protocol_fee_share() -> Weight {
+		(18_935_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn add_liquidity_single_asset() -> Weight {
+		(287_412_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	fn remove_liquidity_to_single_asset() -> Weight {
+		(286_557_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	fn set_pool_state() -> Weight {
+		(22_906_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_circuit_breaker_limits() -> Weight {
+		(21_432_000 as Weight).saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn set_dynamic_fee() -> Weight {
+		(23_418_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -112,4 +164,47 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
 	}
+	fn sell_route(n: u32) -> Weight {
+		(13_410_000 as Weight) // Standard Error: 41_000
+			.saturating_add((119_832_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(n as Weight)))
+	}
+	fn buy_route(n: u32) -> Weight {
+		(15_982_000 as Weight) // Standard Error: 45_000
+			.saturating_add((126_310_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads((7 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(n as Weight)))
+	}
+	fn set_pool_fee() -> Weight {
+		(22_417_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_protocol_fee_share() -> Weight {
+		(18_935_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn add_liquidity_single_asset() -> Weight {
+		(287_412_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	fn remove_liquidity_to_single_asset() -> Weight {
+		(286_557_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	fn set_pool_state() -> Weight {
+		(22_906_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_circuit_breaker_limits() -> Weight {
+		(21_432_000 as Weight).saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn set_dynamic_fee() -> Weight {
+		(23_418_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
 }
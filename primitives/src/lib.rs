@@ -107,6 +107,7 @@ pub mod nft {
 		Redeemable = 2_isize,
 		Auction = 3_isize,
 		HydraHeads = 4_isize,
+		ConcentratedLiquidity = 5_isize,
 	}
 
 	impl Default for ClassType {
@@ -138,7 +139,10 @@ pub mod nft {
 		}
 
 		fn can_transfer(class_type: &ClassType) -> bool {
-			matches!(*class_type, ClassType::Marketplace | ClassType::LiquidityMining)
+			matches!(
+				*class_type,
+				ClassType::Marketplace | ClassType::LiquidityMining | ClassType::ConcentratedLiquidity
+			)
 		}
 
 		fn can_burn(class_type: &ClassType) -> bool {
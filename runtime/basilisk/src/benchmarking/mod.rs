@@ -43,6 +43,7 @@ pub fn create_pool(who: AccountId, asset_a: AssetId, asset_b: AssetId, amount: B
 		asset_a,
 		asset_b,
 		amount,
-		price
+		price,
+		None
 	));
 }
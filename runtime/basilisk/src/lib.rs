@@ -70,6 +70,8 @@ mod xcm;
 
 mod benchmarking;
 
+use pallet_exchange_rpc_runtime_api as exchange_rpc;
+use pallet_stableswap_rpc_runtime_api as stableswap_rpc;
 use pallet_xyk_rpc_runtime_api as xyk_rpc;
 
 use orml_currencies::BasicCurrencyAdapter;
@@ -105,10 +107,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("basilisk"),
 	impl_name: create_runtime_str!("basilisk"),
 	authoring_version: 1,
-	spec_version: 47,
+	spec_version: 48,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 0,
 };
 
@@ -127,6 +129,8 @@ impl Contains<Call> for BaseFilter {
 		#[allow(clippy::match_like_matches_macro)]
 		match call {
 			Call::XYK(_) => false,
+			Call::Stableswap(_) => false,
+			Call::ConcentratedLiquidity(_) => false,
 			Call::Exchange(_) => false,
 			Call::NFT(_) => false,
 			Call::Marketplace(_) => false,
@@ -146,7 +150,7 @@ impl Contains<Call> for BaseFilter {
 use common_runtime::adapter::OrmlTokensAdapter;
 use primitives::{
 	nft::{ClassType, NftPermissions},
-	ClassId, InstanceId,
+	ClassId, InstanceId, Price,
 };
 use smallvec::smallvec;
 use sp_runtime::traits::BlockNumberProvider;
@@ -345,7 +349,7 @@ impl pallet_transaction_multi_payment::Config for Runtime {
 	type Event = Event;
 	type AcceptedCurrencyOrigin = EnsureSuperMajorityTechCommitteeOrRoot;
 	type Currencies = Currencies;
-	type SpotPriceProvider = pallet_xyk::XYKSpotPrice<Runtime>;
+	type SpotPriceProvider = pallet_xyk::XYKTwapSpotPrice<Runtime, MultiPaymentPricePeriod>;
 	type WeightInfo = common_runtime::weights::payment::BasiliskWeight<Runtime>;
 	type WithdrawFeeForSetCurrency = MultiPaymentCurrencySetFee;
 	type WeightToFee = WeightToFee;
@@ -370,7 +374,14 @@ impl InstanceFilter<Call> for ProxyType {
 					| Call::Treasury(..) | Call::Tips(..)
 					| Call::Utility(..)
 			),
-			ProxyType::Exchange => matches!(c, Call::XYK(..) | Call::Exchange(..) | Call::LBP(..) | Call::NFT(..)),
+			ProxyType::Exchange => matches!(
+				c,
+				Call::XYK(..)
+					| Call::Stableswap(..)
+					| Call::ConcentratedLiquidity(..)
+					| Call::Exchange(..) | Call::LBP(..)
+					| Call::NFT(..)
+			),
 			// Transfer group doesn't include cross-chain transfers
 			ProxyType::Transfer => matches!(c, Call::Balances(..) | Call::Currencies(..) | Call::Tokens(..)),
 		}
@@ -455,27 +466,68 @@ impl pallet_asset_registry::Config for Runtime {
 	type WeightInfo = common_runtime::weights::asset_registry::BasiliskWeight<Runtime>;
 }
 
+parameter_types! {
+	pub TreasuryAccount: AccountId = TreasuryPalletId::get().into_account();
+}
+
 impl pallet_xyk::Config for Runtime {
 	type Event = Event;
 	type AssetRegistry = AssetRegistry;
+	type ShareTokenCleanup = common_runtime::adapter::XYKShareTokenCleanup<Runtime>;
 	type AssetPairAccountId = AssetPairAccountId<Self>;
 	type Currency = Currencies;
 	type NativeAssetId = NativeAssetId;
 	type WeightInfo = common_runtime::weights::xyk::BasiliskWeight<Runtime>;
 	type GetExchangeFee = ExchangeFee;
+	type FeeTiers = ExchangeFeeTiers;
+	type UpdatePoolFeeOrigin = EnsureSuperMajorityTechCommitteeOrRoot;
+	type UpdatePoolStateOrigin = EnsureSuperMajorityTechCommitteeOrRoot;
+	type ProtocolFeeAccount = TreasuryAccount;
 	type MinTradingLimit = MinTradingLimit;
 	type MinPoolLiquidity = MinPoolLiquidity;
+	type LockedLiquidity = LockedLiquidity;
 	type MaxInRatio = MaxInRatio;
 	type MaxOutRatio = MaxOutRatio;
+	type MaxRouteHops = MaxRouteHops;
+	type MaxPriceObservations = MaxPriceObservations;
 	type CanCreatePool = pallet_lbp::DisallowWhenLBPPoolRunning<Runtime>;
 	type AMMHandler = pallet_price_oracle::PriceOracleHandler<Runtime>;
 }
 
+impl pallet_stableswap::Config for Runtime {
+	type Event = Event;
+	type AssetRegistry = AssetRegistry;
+	type Currency = Currencies;
+	type PalletId = StableswapPalletId;
+	type AuthorityOrigin = EnsureSuperMajorityTechCommitteeOrRoot;
+	type MinAmplification = MinStableswapAmplification;
+	type MaxAmplification = MaxStableswapAmplification;
+	type MaxAssetsInPool = MaxAssetsInStableswapPool;
+	type MinTradingLimit = MinTradingLimit;
+	type MinPoolLiquidity = MinPoolLiquidity;
+	type MaxInRatio = MaxInRatio;
+	type MaxOutRatio = MaxOutRatio;
+	type WeightInfo = common_runtime::weights::stableswap::BasiliskWeight<Runtime>;
+}
+
+impl pallet_concentrated_liquidity::Config for Runtime {
+	type Event = Event;
+	type MultiCurrency = Currencies;
+	type PalletId = ConcentratedLiquidityPalletId;
+	type CreatePoolOrigin = EnsureSuperMajorityTechCommitteeOrRoot;
+	type NftClass = ConcentratedLiquidityNftClass;
+	type MaxInitializedTicks = MaxInitializedTicks;
+	type MaxTicksCrossed = MaxTicksCrossed;
+	type MinTradingLimit = MinTradingLimit;
+	type WeightInfo = common_runtime::weights::concentrated_liquidity::BasiliskWeight<Runtime>;
+}
+
 impl pallet_exchange::Config for Runtime {
 	type Event = Event;
 	type AMMPool = XYK;
 	type Resolver = Exchange;
 	type Currency = Currencies;
+	type MaxOrders = ExchangeMaxOrders;
 	type WeightInfo = common_runtime::weights::exchange::BasiliskWeight<Runtime>;
 }
 
@@ -484,14 +536,19 @@ impl pallet_lbp::Config for Runtime {
 	type MultiCurrency = Currencies;
 	type LockedBalance = MultiCurrencyLockedBalance<Runtime>;
 	type CreatePoolOrigin = EnsureSuperMajorityTechCommitteeOrRoot;
+	type EmergencyOrigin = EnsureSuperMajorityTechCommitteeOrRoot;
 	type LBPWeightFunction = pallet_lbp::LBPWeightFunction;
 	type AssetPairAccountId = AssetPairAccountId<Self>;
 	type MinTradingLimit = MinTradingLimit;
 	type MinPoolLiquidity = MinPoolLiquidity;
 	type MaxInRatio = MaxInRatio;
 	type MaxOutRatio = MaxOutRatio;
+	type MaxRepayFee = LBPMaxRepayFee;
+	type PriceHistoryLength = LBPPriceHistoryLength;
+	type PriceHistoryGracePeriod = LBPPriceHistoryGracePeriod;
 	type WeightInfo = common_runtime::weights::lbp::BasiliskWeight<Runtime>;
 	type BlockNumberProvider = RelayChainBlockNumberProvider<Runtime>;
+	type GraduationHandler = common_runtime::adapter::XYKGraduation<Runtime>;
 }
 
 impl pallet_price_oracle::Config for Runtime {
@@ -909,6 +966,8 @@ construct_runtime!(
 		PriceOracle: pallet_price_oracle::{Pallet, Call, Storage, Event<T>} = 107,
 		RelayChainInfo: pallet_relaychain_info::{Pallet, Event<T>} = 108,
 		Marketplace: pallet_marketplace::{Pallet, Call, Event<T>, Storage} = 109,
+		Stableswap: pallet_stableswap::{Pallet, Call, Storage, Event<T>} = 110,
+		ConcentratedLiquidity: pallet_concentrated_liquidity::{Pallet, Call, Storage, Event<T>} = 111,
 
 		// ORML related modules - runtime module index for orml starts at 150
 		Currencies: orml_currencies::{Pallet, Call, Event<T>} = 150,
@@ -944,6 +1003,7 @@ pub type SignedExtra = (
 	frame_system::CheckWeight<Runtime>,
 	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
 	pallet_transaction_multi_payment::CurrencyBalanceCheck<Runtime>,
+	pallet_xyk::ValidateTradeExpiry<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
@@ -1108,15 +1168,315 @@ impl_runtime_apis! {
 		fn get_pool_id(asset_a: AssetId, asset_b: AssetId) -> AccountId{
 			XYK::pair_account_from_assets(asset_a, asset_b)
 		}
+
+		fn quote_sell(
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount: Balance,
+		) -> Option<xyk_rpc::TradeQuote<Balance>> {
+			XYK::quote_sell(asset_in, asset_out, amount).map(|quote| xyk_rpc::TradeQuote {
+				amount: quote.amount,
+				fee: quote.fee,
+				price_impact: quote.price_impact,
+				below_min_trading_limit: quote.below_min_trading_limit,
+				max_in_ratio_exceeded: quote.max_in_ratio_exceeded,
+				max_out_ratio_exceeded: quote.max_out_ratio_exceeded,
+				fee_rate: quote.fee_rate,
+			})
+		}
+
+		fn quote_buy(
+			asset_out: AssetId,
+			asset_in: AssetId,
+			amount: Balance,
+		) -> Option<xyk_rpc::TradeQuote<Balance>> {
+			XYK::quote_buy(asset_out, asset_in, amount).map(|quote| xyk_rpc::TradeQuote {
+				amount: quote.amount,
+				fee: quote.fee,
+				price_impact: quote.price_impact,
+				below_min_trading_limit: quote.below_min_trading_limit,
+				max_in_ratio_exceeded: quote.max_in_ratio_exceeded,
+				max_out_ratio_exceeded: quote.max_out_ratio_exceeded,
+				fee_rate: quote.fee_rate,
+			})
+		}
+
+		fn get_twap(asset_a: AssetId, asset_b: AssetId, period: u32) -> Option<Price> {
+			XYK::get_twap(asset_a, asset_b, period)
+		}
+
+		fn get_pool_state(asset_a: AssetId, asset_b: AssetId) -> Option<xyk_rpc::PoolState> {
+			let pool_account = XYK::pair_account_from_assets(asset_a, asset_b);
+
+			XYK::pool_assets(&pool_account).map(|_| match XYK::pool_state(&pool_account) {
+				pallet_xyk::PoolState::Active => xyk_rpc::PoolState::Active,
+				pallet_xyk::PoolState::TradingPaused => xyk_rpc::PoolState::TradingPaused,
+				pallet_xyk::PoolState::WithdrawOnly => xyk_rpc::PoolState::WithdrawOnly,
+			})
+		}
+
+		fn get_pools(
+			start_after: Option<AccountId>,
+			limit: u32,
+		) -> Vec<xyk_rpc::PoolInfo<AccountId, AssetId, Balance>> {
+			XYK::pools(start_after, limit).into_iter().map(|pool| xyk_rpc::PoolInfo {
+				pool_account: pool.pool_account,
+				asset_a: pool.asset_a,
+				asset_b: pool.asset_b,
+				asset_a_reserve: pool.asset_a_reserve,
+				asset_b_reserve: pool.asset_b_reserve,
+				share_token: pool.share_token,
+				total_shares: pool.total_shares,
+			}).collect()
+		}
+
+		fn get_liquidity_positions(
+			who: AccountId,
+		) -> Vec<xyk_rpc::LiquidityPosition<AccountId, AssetId, Balance>> {
+			XYK::liquidity_positions(&who).into_iter().map(|position| xyk_rpc::LiquidityPosition {
+				pool_account: position.pool_account,
+				asset_a: position.asset_a,
+				asset_b: position.asset_b,
+				shares: position.shares,
+				asset_a_amount: position.asset_a_amount,
+				asset_b_amount: position.asset_b_amount,
+			}).collect()
+		}
+	}
+
+	impl exchange_rpc::ExchangeApi<
+		Block,
+		AccountId,
+		AssetId,
+		Balance,
+		BlockNumber,
+	> for Runtime {
+		fn get_order_book(
+			asset_sell: AssetId,
+			asset_buy: AssetId,
+		) -> Vec<exchange_rpc::LimitOrder<AccountId, AssetId, Balance, BlockNumber>> {
+			Exchange::orders_in_book(asset_sell, asset_buy)
+				.into_iter()
+				.map(|(order_id, order)| exchange_rpc::LimitOrder {
+					order_id,
+					who: order.who,
+					asset_sell: order.assets.asset_in,
+					asset_buy: order.assets.asset_out,
+					amount: order.amount,
+					price: order.price,
+					expires_at: order.expires_at,
+				})
+				.collect()
+		}
+
+		fn get_orders_of(
+			who: AccountId,
+		) -> Vec<exchange_rpc::LimitOrder<AccountId, AssetId, Balance, BlockNumber>> {
+			Exchange::orders_of(&who)
+				.into_iter()
+				.map(|(order_id, order)| exchange_rpc::LimitOrder {
+					order_id,
+					who: order.who,
+					asset_sell: order.assets.asset_in,
+					asset_buy: order.assets.asset_out,
+					amount: order.amount,
+					price: order.price,
+					expires_at: order.expires_at,
+				})
+				.collect()
+		}
+
+		fn get_order(
+			order_id: u32,
+		) -> Option<exchange_rpc::LimitOrder<AccountId, AssetId, Balance, BlockNumber>> {
+			Exchange::order(order_id).map(|order| exchange_rpc::LimitOrder {
+				order_id,
+				who: order.who,
+				asset_sell: order.assets.asset_in,
+				asset_buy: order.assets.asset_out,
+				amount: order.amount,
+				price: order.price,
+				expires_at: order.expires_at,
+			})
+		}
+	}
+
+	impl stableswap_rpc::StableswapApi<
+		Block,
+		AccountId,
+		AssetId,
+		Balance,
+	> for Runtime {
+		fn get_pool_account(pool_id: u32) -> AccountId {
+			Stableswap::pool_account(pool_id)
+		}
+
+		fn get_reserves(pool_id: u32) -> Vec<(AssetId, Balance)> {
+			Stableswap::pools(pool_id).map(|pool| {
+				let reserves = Stableswap::reserves(&pool, &Stableswap::pool_account(pool_id));
+				pool.assets.into_iter().zip(reserves.into_iter()).collect()
+			}).unwrap_or_default()
+		}
+
+		fn get_amplification(pool_id: u32) -> Option<u16> {
+			Stableswap::pools(pool_id).map(|pool| Stableswap::current_amplification(&pool) as u16)
+		}
+
+		fn quote_sell(
+			pool_id: u32,
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount: Balance,
+		) -> Option<stableswap_rpc::TradeQuote<Balance>> {
+			Stableswap::calculate_sell(pool_id, asset_in, asset_out, amount).ok().map(|(amount_out, fee)| {
+				stableswap_rpc::TradeQuote {
+					amount: amount_out.saturating_sub(fee),
+					fee,
+				}
+			})
+		}
+
+		fn quote_buy(
+			pool_id: u32,
+			asset_out: AssetId,
+			asset_in: AssetId,
+			amount: Balance,
+		) -> Option<stableswap_rpc::TradeQuote<Balance>> {
+			Stableswap::calculate_buy(pool_id, asset_out, asset_in, amount).ok().map(|(amount_in, fee)| {
+				stableswap_rpc::TradeQuote {
+					amount: amount_in.saturating_add(fee),
+					fee,
+				}
+			})
+		}
 	}
 
 	impl pallet_lbp_rpc_runtime_api::LBPApi<
 		Block,
 		AccountId,
 		AssetId,
+		Balance,
+		BlockNumber,
+		LBPPool,
 	> for Runtime {
 		fn get_pool_id(asset_a: AssetId, asset_b: AssetId) -> AccountId{
-			LBP::pair_account_from_assets(asset_a, asset_b)
+			LBP::current_round_account(asset_a, asset_b)
+		}
+
+		fn get_pool_data(
+			asset_a: AssetId,
+			asset_b: AssetId,
+			block: Option<BlockNumber>,
+		) -> Option<pallet_lbp_rpc_runtime_api::PoolInfo<AccountId, LBPPool, Balance>> {
+			LBP::sale_info(asset_a, asset_b, block).map(|info| pallet_lbp_rpc_runtime_api::PoolInfo {
+				pool_account: info.pool_account,
+				pool: info.pool,
+				status: match info.status {
+					pallet_lbp::SaleStatus::NotStarted => pallet_lbp_rpc_runtime_api::SaleStatus::NotStarted,
+					pallet_lbp::SaleStatus::Running => pallet_lbp_rpc_runtime_api::SaleStatus::Running,
+					pallet_lbp::SaleStatus::Paused => pallet_lbp_rpc_runtime_api::SaleStatus::Paused,
+					pallet_lbp::SaleStatus::Ended => pallet_lbp_rpc_runtime_api::SaleStatus::Ended,
+				},
+				weights: info.weights,
+				asset_a_reserve: info.asset_a_reserve,
+				asset_b_reserve: info.asset_b_reserve,
+				spot_price: info.spot_price,
+				amount_raised: info.amount_raised,
+				repay_target_remaining: info.repay_target_remaining,
+				fee_rate: info.fee_rate,
+			})
+		}
+
+		fn quote_sell(
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount: Balance,
+		) -> Option<pallet_lbp_rpc_runtime_api::TradeQuote<AssetId, Balance>> {
+			LBP::quote_sell(asset_in, asset_out, amount).map(|quote| pallet_lbp_rpc_runtime_api::TradeQuote {
+				amount: quote.amount,
+				fee: quote.fee,
+				fee_asset: quote.fee_asset,
+				fee_rate: quote.fee_rate,
+				repay_fee_applied: quote.repay_fee_applied,
+				max_in_ratio_exceeded: quote.max_in_ratio_exceeded,
+				max_out_ratio_exceeded: quote.max_out_ratio_exceeded,
+			})
+		}
+
+		fn quote_buy(
+			asset_out: AssetId,
+			asset_in: AssetId,
+			amount: Balance,
+		) -> Option<pallet_lbp_rpc_runtime_api::TradeQuote<AssetId, Balance>> {
+			LBP::quote_buy(asset_out, asset_in, amount).map(|quote| pallet_lbp_rpc_runtime_api::TradeQuote {
+				amount: quote.amount,
+				fee: quote.fee,
+				fee_asset: quote.fee_asset,
+				fee_rate: quote.fee_rate,
+				repay_fee_applied: quote.repay_fee_applied,
+				max_in_ratio_exceeded: quote.max_in_ratio_exceeded,
+				max_out_ratio_exceeded: quote.max_out_ratio_exceeded,
+			})
+		}
+
+		fn get_remaining_allowance(
+			asset_a: AssetId,
+			asset_b: AssetId,
+			who: AccountId,
+			round: Option<u32>,
+		) -> Option<pallet_lbp_rpc_runtime_api::Allowance<Balance>> {
+			LBP::remaining_allowance(asset_a, asset_b, &who, round).map(|allowance| pallet_lbp_rpc_runtime_api::Allowance {
+				allowed: allowance.allowed,
+				remaining: allowance.remaining,
+			})
+		}
+
+		fn get_vesting(
+			asset_a: AssetId,
+			asset_b: AssetId,
+			who: AccountId,
+			round: Option<u32>,
+		) -> Option<pallet_lbp_rpc_runtime_api::VestingInfo<AssetId, Balance>> {
+			LBP::vesting_of(asset_a, asset_b, &who, round).map(|(vesting, claimable)| pallet_lbp_rpc_runtime_api::VestingInfo {
+				asset: vesting.asset,
+				total: vesting.total,
+				claimed: vesting.claimed,
+				claimable,
+			})
+		}
+
+		fn get_sale_rounds_count(asset_a: AssetId, asset_b: AssetId) -> u32 {
+			LBP::sale_rounds(LBP::pair_account_from_assets(asset_a, asset_b))
+		}
+
+		fn get_sale_round(
+			asset_a: AssetId,
+			asset_b: AssetId,
+			round: u32,
+		) -> Option<pallet_lbp_rpc_runtime_api::SaleRound<AccountId, LBPPool, Balance, BlockNumber>> {
+			LBP::sale_round(asset_a, asset_b, round).map(|(pool_account, sale_round)| pallet_lbp_rpc_runtime_api::SaleRound {
+				pool_account,
+				pool: sale_round.pool,
+				amount_raised: sale_round.amount_raised,
+				closed_at: sale_round.closed_at,
+			})
+		}
+
+		fn get_price_history(
+			asset_a: AssetId,
+			asset_b: AssetId,
+			round: Option<u32>,
+		) -> Vec<pallet_lbp_rpc_runtime_api::PriceSnapshot<BlockNumber, Balance>> {
+			LBP::price_history_of(asset_a, asset_b, round)
+				.into_iter()
+				.map(|snapshot| pallet_lbp_rpc_runtime_api::PriceSnapshot {
+					block: snapshot.block,
+					spot_price: snapshot.spot_price,
+					weights: snapshot.weights,
+					asset_a_reserve: snapshot.reserves.0,
+					asset_b_reserve: snapshot.reserves.1,
+				})
+				.collect()
 		}
 	}
 
@@ -1137,6 +1497,8 @@ impl_runtime_apis! {
 			let mut list = Vec::<BenchmarkList>::new();
 
 			list_benchmark!(list, extra, pallet_xyk, XYK);
+			list_benchmark!(list, extra, pallet_stableswap, Stableswap);
+			list_benchmark!(list, extra, pallet_concentrated_liquidity, ConcentratedLiquidity);
 			list_benchmark!(list, extra, pallet_lbp, LBP);
 			list_benchmark!(list, extra, pallet_price_oracle, PriceOracle);
 			list_benchmark!(list, extra, pallet_exchange, ExchangeBench::<Runtime>);
@@ -1199,6 +1561,8 @@ impl_runtime_apis! {
 
 			// Basilisk pallets
 			add_benchmark!(params, batches, pallet_xyk, XYK);
+			add_benchmark!(params, batches, pallet_stableswap, Stableswap);
+			add_benchmark!(params, batches, pallet_concentrated_liquidity, ConcentratedLiquidity);
 			add_benchmark!(params, batches, pallet_lbp, LBP);
 			add_benchmark!(params, batches, pallet_price_oracle, PriceOracle);
 			add_benchmark!(params, batches, pallet_exchange, ExchangeBench::<Runtime>);
@@ -1,10 +1,14 @@
 use frame_support::dispatch::DispatchError;
 use frame_support::sp_runtime::DispatchResult;
 use frame_support::traits::BalanceStatus;
+use frame_system::RawOrigin;
+use hydradx_traits::{Registry, AMM};
 use orml_traits::currency::TransferAll;
 use orml_traits::{
 	LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency, MultiReservableCurrency,
 };
+use primitives::{asset::AssetPair, AssetId, Balance, Price};
+use sp_runtime::FixedPointNumber;
 
 pub struct OrmlTokensAdapter<T>(sp_std::marker::PhantomData<T>);
 
@@ -149,3 +153,70 @@ impl<T: orml_tokens::Config> TransferAll<T::AccountId> for OrmlTokensAdapter<T>
 		<orml_tokens::Pallet<T> as TransferAll<T::AccountId>>::transfer_all(source, dest)
 	}
 }
+
+/// Graduates finished LBP sales into XYK pools.
+pub struct XYKGraduation<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: pallet_xyk::Config> pallet_lbp::GraduationHandler<T::AccountId> for XYKGraduation<T> {
+	fn create_pool(
+		who: &T::AccountId,
+		asset_a: AssetId,
+		amount_a: Balance,
+		asset_b: AssetId,
+		amount_b: Balance,
+	) -> Result<(AssetId, Balance), DispatchError> {
+		let initial_price = Price::checked_from_rational(amount_b, amount_a).ok_or(DispatchError::Other("Overflow"))?;
+
+		let asset_pair = AssetPair {
+			asset_in: asset_a,
+			asset_out: asset_b,
+		};
+
+		// Share token can already be registered and held by the owner, only shares minted by the pool
+		// creation are handed over.
+		let shares_before = <T as pallet_xyk::Config>::AssetRegistry::retrieve_asset(&asset_pair.name())
+			.map(|share_token| <T as pallet_xyk::Config>::Currency::free_balance(share_token, who))
+			.unwrap_or_default();
+
+		pallet_xyk::Pallet::<T>::create_pool(
+			RawOrigin::Signed(who.clone()).into(),
+			asset_a,
+			asset_b,
+			amount_a,
+			initial_price,
+			None,
+		)?;
+
+		let share_token = <pallet_xyk::Pallet<T> as AMM<_, _, _, _>>::get_share_token(asset_pair);
+		let shares = <T as pallet_xyk::Config>::Currency::free_balance(share_token, who).saturating_sub(shares_before);
+
+		Ok((share_token, shares))
+	}
+}
+
+/// Renames share tokens of destroyed XYK pools in the asset registry.
+///
+/// Share token is registered under the name of the asset pair, so renaming it lets a new share token
+/// be registered when the pool is created again.
+pub struct XYKShareTokenCleanup<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: pallet_asset_registry::Config<AssetId = AssetId>> pallet_xyk::ShareTokenCleanup<AssetId>
+	for XYKShareTokenCleanup<T>
+{
+	fn deregister_share_token(asset_a: AssetId, asset_b: AssetId, share_token: AssetId) -> DispatchResult {
+		let mut name = AssetPair {
+			asset_in: asset_a,
+			asset_out: asset_b,
+		}
+		.name();
+		name.extend_from_slice(&share_token.to_le_bytes());
+
+		pallet_asset_registry::Pallet::<T>::update(
+			RawOrigin::Root.into(),
+			share_token,
+			name,
+			pallet_asset_registry::AssetType::PoolShare(asset_a, asset_b),
+			None,
+		)
+	}
+}
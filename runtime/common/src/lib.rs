@@ -65,6 +65,9 @@ pub type Header = generic::Header<BlockNumber, BlakeTwo256>;
 /// Block type.
 pub type Block = generic::Block<Header, UncheckedExtrinsic>;
 
+/// LBP pool configuration as exposed by the LBP runtime API.
+pub type LBPPool = pallet_lbp::Pool<AccountId, BlockNumber>;
+
 /// We assume that an on-initialize consumes 2.5% of the weight on average, hence a single extrinsic
 /// will not be allowed to consume more than `AvailableBlockRatio - 2.5%`.
 pub const AVERAGE_ON_INITIALIZE_RATIO: Perbill = Perbill::from_perthousand(25);
@@ -117,6 +120,8 @@ parameter_types! {
 	/// that combined with `AdjustmentVariable`, we can recover from the minimum.
 	pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 1_000_000u128);
 	pub const MultiPaymentCurrencySetFee: Pays = Pays::Yes;
+	/// Number of blocks the average XYK price used to convert fees paid in other currencies is taken over.
+	pub const MultiPaymentPricePeriod: BlockNumber = 10 * MINUTES;
 }
 
 // pallet proxy
@@ -147,13 +152,22 @@ parameter_types! {
 // pallet xyk
 parameter_types! {
 	pub ExchangeFee: (u32, u32) = (2, 1_000);
+	pub ExchangeFeeTiers: sp_std::vec::Vec<(u32, u32)> = sp_std::vec![(5, 10_000), (2, 1_000), (1, 100)];
 	pub const MinTradingLimit: Balance = MIN_TRADING_LIMIT;
 	pub const MinPoolLiquidity: Balance = MIN_POOL_LIQUIDITY;
+	pub const LockedLiquidity: Balance = 1_000;
 	pub const MaxInRatio: u128 = MAX_IN_RATIO;
 	pub const MaxOutRatio: u128 = MAX_OUT_RATIO;
+	pub const MaxRouteHops: u32 = 5;
+	pub const MaxPriceObservations: u32 = 100;
 	pub const RegistryStrLimit: u32 = 32;
 }
 
+// pallet exchange
+parameter_types! {
+	pub const ExchangeMaxOrders: u32 = 200;
+}
+
 // pallet duster
 parameter_types! {
 	pub const DustingReward: u128 = 0;
@@ -162,6 +176,26 @@ parameter_types! {
 // pallet lbp
 parameter_types! {
 	pub LBPExchangeFee: (u32, u32) = (2, 1_000);
+	pub const LBPMaxRepayFee: (u32, u32) = (2, 10);
+	pub const LBPPriceHistoryLength: u32 = 300;
+	// one day of relay chain blocks
+	pub const LBPPriceHistoryGracePeriod: BlockNumber = 14_400;
+}
+
+// pallet stableswap
+parameter_types! {
+	pub const StableswapPalletId: PalletId = PalletId(*b"stblswap");
+	pub const MinStableswapAmplification: u16 = 1;
+	pub const MaxStableswapAmplification: u16 = 10_000;
+	pub const MaxAssetsInStableswapPool: u32 = 5;
+}
+
+// pallet concentrated liquidity
+parameter_types! {
+	pub const ConcentratedLiquidityPalletId: PalletId = PalletId(*b"concliq_");
+	pub const ConcentratedLiquidityNftClass: primitives::ClassId = 2;
+	pub const MaxInitializedTicks: u32 = 1_000;
+	pub const MaxTicksCrossed: u32 = 32;
 }
 
 // pallet democracy
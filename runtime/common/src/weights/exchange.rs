@@ -106,4 +106,27 @@ impl<T: frame_system::Config> WeightInfo for BasiliskWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(15 as Weight))
 			.saturating_add(T::DbWeight::get().writes(7 as Weight))
 	}
+	fn place_order() -> Weight {
+		(58_312_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn cancel_order() -> Weight {
+		(49_837_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn modify_order() -> Weight {
+		(55_104_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn process_orders(n: u32) -> Weight {
+		(4_126_000 as Weight)
+			// Standard Error: 41_000
+			.saturating_add((142_318_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((11 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((10 as Weight).saturating_mul(n as Weight)))
+	}
 }
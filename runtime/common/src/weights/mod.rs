@@ -1,6 +1,7 @@
 pub mod asset_registry;
 pub mod balances;
 pub mod collator_selection;
+pub mod concentrated_liquidity;
 pub mod currencies;
 pub mod democracy;
 pub mod duster;
@@ -11,6 +12,7 @@ pub mod nft;
 pub mod payment;
 pub mod price_oracle;
 pub mod scheduler;
+pub mod stableswap;
 pub mod system;
 pub mod timestamp;
 pub mod tokens;
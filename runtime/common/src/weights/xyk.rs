@@ -74,4 +74,47 @@ impl<T: frame_system::Config> WeightInfo for BasiliskWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(11 as Weight))
 			.saturating_add(T::DbWeight::get().writes(5 as Weight))
 	}
+	fn sell_route(n: u32) -> Weight {
+		(14_276_000 as Weight) // Standard Error: 52_000
+			.saturating_add((127_118_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads((11 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(n as Weight)))
+	}
+	fn buy_route(n: u32) -> Weight {
+		(16_530_000 as Weight) // Standard Error: 57_000
+			.saturating_add((133_904_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads((13 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(n as Weight)))
+	}
+	fn set_pool_fee() -> Weight {
+		(24_108_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_protocol_fee_share() -> Weight {
+		(19_854_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn add_liquidity_single_asset() -> Weight {
+		(275_630_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+	}
+	fn remove_liquidity_to_single_asset() -> Weight {
+		(269_941_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	fn set_pool_state() -> Weight {
+		(24_473_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_circuit_breaker_limits() -> Weight {
+		(22_871_000 as Weight).saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn set_dynamic_fee() -> Weight {
+		(24_987_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
 }